@@ -2,6 +2,7 @@ use super::achievement_definition::{AchievementDefinition, AchievementDefinition
 use super::achievement_statistic::*;
 use super::game_statistics::GameStatistics;
 use crate::game::Game;
+use crate::player_profile::PlayerProfile;
 use eval::{eval, to_value as eval_to_value};
 
 pub struct AchievementEvaluator {
@@ -16,10 +17,23 @@ impl AchievementEvaluator {
     }
 
     pub fn evaluate(&self, game: &Game) -> Vec<&AchievementDefinition> {
-        let statistics = GameStatistics::new(game);
+        self.evaluate_statistics(&GameStatistics::new(game))
+    }
+
+    /// Evaluates the achievements with the streak and time zone of the
+    /// player.
+    pub fn evaluate_for_player(
+        &self,
+        game: &Game,
+        profile: &PlayerProfile,
+    ) -> Vec<&AchievementDefinition> {
+        self.evaluate_statistics(&GameStatistics::with_profile(game, profile))
+    }
+
+    fn evaluate_statistics(&self, statistics: &GameStatistics) -> Vec<&AchievementDefinition> {
         self.definitions
             .iter()
-            .filter(|def| self.evaluate_condition(&def.condition, &statistics))
+            .filter(|def| self.evaluate_condition(&def.condition, statistics))
             .collect()
     }
 
@@ -55,6 +69,9 @@ impl AchievementEvaluator {
                 "different_challenge_types_completed",
                 &statistics.different_challenge_types_completed().to_string(),
             )
+            .replace("current_streak", &statistics.current_streak().to_string())
+            .replace("longest_streak", &statistics.longest_streak().to_string())
+            .replace("active_days", &statistics.active_days().to_string())
             .replace("&", "&&")
             .replace("|", "||")
     }
@@ -96,4 +113,32 @@ mod tests {
 
         assert_eq!(achieved.len(), 0);
     }
+
+    #[test]
+    fn test_streak_achievement() {
+        let evaluator = AchievementEvaluator::new(
+            r#"
+    achievements:
+      - id: three_days
+        name: Three Days
+        description: Learn three days in a row
+        icon: 🔥
+        condition: "current_streak >= 3"
+    "#,
+        )
+        .unwrap();
+        let mut game = Game::default();
+        let now = chrono::Utc::now();
+        for days_ago in 0..3 {
+            let mut challenge = game.create_challenge("konnektoren-1").unwrap();
+            challenge.end_time = Some(now - chrono::Duration::days(days_ago));
+            game.challenge_history.add_challenge(challenge);
+        }
+
+        assert_eq!(evaluator.evaluate(&game).len(), 1);
+        let mut profile = PlayerProfile::default();
+        assert!(evaluator.evaluate_for_player(&game, &profile).is_empty());
+        profile.update_streak(&game, now);
+        assert_eq!(evaluator.evaluate_for_player(&game, &profile).len(), 1);
+    }
 }
@@ -81,6 +81,45 @@ pub trait DifferentChallengeTypesCompleted: AchievementStatistic {
     }
 }
 
+pub trait CurrentStreak: AchievementStatistic {
+    fn name(&self) -> &str {
+        "current_streak"
+    }
+    fn description(&self) -> &str {
+        "Days in a row with at least one finished challenge"
+    }
+    fn current_streak(&self) -> u32;
+    fn value(&self) -> f64 {
+        self.current_streak() as f64
+    }
+}
+
+pub trait LongestStreak: AchievementStatistic {
+    fn name(&self) -> &str {
+        "longest_streak"
+    }
+    fn description(&self) -> &str {
+        "Longest number of days in a row with at least one finished challenge"
+    }
+    fn longest_streak(&self) -> u32;
+    fn value(&self) -> f64 {
+        self.longest_streak() as f64
+    }
+}
+
+pub trait ActiveDays: AchievementStatistic {
+    fn name(&self) -> &str {
+        "active_days"
+    }
+    fn description(&self) -> &str {
+        "Number of days with at least one finished challenge"
+    }
+    fn active_days(&self) -> u32;
+    fn value(&self) -> f64 {
+        self.active_days() as f64
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,6 +171,11 @@ mod tests {
             5
         }
     }
+    impl CurrentStreak for DummyStats {
+        fn current_streak(&self) -> u32 {
+            4
+        }
+    }
 
     #[test]
     fn test_total_challenges_trait() {
@@ -207,4 +251,16 @@ mod tests {
         assert_eq!(stats.different_challenge_types_completed(), 5);
         assert_eq!(DifferentChallengeTypesCompleted::value(&stats), 5.0);
     }
+
+    #[test]
+    fn test_current_streak_trait() {
+        let stats = DummyStats;
+        assert_eq!(CurrentStreak::name(&stats), "current_streak");
+        assert_eq!(
+            CurrentStreak::description(&stats),
+            "Days in a row with at least one finished challenge"
+        );
+        assert_eq!(stats.current_streak(), 4);
+        assert_eq!(CurrentStreak::value(&stats), 4.0);
+    }
 }
@@ -0,0 +1,284 @@
+//! Adaptive task selection.
+//!
+//! A [`TaskPattern::Adaptive`](super::task_pattern::TaskPattern::Adaptive)
+//! pattern picks tasks from a challenge at random, but weights every task by
+//! how the player did on it in the [`ChallengeHistory`]: tasks answered wrong
+//! or slowly come up more often, mastered tasks less often, and tasks the
+//! player has never seen are mixed in.
+use crate::challenges::spaced_repetition::task::{retain_tasks, task_keys, task_outcomes};
+use crate::challenges::{ChallengeHistory, ChallengeType, TaskKey, Timed};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::collections::{HashMap, HashSet};
+
+/// Weight of a task the player has never answered.
+pub const UNSEEN_WEIGHT: f64 = 2.0;
+/// Extra weight for a task that was always answered wrong.
+pub const WRONG_WEIGHT: f64 = 3.0;
+/// Extra weight for a task that was always answered slowly.
+pub const SLOW_WEIGHT: f64 = 1.0;
+/// Factor applied to the weight of a mastered task.
+pub const MASTERED_FACTOR: f64 = 0.2;
+/// Seconds per task above which an answer counts as slow.
+pub const SLOW_TASK_SECONDS: f64 = 30.0;
+/// Correct answers in a row after which a task counts as mastered.
+pub const MASTERED_STREAK: usize = 3;
+
+/// How a player did on a single task so far.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskStats {
+    /// How often the task was answered
+    pub answered: usize,
+    /// How often the answer was wrong
+    pub wrong: usize,
+    /// How often the answer was slow
+    pub slow: usize,
+    /// Correct answers in a row, counting from the latest answer
+    pub streak: usize,
+}
+
+impl TaskStats {
+    /// Records one answer. Answers have to be recorded in the order they
+    /// were given.
+    pub fn record(&mut self, correct: bool, slow: bool) {
+        self.answered += 1;
+        self.slow += slow as usize;
+        if correct {
+            self.streak += 1;
+        } else {
+            self.wrong += 1;
+            self.streak = 0;
+        }
+    }
+
+    pub fn is_mastered(&self) -> bool {
+        self.streak >= MASTERED_STREAK
+    }
+
+    /// Returns the selection weight of the task.
+    pub fn weight(&self) -> f64 {
+        if self.answered == 0 {
+            return UNSEEN_WEIGHT;
+        }
+        let answered = self.answered as f64;
+        let weight = 1.0
+            + WRONG_WEIGHT * self.wrong as f64 / answered
+            + SLOW_WEIGHT * self.slow as f64 / answered;
+        if self.is_mastered() {
+            weight * MASTERED_FACTOR
+        } else {
+            weight
+        }
+    }
+}
+
+/// The [`TaskStats`] of every task a player has answered.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskWeights {
+    stats: HashMap<TaskKey, TaskStats>,
+}
+
+impl TaskWeights {
+    /// Replays the answers of all challenges in the history. The time a
+    /// challenge took is spread evenly over its answered tasks.
+    pub fn from_history(history: &ChallengeHistory) -> Self {
+        let mut weights = TaskWeights::default();
+        for challenge in &history.challenges {
+            let outcomes = task_outcomes(challenge);
+            let slow = challenge.elapsed_time().is_some_and(|elapsed| {
+                elapsed.num_seconds() as f64 / outcomes.len().max(1) as f64 > SLOW_TASK_SECONDS
+            });
+            for (key, correct) in outcomes {
+                weights.stats.entry(key).or_default().record(correct, slow);
+            }
+        }
+        weights
+    }
+
+    pub fn stats(&self, key: &TaskKey) -> Option<&TaskStats> {
+        self.stats.get(key)
+    }
+
+    /// Returns the selection weight of a task, see [`TaskStats::weight`].
+    pub fn weight(&self, key: &TaskKey) -> f64 {
+        self.stats.get(key).map_or(UNSEEN_WEIGHT, TaskStats::weight)
+    }
+
+    /// Picks up to `count` distinct tasks of the challenge type, weighted by
+    /// [`TaskWeights::weight`].
+    pub fn choose<R: Rng + ?Sized>(
+        &self,
+        challenge_type: &ChallengeType,
+        count: usize,
+        rng: &mut R,
+    ) -> HashSet<TaskKey> {
+        let mut keys = task_keys(challenge_type);
+        let mut seen = HashSet::new();
+        keys.retain(|key| seen.insert(key.clone()));
+        match keys.choose_multiple_weighted(rng, count.min(keys.len()), |key| self.weight(key)) {
+            Ok(chosen) => chosen.cloned().collect(),
+            Err(_) => keys.into_iter().take(count).collect(),
+        }
+    }
+}
+
+impl ChallengeType {
+    /// Selects `count` tasks weighted by the player's history, see
+    /// [`TaskWeights`]. Challenge types without reviewable tasks fall back to
+    /// a random selection.
+    pub fn of_tasks_adaptive<R: Rng + ?Sized>(
+        &self,
+        count: usize,
+        history: &ChallengeHistory,
+        rng: &mut R,
+    ) -> Self {
+        if task_keys(self).is_empty() {
+            return self.of_tasks_with_rng(
+                &crate::challenges::task_pattern::TaskPattern::Random(count, None),
+                rng,
+            );
+        }
+        let chosen = TaskWeights::from_history(history).choose(self, count, rng);
+        retain_tasks(self, |key| chosen.contains(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{
+        Challenge, ChallengeConfig, ChallengeResult, MultipleChoice, MultipleChoiceOption, Question,
+    };
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn dataset() -> MultipleChoice {
+        MultipleChoice {
+            id: "adaptive".to_string(),
+            options: (0..2)
+                .map(|id| MultipleChoiceOption {
+                    id,
+                    name: id.to_string(),
+                })
+                .collect(),
+            questions: (0..10)
+                .map(|index| Question {
+                    question: format!("Question {}", index),
+                    option: index % 2,
+                    ..Question::default()
+                })
+                .collect(),
+            ..MultipleChoice::default()
+        }
+    }
+
+    /// A challenge that answered the first `answers.len()` questions of the
+    /// test dataset.
+    fn played(answers: &[bool], seconds: i64) -> Challenge {
+        let dataset = dataset();
+        let mut challenge = Challenge::new(
+            &ChallengeType::MultipleChoice(dataset.clone()),
+            &ChallengeConfig::default(),
+        );
+        let options = dataset
+            .questions
+            .iter()
+            .zip(answers)
+            .map(|(question, &correct)| MultipleChoiceOption {
+                id: if correct {
+                    question.option
+                } else {
+                    question.option + 1
+                },
+                name: String::new(),
+            })
+            .collect();
+        challenge.challenge_result = ChallengeResult::MultipleChoice(options);
+        let end = chrono::Utc::now();
+        challenge.start_time = Some(end - chrono::Duration::seconds(seconds));
+        challenge.end_time = Some(end);
+        challenge
+    }
+
+    #[test]
+    fn task_stats_weights() {
+        let unseen = TaskStats::default();
+        assert_eq!(unseen.weight(), UNSEEN_WEIGHT);
+
+        let mut wrong = TaskStats::default();
+        wrong.record(false, false);
+        assert_eq!(wrong.weight(), 1.0 + WRONG_WEIGHT);
+
+        let mut slow = TaskStats::default();
+        slow.record(true, true);
+        assert_eq!(slow.weight(), 1.0 + SLOW_WEIGHT);
+
+        let mut mastered = TaskStats::default();
+        for _ in 0..MASTERED_STREAK {
+            mastered.record(true, false);
+        }
+        assert!(mastered.is_mastered());
+        assert_eq!(mastered.weight(), MASTERED_FACTOR);
+
+        mastered.record(false, false);
+        assert!(!mastered.is_mastered());
+    }
+
+    #[test]
+    fn weights_from_history() {
+        let history = ChallengeHistory {
+            challenges: vec![played(&[true, false], 10), played(&[true, false], 10)],
+        };
+        let weights = TaskWeights::from_history(&history);
+        let keys = task_keys(&ChallengeType::MultipleChoice(dataset()));
+
+        assert_eq!(weights.stats(&keys[0]).unwrap().streak, 2);
+        assert_eq!(weights.stats(&keys[1]).unwrap().wrong, 2);
+        assert!(weights.weight(&keys[1]) > weights.weight(&keys[2]));
+        assert!(weights.weight(&keys[2]) > weights.weight(&keys[0]));
+    }
+
+    #[test]
+    fn slow_challenges_mark_tasks_slow() {
+        let history = ChallengeHistory {
+            challenges: vec![played(&[true, true], 120)],
+        };
+        let weights = TaskWeights::from_history(&history);
+        let keys = task_keys(&ChallengeType::MultipleChoice(dataset()));
+        assert_eq!(weights.stats(&keys[0]).unwrap().slow, 1);
+    }
+
+    #[test]
+    fn adaptive_selection_prefers_weak_tasks() {
+        let challenge_type = ChallengeType::MultipleChoice(dataset());
+        let keys = task_keys(&challenge_type);
+        let mut answers = vec![true; keys.len()];
+        answers[3] = false;
+        let history = ChallengeHistory {
+            challenges: vec![played(&answers, 10); MASTERED_STREAK],
+        };
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let picks = (0..200)
+            .filter(|_| {
+                let selected = challenge_type.of_tasks_adaptive(1, &history, &mut rng);
+                task_keys(&selected) == vec![keys[3].clone()]
+            })
+            .count();
+        // The weak task weighs 4.0, the nine mastered ones 0.2 each
+        assert!(picks > 100, "weak task picked only {} times", picks);
+    }
+
+    #[test]
+    fn adaptive_selection_without_history_mixes_all_tasks() {
+        let challenge_type = ChallengeType::MultipleChoice(dataset());
+        let selected = challenge_type.of_tasks_adaptive(
+            5,
+            &ChallengeHistory::default(),
+            &mut StdRng::seed_from_u64(1),
+        );
+        let keys = task_keys(&selected);
+        assert_eq!(keys.len(), 5);
+        assert_eq!(keys.iter().collect::<HashSet<_>>().len(), 5);
+    }
+}
@@ -39,6 +39,9 @@ pub trait Base64Serializable {
 // Implement the trait for ChallengeType
 impl Base64Serializable for crate::challenges::challenge_type::ChallengeType {}
 
+// Implement the trait for Challenge, so a shared challenge keeps its seed
+impl Base64Serializable for crate::challenges::challenge::Challenge {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,6 +75,8 @@ mod tests {
                 option: 2,
                 image: Some("fa-regular fa-house-chimney-window".to_string()),
             }],
+            level: None,
+            tags: vec![],
         });
 
         // Encode to base64
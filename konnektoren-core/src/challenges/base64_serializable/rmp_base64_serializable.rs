@@ -10,8 +10,10 @@ pub trait RmpBase64Serializable {
     where
         Self: Serialize,
     {
-        let serialized_bytes =
-            rmp_serde::to_vec(self).map_err(|e| ChallengeError::Serialization(e.to_string()))?;
+        // Structs are written as maps, so optional fields that are skipped
+        // when empty do not shift the fields after them.
+        let serialized_bytes = rmp_serde::to_vec_named(self)
+            .map_err(|e| ChallengeError::Serialization(e.to_string()))?;
 
         let encoded = base64::engine::general_purpose::STANDARD.encode(serialized_bytes);
         Ok(encoded)
@@ -70,6 +72,8 @@ mod tests {
                 option: 2,
                 image: Some("fa-regular fa-house-chimney-window".to_string()),
             }],
+            level: None,
+            tags: vec![],
         });
 
         let encoded = challenge_type.to_base64().unwrap();
@@ -117,6 +121,8 @@ mod tests {
                 option: 2,
                 image: Some("fa-regular fa-house-chimney-window".to_string()),
             }],
+            level: None,
+            tags: vec![],
         });
 
         // Encode to rmp_serde + base64
@@ -140,6 +146,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_encode_decode_skipped_fields_rmp() {
+        let mut gap_fill = crate::challenges::GapFill::default();
+        gap_fill.level = None;
+        gap_fill.tags = vec!["perfekt".to_string()];
+        let challenge_type = ChallengeType::GapFill(gap_fill);
+
+        let encoded = challenge_type.to_rmp_base64().unwrap();
+        let decoded = ChallengeType::from_rmp_base64(&encoded).unwrap();
+        assert_eq!(decoded, challenge_type);
+    }
+
     #[test]
     fn test_encode_decode_empty_string_rmp() {
         let empty_data = "";
@@ -0,0 +1,66 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Level of the Common European Framework of Reference for Languages,
+/// written as `A1` to `C2`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[strum(ascii_case_insensitive)]
+pub enum CefrLevel {
+    #[serde(alias = "a1")]
+    A1,
+    #[serde(alias = "a2")]
+    A2,
+    #[serde(alias = "b1")]
+    B1,
+    #[serde(alias = "b2")]
+    B2,
+    #[serde(alias = "c1")]
+    C1,
+    #[serde(alias = "c2")]
+    C2,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn levels_are_ordered() {
+        let levels: Vec<CefrLevel> = CefrLevel::iter().collect();
+        assert_eq!(levels.len(), 6);
+        assert!(levels.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(CefrLevel::from_str("b2").unwrap(), CefrLevel::B2);
+        assert_eq!(CefrLevel::C1.to_string(), "C1");
+        assert!(CefrLevel::from_str("D1").is_err());
+    }
+
+    #[test]
+    fn serde_accepts_lowercase() {
+        let level: CefrLevel = serde_yaml::from_str("a2").unwrap();
+        assert_eq!(level, CefrLevel::A2);
+        assert_eq!(serde_yaml::to_string(&level).unwrap().trim(), "A2");
+    }
+}
@@ -1,7 +1,10 @@
 use crate::challenges::Timed;
 use crate::challenges::error::{ChallengeError, Result};
 use crate::challenges::{
-    ChallengeConfig, ChallengeInput, ChallengeResult, ChallengeType, CustomChallengeResult,
+    AnswerVerdict, ChallengeConfig, ChallengeInput, ChallengeResult, ChallengeType,
+    ContextItemChoiceAnswers, CustomChallengeResult, ErrorCorrectionAnswer, GapFillAnswer, Hint,
+    HintUsage, MatchingAnswer, MultipleChoiceOption, OrderingResult, RewardContext, SortTableRow,
+    TranslationAnswer, VocabularyAnswer,
 };
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
@@ -15,6 +18,18 @@ pub struct Challenge {
     pub challenge_result: ChallengeResult,
     pub start_time: Option<DateTime<Utc>>,
     pub end_time: Option<DateTime<Utc>>,
+    /// The hints revealed while playing, in the order they were requested
+    #[serde(default)]
+    pub hints: Vec<HintUsage>,
+    /// The seed the random tasks were selected with, to replay the same tasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// The indices of the tasks skipped because their time ran out
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timed_out_tasks: Vec<usize>,
+    /// The indices of the tasks the player moved past without answering
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_tasks: Vec<usize>,
 }
 
 impl Challenge {
@@ -29,8 +44,11 @@ impl Challenge {
             ChallengeType::Ordering(_) => ChallengeResult::Ordering(Vec::new()),
             ChallengeType::Custom(_) => ChallengeResult::Custom(CustomChallengeResult::default()),
             ChallengeType::Placeholder(_) => ChallengeResult::MultipleChoice(Vec::new()), // Placeholder uses MC
-            ChallengeType::Vocabulary(_) => ChallengeResult::Vocabulary,
+            ChallengeType::Vocabulary(_) => ChallengeResult::Vocabulary(Vec::new()),
             ChallengeType::Dialog(_) => ChallengeResult::Dialog(Vec::new()),
+            ChallengeType::Matching(_) => ChallengeResult::Matching(Vec::new()),
+            ChallengeType::Translation(_) => ChallengeResult::Translation(Vec::new()),
+            ChallengeType::ErrorCorrection(_) => ChallengeResult::ErrorCorrection(Vec::new()),
         };
 
         Challenge {
@@ -39,6 +57,10 @@ impl Challenge {
             challenge_result,
             start_time: None,
             end_time: None,
+            hints: Vec::new(),
+            seed: None,
+            timed_out_tasks: Vec::new(),
+            skipped_tasks: Vec::new(),
         }
     }
 
@@ -49,21 +71,145 @@ impl Challenge {
     pub fn solved(&self) -> bool {
         !self.challenge_result.is_empty()
     }
+
+    /// Returns the number of hints revealed so far.
+    pub fn hints_used(&self) -> usize {
+        self.hints.len()
+    }
+
+    /// Returns the hints already revealed for a task.
+    pub fn hints_for_task(&self, task_index: usize) -> Vec<&Hint> {
+        self.hints
+            .iter()
+            .filter(|usage| usage.task_index == task_index)
+            .map(|usage| &usage.hint)
+            .collect()
+    }
+
+    /// Returns the answer recorded for a task the player moved past without
+    /// answering, which is always graded wrong, or `None` if the challenge
+    /// type keeps no answer per task.
+    pub fn unanswered_input(&self, task_index: usize) -> Option<ChallengeInput> {
+        let input = match &self.challenge_type {
+            // No option has this id
+            ChallengeType::MultipleChoice(_) => {
+                ChallengeInput::MultipleChoice(MultipleChoiceOption {
+                    id: usize::MAX,
+                    name: String::new(),
+                })
+            }
+            ChallengeType::ContextualChoice(cc) => {
+                let choices = cc
+                    .items
+                    .get(task_index)
+                    .map(|item| item.choices.len())
+                    .unwrap_or_default();
+                ChallengeInput::ContextualChoice(ContextItemChoiceAnswers {
+                    ids: vec![usize::MAX; choices],
+                })
+            }
+            ChallengeType::GapFill(_) => ChallengeInput::GapFill(GapFillAnswer {
+                question_index: task_index,
+                answers: vec![],
+            }),
+            ChallengeType::SortTable(_) => ChallengeInput::SortTable(SortTableRow::default()),
+            ChallengeType::Ordering(_) => ChallengeInput::Ordering(OrderingResult::default()),
+            ChallengeType::Vocabulary(vocabulary) => {
+                // An unanswered card counts as not known
+                let item_id = vocabulary
+                    .items
+                    .get(task_index)
+                    .map(|item| item.id)
+                    .unwrap_or_default();
+                ChallengeInput::Vocabulary(VocabularyAnswer {
+                    item_id,
+                    ..Default::default()
+                })
+            }
+            ChallengeType::Translation(_) => ChallengeInput::Translation(TranslationAnswer {
+                item_index: task_index,
+                text: String::new(),
+            }),
+            // A sentence without corrections finds no mistakes
+            ChallengeType::ErrorCorrection(_) => {
+                ChallengeInput::ErrorCorrection(ErrorCorrectionAnswer {
+                    sentence_index: task_index,
+                    corrections: vec![],
+                })
+            }
+            ChallengeType::Matching(matching) => {
+                // An unconnected pair counts as wrong
+                let left = matching
+                    .pairs
+                    .get(task_index)
+                    .map(|pair| pair.id)
+                    .unwrap_or_default();
+                ChallengeInput::Matching(MatchingAnswer {
+                    left,
+                    right: usize::MAX,
+                })
+            }
+            _ => return None,
+        };
+        Some(input)
+    }
+
+    /// Records that the player moved past a task without answering it.
+    pub fn skip_task(&mut self, task_index: usize) {
+        if !self.skipped_tasks.contains(&task_index) {
+            self.skipped_tasks.push(task_index);
+        }
+    }
+
+    /// Returns whether the player answered the task in time, rather than
+    /// skipping it or running out of time.
+    pub fn answered_in_time(&self, task_index: usize) -> bool {
+        !self.skipped_tasks.contains(&task_index) && !self.timed_out_tasks.contains(&task_index)
+    }
+
+    /// Reveals and records the next hint for a task.
+    pub fn request_hint(&mut self, task_index: usize) -> Result<Hint> {
+        let revealed = self.hints_for_task(task_index).len();
+        let hint = self
+            .challenge_type
+            .hints(task_index)
+            .into_iter()
+            .nth(revealed)
+            .ok_or(ChallengeError::NoMoreHints)?;
+        self.hints.push(HintUsage {
+            task_index,
+            hint: hint.clone(),
+        });
+        Ok(hint)
+    }
 }
 
 impl Solvable for Challenge {
-    fn solve(&mut self, input: ChallengeInput, task_index: usize) -> Result<bool> {
+    fn grade(&mut self, input: ChallengeInput, task_index: usize) -> Result<AnswerVerdict> {
         self.update_end_time();
 
-        match self.challenge_result.set_input(task_index, input.clone()) {
+        let answered = self.challenge_result.len();
+        let recorded = self.challenge_result.set_input(task_index, input.clone());
+        if recorded.is_ok() {
+            // Tasks jumped over are padded in the result, but not answered.
+            // Dialog answers are appended, so they are never padded.
+            if !matches!(self.challenge_result, ChallengeResult::Dialog(_)) {
+                for skipped in answered..task_index {
+                    self.skip_task(skipped);
+                }
+            }
+            self.skipped_tasks.retain(|&index| index != task_index);
+        }
+
+        match recorded {
             Ok(_) => match (&self.challenge_type, &self.challenge_result) {
                 (ChallengeType::MultipleChoice(mc), ChallengeResult::MultipleChoice(results)) => {
                     if let (Some(question), Some(result)) =
                         (mc.questions.get(task_index), results.get(task_index))
                     {
-                        Ok(question.option == result.id)
+                        Ok((question.option == result.id).into())
                     } else {
-                        Ok(false)
+                        Ok(AnswerVerdict::Wrong)
                     }
                 }
                 (
@@ -73,47 +219,76 @@ impl Solvable for Challenge {
                     if let (Some(item), Some(choice)) =
                         (cc.items.get(task_index), results.get(task_index))
                     {
-                        Ok(item.choices.iter().zip(&choice.ids).all(|(c, &id)| {
-                            c.options
-                                .get(id)
-                                .is_some_and(|selected| *selected == c.correct_answer)
-                        }))
+                        Ok(item.grade(choice, &cc.grading))
                     } else {
-                        Ok(false)
+                        Ok(AnswerVerdict::Wrong)
                     }
                 }
                 (ChallengeType::GapFill(gf), ChallengeResult::GapFill(results)) => {
                     if let (Some(question), Some(answer)) =
                         (gf.questions.get(task_index), results.get(task_index))
                     {
-                        if question.gaps.len() != answer.answers.len() {
-                            return Ok(false);
-                        }
-
-                        Ok(question
-                            .gaps
-                            .iter()
-                            .zip(answer.answers.iter())
-                            .all(|(gap, ans)| gap.correct == *ans))
+                        Ok(question.grade(&answer.answers, &gf.grading))
                     } else {
-                        Ok(false)
+                        Ok(AnswerVerdict::Wrong)
                     }
                 }
                 (ChallengeType::SortTable(st), ChallengeResult::SortTable(results)) => {
                     if let (Some(row), Some(result)) =
                         (st.rows.get(task_index), results.get(task_index))
                     {
-                        Ok(row.values == result.values)
+                        Ok((row.values == result.values).into())
                     } else {
-                        Ok(false)
+                        Ok(AnswerVerdict::Wrong)
                     }
                 }
-                (ChallengeType::Informative(_), ChallengeResult::Informative) => Ok(true),
-                (ChallengeType::Custom(_), ChallengeResult::Custom(_)) => Ok(true),
+                (ChallengeType::Ordering(ordering), ChallengeResult::Ordering(results)) => {
+                    if let (Some(item), Some(result)) =
+                        (ordering.items.get(task_index), results.get(task_index))
+                    {
+                        Ok((item.correct_order == result.order).into())
+                    } else {
+                        Ok(AnswerVerdict::Wrong)
+                    }
+                }
+                (ChallengeType::Informative(_), ChallengeResult::Informative) => {
+                    Ok(AnswerVerdict::Correct)
+                }
+                (ChallengeType::Custom(_), ChallengeResult::Custom(_)) => {
+                    Ok(AnswerVerdict::Correct)
+                }
+                (ChallengeType::Vocabulary(vocabulary), ChallengeResult::Vocabulary(results)) => {
+                    match results.get(task_index) {
+                        Some(answer) => Ok(vocabulary.grade_answer(answer)),
+                        None => Ok(AnswerVerdict::Wrong),
+                    }
+                }
+                (ChallengeType::Matching(matching), ChallengeResult::Matching(results)) => {
+                    match results.get(task_index) {
+                        Some(answer) => Ok(matching.is_correct(answer).into()),
+                        None => Ok(AnswerVerdict::Wrong),
+                    }
+                }
+                (
+                    ChallengeType::Translation(translation),
+                    ChallengeResult::Translation(results),
+                ) => match results.get(task_index) {
+                    Some(answer) => Ok(translation.grade_answer(answer)),
+                    None => Ok(AnswerVerdict::Wrong),
+                },
+                (
+                    ChallengeType::ErrorCorrection(error_correction),
+                    ChallengeResult::ErrorCorrection(results),
+                ) => match results.get(task_index) {
+                    Some(answer) => Ok(error_correction.grade_answer(answer)),
+                    None => Ok(AnswerVerdict::Wrong),
+                },
                 (ChallengeType::Dialog(dialog), ChallengeResult::Dialog(_)) => {
                     if let ChallengeInput::Dialog(answer) = &input {
                         match dialog.turns.get(answer.turn_index) {
-                            Some(turn) => Ok(turn.correct_option == Some(answer.selected_option)),
+                            Some(turn) => {
+                                Ok((turn.correct_option == Some(answer.selected_option)).into())
+                            }
                             None => Err(ChallengeError::InvalidInput(format!(
                                 "turn index {} out of bounds",
                                 answer.turn_index
@@ -127,14 +302,41 @@ impl Solvable for Challenge {
                 }
                 _ => Err(ChallengeError::InvalidChallengeType),
             },
-            Err(_) => Ok(false),
+            Err(_) => Ok(AnswerVerdict::Wrong),
         }
     }
 }
 
 impl Performance for Challenge {
     fn performance(&self, result: &ChallengeResult) -> u32 {
-        self.challenge_type.performance(result)
+        // Skipped tasks and tasks whose time ran out earn nothing, even if
+        // answered after the time ran out
+        let mut result = result.clone();
+        for task_index in 0..result.len() {
+            if !self.answered_in_time(task_index)
+                && let Some(input) = self.unanswered_input(task_index)
+            {
+                let _ = result.set_input(task_index, input);
+            }
+        }
+        self.challenge_config
+            .scoring
+            .score(&self.challenge_type, &result)
+    }
+
+    /// Uses the reward policy of the config, or else the default one. The
+    /// policy of the game path is only known to the game, see
+    /// `Game::calculate_stars`.
+    fn stars(&self, result: &ChallengeResult) -> u32 {
+        let context = RewardContext {
+            performance: self.performance(result),
+            ..RewardContext::of(self)
+        };
+        self.challenge_config
+            .rewards
+            .clone()
+            .unwrap_or_default()
+            .stars_for(&context)
     }
 }
 
@@ -190,6 +392,64 @@ mod tests {
         assert!(result.unwrap());
     }
 
+    #[test]
+    fn request_hints() {
+        let challenge_type = ChallengeType::GapFill(GapFill::default());
+        let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+        let hints = challenge_type.hints(0);
+
+        assert_eq!(challenge.request_hint(0), Ok(hints[0].clone()));
+        assert_eq!(challenge.request_hint(0), Ok(hints[1].clone()));
+        assert_eq!(challenge.hints_used(), 2);
+        assert_eq!(challenge.hints_for_task(0), vec![&hints[0], &hints[1]]);
+        assert!(challenge.hints_for_task(1).is_empty());
+
+        for _ in 2..hints.len() {
+            challenge.request_hint(0).unwrap();
+        }
+        assert_eq!(challenge.request_hint(0), Err(ChallengeError::NoMoreHints));
+        assert_eq!(challenge.hints_used(), hints.len());
+    }
+
+    #[test]
+    fn unanswered_input_is_wrong() {
+        let challenge_type = ChallengeType::default();
+        let ChallengeType::MultipleChoice(dataset) = &challenge_type else {
+            unreachable!()
+        };
+        let first_option = dataset.options[0].id;
+        let task_index = dataset
+            .questions
+            .iter()
+            .position(|question| question.option == first_option)
+            .unwrap();
+        let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+
+        let input = challenge.unanswered_input(task_index).unwrap();
+        assert!(!challenge.solve(input, task_index).unwrap());
+    }
+
+    #[test]
+    fn timed_out_tasks_earn_nothing() {
+        let challenge_type = ChallengeType::default();
+        let ChallengeType::MultipleChoice(dataset) = &challenge_type else {
+            unreachable!()
+        };
+        let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+        for (task_index, question) in dataset.questions.iter().enumerate() {
+            let input = ChallengeInput::MultipleChoice(MultipleChoiceOption {
+                id: question.option,
+                name: String::new(),
+            });
+            challenge.solve(input, task_index).unwrap();
+        }
+        assert_eq!(challenge.performance(&challenge.challenge_result), 100);
+
+        challenge.timed_out_tasks = vec![0];
+        let expected = 100 * (dataset.questions.len() as u32 - 1) / dataset.questions.len() as u32;
+        assert_eq!(challenge.performance(&challenge.challenge_result), expected);
+    }
+
     #[test]
     fn performance_with_timer() {
         let challenge_type = ChallengeType::default();
@@ -300,7 +560,7 @@ mod tests {
         let voc_challenge = Challenge::new(&voc_type, &ChallengeConfig::default());
         assert!(matches!(
             voc_challenge.challenge_result,
-            ChallengeResult::Vocabulary
+            ChallengeResult::Vocabulary(_)
         ));
 
         // Dialog
@@ -310,6 +570,30 @@ mod tests {
             dlg_challenge.challenge_result,
             ChallengeResult::Dialog(_)
         ));
+
+        // Matching
+        let mat_type = ChallengeType::Matching(Matching::default());
+        let mat_challenge = Challenge::new(&mat_type, &ChallengeConfig::default());
+        assert!(matches!(
+            mat_challenge.challenge_result,
+            ChallengeResult::Matching(_)
+        ));
+
+        // Translation
+        let tr_type = ChallengeType::Translation(Translation::default());
+        let tr_challenge = Challenge::new(&tr_type, &ChallengeConfig::default());
+        assert!(matches!(
+            tr_challenge.challenge_result,
+            ChallengeResult::Translation(_)
+        ));
+
+        // ErrorCorrection
+        let ec_type = ChallengeType::ErrorCorrection(ErrorCorrection::default());
+        let ec_challenge = Challenge::new(&ec_type, &ChallengeConfig::default());
+        assert!(matches!(
+            ec_challenge.challenge_result,
+            ChallengeResult::ErrorCorrection(_)
+        ));
     }
 
     #[test]
@@ -427,6 +711,9 @@ mod tests {
                     },
                 ],
             }],
+            grading: GradingPolicy::default(),
+            level: None,
+            tags: vec![],
         };
 
         let challenge_type = ChallengeType::ContextualChoice(contextual_choice);
@@ -460,4 +747,138 @@ mod tests {
         assert!(result.is_ok(), "Solve should not error: {:?}", result);
         assert!(!result.unwrap(), "Should be incorrect");
     }
+
+    #[test]
+    fn grade_gap_fill_with_typo() {
+        let challenge_type = ChallengeType::GapFill(GapFill {
+            grading: GradingPolicy::tolerant(),
+            ..GapFill::default()
+        });
+        let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+
+        let input = ChallengeInput::GapFill(GapFillAnswer {
+            question_index: 0,
+            answers: vec!["Bin".to_string(), "gefahen".to_string()],
+        });
+        let verdict = challenge.grade(input.clone(), 0).unwrap();
+        assert_eq!(verdict, AnswerVerdict::CorrectWithTypo);
+        assert!(challenge.solve(input, 0).unwrap());
+
+        let input = ChallengeInput::GapFill(GapFillAnswer {
+            question_index: 0,
+            answers: vec!["bin".to_string(), "gefahrt".to_string()],
+        });
+        assert_eq!(challenge.grade(input, 0).unwrap(), AnswerVerdict::Wrong);
+    }
+
+    #[test]
+    fn solve_vocabulary_recall() {
+        let vocabulary = Vocabulary {
+            mode: VocabularyMode::Recall,
+            ..Vocabulary::default()
+        };
+        let challenge_type = ChallengeType::Vocabulary(vocabulary.clone());
+        let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+
+        let typed = |index: usize, text: &str| {
+            ChallengeInput::Vocabulary(VocabularyAnswer {
+                item_id: vocabulary.items[index].id,
+                response: VocabularyResponse::Typed(text.to_string()),
+            })
+        };
+
+        assert!(challenge.solve(typed(0, "the apple"), 0).unwrap());
+        assert!(!challenge.solve(typed(1, "the cat"), 1).unwrap());
+        assert_eq!(challenge.challenge_result.len(), 2);
+
+        let performance = challenge.performance(&challenge.challenge_result);
+        assert_eq!(performance, (100 / vocabulary.quiz_items().count()) as u32);
+    }
+
+    #[test]
+    fn solve_ordering_with_partial_credit() {
+        let ordering = Ordering::default();
+        let item = &ordering.items[0];
+        let mut wrong_order = item.correct_order.clone();
+        wrong_order.swap(0, 1);
+        let config = ChallengeConfig {
+            scoring: ScoringPolicy::PartialCredit,
+            ..ChallengeConfig::default()
+        };
+        let challenge_type = ChallengeType::Ordering(ordering.clone());
+        let mut challenge = Challenge::new(&challenge_type, &config);
+
+        let input = ChallengeInput::Ordering(OrderingResult { order: wrong_order });
+        assert!(!challenge.solve(input, 0).unwrap());
+
+        let elements = item.correct_order.len();
+        let expected = 100 * (elements - 1) / (elements * ordering.items.len());
+        let performance = challenge.performance(&challenge.challenge_result);
+        assert_eq!(performance, expected as u32);
+    }
+
+    #[test]
+    fn solve_matching() {
+        let matching = Matching::default();
+        let challenge_type = ChallengeType::Matching(matching.clone());
+        let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+
+        let first = matching.pairs[0].id;
+        let second = matching.pairs[1].id;
+        let input = |left, right| ChallengeInput::Matching(MatchingAnswer { left, right });
+
+        assert!(challenge.solve(input(first, first), 0).unwrap());
+        assert!(!challenge.solve(input(second, first), 1).unwrap());
+
+        let performance = challenge.performance(&challenge.challenge_result);
+        assert_eq!(performance, (100 / matching.pairs.len()) as u32);
+    }
+
+    #[test]
+    fn solve_translation_with_partial_credit() {
+        let translation = Translation::default();
+        let challenge_type = ChallengeType::Translation(translation.clone());
+        let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+
+        let input = |item_index, text: &str| {
+            ChallengeInput::Translation(TranslationAnswer {
+                item_index,
+                text: text.to_string(),
+            })
+        };
+
+        let reference = translation.items[0].translation.clone();
+        assert!(challenge.solve(input(0, &reference), 0).unwrap());
+        assert!(!challenge.solve(input(1, ""), 1).unwrap());
+
+        let performance = challenge.performance(&challenge.challenge_result);
+        assert_eq!(performance, (100 / translation.items.len()) as u32);
+    }
+
+    #[test]
+    fn solve_error_correction_credits_found_mistakes() {
+        let error_correction = ErrorCorrection::default();
+        let challenge_type = ChallengeType::ErrorCorrection(error_correction.clone());
+        let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+
+        let input = |sentence_index: usize, text: &str| {
+            let error = &error_correction.sentences[sentence_index].errors[0];
+            ChallengeInput::ErrorCorrection(ErrorCorrectionAnswer {
+                sentence_index,
+                corrections: vec![WordCorrection {
+                    position: error.position,
+                    text: text.to_string(),
+                }],
+            })
+        };
+
+        let fix = error_correction.sentences[0].errors[0].correction.clone();
+        assert!(challenge.solve(input(0, &fix), 0).unwrap());
+        // Found, but not fixed
+        assert!(!challenge.solve(input(1, "und"), 1).unwrap());
+
+        let performance = challenge.performance(&challenge.challenge_result);
+        let expected = 150 / (2 * error_correction.sentences.len());
+        assert_eq!(performance, expected as u32);
+    }
 }
@@ -1,4 +1,7 @@
+use super::CefrLevel;
 use super::challenge_variant::ChallengeVariant;
+use super::performance::{RewardPolicy, ScoringPolicy};
+use super::time_limit::TimeLimit;
 use crate::challenges::task_pattern::TaskPattern;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
@@ -13,14 +16,49 @@ pub struct ChallengeConfig {
     pub challenge: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variant: Option<ChallengeVariant>,
-    /// Number or range of tasks to select (e.g. "10", "5:random", "1..5")
+    /// Number or range of tasks to select (e.g. "10", "5:random", "1..5",
+    /// "5:random@42" for a fixed seed or "10:adaptive" to practice weak tasks)
     #[cfg_attr(feature = "schema", schemars(with = "String"))]
     pub tasks: TaskPattern,
+    /// XP needed before the challenge unlocks
     pub unlock_points: usize,
+    /// Stars, summed over the best attempt of every challenge, needed before
+    /// the challenge unlocks
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub unlock_stars: u32,
+    /// Ids of the challenges that have to be completed first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    /// A side quest, not needed to finish the game path
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub elective: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub position: Option<(i32, i32)>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
+    /// How partly correct tasks are scored
+    #[serde(default)]
+    pub scoring: ScoringPolicy,
+    /// Overrides the reward policy of the game path for this challenge
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rewards: Option<RewardPolicy>,
+    /// CEFR level, overrides the level of the challenge type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, added to the tags of the challenge type
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Time limits for the challenge and its tasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit: Option<TimeLimit>,
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
 }
 
 impl Default for ChallengeConfig {
@@ -33,8 +71,16 @@ impl Default for ChallengeConfig {
             variant: None,
             tasks: 10.into(),
             unlock_points: 0,
+            unlock_stars: 0,
+            requires: vec![],
+            elective: false,
             position: Some((0, 0)),
             icon: None,
+            scoring: ScoringPolicy::default(),
+            rewards: None,
+            level: None,
+            tags: vec![],
+            time_limit: None,
         }
     }
 }
@@ -55,6 +101,12 @@ mod tests {
         assert_eq!(challenge_config.challenge, "konnektoren");
         assert_eq!(challenge_config.tasks, 10.into());
         assert_eq!(challenge_config.unlock_points, 0);
+        assert_eq!(challenge_config.unlock_stars, 0);
+        assert!(challenge_config.requires.is_empty());
+        assert!(!challenge_config.elective);
         assert_eq!(challenge_config.icon, None);
+        assert_eq!(challenge_config.scoring, ScoringPolicy::Exact);
+        assert_eq!(challenge_config.rewards, None);
+        assert_eq!(challenge_config.time_limit, None);
     }
 }
@@ -1,8 +1,14 @@
 use crate::challenges::Base64Serializable;
+use crate::challenges::CefrLevel;
 use crate::challenges::challenge::Challenge;
 use crate::challenges::challenge_config::ChallengeConfig;
+use crate::challenges::challenge_history::ChallengeHistory;
+use crate::challenges::challenge_query::ChallengeQuery;
 use crate::challenges::challenge_type::ChallengeType;
 use crate::challenges::error::{ChallengeError, Result};
+use crate::challenges::task_pattern::TaskPattern;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng, thread_rng};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -31,16 +37,117 @@ impl ChallengeFactory {
         }
     }
 
+    /// Creates a challenge with the tasks of the config. Random task patterns
+    /// draw a fresh seed, unless the pattern has its own, and the seed is
+    /// stored on the challenge so the same tasks can be selected again.
     pub fn create_challenge(&self, challenge_config: &ChallengeConfig) -> Result<Challenge> {
-        let challenge_type = self
-            .challenge_types
+        match &challenge_config.tasks {
+            tasks if tasks.is_random() => {
+                let seed = tasks.seed().unwrap_or_else(|| thread_rng().r#gen());
+                self.create_challenge_with_seed(challenge_config, seed)
+            }
+            tasks => {
+                let challenge_type = self.find_challenge_type(challenge_config)?;
+                Ok(Challenge::new(
+                    &challenge_type.of_tasks(tasks),
+                    challenge_config,
+                ))
+            }
+        }
+    }
+
+    /// Creates a challenge like [`ChallengeFactory::create_challenge`], but an
+    /// adaptive task pattern selects the tasks the player needs to practice
+    /// according to `history`. The selection draws a fresh seed, which is
+    /// stored on the challenge.
+    pub fn create_challenge_for_history(
+        &self,
+        challenge_config: &ChallengeConfig,
+        history: &ChallengeHistory,
+    ) -> Result<Challenge> {
+        match &challenge_config.tasks {
+            TaskPattern::Adaptive(_) => self.create_challenge_for_history_with_seed(
+                challenge_config,
+                history,
+                thread_rng().r#gen(),
+            ),
+            _ => self.create_challenge(challenge_config),
+        }
+    }
+
+    /// Creates a challenge whose adaptive tasks are selected with `seed`, so
+    /// the same seed and history select the same tasks.
+    pub fn create_challenge_for_history_with_seed(
+        &self,
+        challenge_config: &ChallengeConfig,
+        history: &ChallengeHistory,
+        seed: u64,
+    ) -> Result<Challenge> {
+        match &challenge_config.tasks {
+            TaskPattern::Adaptive(count) => {
+                let challenge_type = self.find_challenge_type(challenge_config)?;
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut challenge = Challenge::new(
+                    &challenge_type.of_tasks_adaptive(*count, history, &mut rng),
+                    challenge_config,
+                );
+                challenge.seed = Some(seed);
+                Ok(challenge)
+            }
+            _ => self.create_challenge_with_seed(challenge_config, seed),
+        }
+    }
+
+    /// Creates a challenge whose random tasks are selected with `seed`.
+    pub fn create_challenge_with_seed(
+        &self,
+        challenge_config: &ChallengeConfig,
+        seed: u64,
+    ) -> Result<Challenge> {
+        let challenge_type = self.find_challenge_type(challenge_config)?;
+        let tasks = challenge_config.tasks.with_seed(seed);
+        let mut challenge = Challenge::new(&challenge_type.of_tasks(&tasks), challenge_config);
+        challenge.seed = tasks.seed();
+        Ok(challenge)
+    }
+
+    /// Returns the challenge types whose level and tags match the query.
+    pub fn challenge_types_matching(&self, query: &ChallengeQuery) -> Vec<&ChallengeType> {
+        self.challenge_types
+            .iter()
+            .filter(|challenge_type| query.matches(challenge_type.level(), challenge_type.tags()))
+            .collect()
+    }
+
+    /// Returns the level of the config, or else the level of its challenge
+    /// type.
+    pub fn level_of(&self, challenge_config: &ChallengeConfig) -> Option<CefrLevel> {
+        challenge_config.level.or_else(|| {
+            self.find_challenge_type(challenge_config)
+                .ok()
+                .and_then(ChallengeType::level)
+        })
+    }
+
+    /// Returns the tags of the config followed by the tags of its challenge
+    /// type, without duplicates.
+    pub fn tags_of(&self, challenge_config: &ChallengeConfig) -> Vec<String> {
+        let mut tags = challenge_config.tags.clone();
+        if let Ok(challenge_type) = self.find_challenge_type(challenge_config) {
+            for tag in challenge_type.tags() {
+                if !tags.iter().any(|known| known.eq_ignore_ascii_case(tag)) {
+                    tags.push(tag.clone());
+                }
+            }
+        }
+        tags
+    }
+
+    fn find_challenge_type(&self, challenge_config: &ChallengeConfig) -> Result<&ChallengeType> {
+        self.challenge_types
             .iter()
             .find(|challenge_type| challenge_type.id() == challenge_config.challenge)
-            .ok_or(ChallengeError::ChallengeTypeNotFound)?;
-        Ok(Challenge::new(
-            &challenge_type.of_tasks(&challenge_config.tasks),
-            challenge_config,
-        ))
+            .ok_or(ChallengeError::ChallengeTypeNotFound)
     }
 
     pub fn add_challenge_from_base64(&mut self, base64_data: &str) -> Result<()> {
@@ -84,6 +191,119 @@ mod tests {
         }
     }
 
+    #[test]
+    fn same_seed_selects_same_tasks() {
+        let factory = ChallengeFactory::default();
+        let challenge_config = ChallengeConfig {
+            challenge: "konnektoren".to_string(),
+            tasks: "5:random".into(),
+            ..Default::default()
+        };
+
+        let first = factory.create_challenge(&challenge_config).unwrap();
+        let seed = first.seed.expect("random tasks have a seed");
+        let replay = factory
+            .create_challenge_with_seed(&challenge_config, seed)
+            .unwrap();
+        assert_eq!(first.challenge_type, replay.challenge_type);
+        assert_eq!(replay.seed, Some(seed));
+
+        let seeded_config = ChallengeConfig {
+            tasks: "5:random@42".into(),
+            ..challenge_config.clone()
+        };
+        let seeded = factory.create_challenge(&seeded_config).unwrap();
+        assert_eq!(seeded.seed, Some(42));
+        assert_eq!(
+            seeded.challenge_type,
+            factory
+                .create_challenge_with_seed(&challenge_config, 42)
+                .unwrap()
+                .challenge_type
+        );
+    }
+
+    #[test]
+    fn adaptive_tasks_use_history() {
+        let factory = ChallengeFactory::default();
+        let challenge_config = ChallengeConfig {
+            challenge: "konnektoren".to_string(),
+            tasks: "4:adaptive".into(),
+            ..Default::default()
+        };
+        let challenge = factory
+            .create_challenge_for_history(&challenge_config, &ChallengeHistory::default())
+            .unwrap();
+        match challenge.challenge_type {
+            ChallengeType::MultipleChoice(dataset) => assert!(!dataset.questions.is_empty()),
+            _ => panic!("Invalid challenge type"),
+        }
+    }
+
+    #[test]
+    fn same_seed_and_history_select_same_tasks() {
+        let factory = ChallengeFactory::default();
+        let challenge_config = ChallengeConfig {
+            challenge: "konnektoren".to_string(),
+            tasks: "4:adaptive".into(),
+            ..Default::default()
+        };
+        let mut history = ChallengeHistory::default();
+        history.add_challenge(
+            factory
+                .create_challenge(&ChallengeConfig {
+                    tasks: 3.into(),
+                    ..challenge_config.clone()
+                })
+                .unwrap(),
+        );
+
+        let first = factory
+            .create_challenge_for_history(&challenge_config, &history)
+            .unwrap();
+        let seed = first.seed.expect("adaptive tasks have a seed");
+        let replay = factory
+            .create_challenge_for_history_with_seed(&challenge_config, &history, seed)
+            .unwrap();
+        assert_eq!(first.challenge_type, replay.challenge_type);
+        assert_eq!(replay.seed, Some(seed));
+    }
+
+    #[test]
+    fn exact_tasks_have_no_seed() {
+        let factory = ChallengeFactory::default();
+        let challenge_config = ChallengeConfig {
+            challenge: "konnektoren".to_string(),
+            tasks: 3.into(),
+            ..Default::default()
+        };
+        let challenge = factory.create_challenge(&challenge_config).unwrap();
+        assert_eq!(challenge.seed, None);
+    }
+
+    #[test]
+    fn shared_challenge_keeps_seed() {
+        let factory = ChallengeFactory::default();
+        let challenge_config = ChallengeConfig {
+            challenge: "konnektoren".to_string(),
+            tasks: "3:random".into(),
+            ..Default::default()
+        };
+        let challenge = factory
+            .create_challenge_with_seed(&challenge_config, 7)
+            .unwrap();
+
+        let shared = Challenge::from_base64(&challenge.to_base64().unwrap()).unwrap();
+        assert_eq!(shared.seed, Some(7));
+        assert_eq!(
+            factory
+                .create_challenge_with_seed(&shared.challenge_config, shared.seed.unwrap())
+                .unwrap()
+                .challenge_type,
+            challenge.challenge_type
+        );
+    }
+
     #[test]
     fn test_add_challenge_from_base64() {
         let mut factory = ChallengeFactory::new();
@@ -99,6 +319,32 @@ mod tests {
         assert_eq!(factory.challenge_types[0].id(), original_challenge.id());
     }
 
+    #[test]
+    fn query_includes_challenge_type_level_and_tags() {
+        let mut factory = ChallengeFactory::default();
+        if let ChallengeType::MultipleChoice(dataset) = &mut factory.challenge_types[0] {
+            dataset.level = Some(CefrLevel::B1);
+            dataset.tags = vec!["konnektoren".to_string()];
+        }
+        let inherited = ChallengeConfig {
+            challenge: factory.challenge_types[0].id().to_string(),
+            ..ChallengeConfig::default()
+        };
+        let overridden = ChallengeConfig {
+            level: Some(CefrLevel::B2),
+            ..inherited.clone()
+        };
+
+        let query = ChallengeQuery::new()
+            .level(CefrLevel::B1)
+            .tag("Konnektoren");
+        assert_eq!(factory.challenge_types_matching(&query).len(), 1);
+        assert!(query.matches(factory.level_of(&inherited), &factory.tags_of(&inherited)));
+        assert_eq!(factory.level_of(&overridden), Some(CefrLevel::B2));
+        assert!(!query.matches(factory.level_of(&overridden), &factory.tags_of(&overridden)));
+        assert!(!query.matches(inherited.level, &inherited.tags));
+    }
+
     #[test]
     fn test_export_challenge_to_base64() {
         let factory = ChallengeFactory::default();
@@ -1,6 +1,6 @@
 use crate::challenges::{
-    ContextItemChoiceAnswers, DialogAnswer, GapFillAnswer, MultipleChoiceOption, OrderingResult,
-    SortTableRow,
+    ContextItemChoiceAnswers, DialogAnswer, ErrorCorrectionAnswer, GapFillAnswer, MatchingAnswer,
+    MultipleChoiceOption, OrderingResult, SortTableRow, TranslationAnswer, VocabularyAnswer,
 };
 use serde::{Deserialize, Serialize};
 
@@ -12,4 +12,8 @@ pub enum ChallengeInput {
     SortTable(SortTableRow),
     Ordering(OrderingResult),
     Dialog(DialogAnswer),
+    Vocabulary(VocabularyAnswer),
+    Matching(MatchingAnswer),
+    Translation(TranslationAnswer),
+    ErrorCorrection(ErrorCorrectionAnswer),
 }
@@ -0,0 +1,88 @@
+use super::CefrLevel;
+use std::ops::RangeInclusive;
+
+/// Selects challenges by CEFR level and topic tags, e.g. all A2 challenges
+/// tagged `konnektoren`.
+///
+/// ```
+/// use konnektoren_core::challenges::{CefrLevel, ChallengeQuery};
+///
+/// let query = ChallengeQuery::new().level(CefrLevel::A2).tag("konnektoren");
+/// assert!(query.matches(Some(CefrLevel::A2), &["Konnektoren".to_string()]));
+/// assert!(!query.matches(None, &["konnektoren".to_string()]));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChallengeQuery {
+    /// Levels to select, any level if not set
+    pub levels: Option<RangeInclusive<CefrLevel>>,
+    /// Tags that all have to be present, compared case-insensitively
+    pub tags: Vec<String>,
+}
+
+impl ChallengeQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn level(self, level: CefrLevel) -> Self {
+        self.levels(level..=level)
+    }
+
+    pub fn levels(mut self, levels: RangeInclusive<CefrLevel>) -> Self {
+        self.levels = Some(levels);
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Returns true if a challenge with the level and tags is selected.
+    /// Challenges without a level are only selected if no levels are set.
+    pub fn matches(&self, level: Option<CefrLevel>, tags: &[String]) -> bool {
+        let level_matches = match (&self.levels, level) {
+            (None, _) => true,
+            (Some(levels), Some(level)) => levels.contains(&level),
+            (Some(_), None) => false,
+        };
+        level_matches
+            && self
+                .tags
+                .iter()
+                .all(|wanted| tags.iter().any(|tag| tag.eq_ignore_ascii_case(wanted)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        let query = ChallengeQuery::new();
+        assert!(query.matches(None, &[]));
+        assert!(query.matches(Some(CefrLevel::C2), &tags(&["grammar"])));
+    }
+
+    #[test]
+    fn match_level_range() {
+        let query = ChallengeQuery::new().levels(CefrLevel::A2..=CefrLevel::B1);
+        assert!(!query.matches(Some(CefrLevel::A1), &[]));
+        assert!(query.matches(Some(CefrLevel::A2), &[]));
+        assert!(query.matches(Some(CefrLevel::B1), &[]));
+        assert!(!query.matches(Some(CefrLevel::B2), &[]));
+        assert!(!query.matches(None, &[]));
+    }
+
+    #[test]
+    fn all_tags_are_required() {
+        let query = ChallengeQuery::new().tag("konnektoren").tag("b1-exam");
+        assert!(query.matches(None, &tags(&["B1-Exam", "konnektoren", "grammar"])));
+        assert!(!query.matches(None, &tags(&["konnektoren"])));
+    }
+}
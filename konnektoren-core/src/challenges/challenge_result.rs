@@ -1,11 +1,23 @@
 use crate::challenges::error::{ChallengeError, Result};
 use crate::challenges::{
-    ChallengeInput, ContextItemChoiceAnswers, CustomChallengeResult, DialogAnswer, GapFillAnswer,
-    MultipleChoiceOption, OrderingResult, SortTableRow,
+    ChallengeInput, ContextItemChoiceAnswers, CustomChallengeResult, DialogAnswer,
+    ErrorCorrectionAnswer, GapFillAnswer, MatchingAnswer, MultipleChoiceOption, OrderingResult,
+    SortTableRow, TranslationAnswer, VocabularyAnswer,
 };
-use serde::{Deserialize, Serialize};
-
+use serde::de::value::{
+    EnumAccessDeserializer, MapAccessDeserializer, StrDeserializer, U32Deserializer,
+};
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+
+/// The answers given to the tasks of a challenge.
+///
+/// Results saved before vocabulary answers were recorded hold the unit
+/// variant `"Vocabulary"`; they are still read, as a vocabulary result
+/// without answers.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(remote = "Self")]
 pub enum ChallengeResult {
     MultipleChoice(Vec<MultipleChoiceOption>),
     ContextualChoice(Vec<ContextItemChoiceAnswers>),
@@ -14,8 +26,63 @@ pub enum ChallengeResult {
     Informative,
     Ordering(Vec<OrderingResult>),
     Custom(CustomChallengeResult),
-    Vocabulary,
+    Vocabulary(Vec<VocabularyAnswer>),
     Dialog(Vec<DialogAnswer>),
+    Matching(Vec<MatchingAnswer>),
+    Translation(Vec<TranslationAnswer>),
+    ErrorCorrection(Vec<ErrorCorrectionAnswer>),
+}
+
+impl Serialize for ChallengeResult {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        ChallengeResult::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ChallengeResult {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(ChallengeResultVisitor)
+    }
+}
+
+/// Reads the legacy unit variant `Vocabulary` and hands everything else to
+/// the derived implementation.
+struct ChallengeResultVisitor;
+
+impl<'de> Visitor<'de> for ChallengeResultVisitor {
+    type Value = ChallengeResult;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a challenge result")
+    }
+
+    fn visit_str<E: de::Error>(self, variant: &str) -> std::result::Result<Self::Value, E> {
+        match variant {
+            "Vocabulary" => Ok(ChallengeResult::Vocabulary(Vec::new())),
+            _ => ChallengeResult::deserialize(StrDeserializer::<E>::new(variant)),
+        }
+    }
+
+    /// Unit variants written by their index, e.g. by MessagePack.
+    fn visit_u64<E: de::Error>(self, index: u64) -> std::result::Result<Self::Value, E> {
+        let index = u32::try_from(index)
+            .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(index), &self))?;
+        ChallengeResult::deserialize(U32Deserializer::<E>::new(index))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(
+        self,
+        map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        ChallengeResult::deserialize(MapAccessDeserializer::new(map))
+    }
+
+    fn visit_enum<A: de::EnumAccess<'de>>(
+        self,
+        data: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        ChallengeResult::deserialize(EnumAccessDeserializer::new(data))
+    }
 }
 
 impl Default for ChallengeResult {
@@ -74,7 +141,15 @@ impl ChallengeResult {
             },
             ChallengeResult::Informative => Ok(()),
             ChallengeResult::Custom(_) => Ok(()),
-            ChallengeResult::Vocabulary => Ok(()),
+            ChallengeResult::Vocabulary(answers) => match input {
+                ChallengeInput::Vocabulary(answer) => {
+                    answers.push(answer);
+                    Ok(())
+                }
+                _ => Err(ChallengeError::InvalidInput(
+                    "Expected Vocabulary input".to_string(),
+                )),
+            },
             ChallengeResult::Dialog(answers) => match input {
                 ChallengeInput::Dialog(answer) => {
                     answers.push(answer);
@@ -84,6 +159,33 @@ impl ChallengeResult {
                     "Expected Dialog input".to_string(),
                 )),
             },
+            ChallengeResult::Matching(answers) => match input {
+                ChallengeInput::Matching(answer) => {
+                    answers.push(answer);
+                    Ok(())
+                }
+                _ => Err(ChallengeError::InvalidInput(
+                    "Expected Matching input".to_string(),
+                )),
+            },
+            ChallengeResult::Translation(answers) => match input {
+                ChallengeInput::Translation(answer) => {
+                    answers.push(answer);
+                    Ok(())
+                }
+                _ => Err(ChallengeError::InvalidInput(
+                    "Expected Translation input".to_string(),
+                )),
+            },
+            ChallengeResult::ErrorCorrection(answers) => match input {
+                ChallengeInput::ErrorCorrection(answer) => {
+                    answers.push(answer);
+                    Ok(())
+                }
+                _ => Err(ChallengeError::InvalidInput(
+                    "Expected ErrorCorrection input".to_string(),
+                )),
+            },
         }
     }
 
@@ -162,7 +264,18 @@ impl ChallengeResult {
             },
             ChallengeResult::Informative => Ok(()),
             ChallengeResult::Custom(_) => Ok(()),
-            ChallengeResult::Vocabulary => Ok(()),
+            ChallengeResult::Vocabulary(answers) => match input {
+                ChallengeInput::Vocabulary(answer) => {
+                    while answers.len() <= index {
+                        answers.push(VocabularyAnswer::default());
+                    }
+                    answers[index] = answer;
+                    Ok(())
+                }
+                _ => Err(ChallengeError::InvalidInput(
+                    "Expected Vocabulary input".to_string(),
+                )),
+            },
             ChallengeResult::Dialog(answers) => match input {
                 ChallengeInput::Dialog(answer) => {
                     answers.push(answer);
@@ -172,6 +285,42 @@ impl ChallengeResult {
                     "Expected Dialog input".to_string(),
                 )),
             },
+            ChallengeResult::Matching(answers) => match input {
+                ChallengeInput::Matching(answer) => {
+                    while answers.len() <= index {
+                        answers.push(MatchingAnswer::default());
+                    }
+                    answers[index] = answer;
+                    Ok(())
+                }
+                _ => Err(ChallengeError::InvalidInput(
+                    "Expected Matching input".to_string(),
+                )),
+            },
+            ChallengeResult::Translation(answers) => match input {
+                ChallengeInput::Translation(answer) => {
+                    while answers.len() <= index {
+                        answers.push(TranslationAnswer::default());
+                    }
+                    answers[index] = answer;
+                    Ok(())
+                }
+                _ => Err(ChallengeError::InvalidInput(
+                    "Expected Translation input".to_string(),
+                )),
+            },
+            ChallengeResult::ErrorCorrection(answers) => match input {
+                ChallengeInput::ErrorCorrection(answer) => {
+                    while answers.len() <= index {
+                        answers.push(ErrorCorrectionAnswer::default());
+                    }
+                    answers[index] = answer;
+                    Ok(())
+                }
+                _ => Err(ChallengeError::InvalidInput(
+                    "Expected ErrorCorrection input".to_string(),
+                )),
+            },
         }
     }
 
@@ -184,8 +333,11 @@ impl ChallengeResult {
             ChallengeResult::Ordering(results) => results.len(),
             ChallengeResult::Informative => 0,
             ChallengeResult::Custom(_) => 0,
-            ChallengeResult::Vocabulary => 0,
+            ChallengeResult::Vocabulary(answers) => answers.len(),
             ChallengeResult::Dialog(answers) => answers.len(),
+            ChallengeResult::Matching(answers) => answers.len(),
+            ChallengeResult::Translation(answers) => answers.len(),
+            ChallengeResult::ErrorCorrection(answers) => answers.len(),
         }
     }
 
@@ -198,8 +350,11 @@ impl ChallengeResult {
             ChallengeResult::Ordering(results) => results.is_empty(),
             ChallengeResult::Informative => true,
             ChallengeResult::Custom(_) => true,
-            ChallengeResult::Vocabulary => true,
+            ChallengeResult::Vocabulary(answers) => answers.is_empty(),
             ChallengeResult::Dialog(answers) => answers.is_empty(),
+            ChallengeResult::Matching(answers) => answers.is_empty(),
+            ChallengeResult::Translation(answers) => answers.is_empty(),
+            ChallengeResult::ErrorCorrection(answers) => answers.is_empty(),
         }
     }
 }
@@ -493,4 +648,64 @@ mod tests {
         assert!(res.is_err());
         assert!(matches!(res.unwrap_err(), ChallengeError::InvalidInput(_)));
     }
+
+    #[test]
+    fn deserialize_legacy_vocabulary_result() {
+        let result: ChallengeResult = serde_json::from_str(r#""Vocabulary""#).unwrap();
+        assert_eq!(result, ChallengeResult::Vocabulary(Vec::new()));
+
+        let result: ChallengeResult = serde_yaml::from_str("Vocabulary").unwrap();
+        assert_eq!(result, ChallengeResult::Vocabulary(Vec::new()));
+
+        // a challenge from the history of an old saved state
+        let mut saved = serde_json::to_value(crate::challenges::Challenge::default()).unwrap();
+        saved["challenge_result"] = serde_json::json!("Vocabulary");
+        let challenge: crate::challenges::Challenge = serde_json::from_value(saved).unwrap();
+        assert_eq!(
+            challenge.challenge_result,
+            ChallengeResult::Vocabulary(Vec::new())
+        );
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let results = vec![
+            ChallengeResult::Informative,
+            ChallengeResult::MultipleChoice(vec![MultipleChoiceOption::default()]),
+            ChallengeResult::Vocabulary(vec![VocabularyAnswer::default()]),
+        ];
+        for result in results {
+            let json = serde_json::to_string(&result).unwrap();
+            assert_eq!(
+                serde_json::from_str::<ChallengeResult>(&json).unwrap(),
+                result
+            );
+            let yaml = serde_yaml::to_string(&result).unwrap();
+            assert_eq!(
+                serde_yaml::from_str::<ChallengeResult>(&yaml).unwrap(),
+                result
+            );
+            let msgpack = rmp_serde::to_vec_named(&result).unwrap();
+            assert_eq!(
+                rmp_serde::from_slice::<ChallengeResult>(&msgpack).unwrap(),
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_vocabulary_set_input() {
+        let mut result = ChallengeResult::Vocabulary(Vec::new());
+        assert!(result.is_empty());
+
+        let input = ChallengeInput::Vocabulary(crate::challenges::VocabularyAnswer {
+            item_id: 2,
+            response: crate::challenges::VocabularyResponse::Known(true),
+        });
+        assert!(result.set_input(1, input).is_ok());
+        assert_eq!(result.len(), 2);
+
+        let wrong = ChallengeInput::MultipleChoice(MultipleChoiceOption::default());
+        assert!(result.add_input(wrong).is_err());
+    }
 }
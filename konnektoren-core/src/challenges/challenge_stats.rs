@@ -1,11 +1,20 @@
-use crate::challenges::{ChallengeConfig, ChallengeHistory, Performance};
+use crate::challenges::{
+    Challenge, ChallengeConfig, ChallengeHistory, Performance, RewardContext, RewardPolicy,
+};
 
 pub trait ChallengeStats {
     fn challenges(&self) -> usize;
 
     fn completed_challenges(&self) -> usize;
 
-    fn stars(&self) -> u32;
+    /// The policy that decides how many stars the performance is worth.
+    fn reward_policy(&self) -> RewardPolicy {
+        RewardPolicy::default()
+    }
+
+    fn stars(&self) -> u32 {
+        self.reward_policy().stars(self.performance())
+    }
 
     fn performance(&self) -> u32;
 
@@ -21,19 +30,6 @@ impl ChallengeStats for ChallengeHistory {
         self.challenges.iter().filter(|c| c.solved()).count()
     }
 
-    fn stars(&self) -> u32 {
-        let performance = self.performance();
-        if performance >= 80 {
-            3
-        } else if performance >= 60 {
-            2
-        } else if performance >= 40 {
-            1
-        } else {
-            0
-        }
-    }
-
     fn performance(&self) -> u32 {
         let challenges = self.challenges();
         if challenges == 0 {
@@ -50,62 +46,58 @@ impl ChallengeStats for ChallengeHistory {
 
 impl ChallengeStats for (&ChallengeConfig, &ChallengeHistory) {
     fn challenges(&self) -> usize {
-        self.1
-            .challenges
-            .iter()
-            .filter(|c| c.challenge_config.id == self.0.id)
-            .count()
+        attempts(self.0, self.1).count()
     }
 
     fn completed_challenges(&self) -> usize {
-        self.1
-            .challenges
-            .iter()
-            .filter(|c| {
-                c.challenge_config.id == self.0.id
-                    && c.challenge_result.len() == c.challenge_config.tasks.len()
-            })
+        attempts(self.0, self.1)
+            .filter(|c| c.challenge_result.len() == c.challenge_config.tasks.len())
             .count()
     }
 
+    /// The challenge's own override, otherwise the default policy. Use
+    /// `(&ChallengeConfig, &Game)` for the policy of its game path.
+    fn reward_policy(&self) -> RewardPolicy {
+        self.0.rewards.clone().unwrap_or_default()
+    }
+
+    /// The stars of the best attempt, counted like the stars of the game.
     fn stars(&self) -> u32 {
-        let performance = self.performance();
-        if performance >= 80 {
-            3
-        } else if performance >= 60 {
-            2
-        } else if performance >= 40 {
-            1
-        } else {
-            0
-        }
+        let policy = self.reward_policy();
+        attempts(self.0, self.1)
+            .map(|c| policy.stars_for(&RewardContext::of(c)))
+            .max()
+            .unwrap_or_default()
     }
 
+    /// The average performance of the attempts.
     fn performance(&self) -> u32 {
         let challenges = self.challenges();
         if challenges == 0 {
             return 0;
         }
-        let total_performance: u32 = self
-            .1
-            .challenges
-            .iter()
+        let total_performance: u32 = attempts(self.0, self.1)
             .map(|c| c.performance(&c.challenge_result))
             .sum();
         total_performance / challenges as u32
     }
 
     fn solved(&self) -> bool {
-        let challenge_id = self.0.id.clone();
-        self.1
-            .challenges
-            .iter()
-            .filter(|c| c.challenge_config.id == challenge_id)
-            .count()
-            > 0
+        attempts(self.0, self.1).count() > 0
     }
 }
 
+/// Returns the attempts of the challenge in the history.
+fn attempts<'a>(
+    config: &'a ChallengeConfig,
+    history: &'a ChallengeHistory,
+) -> impl Iterator<Item = &'a Challenge> {
+    history
+        .challenges
+        .iter()
+        .filter(move |c| c.challenge_config.id == config.id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,4 +184,71 @@ mod tests {
         assert_eq!(tuple_stats.challenges(), 1);
         assert!(tuple_stats.solved());
     }
+
+    #[test]
+    fn test_tuple_stars_follow_reward_policy() {
+        let mut config = ChallengeConfig::default();
+        let mut history = ChallengeHistory::new();
+        history.add_challenge(Challenge::new(&ChallengeType::default(), &config));
+        assert_eq!((&config, &history).stars(), 0);
+
+        let mut rewards = RewardPolicy::default();
+        rewards.stars.one = 0;
+        config.rewards = Some(rewards);
+        assert_eq!((&config, &history).stars(), 1);
+    }
+
+    fn solved_challenge(config: &ChallengeConfig) -> Challenge {
+        let challenge_type = ChallengeType::default();
+        let ChallengeType::MultipleChoice(dataset) = &challenge_type else {
+            unreachable!()
+        };
+        let mut challenge = Challenge::new(&challenge_type, config);
+        challenge.challenge_result = ChallengeResult::MultipleChoice(
+            dataset
+                .questions
+                .iter()
+                .map(|q| MultipleChoiceOption {
+                    id: q.option,
+                    name: String::new(),
+                })
+                .collect(),
+        );
+        challenge
+    }
+
+    #[test]
+    fn test_tuple_counts_only_its_attempts() {
+        let config = ChallengeConfig::default();
+        let other = ChallengeConfig {
+            id: "other".to_string(),
+            ..ChallengeConfig::default()
+        };
+
+        let mut history = ChallengeHistory::new();
+        history.add_challenge(Challenge::new(&ChallengeType::default(), &config));
+        history.add_challenge(solved_challenge(&other));
+        assert_eq!((&config, &history).performance(), 0);
+        assert_eq!((&config, &history).stars(), 0);
+        assert_eq!((&other, &history).performance(), 100);
+        assert_eq!((&other, &history).stars(), 3);
+    }
+
+    #[test]
+    fn test_tuple_stars_count_hints() {
+        let mut config = ChallengeConfig::default();
+        let mut rewards = RewardPolicy::default();
+        rewards.hint_star_penalty = 25;
+        config.rewards = Some(rewards);
+
+        let mut challenge = solved_challenge(&config);
+        challenge.request_hint(1).unwrap();
+        let mut history = ChallengeHistory::new();
+        history.add_challenge(challenge.clone());
+        assert_eq!((&config, &history).stars(), 2);
+        assert_eq!(
+            (&config, &history).stars(),
+            challenge.stars(&challenge.challenge_result)
+        );
+    }
 }
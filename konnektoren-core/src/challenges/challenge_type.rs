@@ -1,11 +1,17 @@
-use super::{ChallengeResult, ContextualChoice, Custom, Performance, Placeholder};
+use super::{CefrLevel, ChallengeResult, ContextualChoice, Custom, Performance, Placeholder};
 use crate::challenges::dialog::Dialog;
+use crate::challenges::error_correction::ErrorCorrection;
+use crate::challenges::gap_fill::GapFill;
 use crate::challenges::informative::Informative;
+use crate::challenges::matching::Matching;
 use crate::challenges::multiple_choice::MultipleChoice;
 use crate::challenges::ordering::Ordering;
 use crate::challenges::sort_table::SortTable;
 use crate::challenges::task_pattern::TaskPattern;
-use crate::challenges::{gap_fill::GapFill, vocabulary::Vocabulary};
+use crate::challenges::translation::Translation;
+use crate::challenges::vocabulary::{Vocabulary, VocabularyMode};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng, thread_rng};
 #[cfg(feature = "schema")]
 use schemars::{JsonSchema, schema_for};
 use serde::{Deserialize, Serialize};
@@ -26,6 +32,9 @@ pub enum ChallengeType {
     Placeholder(Placeholder),
     Vocabulary(Vocabulary),
     Dialog(Dialog),
+    Matching(Matching),
+    Translation(Translation),
+    ErrorCorrection(ErrorCorrection),
 }
 
 impl Default for ChallengeType {
@@ -57,6 +66,9 @@ impl ChallengeType {
             ChallengeType::Placeholder(_) => schema_for!(Placeholder),
             ChallengeType::Vocabulary(_) => schema_for!(Vocabulary),
             ChallengeType::Dialog(_) => schema_for!(Dialog),
+            ChallengeType::Matching(_) => schema_for!(Matching),
+            ChallengeType::Translation(_) => schema_for!(Translation),
+            ChallengeType::ErrorCorrection(_) => schema_for!(ErrorCorrection),
         };
         serde_json::to_value(schema).expect("schemars Schema is always JSON-serializable")
     }
@@ -70,27 +82,44 @@ impl ChallengeType {
 
 impl ChallengeType {
     pub fn of_tasks(&self, task_pattern: &TaskPattern) -> Self {
+        self.of_tasks_with_rng(task_pattern, &mut thread_rng())
+    }
+
+    /// Selects the tasks like [`ChallengeType::of_tasks`], but random
+    /// patterns always pick the same tasks for the same seed.
+    pub fn of_tasks_seeded(&self, task_pattern: &TaskPattern, seed: u64) -> Self {
+        self.of_tasks_with_rng(task_pattern, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// Selects the tasks of the pattern, using `rng` for random patterns.
+    pub fn of_tasks_with_rng<R: Rng + ?Sized>(
+        &self,
+        task_pattern: &TaskPattern,
+        rng: &mut R,
+    ) -> Self {
         match self {
             ChallengeType::MultipleChoice(dataset) => {
-                let selected_questions = task_pattern.select_items(&dataset.questions);
+                let selected_questions =
+                    task_pattern.select_items_with_rng(&dataset.questions, rng);
                 let mut new_dataset = dataset.clone();
                 new_dataset.questions = selected_questions;
                 ChallengeType::MultipleChoice(new_dataset)
             }
             ChallengeType::ContextualChoice(dataset) => {
-                let selected_items = task_pattern.select_items(&dataset.items);
+                let selected_items = task_pattern.select_items_with_rng(&dataset.items, rng);
                 let mut new_dataset = dataset.clone();
                 new_dataset.items = selected_items;
                 ChallengeType::ContextualChoice(new_dataset)
             }
             ChallengeType::GapFill(dataset) => {
-                let selected_questions = task_pattern.select_items(&dataset.questions);
+                let selected_questions =
+                    task_pattern.select_items_with_rng(&dataset.questions, rng);
                 let mut new_dataset = dataset.clone();
                 new_dataset.questions = selected_questions;
                 ChallengeType::GapFill(new_dataset)
             }
             ChallengeType::SortTable(dataset) => {
-                let selected_rows = task_pattern.select_items(&dataset.rows);
+                let selected_rows = task_pattern.select_items_with_rng(&dataset.rows, rng);
                 let mut new_dataset = dataset.clone();
                 new_dataset.rows = selected_rows;
                 ChallengeType::SortTable(new_dataset)
@@ -100,18 +129,37 @@ impl ChallengeType {
             ChallengeType::Custom(dataset) => {
                 let mut dataset = dataset.clone();
                 let ids: Vec<_> = (0..100).collect();
-                let task_ids = task_pattern.select_items(&ids);
+                let task_ids = task_pattern.select_items_with_rng(&ids, rng);
                 dataset.task_ids = Some(task_ids);
                 ChallengeType::Custom(dataset)
             }
             ChallengeType::Placeholder(dataset) => ChallengeType::Placeholder(dataset.clone()),
             ChallengeType::Vocabulary(dataset) => {
-                let selected_items = task_pattern.select_items(&dataset.items);
+                let selected_items = task_pattern.select_items_with_rng(&dataset.items, rng);
                 let mut new_dataset = dataset.clone();
                 new_dataset.items = selected_items;
                 ChallengeType::Vocabulary(new_dataset)
             }
             ChallengeType::Dialog(dataset) => ChallengeType::Dialog(dataset.clone()),
+            ChallengeType::Matching(dataset) => {
+                let selected_pairs = task_pattern.select_items_with_rng(&dataset.pairs, rng);
+                let mut new_dataset = dataset.clone();
+                new_dataset.pairs = selected_pairs;
+                ChallengeType::Matching(new_dataset)
+            }
+            ChallengeType::Translation(dataset) => {
+                let selected_items = task_pattern.select_items_with_rng(&dataset.items, rng);
+                let mut new_dataset = dataset.clone();
+                new_dataset.items = selected_items;
+                ChallengeType::Translation(new_dataset)
+            }
+            ChallengeType::ErrorCorrection(dataset) => {
+                let selected_sentences =
+                    task_pattern.select_items_with_rng(&dataset.sentences, rng);
+                let mut new_dataset = dataset.clone();
+                new_dataset.sentences = selected_sentences;
+                ChallengeType::ErrorCorrection(new_dataset)
+            }
         }
     }
 
@@ -127,6 +175,45 @@ impl ChallengeType {
             ChallengeType::Placeholder(dataset) => &dataset.name,
             ChallengeType::Vocabulary(dataset) => &dataset.name,
             ChallengeType::Dialog(dataset) => &dataset.name,
+            ChallengeType::Matching(dataset) => &dataset.name,
+            ChallengeType::Translation(dataset) => &dataset.name,
+            ChallengeType::ErrorCorrection(dataset) => &dataset.name,
+        }
+    }
+
+    pub fn level(&self) -> Option<CefrLevel> {
+        match self {
+            ChallengeType::MultipleChoice(dataset) => dataset.level,
+            ChallengeType::ContextualChoice(dataset) => dataset.level,
+            ChallengeType::GapFill(dataset) => dataset.level,
+            ChallengeType::SortTable(dataset) => dataset.level,
+            ChallengeType::Informative(dataset) => dataset.level,
+            ChallengeType::Ordering(dataset) => dataset.level,
+            ChallengeType::Custom(dataset) => dataset.level,
+            ChallengeType::Placeholder(dataset) => dataset.level,
+            ChallengeType::Vocabulary(dataset) => dataset.level,
+            ChallengeType::Dialog(dataset) => dataset.level,
+            ChallengeType::Matching(dataset) => dataset.level,
+            ChallengeType::Translation(dataset) => dataset.level,
+            ChallengeType::ErrorCorrection(dataset) => dataset.level,
+        }
+    }
+
+    pub fn tags(&self) -> &[String] {
+        match self {
+            ChallengeType::MultipleChoice(dataset) => &dataset.tags,
+            ChallengeType::ContextualChoice(dataset) => &dataset.tags,
+            ChallengeType::GapFill(dataset) => &dataset.tags,
+            ChallengeType::SortTable(dataset) => &dataset.tags,
+            ChallengeType::Informative(dataset) => &dataset.tags,
+            ChallengeType::Ordering(dataset) => &dataset.tags,
+            ChallengeType::Custom(dataset) => &dataset.tags,
+            ChallengeType::Placeholder(dataset) => &dataset.tags,
+            ChallengeType::Vocabulary(dataset) => &dataset.tags,
+            ChallengeType::Dialog(dataset) => &dataset.tags,
+            ChallengeType::Matching(dataset) => &dataset.tags,
+            ChallengeType::Translation(dataset) => &dataset.tags,
+            ChallengeType::ErrorCorrection(dataset) => &dataset.tags,
         }
     }
 
@@ -142,6 +229,9 @@ impl ChallengeType {
             ChallengeType::Placeholder(dataset) => &dataset.id,
             ChallengeType::Vocabulary(dataset) => &dataset.id,
             ChallengeType::Dialog(dataset) => &dataset.id,
+            ChallengeType::Matching(dataset) => &dataset.id,
+            ChallengeType::Translation(dataset) => &dataset.id,
+            ChallengeType::ErrorCorrection(dataset) => &dataset.id,
         }
     }
 }
@@ -170,11 +260,7 @@ impl Performance for ChallengeType {
                 }
                 let mut score = 0;
                 for (item, choice) in dataset.items.iter().zip(choices.iter()) {
-                    if item.choices.iter().zip(&choice.ids).all(|(c, &id)| {
-                        c.options
-                            .get(id)
-                            .is_some_and(|selected| *selected == c.correct_answer)
-                    }) {
+                    if item.grade(choice, &dataset.grading).is_accepted() {
                         score += 1;
                     }
                 }
@@ -187,10 +273,8 @@ impl Performance for ChallengeType {
                 let mut score = 0;
                 for (question, answer) in dataset.questions.iter().zip(answers.iter()) {
                     if question
-                        .gaps
-                        .iter()
-                        .zip(answer.answers.iter())
-                        .all(|(gap, ans)| gap.correct == *ans)
+                        .grade(&answer.answers, &dataset.grading)
+                        .is_accepted()
                     {
                         score += 1;
                     }
@@ -226,8 +310,29 @@ impl Performance for ChallengeType {
                 (100.0 * result.performance) as u32
             }
             (ChallengeType::Placeholder(_), _) => 0,
-            // Add this line to handle Vocabulary challenges
-            (ChallengeType::Vocabulary(_), ChallengeResult::Vocabulary) => 100,
+            (ChallengeType::Vocabulary(dataset), ChallengeResult::Vocabulary(answers)) => {
+                let total = dataset.quiz_items().count();
+                if dataset.mode == VocabularyMode::Study || total == 0 {
+                    return 100; // Study mode — the cards are only read
+                }
+                let correct = dataset
+                    .items
+                    .iter()
+                    .zip(answers.iter())
+                    .filter(|(item, answer)| {
+                        item.translation.is_some()
+                            && answer.item_id == item.id
+                            && dataset.grade_answer(answer).is_accepted()
+                    })
+                    .count();
+                (100 * correct / total) as u32
+            }
+            (ChallengeType::Dialog(dialog), ChallengeResult::Dialog(answers))
+                if dialog.is_branching() =>
+            {
+                // Only the turns on the path the player took are scored
+                dialog.path(answers).score()
+            }
             (ChallengeType::Dialog(dialog), ChallengeResult::Dialog(answers)) => {
                 let quiz_turns: Vec<_> = dialog.quiz_turns().collect();
                 if quiz_turns.is_empty() {
@@ -243,6 +348,51 @@ impl Performance for ChallengeType {
                     .count();
                 (100 * correct / quiz_turns.len()) as u32
             }
+            (ChallengeType::Matching(dataset), ChallengeResult::Matching(answers)) => {
+                if dataset.pairs.is_empty() {
+                    return 0;
+                }
+                // Only the latest connection of each left entry counts
+                let correct = dataset
+                    .pairs
+                    .iter()
+                    .filter(|pair| {
+                        answers
+                            .iter()
+                            .rev()
+                            .find(|answer| answer.left == pair.id)
+                            .is_some_and(|answer| dataset.is_correct(answer))
+                    })
+                    .count();
+                (100 * correct / dataset.pairs.len()) as u32
+            }
+            (ChallengeType::Translation(dataset), ChallengeResult::Translation(answers)) => {
+                if dataset.items.is_empty() {
+                    return 0;
+                }
+                // Partly correct sentences earn partial credit
+                let credit: f64 = answers
+                    .iter()
+                    .take(dataset.items.len())
+                    .map(|answer| dataset.score_answer(answer))
+                    .sum();
+                (100.0 * credit / dataset.items.len() as f64 + 1e-9) as u32
+            }
+            (
+                ChallengeType::ErrorCorrection(dataset),
+                ChallengeResult::ErrorCorrection(answers),
+            ) => {
+                if dataset.sentences.is_empty() {
+                    return 0;
+                }
+                // Finding a mistake and fixing it are credited separately
+                let credit: f64 = answers
+                    .iter()
+                    .take(dataset.sentences.len())
+                    .map(|answer| dataset.score_answer(answer))
+                    .sum();
+                (100.0 * credit / dataset.sentences.len() as f64 + 1e-9) as u32
+            }
             _ => {
                 tracing::warn!(
                     "Unhandled challenge type/result combination: {:?}",
@@ -290,6 +440,8 @@ pub mod tests {
                 option: 0,
                 image: None,
             }],
+            level: None,
+            tags: vec![],
         });
 
         let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
@@ -328,6 +480,8 @@ pub mod tests {
                 option: 0,
                 image: None,
             }],
+            level: None,
+            tags: vec![],
         });
 
         let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
@@ -581,6 +735,8 @@ pub mod tests {
                     option: 1,
                 },
             ],
+            level: None,
+            tags: vec![],
         });
 
         // 2. Serialize to JSON -- the challenge must be exportable
@@ -23,6 +23,9 @@ pub enum ChallengeVariant {
     CustomPackage,
     DialogObserver,
     DialogQuiz,
+    Matching,
+    Translation,
+    ErrorCorrection,
 }
 
 impl fmt::Display for ChallengeVariant {
@@ -39,6 +42,9 @@ impl fmt::Display for ChallengeVariant {
             ChallengeVariant::CustomPackage => "Custom Package",
             ChallengeVariant::DialogObserver => "Dialog Observer",
             ChallengeVariant::DialogQuiz => "Dialog Quiz",
+            ChallengeVariant::Matching => "Matching Pairs",
+            ChallengeVariant::Translation => "Translation",
+            ChallengeVariant::ErrorCorrection => "Error Correction",
         };
         write!(f, "{}", s)
     }
@@ -95,12 +101,15 @@ mod tests {
     #[test]
     fn test_variant_iteration() {
         let variants: Vec<ChallengeVariant> = ChallengeVariant::iter().collect();
-        assert_eq!(variants.len(), 11);
+        assert_eq!(variants.len(), 14);
         assert!(variants.contains(&ChallengeVariant::MultipleChoice));
         assert!(variants.contains(&ChallengeVariant::MultipleChoice4));
         assert!(variants.contains(&ChallengeVariant::CustomPackage));
         assert!(variants.contains(&ChallengeVariant::DialogObserver));
         assert!(variants.contains(&ChallengeVariant::DialogQuiz));
+        assert!(variants.contains(&ChallengeVariant::Matching));
+        assert!(variants.contains(&ChallengeVariant::Translation));
+        assert!(variants.contains(&ChallengeVariant::ErrorCorrection));
     }
 
     #[test]
@@ -1,3 +1,5 @@
+use crate::challenges::CefrLevel;
+use crate::challenges::grading::{AnswerVerdict, GradingPolicy};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -13,6 +15,15 @@ pub struct ContextualChoice {
     pub description: String,
     /// List of contextual items
     pub items: Vec<ContextItem>,
+    /// How the selected options are compared with the correct answers
+    #[serde(default)]
+    pub grading: GradingPolicy,
+    /// CEFR level of the challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, e.g. "konnektoren"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Default for ContextualChoice {
@@ -49,6 +60,28 @@ pub struct ContextItemChoiceAnswers {
     pub ids: Vec<usize>,
 }
 
+impl ContextItem {
+    /// Grades the selected options of all choices, keeping the worst verdict.
+    pub fn grade(
+        &self,
+        answers: &ContextItemChoiceAnswers,
+        policy: &GradingPolicy,
+    ) -> AnswerVerdict {
+        self.choices.iter().zip(&answers.ids).fold(
+            AnswerVerdict::Correct,
+            |verdict, (choice, &id)| {
+                let choice_verdict = match choice.options.get(id) {
+                    Some(selected) => {
+                        policy.grade_choice(&choice.correct_answer, selected, &choice.options)
+                    }
+                    None => AnswerVerdict::Wrong,
+                };
+                verdict.and(choice_verdict)
+            },
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,5 +172,28 @@ mod tests {
             contextual_choice.description,
             "Fill in the gaps with the correct Konjunktiv II forms."
         );
+        assert_eq!(contextual_choice.grading, GradingPolicy::exact());
+    }
+
+    #[test]
+    fn test_grade_context_item() {
+        let item = ContextItem {
+            template: "Wenn ich reich {0}".to_string(),
+            choices: vec![Choice {
+                id: 0,
+                options: vec!["Wäre".to_string(), "wurde".to_string()],
+                correct_answer: "wäre".to_string(),
+            }],
+        };
+
+        let policy = GradingPolicy::tolerant();
+        let answer = |id| ContextItemChoiceAnswers { ids: vec![id] };
+        assert_eq!(item.grade(&answer(0), &policy), AnswerVerdict::Correct);
+        assert_eq!(item.grade(&answer(1), &policy), AnswerVerdict::Wrong);
+        assert_eq!(item.grade(&answer(5), &policy), AnswerVerdict::Wrong);
+        assert_eq!(
+            item.grade(&answer(0), &GradingPolicy::exact()),
+            AnswerVerdict::Wrong
+        );
     }
 }
@@ -1,3 +1,4 @@
+use crate::challenges::CefrLevel;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -27,6 +28,12 @@ pub struct Custom {
     pub task_ids: Option<Vec<usize>>,
     /// Optional package URL for external content
     pub package_url: Option<String>,
+    /// CEFR level of the challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, e.g. "konnektoren"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Default for Custom {
@@ -59,6 +66,8 @@ mod tests {
             data: data.clone(),
             task_ids: None,
             package_url: None,
+            level: None,
+            tags: vec![],
         };
 
         assert_eq!(dataset.id, id);
@@ -83,6 +92,8 @@ mod tests {
             }),
             task_ids: None,
             package_url: None,
+            level: None,
+            tags: vec![],
         };
 
         let serialized = serde_json::to_string(&dataset).unwrap();
@@ -1,3 +1,4 @@
+use crate::challenges::CefrLevel;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -5,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use super::speaker::Speaker;
 use super::turn::DialogTurn;
 
-/// A two-speaker, linear conversation challenge.
+/// A two-speaker conversation challenge, linear unless it opts into branching.
 ///
 /// A `Dialog` is a scripted exchange between exactly two named characters.
 /// It is presented to the player in one of two game modes, controlled by
@@ -22,12 +23,22 @@ use super::turn::DialogTurn;
 /// appear twice on the game map: once as a cut-scene and once as a scored
 /// challenge.
 ///
+/// # Branching
+///
+/// Turns may carry an `id`, jump to another turn with `next`, let each option
+/// lead somewhere different with `branches`, and end the conversation with an
+/// `ending`.  A branching dialog in Quiz mode is scored only over the
+/// interactive turns on the path the player actually took, see
+/// [`Dialog::path`].  [`Dialog::validate`] checks that every branch ends.
+///
 /// # Data invariants
 ///
 /// * `speakers` always contains **exactly two** entries.
 /// * Any [`DialogTurn`] that has `options` **must** also carry `correct_option`.
 /// * `correct_option` must be a valid index into `options`.
 /// * `turn.speaker` must match one of the two [`Speaker::id`] values.
+/// * Turn ids are unique, and `next` and `branches` only name existing ids.
+/// * `branches`, when present, has one entry per option.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Dialog {
@@ -58,9 +69,20 @@ pub struct Dialog {
     /// uses the `speaker` id on each turn, not the array position.
     pub speakers: [Speaker; 2],
 
-    /// Ordered, flat sequence of turns that make up the dialog.
-    /// No branching — the story always plays out in the same order.
+    /// Ordered sequence of turns that make up the dialog.
+    ///
+    /// Without any of the branching fields on [`DialogTurn`] the story always
+    /// plays out in this order.  Branching dialogs start at the first turn
+    /// and follow [`DialogTurn::next`] and [`DialogTurn::branches`].
     pub turns: Vec<DialogTurn>,
+
+    /// CEFR level the dialog is written for, e.g. `A1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+
+    /// Topic tags used to find the dialog, e.g. `"einkaufen"`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Dialog {
@@ -91,6 +113,36 @@ impl Dialog {
     pub fn speaker_by_id(&self, id: &str) -> Option<&Speaker> {
         self.speakers.iter().find(|s| s.id == id)
     }
+
+    /// Returns `true` when any turn uses ids, jumps or end states.
+    ///
+    /// Linear dialogs keep their original scoring over all interactive turns;
+    /// branching dialogs are scored along the path the player took.
+    pub fn is_branching(&self) -> bool {
+        self.turns.iter().any(|t| t.is_branching())
+    }
+
+    /// Looks up the position of a turn in [`Dialog::turns`] by its id.
+    pub fn turn_index(&self, id: &str) -> Option<usize> {
+        self.turns.iter().position(|t| t.id.as_deref() == Some(id))
+    }
+
+    /// Returns the position of the turn that follows `turn_index` when the
+    /// player picked `selected_option`, or `None` when the dialog ends there.
+    ///
+    /// A turn ends the dialog when it has an [`ending`](DialogTurn::ending),
+    /// when it jumps to an unknown id, or when it is the last turn and does
+    /// not jump anywhere.
+    pub fn successor(&self, turn_index: usize, selected_option: Option<usize>) -> Option<usize> {
+        let turn = self.turns.get(turn_index)?;
+        if turn.ending.is_some() {
+            return None;
+        }
+        match turn.target(selected_option) {
+            Some(id) => self.turn_index(id),
+            None => Some(turn_index + 1).filter(|&next| next < self.turns.len()),
+        }
+    }
 }
 
 impl Default for Dialog {
@@ -135,6 +187,10 @@ mod tests {
                     audio: None,
                     options: None,
                     correct_option: None,
+                    id: None,
+                    next: None,
+                    branches: None,
+                    ending: None,
                 },
                 DialogTurn {
                     speaker: "b".to_string(),
@@ -142,8 +198,14 @@ mod tests {
                     audio: None,
                     options: None,
                     correct_option: None,
+                    id: None,
+                    next: None,
+                    branches: None,
+                    ending: None,
                 },
             ],
+            level: None,
+            tags: vec![],
         }
     }
 
@@ -174,6 +236,10 @@ mod tests {
                     audio: None,
                     options: None,
                     correct_option: None,
+                    id: None,
+                    next: None,
+                    branches: None,
+                    ending: None,
                 },
                 DialogTurn {
                     speaker: "kunde".to_string(),
@@ -185,6 +251,10 @@ mod tests {
                         "Hallo! Was haben Sie?".to_string(),
                     ]),
                     correct_option: Some(0),
+                    id: None,
+                    next: None,
+                    branches: None,
+                    ending: None,
                 },
                 DialogTurn {
                     speaker: "baecker".to_string(),
@@ -192,6 +262,10 @@ mod tests {
                     audio: None,
                     options: None,
                     correct_option: None,
+                    id: None,
+                    next: None,
+                    branches: None,
+                    ending: None,
                 },
                 DialogTurn {
                     speaker: "kunde".to_string(),
@@ -203,6 +277,10 @@ mod tests {
                         "Ich weiß nicht, was das ist.".to_string(),
                     ]),
                     correct_option: Some(1),
+                    id: None,
+                    next: None,
+                    branches: None,
+                    ending: None,
                 },
                 DialogTurn {
                     speaker: "baecker".to_string(),
@@ -210,6 +288,10 @@ mod tests {
                     audio: None,
                     options: None,
                     correct_option: None,
+                    id: None,
+                    next: None,
+                    branches: None,
+                    ending: None,
                 },
                 DialogTurn {
                     speaker: "kunde".to_string(),
@@ -221,6 +303,10 @@ mod tests {
                         "Das ist zu teuer!".to_string(),
                     ]),
                     correct_option: Some(0),
+                    id: None,
+                    next: None,
+                    branches: None,
+                    ending: None,
                 },
                 DialogTurn {
                     speaker: "baecker".to_string(),
@@ -228,8 +314,14 @@ mod tests {
                     audio: None,
                     options: None,
                     correct_option: None,
+                    id: None,
+                    next: None,
+                    branches: None,
+                    ending: None,
                 },
             ],
+            level: None,
+            tags: vec![],
         }
     }
 
@@ -472,6 +564,38 @@ turns:
         assert_eq!(turn3.correct_option, Some(1));
     }
 
+    // ── Branching ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn linear_dialogs_are_not_branching() {
+        assert!(!Dialog::default().is_branching());
+        assert!(!quiz_dialog().is_branching());
+    }
+
+    #[test]
+    fn successor_of_linear_dialog_is_next_turn() {
+        let d = quiz_dialog();
+        assert_eq!(d.successor(0, None), Some(1));
+        assert_eq!(d.successor(1, Some(2)), Some(2));
+        assert_eq!(d.successor(d.turns.len() - 1, None), None);
+    }
+
+    #[test]
+    fn parse_branching_yaml() {
+        let yaml = include_str!("../../../assets/dialog_beim_baecker.yml");
+        let d: Dialog = serde_yaml::from_str(yaml).expect("parse");
+        assert!(d.is_branching());
+        assert_eq!(d.validate(), Ok(()));
+
+        let order = d.turn_index("order").unwrap();
+        assert_eq!(d.successor(order, Some(1)), d.turn_index("pardon"));
+        assert_eq!(
+            d.successor(d.turn_index("pardon").unwrap(), None),
+            Some(order)
+        );
+        assert_eq!(d.successor(d.turn_index("pay").unwrap(), None), None);
+    }
+
     // ── Clone & PartialEq ─────────────────────────────────────────────────────
 
     #[test]
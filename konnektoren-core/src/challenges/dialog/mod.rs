@@ -1,10 +1,14 @@
 pub mod answer;
 #[allow(clippy::module_inception)]
 pub mod dialog;
+pub mod path;
 pub mod speaker;
 pub mod turn;
+pub mod validation;
 
 pub use answer::DialogAnswer;
 pub use dialog::Dialog;
+pub use path::{DialogPath, DialogStep};
 pub use speaker::Speaker;
 pub use turn::DialogTurn;
+pub use validation::DialogError;
@@ -0,0 +1,246 @@
+use super::answer::DialogAnswer;
+use super::dialog::Dialog;
+
+/// One turn of a [`DialogPath`] together with the player's choice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialogStep {
+    /// Position of the turn in [`Dialog::turns`].
+    pub turn_index: usize,
+    /// The option the player picked, `None` if the turn was not answered.
+    pub selected_option: Option<usize>,
+    /// Whether the pick was right — `None` for turns that are not scored.
+    pub correct: Option<bool>,
+}
+
+/// The turns a player went through in a (possibly branching) [`Dialog`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DialogPath {
+    /// The visited turns in the order they were played.
+    pub steps: Vec<DialogStep>,
+    /// The [`ending`](super::DialogTurn::ending) the path arrived at, if any.
+    pub ending: Option<String>,
+}
+
+impl DialogPath {
+    /// Returns the number of interactive turns on the path and how many of
+    /// them were answered correctly.
+    pub fn quiz_results(&self) -> (usize, usize) {
+        let scored = self.steps.iter().filter_map(|step| step.correct);
+        scored.fold((0, 0), |(total, correct), ok| {
+            (total + 1, correct + ok as usize)
+        })
+    }
+
+    /// Returns the Quiz score of this path: `100 * correct / interactive
+    /// turns on the path`, or **100 %** when the path has no interactive turn.
+    pub fn score(&self) -> u32 {
+        match self.quiz_results() {
+            (0, _) => 100,
+            (total, correct) => (100 * correct / total) as u32,
+        }
+    }
+}
+
+impl Dialog {
+    /// Replays the player's answers from the first turn and returns the path
+    /// through the dialog.
+    ///
+    /// Answers are consumed in the order they were given, so a turn that is
+    /// visited again (e.g. after a wrong answer loops back) uses the next
+    /// answer recorded for it.  An unanswered turn follows its
+    /// `correct_option`, or its first option, so the path stays
+    /// deterministic.  The walk stops when it comes back to a turn without
+    /// having used an answer since the last visit, which keeps cycles that
+    /// no answer leads out of from looping forever.
+    pub fn path(&self, answers: &[DialogAnswer]) -> DialogPath {
+        let mut used = vec![false; answers.len()];
+        // Turns visited since the last answer was used
+        let mut visited = vec![false; self.turns.len()];
+        let mut path = DialogPath::default();
+        let mut current = (!self.turns.is_empty()).then_some(0);
+
+        while let Some(index) = current {
+            let turn = &self.turns[index];
+            let answer = turn.options.as_ref().and_then(|_| {
+                (0..answers.len()).find(|&k| !used[k] && answers[k].turn_index == index)
+            });
+
+            match answer {
+                Some(k) => {
+                    used[k] = true;
+                    visited.fill(false);
+                }
+                None if visited[index] => break,
+                None => {}
+            }
+            visited[index] = true;
+
+            let selected_option = answer.map(|k| answers[k].selected_option);
+            path.steps.push(DialogStep {
+                turn_index: index,
+                selected_option,
+                correct: turn
+                    .is_quiz_turn()
+                    .then(|| selected_option.is_some() && selected_option == turn.correct_option),
+            });
+            if turn.ending.is_some() {
+                path.ending = turn.ending.clone();
+            }
+
+            let choice = selected_option
+                .or(turn.correct_option)
+                .or(turn.options.as_ref().map(|_| 0));
+            current = self.successor(index, choice);
+        }
+
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{DialogTurn, Speaker};
+    use super::*;
+
+    fn turn(id: &str, text: &str) -> DialogTurn {
+        DialogTurn {
+            speaker: "a".to_string(),
+            text: text.to_string(),
+            id: Some(id.to_string()),
+            ..DialogTurn::default()
+        }
+    }
+
+    fn quiz(id: &str, correct: usize, branches: &[&str]) -> DialogTurn {
+        DialogTurn {
+            options: Some(vec!["Ja".to_string(), "Nein".to_string()]),
+            correct_option: Some(correct),
+            branches: Some(branches.iter().map(|b| b.to_string()).collect()),
+            ..turn(id, "Frage")
+        }
+    }
+
+    fn ending(id: &str, ending: &str) -> DialogTurn {
+        DialogTurn {
+            ending: Some(ending.to_string()),
+            ..turn(id, "Ende")
+        }
+    }
+
+    /// A question that loops back on a wrong answer, followed by a choice
+    /// between two endings.
+    fn branching_dialog() -> Dialog {
+        Dialog {
+            id: "branching".to_string(),
+            name: "Branching".to_string(),
+            description: "Branching dialog".to_string(),
+            lang: "de".to_string(),
+            scenario: None,
+            image: None,
+            speakers: [
+                Speaker {
+                    id: "a".to_string(),
+                    name: "A".to_string(),
+                    icon: None,
+                },
+                Speaker {
+                    id: "b".to_string(),
+                    name: "B".to_string(),
+                    icon: None,
+                },
+            ],
+            turns: vec![
+                turn("start", "Hallo!"),
+                quiz("ask", 0, &["pay", "again"]),
+                DialogTurn {
+                    next: Some("ask".to_string()),
+                    ..turn("again", "Wie bitte?")
+                },
+                quiz("pay", 1, &["lost", "won"]),
+                ending("won", "happy"),
+                ending("lost", "unhappy"),
+            ],
+            level: None,
+            tags: vec![],
+        }
+    }
+
+    fn answer(turn_index: usize, selected_option: usize) -> DialogAnswer {
+        DialogAnswer {
+            turn_index,
+            selected_option,
+        }
+    }
+
+    fn turns(path: &DialogPath) -> Vec<usize> {
+        path.steps.iter().map(|s| s.turn_index).collect()
+    }
+
+    #[test]
+    fn linear_dialog_visits_every_turn() {
+        let dialog = Dialog::default();
+        let path = dialog.path(&[]);
+        assert_eq!(path.steps.len(), dialog.turns.len());
+        assert_eq!(path.ending, None);
+        // Unanswered quiz turns count as wrong
+        assert_eq!(path.score(), 0);
+    }
+
+    #[test]
+    fn correct_answers_take_the_short_path() {
+        let dialog = branching_dialog();
+        let path = dialog.path(&[answer(1, 0), answer(3, 1)]);
+        assert_eq!(turns(&path), vec![0, 1, 3, 4]);
+        assert_eq!(path.ending.as_deref(), Some("happy"));
+        assert_eq!(path.score(), 100);
+    }
+
+    #[test]
+    fn wrong_answer_loops_back() {
+        let dialog = branching_dialog();
+        let path = dialog.path(&[answer(1, 1), answer(1, 0), answer(3, 0)]);
+        assert_eq!(turns(&path), vec![0, 1, 2, 1, 3, 5]);
+        assert_eq!(path.ending.as_deref(), Some("unhappy"));
+        assert_eq!(path.quiz_results(), (3, 1));
+        assert_eq!(path.score(), 33);
+    }
+
+    #[test]
+    fn retry_loop_taken_twice() {
+        let dialog = branching_dialog();
+        let path = dialog.path(&[answer(1, 1), answer(1, 1), answer(1, 0), answer(3, 1)]);
+        assert_eq!(turns(&path), vec![0, 1, 2, 1, 2, 1, 3, 4]);
+        assert_eq!(path.ending.as_deref(), Some("happy"));
+        assert_eq!(path.quiz_results(), (4, 2));
+    }
+
+    #[test]
+    fn retry_loop_in_bakery_dialog() {
+        let dialog: Dialog =
+            serde_yaml::from_str(include_str!("../../../assets/dialog_beim_baecker.yml")).unwrap();
+        let order = dialog.turn_index("order").unwrap();
+        let pardon = dialog.turn_index("pardon").unwrap();
+        let more = dialog.turn_index("more").unwrap();
+        let answers = [
+            answer(order, 1),
+            answer(order, 1),
+            answer(order, 0),
+            answer(more, 0),
+        ];
+
+        let path = dialog.path(&answers);
+        let visits = |index| turns(&path).iter().filter(|&&t| t == index).count();
+        assert_eq!(visits(order), 3);
+        assert_eq!(visits(pardon), 2);
+        assert_eq!(path.ending.as_deref(), Some("bought-bread"));
+        assert_eq!(path.quiz_results(), (4, 2));
+    }
+
+    #[test]
+    fn revisit_without_answer_stops() {
+        let dialog = branching_dialog();
+        let path = dialog.path(&[answer(1, 1)]);
+        assert_eq!(turns(&path), vec![0, 1, 2]);
+        assert_eq!(path.ending, None);
+    }
+}
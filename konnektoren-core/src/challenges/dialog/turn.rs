@@ -39,6 +39,30 @@ pub struct DialogTurn {
     /// valid index into that vector.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub correct_option: Option<usize>,
+
+    /// Optional identifier that other turns use to jump to this one in a
+    /// branching dialog.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Id of the turn that follows this one.
+    ///
+    /// Defaults to the next turn in [`Dialog::turns`](super::dialog::Dialog::turns),
+    /// so linear dialogs never need it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+
+    /// Id of the turn each option leads to, parallel to [`options`](Self::options).
+    ///
+    /// Lets a choice take the conversation somewhere different, e.g. a wrong
+    /// answer makes the other speaker ask again.  Overrides [`next`](Self::next).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branches: Option<Vec<String>>,
+
+    /// Marks the turn as an end state, named after the outcome of the
+    /// conversation (e.g. `"bought-bread"`).  The dialog stops after it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ending: Option<String>,
 }
 
 impl Default for DialogTurn {
@@ -49,6 +73,10 @@ impl Default for DialogTurn {
             audio: None,
             options: None,
             correct_option: None,
+            id: None,
+            next: None,
+            branches: None,
+            ending: None,
         }
     }
 }
@@ -62,6 +90,20 @@ impl DialogTurn {
     pub fn is_quiz_turn(&self) -> bool {
         self.options.is_some() && self.correct_option.is_some()
     }
+
+    /// Returns `true` when this turn uses any of the branching fields.
+    pub fn is_branching(&self) -> bool {
+        self.id.is_some() || self.next.is_some() || self.branches.is_some() || self.ending.is_some()
+    }
+
+    /// Returns the id of the turn the given option jumps to, falling back to
+    /// [`next`](Self::next) when the option has no branch of its own.
+    pub fn target(&self, selected_option: Option<usize>) -> Option<&str> {
+        selected_option
+            .and_then(|option| self.branches.as_ref()?.get(option))
+            .or(self.next.as_ref())
+            .map(|id| id.as_str())
+    }
 }
 
 #[cfg(test)]
@@ -90,6 +132,10 @@ mod tests {
             audio: None,
             options: Some(vec!["Guten Morgen!".to_string(), "Gute Nacht!".to_string()]),
             correct_option: Some(0),
+            id: None,
+            next: None,
+            branches: None,
+            ending: None,
         };
         assert!(turn.is_quiz_turn());
     }
@@ -108,6 +154,10 @@ mod tests {
             audio: None,
             options: Some(vec!["Hallo!".to_string()]),
             correct_option: None,
+            id: None,
+            next: None,
+            branches: None,
+            ending: None,
         };
         assert!(!turn.is_quiz_turn());
     }
@@ -120,6 +170,10 @@ mod tests {
             audio: None,
             options: None,
             correct_option: Some(0),
+            id: None,
+            next: None,
+            branches: None,
+            ending: None,
         };
         assert!(!turn.is_quiz_turn());
     }
@@ -133,6 +187,8 @@ mod tests {
         assert!(!json.contains("audio"));
         assert!(!json.contains("options"));
         assert!(!json.contains("correct_option"));
+        assert!(!json.contains("branches"));
+        assert!(!json.contains("ending"));
     }
 
     #[test]
@@ -143,6 +199,10 @@ mod tests {
             audio: Some("audio/ja_bitte.mp3".to_string()),
             options: Some(vec!["Ja, bitte.".to_string(), "Nein, danke.".to_string()]),
             correct_option: Some(0),
+            id: None,
+            next: None,
+            branches: None,
+            ending: None,
         };
         let json = serde_json::to_string(&turn).expect("serialize");
         assert!(json.contains("audio"));
@@ -170,6 +230,10 @@ mod tests {
                 "Hallo! Was haben Sie?".to_string(),
             ]),
             correct_option: Some(0),
+            id: None,
+            next: None,
+            branches: None,
+            ending: None,
         };
         let json = serde_json::to_string(&original).expect("serialize");
         let restored: DialogTurn = serde_json::from_str(&json).expect("deserialize");
@@ -207,6 +271,46 @@ correct_option: 0
         assert_eq!(turn, turn2);
     }
 
+    // ── Branching ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn plain_turn_is_not_branching() {
+        let turn = DialogTurn::default();
+        assert!(!turn.is_branching());
+        assert_eq!(turn.target(Some(0)), None);
+    }
+
+    #[test]
+    fn branch_of_option_overrides_next() {
+        let turn = DialogTurn {
+            options: Some(vec!["Ja".to_string(), "Nein".to_string()]),
+            correct_option: Some(0),
+            next: Some("weiter".to_string()),
+            branches: Some(vec!["ja".to_string(), "nein".to_string()]),
+            ..DialogTurn::default()
+        };
+        assert!(turn.is_branching());
+        assert_eq!(turn.target(Some(1)), Some("nein"));
+        assert_eq!(turn.target(Some(5)), Some("weiter"));
+        assert_eq!(turn.target(None), Some("weiter"));
+    }
+
+    #[test]
+    fn parse_branching_yaml() {
+        let yaml = r#"
+id: "ask"
+speaker: "kunde"
+text: "Ja, bitte."
+options: ["Ja, bitte.", "Nein."]
+correct_option: 0
+branches: ["pay", "bye"]
+"#;
+        let turn: DialogTurn = serde_yaml::from_str(yaml).expect("parse");
+        assert_eq!(turn.id.as_deref(), Some("ask"));
+        assert_eq!(turn.target(Some(0)), Some("pay"));
+        assert_eq!(turn.ending, None);
+    }
+
     // ── Clone & PartialEq ────────────────────────────────────────────────────
 
     #[test]
@@ -217,6 +321,10 @@ correct_option: 0
             audio: Some("audio/tschuess.mp3".to_string()),
             options: None,
             correct_option: None,
+            id: None,
+            next: None,
+            branches: None,
+            ending: None,
         };
         let cloned = original.clone();
         assert_eq!(original, cloned);
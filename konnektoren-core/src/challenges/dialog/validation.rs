@@ -0,0 +1,230 @@
+use super::dialog::Dialog;
+use crate::challenges::error::ChallengeError;
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// A structural problem in the turn graph of a [`Dialog`].
+///
+/// Turns are named by their `id`, or by their position when they have none.
+#[derive(Error, Debug, PartialEq)]
+pub enum DialogError {
+    #[error("Duplicate turn id: {0}")]
+    DuplicateTurnId(String),
+
+    #[error("Turn {turn} jumps to unknown turn: {target}")]
+    UnknownTarget { turn: String, target: String },
+
+    #[error("Turn {turn} has {branches} branches for {options} options")]
+    BranchCountMismatch {
+        turn: String,
+        options: usize,
+        branches: usize,
+    },
+
+    #[error("Turn {0} can never reach the end of the dialog")]
+    NeverEnds(String),
+}
+
+impl From<DialogError> for ChallengeError {
+    fn from(error: DialogError) -> Self {
+        ChallengeError::ValidationFailed(error.to_string())
+    }
+}
+
+impl Dialog {
+    /// Checks that every jump names an existing turn and that the dialog can
+    /// be finished from every turn a player can reach.
+    ///
+    /// Loops, such as asking a question again after a wrong answer, are fine
+    /// as long as some option leads out of them.  Linear dialogs are always
+    /// valid.
+    pub fn validate(&self) -> Result<(), DialogError> {
+        let mut ids = HashSet::new();
+        for id in self.turns.iter().filter_map(|t| t.id.as_deref()) {
+            if !ids.insert(id) {
+                return Err(DialogError::DuplicateTurnId(id.to_string()));
+            }
+        }
+
+        for (index, turn) in self.turns.iter().enumerate() {
+            let options = turn.options.as_ref().map_or(0, |o| o.len());
+            if let Some(branches) = &turn.branches
+                && branches.len() != options
+            {
+                return Err(DialogError::BranchCountMismatch {
+                    turn: self.turn_label(index),
+                    options,
+                    branches: branches.len(),
+                });
+            }
+            let mut targets = turn.next.iter().chain(turn.branches.iter().flatten());
+            if let Some(target) = targets.find(|id| !ids.contains(id.as_str())) {
+                return Err(DialogError::UnknownTarget {
+                    turn: self.turn_label(index),
+                    target: target.clone(),
+                });
+            }
+        }
+
+        let successors: Vec<Vec<usize>> = (0..self.turns.len())
+            .map(|index| self.successors(index))
+            .collect();
+
+        // Turns that can be reached from the first one
+        let mut reachable = vec![false; self.turns.len()];
+        let mut stack: Vec<usize> = (!self.turns.is_empty()).then_some(0).into_iter().collect();
+        while let Some(index) = stack.pop() {
+            if !std::mem::replace(&mut reachable[index], true) {
+                stack.extend(&successors[index]);
+            }
+        }
+
+        // Turns from which the dialog can end, found backwards from the ends
+        let mut ends = vec![false; self.turns.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for index in 0..self.turns.len() {
+                if !ends[index]
+                    && (successors[index].is_empty() || successors[index].iter().any(|&s| ends[s]))
+                {
+                    ends[index] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        match (0..self.turns.len()).find(|&index| reachable[index] && !ends[index]) {
+            Some(index) => Err(DialogError::NeverEnds(self.turn_label(index))),
+            None => Ok(()),
+        }
+    }
+
+    /// Every turn that may follow `index`, one per option for branching turns.
+    fn successors(&self, index: usize) -> Vec<usize> {
+        match self.turns[index].options.as_ref() {
+            Some(options) => (0..options.len())
+                .filter_map(|option| self.successor(index, Some(option)))
+                .collect(),
+            None => self.successor(index, None).into_iter().collect(),
+        }
+    }
+
+    fn turn_label(&self, index: usize) -> String {
+        self.turns[index]
+            .id
+            .clone()
+            .unwrap_or_else(|| format!("#{}", index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::DialogTurn;
+    use super::*;
+
+    fn turn(id: &str) -> DialogTurn {
+        DialogTurn {
+            id: Some(id.to_string()),
+            ..DialogTurn::default()
+        }
+    }
+
+    fn choice(id: &str, branches: &[&str]) -> DialogTurn {
+        DialogTurn {
+            options: Some(vec!["Ja".to_string(), "Nein".to_string()]),
+            correct_option: Some(0),
+            branches: Some(branches.iter().map(|b| b.to_string()).collect()),
+            ..turn(id)
+        }
+    }
+
+    fn ending(id: &str) -> DialogTurn {
+        DialogTurn {
+            ending: Some("done".to_string()),
+            ..turn(id)
+        }
+    }
+
+    fn dialog(turns: Vec<DialogTurn>) -> Dialog {
+        Dialog {
+            turns,
+            ..Dialog::default()
+        }
+    }
+
+    #[test]
+    fn linear_dialog_is_valid() {
+        assert_eq!(Dialog::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn retry_loop_with_exit_is_valid() {
+        let dialog = dialog(vec![
+            choice("ask", &["done", "again"]),
+            DialogTurn {
+                next: Some("ask".to_string()),
+                ..turn("again")
+            },
+            ending("done"),
+        ]);
+        assert_eq!(dialog.validate(), Ok(()));
+    }
+
+    #[test]
+    fn loop_without_exit_never_ends() {
+        let dialog = dialog(vec![
+            choice("ask", &["again", "again"]),
+            DialogTurn {
+                next: Some("ask".to_string()),
+                ..turn("again")
+            },
+            ending("done"),
+        ]);
+        assert_eq!(
+            dialog.validate(),
+            Err(DialogError::NeverEnds("ask".to_string()))
+        );
+    }
+
+    #[test]
+    fn unreachable_loops_are_ignored() {
+        let dialog = dialog(vec![
+            ending("done"),
+            DialogTurn {
+                next: Some("island".to_string()),
+                ..turn("island")
+            },
+        ]);
+        assert_eq!(dialog.validate(), Ok(()));
+    }
+
+    #[test]
+    fn unknown_target() {
+        let dialog = dialog(vec![choice("ask", &["done", "nowhere"]), ending("done")]);
+        assert_eq!(
+            dialog.validate(),
+            Err(DialogError::UnknownTarget {
+                turn: "ask".to_string(),
+                target: "nowhere".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn duplicate_ids_and_branch_count() {
+        let duplicate = dialog(vec![ending("done"), ending("done")]);
+        assert_eq!(
+            duplicate.validate(),
+            Err(DialogError::DuplicateTurnId("done".to_string()))
+        );
+
+        let mismatch = dialog(vec![choice("ask", &["done"]), ending("done")]);
+        let error = mismatch.validate().unwrap_err();
+        assert_eq!(error.to_string(), "Turn ask has 1 branches for 2 options");
+        assert!(matches!(
+            ChallengeError::from(error),
+            ChallengeError::ValidationFailed(_)
+        ));
+    }
+}
@@ -17,6 +17,9 @@ pub enum ChallengeError {
     #[error("No previous tasks")]
     NoPreviousTasks,
 
+    #[error("No more hints for this task")]
+    NoMoreHints,
+
     #[error("Invalid option id: {0}")]
     InvalidOptionId(usize),
 
@@ -0,0 +1,461 @@
+use crate::challenges::CefrLevel;
+use crate::challenges::grading::{AnswerVerdict, GradingPolicy};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct ErrorCorrection {
+    /// Unique identifier for the challenge
+    pub id: String,
+    /// Display name of the challenge
+    pub name: String,
+    /// Description of the challenge
+    pub description: String,
+    /// Language code
+    pub lang: String,
+    /// List of sentences containing mistakes
+    pub sentences: Vec<FaultySentence>,
+    /// How typed corrections are compared with the correct ones
+    #[serde(default)]
+    pub grading: GradingPolicy,
+    /// CEFR level of the challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, e.g. "konnektoren"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Default for ErrorCorrection {
+    fn default() -> Self {
+        let data = include_str!("../../../assets/error_correction_default.yml");
+        serde_yaml::from_str(data).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct FaultySentence {
+    /// The sentence with its mistakes
+    pub sentence: String,
+    /// The mistakes to be found and fixed
+    pub errors: Vec<SentenceError>,
+    /// Helpful hints
+    #[serde(default)]
+    pub hints: Vec<String>,
+    /// Translation of the corrected sentence
+    #[serde(default)]
+    pub translation: String,
+    /// Explanation of the grammar rule
+    #[serde(default)]
+    pub explanation: String,
+}
+
+/// The kind of grammatical mistake.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorKind {
+    /// Wrong article, e.g. "der Haus"
+    Article,
+    /// Wrong connector, e.g. "weil" instead of "obwohl"
+    Connector,
+    /// Verb in the wrong position, e.g. "weil ich bin krank"
+    VerbPosition,
+    #[default]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct SentenceError {
+    /// Index of the first faulty word in the sentence (0-based)
+    pub position: usize,
+    /// The faulty words, more than one for mistakes such as the verb position
+    pub text: String,
+    /// The words replacing the faulty ones
+    pub correction: String,
+    #[serde(default)]
+    pub kind: ErrorKind,
+}
+
+/// A correction the player made to one word of a sentence.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct WordCorrection {
+    /// Index of the word the player marked as faulty
+    pub position: usize,
+    /// The correction typed by the player
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct ErrorCorrectionAnswer {
+    /// Index of the sentence being answered
+    pub sentence_index: usize,
+    /// The corrections made to the sentence
+    pub corrections: Vec<WordCorrection>,
+}
+
+/// How a single mistake of a sentence was handled by the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCheck {
+    /// Whether one of the faulty words was marked
+    pub found: bool,
+    /// How the correction of the marked word was graded
+    pub fixed: AnswerVerdict,
+}
+
+impl SentenceError {
+    /// Returns `true` if the word at `position` belongs to this mistake.
+    pub fn covers(&self, position: usize) -> bool {
+        let len = self.text.split_whitespace().count().max(1);
+        (self.position..self.position + len).contains(&position)
+    }
+
+    /// Grades a typed correction. Repeating the faulty words is always wrong,
+    /// even if they are only a typo away from the correction.
+    pub fn grade_fix(&self, given: &str, policy: &GradingPolicy) -> AnswerVerdict {
+        if policy.grade(trim(&self.text), trim(given)) == AnswerVerdict::Correct {
+            return AnswerVerdict::Wrong;
+        }
+        policy.grade(trim(&self.correction), trim(given))
+    }
+}
+
+impl FaultySentence {
+    /// Splits the sentence into the words a player can mark.
+    pub fn words(&self) -> Vec<&str> {
+        self.sentence.split_whitespace().collect()
+    }
+
+    /// Returns the sentence with all mistakes corrected.
+    pub fn corrected(&self) -> String {
+        let words = self.words();
+        let mut corrected = Vec::with_capacity(words.len());
+        let mut position = 0;
+        while position < words.len() {
+            match self.errors.iter().find(|e| e.position == position) {
+                Some(error) => {
+                    corrected.push(error.correction.as_str());
+                    position += error.text.split_whitespace().count().max(1);
+                }
+                None => {
+                    corrected.push(words[position]);
+                    position += 1;
+                }
+            }
+        }
+        corrected.join(" ")
+    }
+
+    /// Checks every mistake of the sentence against the corrections.
+    pub fn check(&self, corrections: &[WordCorrection], policy: &GradingPolicy) -> Vec<ErrorCheck> {
+        self.errors
+            .iter()
+            .map(|error| {
+                let correction = corrections.iter().find(|c| error.covers(c.position));
+                ErrorCheck {
+                    found: correction.is_some(),
+                    fixed: correction
+                        .map(|c| error.grade_fix(&c.text, policy))
+                        .unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+
+    /// Counts the corrections made to words without a mistake.
+    pub fn false_alarms(&self, corrections: &[WordCorrection]) -> usize {
+        corrections
+            .iter()
+            .filter(|c| !self.errors.iter().any(|e| e.covers(c.position)))
+            .count()
+    }
+
+    /// Grades the corrections, keeping the worst verdict. Marking a correct
+    /// word as faulty makes the answer wrong.
+    pub fn grade(&self, corrections: &[WordCorrection], policy: &GradingPolicy) -> AnswerVerdict {
+        if self.false_alarms(corrections) > 0 {
+            return AnswerVerdict::Wrong;
+        }
+        self.check(corrections, policy)
+            .iter()
+            .fold(AnswerVerdict::Correct, |verdict, check| {
+                verdict.and(check.fixed)
+            })
+    }
+
+    /// Returns the credit between `0.0` and `1.0`. Finding a mistake and
+    /// fixing it earn half of its credit each, and every false alarm counts
+    /// like a mistake that was neither found nor fixed.
+    pub fn score(&self, corrections: &[WordCorrection], policy: &GradingPolicy) -> f64 {
+        let checks = self.check(corrections, policy);
+        let earned: usize = checks
+            .iter()
+            .map(|check| check.found as usize + check.fixed.is_accepted() as usize)
+            .sum();
+        let total = 2 * checks.len() + 2 * self.false_alarms(corrections);
+        if total == 0 {
+            return 1.0;
+        }
+        earned as f64 / total as f64
+    }
+}
+
+impl ErrorCorrection {
+    pub fn grade_answer(&self, answer: &ErrorCorrectionAnswer) -> AnswerVerdict {
+        match self.sentences.get(answer.sentence_index) {
+            Some(sentence) => sentence.grade(&answer.corrections, &self.grading),
+            None => AnswerVerdict::Wrong,
+        }
+    }
+
+    pub fn check_answer(&self, answer: &ErrorCorrectionAnswer) -> bool {
+        self.grade_answer(answer).is_accepted()
+    }
+
+    /// Returns the credit between `0.0` and `1.0` earned by an answer.
+    pub fn score_answer(&self, answer: &ErrorCorrectionAnswer) -> f64 {
+        match self.sentences.get(answer.sentence_index) {
+            Some(sentence) => sentence.score(&answer.corrections, &self.grading),
+            None => 0.0,
+        }
+    }
+
+    pub fn get_feedback(&self, answer: &ErrorCorrectionAnswer) -> String {
+        if let Some(sentence) = self.sentences.get(answer.sentence_index) {
+            match self.grade_answer(answer) {
+                AnswerVerdict::Correct => format!("Correct! {}", sentence.explanation),
+                AnswerVerdict::CorrectWithTypo => format!(
+                    "Correct, but watch the spelling: {}. {}",
+                    sentence.corrected(),
+                    sentence.explanation
+                ),
+                AnswerVerdict::Wrong => {
+                    let checks = sentence.check(&answer.corrections, &self.grading);
+                    let found = checks.iter().filter(|c| c.found).count();
+                    let fixed = checks.iter().filter(|c| c.fixed.is_accepted()).count();
+                    let mut feedback = format!(
+                        "Incorrect. You found {} of {} mistakes and fixed {}.\n",
+                        found,
+                        checks.len(),
+                        fixed
+                    );
+                    let false_alarms = sentence.false_alarms(&answer.corrections);
+                    if false_alarms > 0 {
+                        feedback.push_str(&format!(
+                            "{} of the marked words were already correct.\n",
+                            false_alarms
+                        ));
+                    }
+                    feedback.push_str("Hints:\n");
+                    for hint in &sentence.hints {
+                        feedback.push_str(&format!("- {}\n", hint));
+                    }
+                    feedback
+                }
+            }
+        } else {
+            "Invalid sentence index".to_string()
+        }
+    }
+}
+
+/// Drops the punctuation around a correction, so "bin." matches "bin".
+fn trim(text: &str) -> &str {
+    text.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn correction(position: usize, text: &str) -> WordCorrection {
+        WordCorrection {
+            position,
+            text: text.to_string(),
+        }
+    }
+
+    fn sentence() -> FaultySentence {
+        FaultySentence {
+            sentence: "Ich bleibe zu Hause, obwohl ich bin krank.".to_string(),
+            errors: vec![
+                SentenceError {
+                    position: 4,
+                    text: "obwohl".to_string(),
+                    correction: "weil".to_string(),
+                    kind: ErrorKind::Connector,
+                },
+                SentenceError {
+                    position: 6,
+                    text: "bin krank.".to_string(),
+                    correction: "krank bin.".to_string(),
+                    kind: ErrorKind::VerbPosition,
+                },
+            ],
+            hints: vec!["Look at the verb".to_string()],
+            translation: "I stay at home because I am ill.".to_string(),
+            explanation: "'weil' sends the verb to the end".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_error_correction_deserialization() {
+        let yaml = r#"
+        id: "articles"
+        name: "Articles"
+        description: "Find the wrong articles"
+        lang: "de"
+        sentences:
+          - sentence: "Der Haus ist groß."
+            errors:
+              - position: 0
+                text: "Der"
+                correction: "Das"
+                kind: article
+        "#;
+
+        let error_correction: ErrorCorrection = serde_yaml::from_str(yaml).unwrap();
+        let sentence = &error_correction.sentences[0];
+        assert_eq!(sentence.errors[0].kind, ErrorKind::Article);
+        assert!(sentence.hints.is_empty());
+        assert_eq!(sentence.corrected(), "Das Haus ist groß.");
+    }
+
+    #[test]
+    fn default_error_correction() {
+        let error_correction = ErrorCorrection::default();
+        assert!(!error_correction.sentences.is_empty());
+        for sentence in &error_correction.sentences {
+            let words = sentence.words();
+            for error in &sentence.errors {
+                assert_eq!(
+                    words[error.position..].join(" ").find(&error.text),
+                    Some(0),
+                    "{} is not at position {}",
+                    error.text,
+                    error.position
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn corrections_are_graded_exactly_by_default() {
+        let error_correction = ErrorCorrection::default();
+        assert_eq!(error_correction.grading, GradingPolicy::exact());
+
+        let error = &error_correction.sentences[0].errors[0];
+        let typo = format!("{} ", error.correction.to_lowercase());
+        assert_eq!(
+            error.grade_fix(&typo, &error_correction.grading),
+            AnswerVerdict::Wrong
+        );
+    }
+
+    #[test]
+    fn corrected_sentence() {
+        assert_eq!(
+            sentence().corrected(),
+            "Ich bleibe zu Hause, weil ich krank bin."
+        );
+    }
+
+    #[test]
+    fn found_and_fixed_earn_separate_credit() {
+        let sentence = sentence();
+        let policy = GradingPolicy::tolerant();
+
+        let all = [correction(4, "weil"), correction(7, "krank bin")];
+        assert_eq!(sentence.grade(&all, &policy), AnswerVerdict::Correct);
+        assert_eq!(sentence.score(&all, &policy), 1.0);
+
+        // Both found, one fixed
+        let found = [correction(4, "weil"), correction(6, "bin krank")];
+        assert_eq!(sentence.grade(&found, &policy), AnswerVerdict::Wrong);
+        assert_eq!(sentence.score(&found, &policy), 0.75);
+
+        // One found and fixed, one missed
+        let one = [correction(4, "weil")];
+        assert_eq!(sentence.score(&one, &policy), 0.5);
+
+        assert_eq!(sentence.score(&[], &policy), 0.0);
+    }
+
+    #[test]
+    fn repeating_the_mistake_is_no_typo() {
+        let error = SentenceError {
+            position: 1,
+            text: "kommst".to_string(),
+            correction: "kommt".to_string(),
+            kind: ErrorKind::Other,
+        };
+        let policy = GradingPolicy::tolerant();
+        assert_eq!(error.grade_fix("kommst", &policy), AnswerVerdict::Wrong);
+        assert_eq!(
+            error.grade_fix("komt", &policy),
+            AnswerVerdict::CorrectWithTypo
+        );
+        assert_eq!(error.grade_fix("kommt.", &policy), AnswerVerdict::Correct);
+    }
+
+    #[test]
+    fn false_alarms_cost_credit() {
+        let sentence = sentence();
+        let policy = GradingPolicy::tolerant();
+        let corrections = [
+            correction(4, "weil"),
+            correction(6, "krank bin"),
+            correction(0, "Du"),
+        ];
+        assert_eq!(sentence.false_alarms(&corrections), 1);
+        assert_eq!(sentence.grade(&corrections, &policy), AnswerVerdict::Wrong);
+        assert_eq!(sentence.score(&corrections, &policy), 4.0 / 6.0);
+    }
+
+    #[test]
+    fn test_get_feedback() {
+        let error_correction = ErrorCorrection {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            description: "Test".to_string(),
+            lang: "de".to_string(),
+            sentences: vec![sentence()],
+            grading: GradingPolicy::tolerant(),
+            level: None,
+            tags: vec![],
+        };
+
+        let answer = |corrections: Vec<WordCorrection>| ErrorCorrectionAnswer {
+            sentence_index: 0,
+            corrections,
+        };
+
+        let feedback = error_correction.get_feedback(&answer(vec![
+            correction(4, "weil"),
+            correction(6, "krank bin"),
+        ]));
+        assert_eq!(feedback, "Correct! 'weil' sends the verb to the end");
+
+        let feedback = error_correction.get_feedback(&answer(vec![
+            correction(4, "weill"),
+            correction(6, "krank bin"),
+        ]));
+        assert!(feedback.starts_with("Correct, but watch the spelling"));
+
+        let feedback = error_correction.get_feedback(&answer(vec![correction(4, "denn")]));
+        assert!(feedback.contains("You found 1 of 2 mistakes and fixed 0."));
+        assert!(feedback.contains("- Look at the verb"));
+
+        let feedback = error_correction.get_feedback(&ErrorCorrectionAnswer {
+            sentence_index: 5,
+            corrections: vec![],
+        });
+        assert_eq!(feedback, "Invalid sentence index");
+    }
+}
@@ -1,3 +1,5 @@
+use crate::challenges::CefrLevel;
+use crate::challenges::grading::{AnswerVerdict, GradingPolicy};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -15,6 +17,15 @@ pub struct GapFill {
     pub lang: String,
     /// List of gap-fill questions
     pub questions: Vec<GapFillQuestion>,
+    /// How typed answers are compared with the correct ones
+    #[serde(default)]
+    pub grading: GradingPolicy,
+    /// CEFR level of the challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, e.g. "konnektoren"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Default for GapFill {
@@ -59,33 +70,57 @@ pub struct GapFillAnswer {
     pub answers: Vec<String>,
 }
 
+impl GapFillQuestion {
+    /// Grades the answers for all gaps, keeping the worst verdict.
+    pub fn grade(&self, answers: &[String], policy: &GradingPolicy) -> AnswerVerdict {
+        if self.gaps.len() != answers.len() {
+            return AnswerVerdict::Wrong;
+        }
+
+        self.gaps
+            .iter()
+            .zip(answers.iter())
+            .fold(AnswerVerdict::Correct, |verdict, (gap, ans)| {
+                verdict.and(policy.grade_choice(&gap.correct, ans, &gap.options))
+            })
+    }
+}
+
 impl GapFill {
-    pub fn check_answer(&self, answer: &GapFillAnswer) -> bool {
-        if let Some(question) = self.questions.get(answer.question_index) {
-            if question.gaps.len() != answer.answers.len() {
-                return false;
-            }
+    /// Marks a gap in the sentence of a question.
+    pub const GAP_PLACEHOLDER: &str = "__";
 
-            question
-                .gaps
-                .iter()
-                .zip(answer.answers.iter())
-                .all(|(gap, ans)| gap.correct == *ans)
-        } else {
-            false
+    pub fn grade_answer(&self, answer: &GapFillAnswer) -> AnswerVerdict {
+        match self.questions.get(answer.question_index) {
+            Some(question) => question.grade(&answer.answers, &self.grading),
+            None => AnswerVerdict::Wrong,
         }
     }
 
+    pub fn check_answer(&self, answer: &GapFillAnswer) -> bool {
+        self.grade_answer(answer).is_accepted()
+    }
+
     pub fn get_feedback(&self, answer: &GapFillAnswer) -> String {
         if let Some(question) = self.questions.get(answer.question_index) {
-            if self.check_answer(answer) {
-                format!("Correct! {}", question.explanation)
-            } else {
-                let mut feedback = String::from("Incorrect. Hints:\n");
-                for hint in &question.hints {
-                    feedback.push_str(&format!("- {}\n", hint));
+            match self.grade_answer(answer) {
+                AnswerVerdict::Correct => format!("Correct! {}", question.explanation),
+                AnswerVerdict::CorrectWithTypo => {
+                    let spelling: Vec<_> =
+                        question.gaps.iter().map(|g| g.correct.as_str()).collect();
+                    format!(
+                        "Correct, but watch the spelling: {}. {}",
+                        spelling.join(", "),
+                        question.explanation
+                    )
+                }
+                AnswerVerdict::Wrong => {
+                    let mut feedback = String::from("Incorrect. Hints:\n");
+                    for hint in &question.hints {
+                        feedback.push_str(&format!("- {}\n", hint));
+                    }
+                    feedback
                 }
-                feedback
             }
         } else {
             "Invalid question index".to_string()
@@ -154,6 +189,9 @@ mod tests {
                 translation: "Test translation".to_string(),
                 explanation: "Test explanation".to_string(),
             }],
+            grading: GradingPolicy::default(),
+            level: None,
+            tags: vec![],
         };
 
         let correct_answer = GapFillAnswer {
@@ -195,6 +233,9 @@ mod tests {
                 translation: "Test translation".to_string(),
                 explanation: "Test explanation".to_string(),
             }],
+            grading: GradingPolicy::default(),
+            level: None,
+            tags: vec![],
         };
 
         let correct_answer = GapFillAnswer {
@@ -225,4 +266,69 @@ mod tests {
             "Invalid question index"
         );
     }
+
+    #[test]
+    fn existing_datasets_grade_exactly() {
+        let gap_fill = GapFill::default();
+        assert_eq!(gap_fill.grading, GradingPolicy::exact());
+
+        let answer = |first: &str, second: &str| GapFillAnswer {
+            question_index: 0,
+            answers: vec![first.to_string(), second.to_string()],
+        };
+        assert!(gap_fill.check_answer(&answer("bin", "gefahren")));
+        assert!(!gap_fill.check_answer(&answer("Bin", "gefahren")));
+        assert!(!gap_fill.check_answer(&answer("bin", "gefaren")));
+    }
+
+    #[test]
+    fn test_grade_answer_tolerates_spelling() {
+        let yaml = r#"
+        id: "streets"
+        name: "Streets"
+        description: "Test"
+        lang: "de"
+        questions:
+          - sentence: "Ich wohne in der __."
+            gaps:
+              - position: 0
+                options: []
+                correct: "Hauptstraße"
+            hints: []
+            translation: "I live on the main street."
+            explanation: "Test explanation"
+        grading:
+          normalization: {}
+          accept_typos: true
+        "#;
+        let mut gap_fill: GapFill = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(gap_fill.grading, GradingPolicy::tolerant());
+
+        let answer = |text: &str| GapFillAnswer {
+            question_index: 0,
+            answers: vec![text.to_string()],
+        };
+
+        assert_eq!(
+            gap_fill.grade_answer(&answer(" hauptstrasse")),
+            AnswerVerdict::Correct
+        );
+        assert_eq!(
+            gap_fill.grade_answer(&answer("Hauptstrase")),
+            AnswerVerdict::CorrectWithTypo
+        );
+        assert!(
+            gap_fill
+                .get_feedback(&answer("Hauptstrase"))
+                .starts_with("Correct, but watch the spelling: Hauptstraße.")
+        );
+        assert_eq!(
+            gap_fill.grade_answer(&answer("Nebenstraße")),
+            AnswerVerdict::Wrong
+        );
+
+        gap_fill.grading = GradingPolicy::exact();
+        assert!(!gap_fill.check_answer(&answer("hauptstrasse")));
+        assert!(gap_fill.check_answer(&answer("Hauptstraße")));
+    }
 }
@@ -0,0 +1,11 @@
+//! Tolerant grading of free-text answers.
+//!
+//! Answers are normalised with an [`AnswerNormalization`] and compared by a
+//! [`GradingPolicy`], which reports an [`AnswerVerdict`] instead of a bare bool.
+pub mod normalization;
+pub mod policy;
+pub mod verdict;
+
+pub use normalization::AnswerNormalization;
+pub use policy::{GradingPolicy, levenshtein};
+pub use verdict::AnswerVerdict;
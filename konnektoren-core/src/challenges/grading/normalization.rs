@@ -0,0 +1,121 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Describes how answers are normalised before they are compared.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(default)]
+pub struct AnswerNormalization {
+    /// Compare answers without regard to upper and lower case
+    pub ignore_case: bool,
+    /// Trim the answer and collapse inner whitespace to a single space
+    pub collapse_whitespace: bool,
+    /// Treat "ß" as "ss" and "ä", "ö", "ü" as "ae", "oe", "ue"
+    pub transliterate: bool,
+    /// Drop punctuation such as ".", "," or "!"
+    pub ignore_punctuation: bool,
+}
+
+impl Default for AnswerNormalization {
+    fn default() -> Self {
+        AnswerNormalization {
+            ignore_case: true,
+            collapse_whitespace: true,
+            transliterate: true,
+            ignore_punctuation: false,
+        }
+    }
+}
+
+impl AnswerNormalization {
+    /// A normalisation that leaves answers untouched.
+    pub fn none() -> Self {
+        AnswerNormalization {
+            ignore_case: false,
+            collapse_whitespace: false,
+            transliterate: false,
+            ignore_punctuation: false,
+        }
+    }
+
+    pub fn normalize(&self, answer: &str) -> String {
+        let mut normalized = if self.ignore_case {
+            answer.to_lowercase()
+        } else {
+            answer.to_string()
+        };
+
+        if self.transliterate {
+            normalized = transliterate(&normalized);
+        }
+
+        if self.ignore_punctuation {
+            normalized.retain(|c| {
+                !c.is_ascii_punctuation()
+                    && !matches!(c, '„' | '“' | '”' | '‚' | '‘' | '’' | '«' | '»' | '…')
+            });
+        }
+
+        if self.collapse_whitespace {
+            normalized = normalized.split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+
+        normalized
+    }
+}
+
+fn transliterate(answer: &str) -> String {
+    let mut result = String::with_capacity(answer.len());
+    for c in answer.chars() {
+        match c {
+            'ß' => result.push_str("ss"),
+            'ẞ' => result.push_str("SS"),
+            'ä' => result.push_str("ae"),
+            'ö' => result.push_str("oe"),
+            'ü' => result.push_str("ue"),
+            'Ä' => result.push_str("Ae"),
+            'Ö' => result.push_str("Oe"),
+            'Ü' => result.push_str("Ue"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_normalization() {
+        let normalization = AnswerNormalization::default();
+        assert_eq!(normalization.normalize("  Straße "), "strasse");
+        assert_eq!(normalization.normalize("strasse"), "strasse");
+        assert_eq!(normalization.normalize("Über  den\tBerg"), "ueber den berg");
+    }
+
+    #[test]
+    fn none_keeps_answer() {
+        let normalization = AnswerNormalization::none();
+        assert_eq!(normalization.normalize(" Straße "), " Straße ");
+    }
+
+    #[test]
+    fn transliterate_keeps_case() {
+        let normalization = AnswerNormalization {
+            ignore_case: false,
+            ..AnswerNormalization::default()
+        };
+        assert_eq!(normalization.normalize("Ärger"), "Aerger");
+    }
+
+    #[test]
+    fn ignore_punctuation() {
+        let normalization = AnswerNormalization {
+            ignore_punctuation: true,
+            ..AnswerNormalization::default()
+        };
+        assert_eq!(normalization.normalize("„Hallo, Welt!“"), "hallo welt");
+    }
+}
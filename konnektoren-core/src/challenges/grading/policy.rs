@@ -0,0 +1,191 @@
+use super::{AnswerNormalization, AnswerVerdict};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Decides whether a free-text answer is correct, almost correct or wrong.
+///
+/// Every challenge type grades exactly unless its dataset configures
+/// otherwise, see [`GradingPolicy::exact`]. Fields missing from a configured
+/// policy are taken from the exact one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(default)]
+pub struct GradingPolicy {
+    /// How answers are normalised before comparing them
+    pub normalization: AnswerNormalization,
+    /// Whether answers with small spelling mistakes are accepted
+    pub accept_typos: bool,
+    /// Maximum edit distance that still counts as a typo
+    pub max_typo_distance: usize,
+    /// Minimum length of the expected answer before typos are tolerated
+    pub min_typo_length: usize,
+}
+
+impl Default for GradingPolicy {
+    fn default() -> Self {
+        GradingPolicy::exact()
+    }
+}
+
+impl GradingPolicy {
+    /// A policy that only accepts byte-for-byte identical answers. The typo
+    /// limits only apply once a config turns on `accept_typos`.
+    pub fn exact() -> Self {
+        GradingPolicy {
+            normalization: AnswerNormalization::none(),
+            accept_typos: false,
+            max_typo_distance: 1,
+            min_typo_length: 4,
+        }
+    }
+
+    /// A policy that normalises case, whitespace and spelling variants and
+    /// accepts a single typo in answers of four or more characters.
+    pub fn tolerant() -> Self {
+        GradingPolicy {
+            normalization: AnswerNormalization::default(),
+            accept_typos: true,
+            ..GradingPolicy::exact()
+        }
+    }
+
+    /// Grades a free-text answer against the expected one.
+    pub fn grade(&self, expected: &str, given: &str) -> AnswerVerdict {
+        let expected = self.normalization.normalize(expected);
+        let given = self.normalization.normalize(given);
+
+        if expected == given {
+            AnswerVerdict::Correct
+        } else if self.is_typo(&expected, &given) {
+            AnswerVerdict::CorrectWithTypo
+        } else {
+            AnswerVerdict::Wrong
+        }
+    }
+
+    /// Grades an answer that was picked from or typed against a list of
+    /// options. An answer matching one of the other options is always wrong,
+    /// even if it is only a typo away from the expected one.
+    pub fn grade_choice(&self, expected: &str, given: &str, options: &[String]) -> AnswerVerdict {
+        let verdict = self.grade(expected, given);
+        if verdict != AnswerVerdict::CorrectWithTypo {
+            return verdict;
+        }
+
+        let given = self.normalization.normalize(given);
+        let expected = self.normalization.normalize(expected);
+        let is_distractor = options.iter().any(|option| {
+            let option = self.normalization.normalize(option);
+            option != expected && option == given
+        });
+
+        if is_distractor {
+            AnswerVerdict::Wrong
+        } else {
+            verdict
+        }
+    }
+
+    fn is_typo(&self, expected: &str, given: &str) -> bool {
+        self.accept_typos
+            && self.max_typo_distance > 0
+            && expected.chars().count() >= self.min_typo_length
+            && levenshtein(expected, given) <= self.max_typo_distance
+    }
+}
+
+/// Computes the Levenshtein edit distance between two strings, counted in chars.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_distance() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("straße", "strase"), 1);
+    }
+
+    #[test]
+    fn default_policy_is_exact() {
+        assert_eq!(GradingPolicy::default(), GradingPolicy::exact());
+    }
+
+    #[test]
+    fn tolerant_policy() {
+        let policy = GradingPolicy::tolerant();
+        assert_eq!(policy.grade("Straße", "strasse"), AnswerVerdict::Correct);
+        assert_eq!(
+            policy.grade("gefahren", " Gefahren "),
+            AnswerVerdict::Correct
+        );
+        assert_eq!(
+            policy.grade("gefahren", "gefaren"),
+            AnswerVerdict::CorrectWithTypo
+        );
+        assert_eq!(policy.grade("gefahren", "gefahrt"), AnswerVerdict::Wrong);
+    }
+
+    #[test]
+    fn short_answers_need_exact_spelling() {
+        let policy = GradingPolicy::tolerant();
+        assert_eq!(policy.grade("bin", "bim"), AnswerVerdict::Wrong);
+    }
+
+    #[test]
+    fn exact_policy() {
+        let policy = GradingPolicy::exact();
+        assert_eq!(policy.grade("Straße", "Straße"), AnswerVerdict::Correct);
+        assert_eq!(policy.grade("Straße", "strasse"), AnswerVerdict::Wrong);
+        assert_eq!(policy.grade("gefahren", "gefaren"), AnswerVerdict::Wrong);
+    }
+
+    #[test]
+    fn distractors_are_never_typos() {
+        let policy = GradingPolicy::tolerant();
+        let options = vec!["wäre".to_string(), "wären".to_string()];
+        assert_eq!(
+            policy.grade_choice("wäre", "wären", &options),
+            AnswerVerdict::Wrong
+        );
+        assert_eq!(
+            policy.grade_choice("wäre", "waere", &options),
+            AnswerVerdict::Correct
+        );
+        assert_eq!(
+            policy.grade_choice("wäre", "wäer", &options),
+            AnswerVerdict::Wrong
+        );
+    }
+
+    #[test]
+    fn deserialize_partial_policy() {
+        let policy: GradingPolicy = serde_yaml::from_str("accept_typos: true").unwrap();
+        assert!(policy.accept_typos);
+        assert_eq!(policy.max_typo_distance, 1);
+        assert_eq!(policy.normalization, AnswerNormalization::none());
+        assert_eq!(
+            policy.grade("gefahren", "gefaren"),
+            AnswerVerdict::CorrectWithTypo
+        );
+    }
+}
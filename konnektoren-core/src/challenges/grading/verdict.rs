@@ -0,0 +1,87 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The outcome of grading a single answer.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum AnswerVerdict {
+    /// The answer matches the expected one after normalisation
+    Correct,
+    /// The answer is accepted, but contains a small spelling mistake
+    CorrectWithTypo,
+    /// The answer is not accepted
+    #[default]
+    Wrong,
+}
+
+impl AnswerVerdict {
+    /// Returns `true` if the answer counts as solved.
+    pub fn is_accepted(&self) -> bool {
+        matches!(
+            self,
+            AnswerVerdict::Correct | AnswerVerdict::CorrectWithTypo
+        )
+    }
+
+    /// Combines the verdicts of several parts of a task (e.g. the gaps of a
+    /// sentence), keeping the worst one.
+    pub fn and(self, other: AnswerVerdict) -> AnswerVerdict {
+        match (self, other) {
+            (AnswerVerdict::Wrong, _) | (_, AnswerVerdict::Wrong) => AnswerVerdict::Wrong,
+            (AnswerVerdict::CorrectWithTypo, _) | (_, AnswerVerdict::CorrectWithTypo) => {
+                AnswerVerdict::CorrectWithTypo
+            }
+            _ => AnswerVerdict::Correct,
+        }
+    }
+}
+
+impl From<bool> for AnswerVerdict {
+    fn from(correct: bool) -> Self {
+        if correct {
+            AnswerVerdict::Correct
+        } else {
+            AnswerVerdict::Wrong
+        }
+    }
+}
+
+impl fmt::Display for AnswerVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            AnswerVerdict::Correct => "Correct",
+            AnswerVerdict::CorrectWithTypo => "Correct with typo",
+            AnswerVerdict::Wrong => "Wrong",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepted_verdicts() {
+        assert!(AnswerVerdict::Correct.is_accepted());
+        assert!(AnswerVerdict::CorrectWithTypo.is_accepted());
+        assert!(!AnswerVerdict::Wrong.is_accepted());
+    }
+
+    #[test]
+    fn combine_verdicts() {
+        use AnswerVerdict::*;
+        assert_eq!(Correct.and(Correct), Correct);
+        assert_eq!(Correct.and(CorrectWithTypo), CorrectWithTypo);
+        assert_eq!(CorrectWithTypo.and(Wrong), Wrong);
+    }
+
+    #[test]
+    fn serialize_verdict() {
+        let json = serde_json::to_string(&AnswerVerdict::CorrectWithTypo).unwrap();
+        assert_eq!(json, r#""correct-with-typo""#);
+    }
+}
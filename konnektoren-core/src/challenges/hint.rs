@@ -0,0 +1,251 @@
+use crate::challenges::ChallengeType;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A hint that reveals part of the solution of a task.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum Hint {
+    /// An option that is wrong and can be ruled out
+    EliminateOption(String),
+    /// The first letter of the expected answer
+    FirstLetter(String),
+    /// A part of the solution, e.g. the first element of an ordering
+    Reveal(String),
+    /// A hint written by the author of the challenge
+    Text(String),
+    /// The explanation of the grammar rule behind the task
+    Explanation(String),
+}
+
+/// A hint that was revealed while playing a challenge.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct HintUsage {
+    /// Index of the task the hint was requested for
+    pub task_index: usize,
+    pub hint: Hint,
+}
+
+fn first_letter(answer: &str) -> Option<Hint> {
+    answer
+        .chars()
+        .find(|c| c.is_alphanumeric())
+        .map(|c| Hint::FirstLetter(c.to_string()))
+}
+
+fn explanation(text: &str) -> Option<Hint> {
+    (!text.is_empty()).then(|| Hint::Explanation(text.to_string()))
+}
+
+fn eliminate<'a>(options: impl IntoIterator<Item = &'a String>, correct: &str) -> Option<Hint> {
+    options
+        .into_iter()
+        .find(|option| option.as_str() != correct)
+        .map(|option| Hint::EliminateOption(option.clone()))
+}
+
+impl ChallengeType {
+    /// Returns the hints of a task, from the least to the most revealing.
+    ///
+    /// Choice tasks first rule out a wrong option, typed answers start with
+    /// the first letter, and the explanation of the rule comes last.  Tasks
+    /// without a solution, such as informative texts, have no hints.
+    pub fn hints(&self, task_index: usize) -> Vec<Hint> {
+        let hints: Vec<Option<Hint>> = match self {
+            ChallengeType::MultipleChoice(dataset) => match dataset.questions.get(task_index) {
+                Some(question) => {
+                    let correct = dataset.options.iter().find(|o| o.id == question.option);
+                    let correct_name = correct.map(|o| o.name.as_str()).unwrap_or_default();
+                    vec![
+                        eliminate(dataset.options.iter().map(|o| &o.name), correct_name),
+                        first_letter(correct_name),
+                        explanation(&question.help),
+                    ]
+                }
+                None => vec![],
+            },
+            ChallengeType::ContextualChoice(dataset) => match dataset.items.get(task_index) {
+                Some(item) => item
+                    .choices
+                    .iter()
+                    .map(|choice| eliminate(&choice.options, &choice.correct_answer))
+                    .chain(
+                        item.choices
+                            .iter()
+                            .map(|choice| first_letter(&choice.correct_answer)),
+                    )
+                    .collect(),
+                None => vec![],
+            },
+            ChallengeType::GapFill(dataset) => match dataset.questions.get(task_index) {
+                Some(question) => question
+                    .gaps
+                    .iter()
+                    .map(|gap| eliminate(&gap.options, &gap.correct))
+                    .chain(question.gaps.iter().map(|gap| first_letter(&gap.correct)))
+                    .chain(
+                        question
+                            .hints
+                            .iter()
+                            .map(|hint| Some(Hint::Text(hint.clone()))),
+                    )
+                    .chain([explanation(&question.explanation)])
+                    .collect(),
+                None => vec![],
+            },
+            ChallengeType::SortTable(dataset) => match dataset.rows.get(task_index) {
+                Some(row) => row.values.iter().map(|value| first_letter(value)).collect(),
+                None => vec![],
+            },
+            ChallengeType::Ordering(dataset) => match dataset.items.get(task_index) {
+                Some(item) => vec![
+                    item.correct_order
+                        .first()
+                        .and_then(|&index| item.elements.get(index))
+                        .map(|element| Hint::Reveal(element.clone())),
+                ],
+                None => vec![],
+            },
+            ChallengeType::Vocabulary(dataset) => match dataset.items.get(task_index) {
+                Some(item) => vec![item.expected(dataset.direction).and_then(first_letter)],
+                None => vec![],
+            },
+            ChallengeType::Dialog(dataset) => match dataset.quiz_turns().nth(task_index) {
+                Some((_, turn)) => vec![
+                    turn.options
+                        .iter()
+                        .flatten()
+                        .enumerate()
+                        .find(|(index, _)| Some(*index) != turn.correct_option)
+                        .map(|(_, option)| Hint::EliminateOption(option.clone())),
+                ],
+                None => vec![],
+            },
+            ChallengeType::Matching(dataset) => match dataset.pairs.get(task_index) {
+                Some(pair) => vec![first_letter(&pair.right)],
+                None => vec![],
+            },
+            ChallengeType::Translation(dataset) => match dataset.items.get(task_index) {
+                Some(item) => std::iter::once(
+                    item.translation
+                        .split_whitespace()
+                        .next()
+                        .map(|word| Hint::Reveal(word.to_string())),
+                )
+                .chain(item.hints.iter().map(|hint| Some(Hint::Text(hint.clone()))))
+                .chain([explanation(&item.explanation)])
+                .collect(),
+                None => vec![],
+            },
+            ChallengeType::ErrorCorrection(dataset) => match dataset.sentences.get(task_index) {
+                Some(sentence) => sentence
+                    .errors
+                    .iter()
+                    .map(|error| Some(Hint::Reveal(error.text.clone())))
+                    .chain(
+                        sentence
+                            .hints
+                            .iter()
+                            .map(|hint| Some(Hint::Text(hint.clone()))),
+                    )
+                    .chain([explanation(&sentence.explanation)])
+                    .collect(),
+                None => vec![],
+            },
+            ChallengeType::Informative(_)
+            | ChallengeType::Custom(_)
+            | ChallengeType::Placeholder(_) => vec![],
+        };
+        hints.into_iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{ErrorCorrection, GapFill, Ordering};
+
+    #[test]
+    fn multiple_choice_hints() {
+        let ChallengeType::MultipleChoice(dataset) = ChallengeType::default() else {
+            unreachable!()
+        };
+        let question = &dataset.questions[0];
+        let correct = dataset
+            .options
+            .iter()
+            .find(|o| o.id == question.option)
+            .unwrap();
+        let hints = ChallengeType::MultipleChoice(dataset.clone()).hints(0);
+
+        match &hints[0] {
+            Hint::EliminateOption(option) => assert_ne!(option, &correct.name),
+            other => panic!("expected an eliminated option, got {:?}", other),
+        }
+        assert_eq!(
+            hints[1],
+            Hint::FirstLetter(correct.name.chars().next().unwrap().to_string())
+        );
+        assert_eq!(
+            hints.last(),
+            Some(&Hint::Explanation(question.help.clone()))
+        );
+    }
+
+    #[test]
+    fn gap_fill_hints_are_progressive() {
+        let hints = ChallengeType::GapFill(GapFill::default()).hints(0);
+        assert_eq!(
+            hints,
+            vec![
+                Hint::EliminateOption("habe".to_string()),
+                Hint::EliminateOption("gefahrt".to_string()),
+                Hint::FirstLetter("b".to_string()),
+                Hint::FirstLetter("g".to_string()),
+                Hint::Text("Movement verbs use 'sein' as auxiliary".to_string()),
+                Hint::Text("The past participle of 'fahren' is 'gefahren'".to_string()),
+                Hint::Explanation(
+                    "We use 'sein' with verbs of movement and the past participle form".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn ordering_reveals_first_element() {
+        let dataset = Ordering::default();
+        let item = &dataset.items[0];
+        let first = item.elements[item.correct_order[0]].clone();
+        assert_eq!(
+            ChallengeType::Ordering(dataset).hints(0),
+            vec![Hint::Reveal(first)]
+        );
+    }
+
+    #[test]
+    fn error_correction_reveals_faulty_words() {
+        let dataset = ErrorCorrection::default();
+        let hints = ChallengeType::ErrorCorrection(dataset.clone()).hints(0);
+        assert_eq!(
+            hints[0],
+            Hint::Reveal(dataset.sentences[0].errors[0].text.clone())
+        );
+    }
+
+    #[test]
+    fn no_hints_without_task() {
+        assert!(
+            ChallengeType::GapFill(GapFill::default())
+                .hints(99)
+                .is_empty()
+        );
+        assert!(
+            ChallengeType::Informative(Default::default())
+                .hints(0)
+                .is_empty()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::challenges::CefrLevel;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -13,6 +14,12 @@ pub struct Informative {
     pub description: String,
     /// Multilingual text content
     pub text: Vec<InformativeText>,
+    /// CEFR level of the challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, e.g. "konnektoren"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Default for Informative {
@@ -0,0 +1,155 @@
+use crate::challenges::CefrLevel;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct Matching {
+    /// Unique identifier for the challenge
+    pub id: String,
+    /// Display name of the challenge
+    pub name: String,
+    /// Description of the challenge
+    pub description: String,
+    /// Optional heading of the left column (e.g. "Infinitiv")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left_label: Option<String>,
+    /// Optional heading of the right column (e.g. "Präteritum")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right_label: Option<String>,
+    /// The pairs to be matched, each pair is one task
+    pub pairs: Vec<MatchingPair>,
+    /// CEFR level of the challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, e.g. "konnektoren"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Default for Matching {
+    fn default() -> Self {
+        let data = include_str!("../../../assets/matching_default.yml");
+        serde_yaml::from_str(data).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct MatchingPair {
+    /// Pair identifier
+    pub id: usize,
+    /// Entry of the left column
+    pub left: String,
+    /// Entry of the right column that belongs to `left`
+    pub right: String,
+    /// Optional image shown instead of the left entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// A connection the player drew between the two columns.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct MatchingAnswer {
+    /// Id of the pair whose left entry was picked
+    pub left: usize,
+    /// Id of the pair whose right entry was connected to it
+    pub right: usize,
+}
+
+impl Matching {
+    pub fn pair_by_id(&self, id: usize) -> Option<&MatchingPair> {
+        self.pairs.iter().find(|pair| pair.id == id)
+    }
+
+    /// Returns `true` if the answer connects a left entry with its right entry.
+    /// Pairs sharing the same right entry are interchangeable.
+    pub fn is_correct(&self, answer: &MatchingAnswer) -> bool {
+        match (self.pair_by_id(answer.left), self.pair_by_id(answer.right)) {
+            (Some(left), Some(right)) => left.right == right.right,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_matching() {
+        let matching = Matching::default();
+        assert_eq!(matching.id, "strong-verbs");
+        assert!(!matching.pairs.is_empty());
+        assert_eq!(matching.left_label.as_deref(), Some("Infinitiv"));
+    }
+
+    #[test]
+    fn test_matching_deserialization() {
+        let yaml = r#"
+        id: "animals"
+        name: "Animals"
+        description: "Match the pictures with the nouns"
+        pairs:
+          - id: 0
+            left: "Hund"
+            right: "der Hund"
+            image: "dog.png"
+          - id: 1
+            left: "Katze"
+            right: "die Katze"
+        "#;
+
+        let matching: Matching = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(matching.pairs.len(), 2);
+        assert_eq!(matching.left_label, None);
+        assert_eq!(matching.pairs[0].image.as_deref(), Some("dog.png"));
+        assert_eq!(matching.pairs[1].image, None);
+    }
+
+    #[test]
+    fn check_answers() {
+        let matching = Matching {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            description: "Test".to_string(),
+            left_label: None,
+            right_label: None,
+            pairs: vec![
+                MatchingPair {
+                    id: 0,
+                    left: "gehen".to_string(),
+                    right: "ging".to_string(),
+                    image: None,
+                },
+                MatchingPair {
+                    id: 1,
+                    left: "laufen".to_string(),
+                    right: "lief".to_string(),
+                    image: None,
+                },
+                MatchingPair {
+                    id: 2,
+                    left: "Auto".to_string(),
+                    right: "car".to_string(),
+                    image: None,
+                },
+                MatchingPair {
+                    id: 3,
+                    left: "Wagen".to_string(),
+                    right: "car".to_string(),
+                    image: None,
+                },
+            ],
+            level: None,
+            tags: vec![],
+        };
+
+        assert!(matching.is_correct(&MatchingAnswer { left: 0, right: 0 }));
+        assert!(!matching.is_correct(&MatchingAnswer { left: 0, right: 1 }));
+        assert!(matching.is_correct(&MatchingAnswer { left: 3, right: 2 }));
+        assert!(!matching.is_correct(&MatchingAnswer { left: 0, right: 9 }));
+    }
+}
@@ -1,10 +1,13 @@
 //! This module contains all the challenges that can be solved by the user.
+pub mod adaptive;
 pub mod base64_serializable;
+pub mod cefr_level;
 pub mod challenge;
 pub mod challenge_config;
 pub mod challenge_factory;
 pub mod challenge_history;
 pub mod challenge_input;
+pub mod challenge_query;
 pub mod challenge_result;
 pub mod challenge_stats;
 pub mod challenge_type;
@@ -13,8 +16,12 @@ pub mod contextual_choice;
 pub mod custom;
 pub mod dialog;
 pub mod error;
+pub mod error_correction;
 pub mod gap_fill;
+pub mod grading;
+pub mod hint;
 pub mod informative;
+pub mod matching;
 pub mod multiple_choice;
 pub mod ordering;
 #[cfg(feature = "js")]
@@ -25,35 +32,59 @@ pub mod placeholder;
 pub mod review;
 pub mod solvable;
 pub mod sort_table;
+pub mod spaced_repetition;
 pub mod task_pattern;
+pub mod time_limit;
 pub mod timed;
+pub mod translation;
 pub mod vocabulary;
 
+pub use adaptive::{TaskStats, TaskWeights};
 pub use base64_serializable::{Base64Serializable, RmpBase64Serializable};
+pub use cefr_level::CefrLevel;
 pub use challenge::Challenge;
 pub use challenge_config::ChallengeConfig;
 pub use challenge_factory::ChallengeFactory;
 pub use challenge_history::ChallengeHistory;
 pub use challenge_input::ChallengeInput;
+pub use challenge_query::ChallengeQuery;
 pub use challenge_result::ChallengeResult;
 pub use challenge_stats::ChallengeStats;
 pub use challenge_type::ChallengeType;
 pub use challenge_variant::ChallengeVariant;
 pub use contextual_choice::*;
 pub use custom::*;
-pub use dialog::{Dialog, DialogAnswer, DialogTurn, Speaker};
+pub use dialog::{Dialog, DialogAnswer, DialogError, DialogPath, DialogStep, DialogTurn, Speaker};
 pub use error::*;
+pub use error_correction::{
+    ErrorCheck, ErrorCorrection, ErrorCorrectionAnswer, ErrorKind, FaultySentence, SentenceError,
+    WordCorrection,
+};
 pub use gap_fill::{Gap, GapFill, GapFillAnswer, GapFillQuestion};
+pub use grading::{AnswerNormalization, AnswerVerdict, GradingPolicy};
+pub use hint::{Hint, HintUsage};
 pub use informative::{Informative, InformativeText};
+pub use matching::{Matching, MatchingAnswer, MatchingPair};
 pub use multiple_choice::*;
 pub use ordering::{Ordering, OrderingItem, OrderingResult};
 #[cfg(feature = "js")]
 pub use package::*;
-pub use performance::Performance;
+pub use performance::{
+    LmsScore, Performance, RewardContext, RewardPolicy, ScoringPolicy, SpeedBonus, StarThresholds,
+    XpFormula,
+};
 pub use performance_record::PerformanceRecord;
 pub use placeholder::{Placeholder, PlaceholderType};
 pub use review::Review;
 pub use solvable::Solvable;
 pub use sort_table::{SortTable, SortTableColumn, SortTableRow};
-pub use timed::Timed;
-pub use vocabulary::{Vocabulary, VocabularyItem};
+pub use spaced_repetition::{ReviewCard, ReviewScheduler, TaskKey};
+pub use time_limit::{TimeLimit, TimeStatus};
+pub use timed::{Clock, ManualClock, SystemClock, Timed};
+pub use translation::{
+    AlignedToken, Alignment, TokenStatus, Translation, TranslationAnswer, TranslationItem,
+};
+pub use vocabulary::{
+    Vocabulary, VocabularyAnswer, VocabularyDirection, VocabularyItem, VocabularyMode,
+    VocabularyResponse,
+};
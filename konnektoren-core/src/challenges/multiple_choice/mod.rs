@@ -1,3 +1,4 @@
+use crate::challenges::CefrLevel;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,12 @@ pub struct MultipleChoice {
     pub options: Vec<MultipleChoiceOption>,
     /// List of questions
     pub questions: Vec<Question>,
+    /// CEFR level of the challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, e.g. "konnektoren"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -78,6 +85,8 @@ mod tests {
             lang,
             options,
             questions,
+            level: None,
+            tags: vec![],
         };
         assert_eq!(dataset.name, "Test");
         assert_eq!(dataset.options.len(), 2);
@@ -1,3 +1,4 @@
+use crate::challenges::CefrLevel;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -13,6 +14,12 @@ pub struct Ordering {
     pub description: String,
     /// List of ordering items
     pub items: Vec<OrderingItem>,
+    /// CEFR level of the challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, e.g. "konnektoren"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Default for Ordering {
@@ -62,6 +69,8 @@ mod tests {
             name,
             description,
             items,
+            level: None,
+            tags: vec![],
         };
         assert_eq!(ordering.id, "123");
         assert_eq!(ordering.name, "Test");
@@ -0,0 +1,132 @@
+use super::Performance;
+use crate::challenges::Challenge;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The result of a challenge as reported to a learning management system.
+///
+/// The score is the performance of the challenge under its scoring policy
+/// and it counts as passed once at least one star is earned, so an LMS sees
+/// the same outcome as the game.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct LmsScore {
+    /// Performance in percent
+    pub raw: u32,
+    pub stars: u32,
+    pub passed: bool,
+}
+
+impl LmsScore {
+    pub fn of(challenge: &Challenge) -> Self {
+        let result = &challenge.challenge_result;
+        let stars = challenge.stars(result);
+        LmsScore {
+            raw: challenge.performance(result),
+            stars,
+            passed: stars > 0,
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        if self.passed { "passed" } else { "failed" }
+    }
+
+    /// Returns the values of the SCORM 1.2 data model elements.
+    pub fn scorm12(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("cmi.core.score.raw", self.raw.to_string()),
+            ("cmi.core.score.min", "0".to_string()),
+            ("cmi.core.score.max", "100".to_string()),
+            ("cmi.core.lesson_status", self.status().to_string()),
+        ]
+    }
+
+    /// Returns the values of the SCORM 2004 data model elements.
+    pub fn scorm2004(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("cmi.score.raw", self.raw.to_string()),
+            ("cmi.score.min", "0".to_string()),
+            ("cmi.score.max", "100".to_string()),
+            ("cmi.score.scaled", format!("{}", self.raw as f64 / 100.0)),
+            ("cmi.completion_status", "completed".to_string()),
+            ("cmi.success_status", self.status().to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{
+        ChallengeConfig, ChallengeResult, ChallengeType, MultipleChoiceOption, RewardPolicy,
+    };
+
+    fn challenge(correct: usize, rewards: Option<RewardPolicy>) -> Challenge {
+        let challenge_type = ChallengeType::default();
+        let ChallengeType::MultipleChoice(dataset) = &challenge_type else {
+            panic!("expected a multiple choice challenge");
+        };
+        let config = ChallengeConfig {
+            rewards,
+            ..Default::default()
+        };
+        let mut challenge = Challenge::new(&challenge_type, &config);
+        let answers = dataset
+            .questions
+            .iter()
+            .enumerate()
+            .map(|(index, question)| MultipleChoiceOption {
+                id: if index < correct {
+                    question.option
+                } else {
+                    usize::MAX
+                },
+                name: String::new(),
+            })
+            .collect();
+        challenge.challenge_result = ChallengeResult::MultipleChoice(answers);
+        challenge
+    }
+
+    #[test]
+    fn score_follows_performance() {
+        let challenge = challenge(usize::MAX, None);
+        let score = LmsScore::of(&challenge);
+        assert_eq!(score.raw, 100);
+        assert_eq!(score.stars, 3);
+        assert!(score.passed);
+        assert!(
+            score
+                .scorm12()
+                .contains(&("cmi.core.lesson_status", "passed".to_string()))
+        );
+        assert!(
+            score
+                .scorm2004()
+                .contains(&("cmi.score.scaled", "1".to_string()))
+        );
+    }
+
+    #[test]
+    fn failed_below_one_star() {
+        let score = LmsScore::of(&challenge(0, None));
+        assert_eq!(score.raw, 0);
+        assert!(!score.passed);
+        assert!(
+            score
+                .scorm2004()
+                .contains(&("cmi.success_status", "failed".to_string()))
+        );
+    }
+
+    #[test]
+    fn passing_uses_the_reward_policy() {
+        let mut rewards = RewardPolicy::default();
+        rewards.stars.one = 101;
+        let score = LmsScore::of(&challenge(usize::MAX, Some(rewards)));
+        assert_eq!(score.raw, 100);
+        assert!(!score.passed);
+    }
+}
@@ -1,23 +1,22 @@
+mod lms_score;
+mod reward_policy;
+mod scoring_policy;
+
+pub use lms_score::LmsScore;
+pub use reward_policy::{RewardContext, RewardPolicy, SpeedBonus, StarThresholds, XpFormula};
+pub use scoring_policy::{ScoringPolicy, kendall_tau_credit, longest_correct_subsequence};
+
 use super::ChallengeResult;
 
 pub trait Performance {
     /// Returns the performance in percentage.
     fn performance(&self, result: &ChallengeResult) -> u32;
 
-    /// Returns the number of stars based on the performance.
-    /// 3 stars for 80% or more, 2 stars for 60% or more, 1 star for 40% or more, 0 stars otherwise.
+    /// Returns the number of stars based on the performance, using the default
+    /// `StarThresholds`: 3 stars for 80% or more, 2 stars for 60% or more, 1 star for 40% or more, 0 stars otherwise.
     /// The performance is calculated by the `performance` method.
     fn stars(&self, result: &ChallengeResult) -> u32 {
-        let performance = self.performance(result);
-        if performance >= 80 {
-            3
-        } else if performance >= 60 {
-            2
-        } else if performance >= 40 {
-            1
-        } else {
-            0
-        }
+        StarThresholds::default().stars(self.performance(result))
     }
 }
 
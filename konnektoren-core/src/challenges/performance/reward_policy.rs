@@ -0,0 +1,320 @@
+use super::Performance;
+use crate::Xp;
+use crate::challenges::{Challenge, Timed};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Minimum performance in percent needed for each star.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(default)]
+pub struct StarThresholds {
+    /// Performance needed for one star
+    pub one: u32,
+    /// Performance needed for two stars
+    pub two: u32,
+    /// Performance needed for three stars
+    pub three: u32,
+}
+
+impl Default for StarThresholds {
+    fn default() -> Self {
+        StarThresholds {
+            one: 40,
+            two: 60,
+            three: 80,
+        }
+    }
+}
+
+impl StarThresholds {
+    /// Returns the number of stars (0 to 3) earned with the given performance.
+    pub fn stars(&self, performance: u32) -> u32 {
+        if performance >= self.three {
+            3
+        } else if performance >= self.two {
+            2
+        } else if performance >= self.one {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// How the base XP of a finished challenge is calculated.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum XpFormula {
+    /// The performance multiplied by the number of stars
+    PerformanceTimesStars,
+    /// The performance divided by a fixed divisor
+    Performance { divisor: u32 },
+    /// A fixed amount of XP for each star
+    PerStar { xp: Xp },
+}
+
+/// A tenth of the performance, the scale the `unlock_points` of the game
+/// paths are set for.
+impl Default for XpFormula {
+    fn default() -> Self {
+        XpFormula::Performance { divisor: 10 }
+    }
+}
+
+impl XpFormula {
+    pub fn xp(&self, performance: u32, stars: u32) -> Xp {
+        match self {
+            XpFormula::PerformanceTimesStars => performance * stars,
+            XpFormula::Performance { divisor } => performance / (*divisor).max(1),
+            XpFormula::PerStar { xp } => xp * stars,
+        }
+    }
+}
+
+/// Extra XP for finishing a challenge quickly.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct SpeedBonus {
+    /// The challenge has to be finished within this many seconds
+    pub within_seconds: i64,
+    /// The XP added to the reward
+    pub xp: Xp,
+}
+
+/// Everything about a finished challenge that influences its reward.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RewardContext {
+    /// The performance in percent
+    pub performance: u32,
+    /// The time the challenge took, if it was timed
+    pub elapsed_seconds: Option<i64>,
+    /// Whether this was the first attempt at the challenge
+    pub first_attempt: bool,
+    /// The number of hints that were used
+    pub hints_used: u32,
+    /// The number of tasks skipped because their time ran out
+    pub timed_out_tasks: u32,
+}
+
+impl RewardContext {
+    /// Collects the reward context of a finished challenge. Whether it was
+    /// the first attempt depends on the history and is left `false`.
+    pub fn of(challenge: &Challenge) -> Self {
+        RewardContext {
+            performance: challenge.performance(&challenge.challenge_result),
+            elapsed_seconds: challenge
+                .elapsed_time()
+                .map(|elapsed| elapsed.num_seconds()),
+            first_attempt: false,
+            hints_used: challenge.hints_used() as u32,
+            timed_out_tasks: challenge.timed_out_tasks.len() as u32,
+        }
+    }
+}
+
+/// Defines how stars and XP are awarded for a finished challenge.
+///
+/// Set once per `GamePath` and optionally overridden by a `ChallengeConfig`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(default)]
+pub struct RewardPolicy {
+    /// The star cut-offs
+    pub stars: StarThresholds,
+    /// The base XP formula
+    pub xp: XpFormula,
+    /// Extra XP for fast completions
+    pub speed_bonus: Option<SpeedBonus>,
+    /// Extra XP for earning a star on the first attempt
+    pub first_attempt_bonus: Xp,
+    /// XP deducted for each hint used
+    pub hint_penalty: Xp,
+    /// Performance points deducted for each hint before the stars are counted
+    pub hint_star_penalty: u32,
+    /// XP deducted for each task whose time ran out
+    pub timeout_penalty: Xp,
+}
+
+impl Default for RewardPolicy {
+    fn default() -> Self {
+        RewardPolicy {
+            stars: StarThresholds::default(),
+            xp: XpFormula::default(),
+            speed_bonus: None,
+            first_attempt_bonus: 0,
+            hint_penalty: 0,
+            hint_star_penalty: 0,
+            timeout_penalty: 0,
+        }
+    }
+}
+
+impl RewardPolicy {
+    /// Returns the number of stars earned with the given performance.
+    pub fn stars(&self, performance: u32) -> u32 {
+        self.stars.stars(performance)
+    }
+
+    /// Returns the number of stars earned with the given performance after
+    /// the penalty for the used hints.
+    pub fn stars_with_hints(&self, performance: u32, hints_used: u32) -> u32 {
+        self.stars(performance.saturating_sub(self.hint_star_penalty * hints_used))
+    }
+
+    /// Returns the stars earned for a finished challenge. Every place that
+    /// shows or counts stars of an attempt goes through this.
+    pub fn stars_for(&self, context: &RewardContext) -> u32 {
+        self.stars_with_hints(context.performance, context.hints_used)
+    }
+
+    /// Returns the XP earned for a finished challenge. Bonuses are only given
+    /// when at least one star was earned, and penalties never make the reward
+    /// negative.
+    pub fn xp(&self, context: &RewardContext) -> Xp {
+        let stars = self.stars_for(context);
+        let mut xp = self.xp.xp(context.performance, stars);
+
+        if stars > 0 {
+            if context.first_attempt {
+                xp += self.first_attempt_bonus;
+            }
+            if let (Some(bonus), Some(elapsed)) = (self.speed_bonus, context.elapsed_seconds)
+                && elapsed <= bonus.within_seconds
+            {
+                xp += bonus.xp;
+            }
+        }
+
+        xp.saturating_sub(self.hint_penalty * context.hints_used)
+            .saturating_sub(self.timeout_penalty * context.timed_out_tasks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_thresholds() {
+        let thresholds = StarThresholds::default();
+        assert_eq!(thresholds.stars(100), 3);
+        assert_eq!(thresholds.stars(80), 3);
+        assert_eq!(thresholds.stars(79), 2);
+        assert_eq!(thresholds.stars(60), 2);
+        assert_eq!(thresholds.stars(40), 1);
+        assert_eq!(thresholds.stars(39), 0);
+    }
+
+    #[test]
+    fn default_xp_is_a_tenth_of_the_performance() {
+        let policy = RewardPolicy::default();
+        let context = RewardContext {
+            performance: 90,
+            ..RewardContext::default()
+        };
+        assert_eq!(policy.xp(&context), 9);
+    }
+
+    #[test]
+    fn xp_formulas() {
+        assert_eq!(XpFormula::Performance { divisor: 10 }.xp(85, 3), 8);
+        assert_eq!(XpFormula::Performance { divisor: 0 }.xp(85, 3), 85);
+        assert_eq!(XpFormula::PerStar { xp: 5 }.xp(85, 3), 15);
+        assert_eq!(XpFormula::PerformanceTimesStars.xp(85, 3), 255);
+    }
+
+    #[test]
+    fn bonuses_and_penalties() {
+        let policy = RewardPolicy {
+            xp: XpFormula::PerStar { xp: 10 },
+            speed_bonus: Some(SpeedBonus {
+                within_seconds: 60,
+                xp: 5,
+            }),
+            first_attempt_bonus: 3,
+            hint_penalty: 4,
+            ..RewardPolicy::default()
+        };
+
+        let context = RewardContext {
+            performance: 100,
+            elapsed_seconds: Some(30),
+            first_attempt: true,
+            hints_used: 1,
+            timed_out_tasks: 0,
+        };
+        assert_eq!(policy.xp(&context), 30 + 5 + 3 - 4);
+
+        let slow = RewardContext {
+            elapsed_seconds: Some(90),
+            first_attempt: false,
+            ..context
+        };
+        assert_eq!(policy.xp(&slow), 30 - 4);
+
+        let failed = RewardContext {
+            performance: 10,
+            ..context
+        };
+        assert_eq!(policy.xp(&failed), 0);
+    }
+
+    #[test]
+    fn timeouts_cost_xp() {
+        let policy = RewardPolicy {
+            xp: XpFormula::PerStar { xp: 10 },
+            timeout_penalty: 4,
+            ..RewardPolicy::default()
+        };
+        let context = RewardContext {
+            performance: 100,
+            timed_out_tasks: 2,
+            ..RewardContext::default()
+        };
+        assert_eq!(policy.xp(&context), 30 - 8);
+
+        let context = RewardContext {
+            timed_out_tasks: 10,
+            ..context
+        };
+        assert_eq!(policy.xp(&context), 0);
+    }
+
+    #[test]
+    fn hints_cost_stars() {
+        let policy = RewardPolicy {
+            hint_star_penalty: 15,
+            ..RewardPolicy::default()
+        };
+        assert_eq!(policy.stars_with_hints(90, 0), 3);
+        assert_eq!(policy.stars_with_hints(90, 1), 2);
+        assert_eq!(policy.stars_with_hints(90, 7), 0);
+
+        let context = RewardContext {
+            performance: 90,
+            hints_used: 1,
+            ..RewardContext::default()
+        };
+        assert_eq!(policy.xp(&context), 9);
+    }
+
+    #[test]
+    fn deserialize_policy() {
+        let yaml = r#"
+stars:
+  three: 90
+xp: !performance
+  divisor: 10
+hint_penalty: 2
+"#;
+        let policy: RewardPolicy = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(policy.stars.three, 90);
+        assert_eq!(policy.stars.two, 60);
+        assert_eq!(policy.xp, XpFormula::Performance { divisor: 10 });
+        assert_eq!(policy.hint_penalty, 2);
+        assert_eq!(policy.speed_bonus, None);
+    }
+}
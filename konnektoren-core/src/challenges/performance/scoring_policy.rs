@@ -0,0 +1,386 @@
+use super::Performance;
+use crate::challenges::{ChallengeResult, ChallengeType};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Decides how much credit a task earns when it is only partly correct.
+/// Selected per challenge through `ChallengeConfig::scoring`.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum ScoringPolicy {
+    /// A task only counts when it is solved completely
+    #[default]
+    Exact,
+    /// Tasks earn credit for each correct part: the cells of a sort table
+    /// row, the gaps of a gap-fill sentence and the choices of a contextual
+    /// item. Orderings earn credit for their longest correctly ordered
+    /// subsequence, if they contain every element exactly once.
+    PartialCredit,
+    /// Like `PartialCredit`, but orderings earn credit for the share of
+    /// element pairs that are in the correct relative order.
+    KendallTau,
+}
+
+impl ScoringPolicy {
+    /// Returns the credit between `0.0` and `1.0` earned by a single task, or
+    /// `None` if the task was not answered or the challenge type has no
+    /// partial credit.
+    pub fn task_credit(
+        &self,
+        challenge_type: &ChallengeType,
+        result: &ChallengeResult,
+        task_index: usize,
+    ) -> Option<f64> {
+        let credit = match (challenge_type, result) {
+            (ChallengeType::SortTable(dataset), ChallengeResult::SortTable(rows)) => {
+                let (row, answer) = (dataset.rows.get(task_index)?, rows.get(task_index)?);
+                match self {
+                    ScoringPolicy::Exact => exact(row.values == answer.values),
+                    _ => share(
+                        row.values
+                            .iter()
+                            .zip(answer.values.iter())
+                            .filter(|(expected, given)| expected == given)
+                            .count(),
+                        row.values.len(),
+                    ),
+                }
+            }
+            (ChallengeType::GapFill(dataset), ChallengeResult::GapFill(answers)) => {
+                let (question, answer) =
+                    (dataset.questions.get(task_index)?, answers.get(task_index)?);
+                match self {
+                    ScoringPolicy::Exact => exact(
+                        question
+                            .grade(&answer.answers, &dataset.grading)
+                            .is_accepted(),
+                    ),
+                    _ => share(
+                        question
+                            .gaps
+                            .iter()
+                            .zip(answer.answers.iter())
+                            .filter(|(gap, given)| {
+                                dataset
+                                    .grading
+                                    .grade_choice(&gap.correct, given, &gap.options)
+                                    .is_accepted()
+                            })
+                            .count(),
+                        question.gaps.len(),
+                    ),
+                }
+            }
+            (
+                ChallengeType::ContextualChoice(dataset),
+                ChallengeResult::ContextualChoice(answers),
+            ) => {
+                let (item, answer) = (dataset.items.get(task_index)?, answers.get(task_index)?);
+                match self {
+                    ScoringPolicy::Exact => {
+                        exact(item.grade(answer, &dataset.grading).is_accepted())
+                    }
+                    _ => share(
+                        item.choices
+                            .iter()
+                            .zip(answer.ids.iter())
+                            .filter(|(choice, id)| {
+                                choice.options.get(**id).is_some_and(|selected| {
+                                    dataset
+                                        .grading
+                                        .grade_choice(
+                                            &choice.correct_answer,
+                                            selected,
+                                            &choice.options,
+                                        )
+                                        .is_accepted()
+                                })
+                            })
+                            .count(),
+                        item.choices.len(),
+                    ),
+                }
+            }
+            (ChallengeType::Ordering(dataset), ChallengeResult::Ordering(results)) => {
+                let (item, answer) = (dataset.items.get(task_index)?, results.get(task_index)?);
+                match self {
+                    ScoringPolicy::Exact => exact(item.correct_order == answer.order),
+                    // Repeated or unknown elements could line up with any order
+                    _ if !is_permutation(&item.correct_order, &answer.order) => 0.0,
+                    ScoringPolicy::PartialCredit => share(
+                        longest_correct_subsequence(&item.correct_order, &answer.order),
+                        item.correct_order.len(),
+                    ),
+                    ScoringPolicy::KendallTau => {
+                        kendall_tau_credit(&item.correct_order, &answer.order)
+                    }
+                }
+            }
+            _ => return None,
+        };
+        Some(credit)
+    }
+
+    /// Returns the performance in percentage of the result under this policy.
+    /// The exact policy gives the same result as `ChallengeType::performance`.
+    pub fn score(&self, challenge_type: &ChallengeType, result: &ChallengeResult) -> u32 {
+        if *self == ScoringPolicy::Exact {
+            return challenge_type.performance(result);
+        }
+
+        let total = match challenge_type {
+            ChallengeType::SortTable(dataset) => dataset.rows.len(),
+            ChallengeType::GapFill(dataset) => dataset.questions.len(),
+            ChallengeType::ContextualChoice(dataset) => dataset.items.len(),
+            ChallengeType::Ordering(dataset) => dataset.items.len(),
+            _ => return challenge_type.performance(result),
+        };
+        if total == 0 {
+            return 0;
+        }
+
+        let credit: f64 = (0..total)
+            .filter_map(|index| self.task_credit(challenge_type, result, index))
+            .sum();
+        // The epsilon keeps sums like 0.7 + 0.3 + ... from rounding down.
+        ((100.0 * credit / total as f64) + 1e-9).floor() as u32
+    }
+}
+
+fn exact(correct: bool) -> f64 {
+    if correct { 1.0 } else { 0.0 }
+}
+
+/// Returns the share of correct parts. A task without parts earns nothing,
+/// so an empty answer never counts as complete.
+fn share(correct: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        correct as f64 / total as f64
+    }
+}
+
+/// Returns `true` if the given order contains each element of the correct
+/// order exactly once.
+fn is_permutation(correct: &[usize], given: &[usize]) -> bool {
+    let mut correct = correct.to_vec();
+    let mut given = given.to_vec();
+    correct.sort_unstable();
+    given.sort_unstable();
+    correct == given
+}
+
+/// Returns the length of the longest subsequence the given order shares with
+/// the correct one.
+pub fn longest_correct_subsequence(correct: &[usize], given: &[usize]) -> usize {
+    let mut previous = vec![0; given.len() + 1];
+    let mut current = vec![0; given.len() + 1];
+
+    for expected in correct {
+        for (j, actual) in given.iter().enumerate() {
+            current[j + 1] = if expected == actual {
+                previous[j] + 1
+            } else {
+                previous[j + 1].max(current[j])
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[given.len()]
+}
+
+/// Returns the share of element pairs the given order puts in the same
+/// relative order as the correct one (the normalised Kendall tau distance,
+/// inverted). Pairs with an element missing from the given order count as
+/// wrong.
+pub fn kendall_tau_credit(correct: &[usize], given: &[usize]) -> f64 {
+    if correct.len() < 2 {
+        return exact(correct == given);
+    }
+
+    let position = |element: &usize| given.iter().position(|g| g == element);
+    let mut concordant = 0;
+    let mut pairs = 0;
+
+    for (i, a) in correct.iter().enumerate() {
+        for b in &correct[i + 1..] {
+            pairs += 1;
+            if let (Some(pa), Some(pb)) = (position(a), position(b))
+                && pa < pb
+            {
+                concordant += 1;
+            }
+        }
+    }
+
+    concordant as f64 / pairs as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{
+        GapFill, GapFillAnswer, Ordering, OrderingItem, OrderingResult, SortTable, SortTableRow,
+    };
+
+    fn ordering() -> ChallengeType {
+        ChallengeType::Ordering(Ordering {
+            id: "ordering".to_string(),
+            name: "Ordering".to_string(),
+            description: "Test".to_string(),
+            items: vec![OrderingItem {
+                elements: vec!["a", "b", "c", "d"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                correct_order: vec![0, 1, 2, 3],
+            }],
+            level: None,
+            tags: vec![],
+        })
+    }
+
+    #[test]
+    fn lcs_length() {
+        assert_eq!(longest_correct_subsequence(&[0, 1, 2, 3], &[0, 1, 2, 3]), 4);
+        assert_eq!(longest_correct_subsequence(&[0, 1, 2, 3], &[1, 0, 2, 3]), 3);
+        assert_eq!(longest_correct_subsequence(&[0, 1, 2, 3], &[3, 2, 1, 0]), 1);
+        assert_eq!(longest_correct_subsequence(&[0, 1, 2, 3], &[]), 0);
+    }
+
+    #[test]
+    fn kendall_tau() {
+        assert_eq!(kendall_tau_credit(&[0, 1, 2, 3], &[0, 1, 2, 3]), 1.0);
+        assert_eq!(kendall_tau_credit(&[0, 1, 2, 3], &[3, 2, 1, 0]), 0.0);
+        assert_eq!(kendall_tau_credit(&[0, 1, 2, 3], &[1, 0, 2, 3]), 5.0 / 6.0);
+        assert_eq!(kendall_tau_credit(&[0], &[0]), 1.0);
+        assert_eq!(kendall_tau_credit(&[0, 1], &[0]), 0.0);
+    }
+
+    #[test]
+    fn ordering_scores() {
+        let challenge_type = ordering();
+        let result = ChallengeResult::Ordering(vec![OrderingResult {
+            order: vec![1, 0, 2, 3],
+        }]);
+
+        assert_eq!(ScoringPolicy::Exact.score(&challenge_type, &result), 0);
+        assert_eq!(
+            ScoringPolicy::PartialCredit.score(&challenge_type, &result),
+            75
+        );
+        assert_eq!(
+            ScoringPolicy::KendallTau.score(&challenge_type, &result),
+            83
+        );
+    }
+
+    #[test]
+    fn ordering_must_be_a_permutation() {
+        let challenge_type = ordering();
+        for order in [
+            vec![0, 1, 2, 3, 0, 1, 2, 3],
+            vec![0, 1, 2],
+            vec![0, 1, 2, 2],
+        ] {
+            let result = ChallengeResult::Ordering(vec![OrderingResult { order }]);
+            assert_eq!(
+                ScoringPolicy::PartialCredit.score(&challenge_type, &result),
+                0
+            );
+            assert_eq!(ScoringPolicy::KendallTau.score(&challenge_type, &result), 0);
+        }
+    }
+
+    #[test]
+    fn empty_answer_without_gaps_earns_nothing() {
+        let mut dataset = GapFill::default();
+        dataset.questions[0].gaps.clear();
+        let challenge_type = ChallengeType::GapFill(dataset);
+        let result = ChallengeResult::GapFill(vec![GapFillAnswer {
+            question_index: 0,
+            answers: vec![],
+        }]);
+
+        assert_eq!(
+            ScoringPolicy::PartialCredit.task_credit(&challenge_type, &result, 0),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn sort_table_per_cell_credit() {
+        let dataset = SortTable::default();
+        let mut row = dataset.rows[0].clone();
+        row.values[0] = "wrong".to_string();
+        let cells = row.values.len();
+        let challenge_type = ChallengeType::SortTable(dataset);
+        let result = ChallengeResult::SortTable(vec![row]);
+
+        let credit = ScoringPolicy::PartialCredit
+            .task_credit(&challenge_type, &result, 0)
+            .unwrap();
+        assert_eq!(credit, (cells - 1) as f64 / cells as f64);
+        assert_eq!(
+            ScoringPolicy::Exact.task_credit(&challenge_type, &result, 0),
+            Some(0.0)
+        );
+        assert_eq!(
+            ScoringPolicy::Exact.task_credit(&challenge_type, &result, 1),
+            None
+        );
+    }
+
+    #[test]
+    fn gap_fill_per_gap_credit() {
+        let dataset = GapFill::default();
+        let question = &dataset.questions[0];
+        let mut answers: Vec<String> = question.gaps.iter().map(|g| g.correct.clone()).collect();
+        answers[0] = "falsch".to_string();
+        let gaps = answers.len();
+        let challenge_type = ChallengeType::GapFill(dataset);
+        let result = ChallengeResult::GapFill(vec![GapFillAnswer {
+            question_index: 0,
+            answers,
+        }]);
+
+        let credit = ScoringPolicy::PartialCredit
+            .task_credit(&challenge_type, &result, 0)
+            .unwrap();
+        assert_eq!(credit, (gaps - 1) as f64 / gaps as f64);
+    }
+
+    #[test]
+    fn exact_matches_challenge_type_performance() {
+        let challenge_type = ChallengeType::SortTable(SortTable::default());
+        let result = ChallengeResult::SortTable(vec![SortTableRow::default()]);
+        assert_eq!(
+            ScoringPolicy::Exact.score(&challenge_type, &result),
+            challenge_type.performance(&result)
+        );
+    }
+
+    #[test]
+    fn other_types_fall_back_to_performance() {
+        let challenge_type = ChallengeType::default();
+        let result = ChallengeResult::MultipleChoice(vec![]);
+        assert_eq!(
+            ScoringPolicy::PartialCredit.score(&challenge_type, &result),
+            challenge_type.performance(&result)
+        );
+    }
+
+    #[test]
+    fn serialize_policy() {
+        let policy: ScoringPolicy = serde_yaml::from_str("kendall-tau").unwrap();
+        assert_eq!(policy, ScoringPolicy::KendallTau);
+        assert_eq!(
+            serde_json::to_string(&ScoringPolicy::PartialCredit).unwrap(),
+            r#""partial-credit""#
+        );
+    }
+}
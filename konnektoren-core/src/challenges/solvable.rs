@@ -1,8 +1,16 @@
 use crate::challenges::challenge_input::ChallengeInput;
 use crate::challenges::error::Result;
+use crate::challenges::grading::AnswerVerdict;
 
 pub trait Solvable {
+    /// Attempts to solve a part of the challenge with the given input.
+    /// Returns whether the part is correct, correct with a typo or wrong.
+    fn grade(&mut self, input: ChallengeInput, task_index: usize) -> Result<AnswerVerdict>;
+
     /// Attempts to solve a part of the challenge with the given input.
     /// Returns `true` if the part is successfully solved, otherwise `false`.
-    fn solve(&mut self, input: ChallengeInput, task_index: usize) -> Result<bool>;
+    fn solve(&mut self, input: ChallengeInput, task_index: usize) -> Result<bool> {
+        self.grade(input, task_index)
+            .map(|verdict| verdict.is_accepted())
+    }
 }
@@ -1,3 +1,4 @@
+use crate::challenges::CefrLevel;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,12 @@ pub struct SortTable {
     pub columns: Vec<SortTableColumn>,
     /// Table rows to be sorted
     pub rows: Vec<SortTableRow>,
+    /// CEFR level of the challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, e.g. "konnektoren"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Default for SortTable {
@@ -81,6 +88,8 @@ mod tests {
             description,
             columns,
             rows,
+            level: None,
+            tags: vec![],
         };
 
         assert_eq!(dataset.id, "123");
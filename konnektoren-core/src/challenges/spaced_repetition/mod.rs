@@ -0,0 +1,13 @@
+//! Spaced repetition of single tasks, based on the SM-2 algorithm.
+//!
+//! Every answered task of a challenge gets a [`ReviewCard`]. The
+//! [`ReviewScheduler`] replays the [`ChallengeHistory`](super::ChallengeHistory)
+//! to keep those cards up to date and synthesizes review challenges from the
+//! tasks that are due.
+pub mod review_card;
+pub mod review_scheduler;
+pub mod task;
+
+pub use review_card::ReviewCard;
+pub use review_scheduler::ReviewScheduler;
+pub use task::TaskKey;
@@ -0,0 +1,143 @@
+use super::TaskKey;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The ease factor every new card starts with.
+pub const DEFAULT_EASE_FACTOR: f32 = 2.5;
+/// The lower bound of the ease factor, as defined by SM-2.
+pub const MIN_EASE_FACTOR: f32 = 1.3;
+/// The SM-2 quality recorded for a correctly solved task.
+pub const CORRECT_QUALITY: u8 = 4;
+/// The SM-2 quality recorded for a wrongly solved task.
+pub const INCORRECT_QUALITY: u8 = 1;
+
+/// The SM-2 learning state of a single task.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReviewCard {
+    /// The task this card belongs to
+    pub key: TaskKey,
+    /// How fast the interval grows after a successful review
+    pub ease_factor: f32,
+    /// Days until the next review
+    pub interval_days: u32,
+    /// Number of successful reviews in a row
+    pub repetitions: u32,
+    /// Number of times the task was forgotten after it had been learned
+    pub lapses: u32,
+    /// When the task is due for its next review
+    pub due: DateTime<Utc>,
+    /// When the task was reviewed the last time
+    pub last_reviewed: Option<DateTime<Utc>>,
+}
+
+impl ReviewCard {
+    /// Creates a new card that is due immediately.
+    pub fn new(key: TaskKey, now: DateTime<Utc>) -> Self {
+        ReviewCard {
+            key,
+            ease_factor: DEFAULT_EASE_FACTOR,
+            interval_days: 0,
+            repetitions: 0,
+            lapses: 0,
+            due: now,
+            last_reviewed: None,
+        }
+    }
+
+    /// Applies an SM-2 review with the given quality (0 = blackout, 5 = perfect).
+    /// A quality below 3 resets the card to an interval of one day.
+    pub fn review(&mut self, quality: u8, reviewed_at: DateTime<Utc>) {
+        let quality = quality.min(5);
+
+        if quality >= 3 {
+            self.interval_days = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval_days as f32 * self.ease_factor).round() as u32,
+            };
+            self.repetitions += 1;
+        } else {
+            if self.repetitions > 0 {
+                self.lapses += 1;
+            }
+            self.repetitions = 0;
+            self.interval_days = 1;
+        }
+
+        let q = (5 - quality) as f32;
+        self.ease_factor = (self.ease_factor + 0.1 - q * (0.08 + q * 0.02)).max(MIN_EASE_FACTOR);
+        self.last_reviewed = Some(reviewed_at);
+        self.due = reviewed_at + Duration::days(self.interval_days as i64);
+    }
+
+    /// Shorthand for [`review`](Self::review) with a pass/fail outcome.
+    pub fn review_outcome(&mut self, correct: bool, reviewed_at: DateTime<Utc>) {
+        let quality = if correct {
+            CORRECT_QUALITY
+        } else {
+            INCORRECT_QUALITY
+        };
+        self.review(quality, reviewed_at);
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.due <= now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn card() -> ReviewCard {
+        let now = Utc.timestamp_opt(1700000000, 0).unwrap();
+        ReviewCard::new(TaskKey::new("konnektoren", "task"), now)
+    }
+
+    #[test]
+    fn new_card_is_due() {
+        let card = card();
+        assert!(card.is_due(card.due));
+        assert_eq!(card.ease_factor, DEFAULT_EASE_FACTOR);
+    }
+
+    #[test]
+    fn intervals_grow_on_success() {
+        let mut card = card();
+        let now = card.due;
+
+        card.review_outcome(true, now);
+        assert_eq!(card.interval_days, 1);
+        card.review_outcome(true, card.due);
+        assert_eq!(card.interval_days, 6);
+        card.review_outcome(true, card.due);
+        assert_eq!(card.interval_days, 15);
+        assert_eq!(card.repetitions, 3);
+        assert_eq!(card.due, now + Duration::days(1 + 6 + 15));
+    }
+
+    #[test]
+    fn failure_resets_card() {
+        let mut card = card();
+        let now = card.due;
+
+        card.review_outcome(true, now);
+        card.review_outcome(true, card.due);
+        card.review_outcome(false, card.due);
+
+        assert_eq!(card.repetitions, 0);
+        assert_eq!(card.interval_days, 1);
+        assert_eq!(card.lapses, 1);
+        assert!(card.ease_factor < DEFAULT_EASE_FACTOR);
+    }
+
+    #[test]
+    fn ease_factor_has_lower_bound() {
+        let mut card = card();
+        for _ in 0..20 {
+            card.review(0, card.due);
+        }
+        assert_eq!(card.ease_factor, MIN_EASE_FACTOR);
+    }
+}
@@ -0,0 +1,248 @@
+use super::task::{merge_tasks, retain_tasks, task_keys, task_outcomes};
+use super::{ReviewCard, TaskKey};
+use crate::challenges::{Challenge, ChallengeConfig, ChallengeHistory, ChallengeType};
+use chrono::{DateTime, Duration, FixedOffset, Offset, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Keeps a [`ReviewCard`] for every task the player has answered and decides
+/// which of them are due for review.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReviewScheduler {
+    /// Review cards indexed by the string form of their [`TaskKey`]
+    pub cards: BTreeMap<String, ReviewCard>,
+}
+
+impl ReviewScheduler {
+    pub fn new() -> Self {
+        ReviewScheduler {
+            cards: BTreeMap::new(),
+        }
+    }
+
+    /// Builds a scheduler by replaying every challenge of the history in order.
+    /// Challenges without an end or start time are treated as played at `now`.
+    pub fn from_history(history: &ChallengeHistory, now: DateTime<Utc>) -> Self {
+        let mut scheduler = ReviewScheduler::new();
+        for challenge in &history.challenges {
+            let reviewed_at = challenge.end_time.or(challenge.start_time).unwrap_or(now);
+            scheduler.record_challenge(challenge, reviewed_at);
+        }
+        scheduler
+    }
+
+    /// Records the outcome of every answered task of the challenge.
+    pub fn record_challenge(&mut self, challenge: &Challenge, reviewed_at: DateTime<Utc>) {
+        for (key, correct) in task_outcomes(challenge) {
+            self.record(key, correct, reviewed_at);
+        }
+    }
+
+    /// Records a single review, creating the card on first sight.
+    pub fn record(&mut self, key: TaskKey, correct: bool, reviewed_at: DateTime<Utc>) {
+        self.cards
+            .entry(key.to_string())
+            .or_insert_with(|| ReviewCard::new(key, reviewed_at))
+            .review_outcome(correct, reviewed_at);
+    }
+
+    pub fn card(&self, key: &TaskKey) -> Option<&ReviewCard> {
+        self.cards.get(&key.to_string())
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Returns the cards that are due at `now`, most overdue first.
+    pub fn due(&self, now: DateTime<Utc>) -> Vec<&ReviewCard> {
+        let mut due: Vec<_> = self.cards.values().filter(|c| c.is_due(now)).collect();
+        due.sort_by(|a, b| a.due.cmp(&b.due).then_with(|| a.key.cmp(&b.key)));
+        due
+    }
+
+    /// Returns the cards that become due before the end of the player's day
+    /// of `now`, in a time zone `utc_offset_minutes` ahead of UTC.
+    pub fn due_today(&self, now: DateTime<Utc>, utc_offset_minutes: i32) -> Vec<&ReviewCard> {
+        let offset = FixedOffset::east_opt(utc_offset_minutes.saturating_mul(60))
+            .unwrap_or_else(|| Utc.fix());
+        let end_of_day = (now.with_timezone(&offset).date_naive() + Duration::days(1))
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is always a valid time")
+            .and_local_timezone(offset)
+            .single()
+            .expect("a fixed offset maps every local time once")
+            .with_timezone(&Utc);
+        self.due(end_of_day - Duration::nanoseconds(1))
+    }
+
+    /// Synthesizes one review challenge per challenge type from the tasks that
+    /// are due today, see [`ReviewScheduler::due_today`]. The task datasets are
+    /// taken from the history, so tasks selected in different sessions are
+    /// merged back together.
+    pub fn review_challenges(
+        &self,
+        history: &ChallengeHistory,
+        now: DateTime<Utc>,
+        utc_offset_minutes: i32,
+    ) -> Vec<Challenge> {
+        let due: Vec<&TaskKey> = self
+            .due_today(now, utc_offset_minutes)
+            .into_iter()
+            .map(|c| &c.key)
+            .collect();
+        if due.is_empty() {
+            return vec![];
+        }
+
+        let mut sources: Vec<(ChallengeType, ChallengeConfig)> = vec![];
+        for challenge in history.challenges.iter().rev() {
+            let id = challenge.challenge_type.id();
+            match sources.iter_mut().find(|(t, _)| t.id() == id) {
+                Some((challenge_type, _)) => merge_tasks(challenge_type, &challenge.challenge_type),
+                None => sources.push((
+                    challenge.challenge_type.clone(),
+                    challenge.challenge_config.clone(),
+                )),
+            }
+        }
+
+        sources
+            .into_iter()
+            .filter_map(|(challenge_type, config)| {
+                let review_type = retain_tasks(&challenge_type, |key| due.contains(&key));
+                let tasks = task_keys(&review_type).len();
+                if tasks == 0 {
+                    return None;
+                }
+                let config = ChallengeConfig {
+                    id: format!("review-{}", review_type.id()),
+                    name: format!("Review: {}", config.name),
+                    challenge: review_type.id().to_string(),
+                    tasks: tasks.into(),
+                    unlock_points: 0,
+                    ..config
+                };
+                Some(Challenge::new(&review_type, &config))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{ChallengeInput, MultipleChoiceOption, Solvable};
+    use chrono::TimeZone;
+
+    fn played_challenge(answers: &[bool], end_time: DateTime<Utc>) -> Challenge {
+        let challenge_type = ChallengeType::default();
+        let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+        let ChallengeType::MultipleChoice(dataset) = &challenge_type else {
+            unreachable!()
+        };
+        for (index, correct) in answers.iter().enumerate() {
+            let expected = dataset.questions[index].option;
+            let id = if *correct {
+                expected
+            } else {
+                dataset
+                    .options
+                    .iter()
+                    .find(|o| o.id != expected)
+                    .unwrap()
+                    .id
+            };
+            let input = ChallengeInput::MultipleChoice(MultipleChoiceOption {
+                id,
+                name: String::new(),
+            });
+            challenge.solve(input, index).unwrap();
+        }
+        challenge.end_time = Some(end_time);
+        challenge
+    }
+
+    fn base_time() -> DateTime<Utc> {
+        Utc.timestamp_opt(1700000000, 0).unwrap()
+    }
+
+    #[test]
+    fn empty_history_has_nothing_due() {
+        let scheduler = ReviewScheduler::from_history(&ChallengeHistory::new(), base_time());
+        assert!(scheduler.is_empty());
+        assert!(scheduler.due_today(base_time(), 0).is_empty());
+    }
+
+    #[test]
+    fn from_history_creates_cards() {
+        let mut history = ChallengeHistory::new();
+        history.add_challenge(played_challenge(&[true, false, true], base_time()));
+
+        let scheduler = ReviewScheduler::from_history(&history, base_time());
+        assert_eq!(scheduler.len(), 3);
+        assert!(scheduler.due(base_time()).is_empty());
+        assert_eq!(scheduler.due(base_time() + Duration::days(1)).len(), 3);
+    }
+
+    #[test]
+    fn correct_answers_are_due_later() {
+        let mut history = ChallengeHistory::new();
+        history.add_challenge(played_challenge(&[true, false], base_time()));
+        history.add_challenge(played_challenge(
+            &[true, false],
+            base_time() + Duration::days(1),
+        ));
+
+        let scheduler = ReviewScheduler::from_history(&history, base_time());
+        let due = scheduler.due(base_time() + Duration::days(2));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].interval_days, 1);
+    }
+
+    #[test]
+    fn due_today_ends_at_the_players_midnight() {
+        let mut scheduler = ReviewScheduler::new();
+        scheduler.record(TaskKey::new("konnektoren", "als"), true, base_time());
+
+        // Shortly after midnight UTC, but still the evening before at UTC-3
+        let now = base_time() + Duration::hours(3);
+        assert_eq!(scheduler.due_today(now, 0).len(), 1);
+        assert!(scheduler.due_today(now, -180).is_empty());
+    }
+
+    #[test]
+    fn review_challenges_contain_due_tasks() {
+        let mut history = ChallengeHistory::new();
+        history.add_challenge(played_challenge(&[true, false, true], base_time()));
+        let mut scheduler = ReviewScheduler::from_history(&history, base_time());
+
+        let keys = task_keys(&history.challenges[0].challenge_type);
+        scheduler.record(keys[0].clone(), true, base_time() + Duration::days(1));
+
+        let reviews = scheduler.review_challenges(&history, base_time() + Duration::days(1), 0);
+        assert_eq!(reviews.len(), 1);
+
+        let review = &reviews[0];
+        assert_eq!(review.challenge_config.id, "review-konnektoren");
+        assert_eq!(review.challenge_config.tasks.len(), 2);
+        assert_eq!(
+            task_keys(&review.challenge_type),
+            vec![keys[1].clone(), keys[2].clone()]
+        );
+    }
+
+    #[test]
+    fn serialize_scheduler() {
+        let mut scheduler = ReviewScheduler::new();
+        scheduler.record(TaskKey::new("konnektoren", "als"), true, base_time());
+
+        let json = serde_json::to_string(&scheduler).unwrap();
+        let deserialized: ReviewScheduler = serde_json::from_str(&json).unwrap();
+        assert_eq!(scheduler, deserialized);
+    }
+}
@@ -0,0 +1,437 @@
+use crate::challenges::{Challenge, ChallengeResult, ChallengeType};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Identifies a single task of a challenge dataset.
+///
+/// Tasks are keyed by their content rather than their position, because
+/// `ChallengeType::of_tasks` may select them in any order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TaskKey {
+    /// The id of the challenge type the task belongs to
+    pub challenge: String,
+    /// The identifier of the task inside the challenge type
+    pub task: String,
+}
+
+impl TaskKey {
+    pub fn new(challenge: impl Into<String>, task: impl Into<String>) -> Self {
+        TaskKey {
+            challenge: challenge.into(),
+            task: task.into(),
+        }
+    }
+}
+
+impl fmt::Display for TaskKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.challenge, self.task)
+    }
+}
+
+/// Returns the keys of all reviewable tasks of a challenge type.
+/// Informative, custom and placeholder challenges have no reviewable tasks.
+pub fn task_keys(challenge_type: &ChallengeType) -> Vec<TaskKey> {
+    let id = challenge_type.id();
+    let tasks: Vec<String> = match challenge_type {
+        ChallengeType::MultipleChoice(dataset) => dataset
+            .questions
+            .iter()
+            .map(|q| q.question.clone())
+            .collect(),
+        ChallengeType::ContextualChoice(dataset) => dataset
+            .items
+            .iter()
+            .map(|item| item.template.clone())
+            .collect(),
+        ChallengeType::GapFill(dataset) => dataset
+            .questions
+            .iter()
+            .map(|q| q.sentence.clone())
+            .collect(),
+        ChallengeType::SortTable(dataset) => {
+            dataset.rows.iter().map(|row| row.id.to_string()).collect()
+        }
+        ChallengeType::Ordering(dataset) => dataset
+            .items
+            .iter()
+            .map(|item| item.elements.join(" "))
+            .collect(),
+        ChallengeType::Vocabulary(dataset) => dataset
+            .items
+            .iter()
+            .map(|item| item.id.to_string())
+            .collect(),
+        ChallengeType::Matching(dataset) => dataset
+            .pairs
+            .iter()
+            .map(|pair| pair.id.to_string())
+            .collect(),
+        ChallengeType::Translation(dataset) => dataset
+            .items
+            .iter()
+            .map(|item| item.sentence.clone())
+            .collect(),
+        ChallengeType::ErrorCorrection(dataset) => dataset
+            .sentences
+            .iter()
+            .map(|s| s.sentence.clone())
+            .collect(),
+        ChallengeType::Dialog(dataset) => dataset
+            .quiz_turns()
+            .map(|(index, _)| dialog_turn_key(index))
+            .collect(),
+        ChallengeType::Informative(_)
+        | ChallengeType::Custom(_)
+        | ChallengeType::Placeholder(_) => {
+            vec![]
+        }
+    };
+    tasks
+        .into_iter()
+        .map(|task| TaskKey::new(id, task))
+        .collect()
+}
+
+/// Returns whether each answered task of the challenge was solved correctly,
+/// using the same comparison as `Solvable::solve`. Skipped tasks and tasks
+/// whose time ran out are left out, as they were not reviewed.
+pub fn task_outcomes(challenge: &Challenge) -> Vec<(TaskKey, bool)> {
+    let keys = task_keys(&challenge.challenge_type);
+    let outcomes: Vec<(usize, bool)> =
+        match (&challenge.challenge_type, &challenge.challenge_result) {
+            (ChallengeType::MultipleChoice(dataset), ChallengeResult::MultipleChoice(options)) => {
+                dataset
+                    .questions
+                    .iter()
+                    .zip(options.iter())
+                    .map(|(question, option)| question.option == option.id)
+                    .enumerate()
+                    .collect()
+            }
+            (
+                ChallengeType::ContextualChoice(dataset),
+                ChallengeResult::ContextualChoice(choices),
+            ) => dataset
+                .items
+                .iter()
+                .zip(choices.iter())
+                .map(|(item, choice)| item.grade(choice, &dataset.grading).is_accepted())
+                .enumerate()
+                .collect(),
+            (ChallengeType::GapFill(dataset), ChallengeResult::GapFill(answers)) => dataset
+                .questions
+                .iter()
+                .zip(answers.iter())
+                .map(|(question, answer)| {
+                    question
+                        .grade(&answer.answers, &dataset.grading)
+                        .is_accepted()
+                })
+                .enumerate()
+                .collect(),
+            (ChallengeType::SortTable(dataset), ChallengeResult::SortTable(rows)) => dataset
+                .rows
+                .iter()
+                .zip(rows.iter())
+                .map(|(row, result)| row.values == result.values)
+                .enumerate()
+                .collect(),
+            (ChallengeType::Ordering(dataset), ChallengeResult::Ordering(results)) => dataset
+                .items
+                .iter()
+                .zip(results.iter())
+                .map(|(item, result)| item.correct_order == result.order)
+                .enumerate()
+                .collect(),
+            (ChallengeType::Vocabulary(dataset), ChallengeResult::Vocabulary(answers)) => answers
+                .iter()
+                .filter_map(|answer| {
+                    let position = dataset.items.iter().position(|i| i.id == answer.item_id)?;
+                    Some((position, dataset.grade_answer(answer).is_accepted()))
+                })
+                .collect(),
+            (ChallengeType::Matching(dataset), ChallengeResult::Matching(answers)) => answers
+                .iter()
+                .filter_map(|answer| {
+                    let position = dataset.pairs.iter().position(|p| p.id == answer.left)?;
+                    Some((position, dataset.is_correct(answer)))
+                })
+                .collect(),
+            (ChallengeType::Translation(dataset), ChallengeResult::Translation(answers)) => answers
+                .iter()
+                .map(|answer| {
+                    (
+                        answer.item_index,
+                        dataset.grade_answer(answer).is_accepted(),
+                    )
+                })
+                .collect(),
+            (
+                ChallengeType::ErrorCorrection(dataset),
+                ChallengeResult::ErrorCorrection(answers),
+            ) => answers
+                .iter()
+                .map(|answer| (answer.sentence_index, dataset.check_answer(answer)))
+                .collect(),
+            (ChallengeType::Dialog(dataset), ChallengeResult::Dialog(answers)) => {
+                let quiz_turns: Vec<usize> = dataset.quiz_turns().map(|(i, _)| i).collect();
+                answers
+                    .iter()
+                    .filter_map(|answer| {
+                        let position = quiz_turns.iter().position(|&i| i == answer.turn_index)?;
+                        let turn = dataset.turns.get(answer.turn_index)?;
+                        Some((
+                            position,
+                            turn.correct_option == Some(answer.selected_option),
+                        ))
+                    })
+                    .collect()
+            }
+            _ => vec![],
+        };
+
+    outcomes
+        .into_iter()
+        .filter(|(index, _)| challenge.answered_in_time(*index))
+        .filter_map(|(index, correct)| keys.get(index).map(|key| (key.clone(), correct)))
+        .collect()
+}
+
+/// Returns a copy of the challenge type that only contains the tasks for which
+/// `keep` returns `true`. Dialogs are kept as a whole as soon as one of their
+/// quiz turns is kept, since their turns only make sense in sequence.
+pub fn retain_tasks(
+    challenge_type: &ChallengeType,
+    keep: impl Fn(&TaskKey) -> bool,
+) -> ChallengeType {
+    let id = challenge_type.id().to_string();
+    let keep = |task: String| keep(&TaskKey::new(id.as_str(), task));
+
+    match challenge_type {
+        ChallengeType::MultipleChoice(dataset) => {
+            let mut dataset = dataset.clone();
+            dataset.questions.retain(|q| keep(q.question.clone()));
+            ChallengeType::MultipleChoice(dataset)
+        }
+        ChallengeType::ContextualChoice(dataset) => {
+            let mut dataset = dataset.clone();
+            dataset.items.retain(|item| keep(item.template.clone()));
+            ChallengeType::ContextualChoice(dataset)
+        }
+        ChallengeType::GapFill(dataset) => {
+            let mut dataset = dataset.clone();
+            dataset.questions.retain(|q| keep(q.sentence.clone()));
+            ChallengeType::GapFill(dataset)
+        }
+        ChallengeType::SortTable(dataset) => {
+            let mut dataset = dataset.clone();
+            dataset.rows.retain(|row| keep(row.id.to_string()));
+            ChallengeType::SortTable(dataset)
+        }
+        ChallengeType::Ordering(dataset) => {
+            let mut dataset = dataset.clone();
+            dataset.items.retain(|item| keep(item.elements.join(" ")));
+            ChallengeType::Ordering(dataset)
+        }
+        ChallengeType::Vocabulary(dataset) => {
+            let mut dataset = dataset.clone();
+            dataset.items.retain(|item| keep(item.id.to_string()));
+            ChallengeType::Vocabulary(dataset)
+        }
+        ChallengeType::Matching(dataset) => {
+            let mut dataset = dataset.clone();
+            dataset.pairs.retain(|pair| keep(pair.id.to_string()));
+            ChallengeType::Matching(dataset)
+        }
+        ChallengeType::Translation(dataset) => {
+            let mut dataset = dataset.clone();
+            dataset.items.retain(|item| keep(item.sentence.clone()));
+            ChallengeType::Translation(dataset)
+        }
+        ChallengeType::ErrorCorrection(dataset) => {
+            let mut dataset = dataset.clone();
+            dataset.sentences.retain(|s| keep(s.sentence.clone()));
+            ChallengeType::ErrorCorrection(dataset)
+        }
+        ChallengeType::Dialog(dataset) => {
+            let mut dataset = dataset.clone();
+            if !dataset.quiz_turns().any(|(i, _)| keep(dialog_turn_key(i))) {
+                dataset.turns.clear();
+            }
+            ChallengeType::Dialog(dataset)
+        }
+        ChallengeType::Informative(_)
+        | ChallengeType::Custom(_)
+        | ChallengeType::Placeholder(_) => challenge_type.clone(),
+    }
+}
+
+/// Appends the tasks of `other` that are missing in `challenge_type`.
+/// Both must be of the same challenge type, otherwise nothing happens.
+pub fn merge_tasks(challenge_type: &mut ChallengeType, other: &ChallengeType) {
+    let known = task_keys(challenge_type);
+    let missing = retain_tasks(other, |key| !known.contains(key));
+
+    match (challenge_type, missing) {
+        (ChallengeType::MultipleChoice(dataset), ChallengeType::MultipleChoice(other)) => {
+            dataset.questions.extend(other.questions)
+        }
+        (ChallengeType::ContextualChoice(dataset), ChallengeType::ContextualChoice(other)) => {
+            dataset.items.extend(other.items)
+        }
+        (ChallengeType::GapFill(dataset), ChallengeType::GapFill(other)) => {
+            dataset.questions.extend(other.questions)
+        }
+        (ChallengeType::SortTable(dataset), ChallengeType::SortTable(other)) => {
+            dataset.rows.extend(other.rows)
+        }
+        (ChallengeType::Ordering(dataset), ChallengeType::Ordering(other)) => {
+            dataset.items.extend(other.items)
+        }
+        (ChallengeType::Vocabulary(dataset), ChallengeType::Vocabulary(other)) => {
+            dataset.items.extend(other.items)
+        }
+        (ChallengeType::Matching(dataset), ChallengeType::Matching(other)) => {
+            dataset.pairs.extend(other.pairs)
+        }
+        (ChallengeType::Translation(dataset), ChallengeType::Translation(other)) => {
+            dataset.items.extend(other.items)
+        }
+        (ChallengeType::ErrorCorrection(dataset), ChallengeType::ErrorCorrection(other)) => {
+            dataset.sentences.extend(other.sentences)
+        }
+        _ => {}
+    }
+}
+
+fn dialog_turn_key(index: usize) -> String {
+    format!("turn-{}", index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{ChallengeConfig, ChallengeInput, MultipleChoiceOption, Solvable};
+
+    #[test]
+    fn task_keys_of_multiple_choice() {
+        let challenge_type = ChallengeType::default();
+        let keys = task_keys(&challenge_type);
+        assert!(!keys.is_empty());
+        assert!(keys.iter().all(|key| key.challenge == "konnektoren"));
+    }
+
+    #[test]
+    fn task_keys_of_informative_are_empty() {
+        let challenge_type = ChallengeType::Informative(Default::default());
+        assert!(task_keys(&challenge_type).is_empty());
+    }
+
+    #[test]
+    fn outcomes_follow_solve() {
+        let challenge_type = ChallengeType::default();
+        let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+        let ChallengeType::MultipleChoice(dataset) = &challenge_type else {
+            unreachable!()
+        };
+        let correct = dataset.questions[0].option;
+        let wrong = dataset
+            .options
+            .iter()
+            .find(|o| o.id != dataset.questions[1].option)
+            .unwrap()
+            .id;
+
+        challenge
+            .solve(
+                ChallengeInput::MultipleChoice(MultipleChoiceOption {
+                    id: correct,
+                    name: String::new(),
+                }),
+                0,
+            )
+            .unwrap();
+        challenge
+            .solve(
+                ChallengeInput::MultipleChoice(MultipleChoiceOption {
+                    id: wrong,
+                    name: String::new(),
+                }),
+                1,
+            )
+            .unwrap();
+
+        let outcomes = task_outcomes(&challenge);
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes[0].1);
+        assert!(!outcomes[1].1);
+    }
+
+    #[test]
+    fn outcomes_leave_out_skipped_tasks() {
+        let challenge_type = ChallengeType::default();
+        let mut challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+        let ChallengeType::MultipleChoice(dataset) = &challenge_type else {
+            unreachable!()
+        };
+        let option = dataset.questions[2].option;
+
+        // Answering the third task pads the first two
+        challenge
+            .solve(
+                ChallengeInput::MultipleChoice(MultipleChoiceOption {
+                    id: option,
+                    name: String::new(),
+                }),
+                2,
+            )
+            .unwrap();
+        assert_eq!(challenge.skipped_tasks, vec![0, 1]);
+
+        let outcomes = task_outcomes(&challenge);
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].0, task_keys(&challenge.challenge_type)[2]);
+
+        // Going back to a skipped task answers it
+        challenge
+            .solve(
+                ChallengeInput::MultipleChoice(MultipleChoiceOption {
+                    id: dataset.questions[0].option,
+                    name: String::new(),
+                }),
+                0,
+            )
+            .unwrap();
+        assert_eq!(challenge.skipped_tasks, vec![1]);
+        assert_eq!(task_outcomes(&challenge).len(), 2);
+    }
+
+    #[test]
+    fn outcomes_leave_out_timed_out_tasks() {
+        let mut challenge = Challenge::new(&ChallengeType::default(), &ChallengeConfig::default());
+        for task_index in 0..2 {
+            let input = challenge.unanswered_input(task_index).unwrap();
+            challenge.solve(input, task_index).unwrap();
+        }
+        challenge.timed_out_tasks = vec![0];
+
+        let outcomes = task_outcomes(&challenge);
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].0, task_keys(&challenge.challenge_type)[1]);
+    }
+
+    #[test]
+    fn retain_and_merge_tasks() {
+        let challenge_type = ChallengeType::default();
+        let keys = task_keys(&challenge_type);
+
+        let mut first = retain_tasks(&challenge_type, |key| key == &keys[0]);
+        let second = retain_tasks(&challenge_type, |key| key == &keys[1]);
+        assert_eq!(task_keys(&first), vec![keys[0].clone()]);
+
+        merge_tasks(&mut first, &second);
+        merge_tasks(&mut first, &second);
+        assert_eq!(task_keys(&first), vec![keys[0].clone(), keys[1].clone()]);
+    }
+}
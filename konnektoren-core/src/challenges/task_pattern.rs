@@ -1,5 +1,6 @@
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{Rng, SeedableRng, thread_rng};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -16,10 +17,26 @@ pub enum TaskPattern {
     Range(RangeInclusive<i32>),
     /// Random selection with optional range
     Random(usize, Option<RangeInclusive<i32>>),
+    /// Random selection with optional range that always picks the same tasks
+    /// for the same seed
+    SeededRandom(usize, Option<RangeInclusive<i32>>, u64),
+    /// Selection weighted by the player's history, see
+    /// [`TaskWeights`](super::adaptive::TaskWeights)
+    Adaptive(usize),
 }
 
 impl TaskPattern {
     pub fn parse(s: &str) -> Result<Self, String> {
+        if let Some((pattern, seed)) = s.split_once('@') {
+            let seed = seed
+                .parse::<u64>()
+                .map_err(|_| "Invalid seed".to_string())?;
+            return match Self::parse(pattern)? {
+                TaskPattern::Random(n, range) => Ok(TaskPattern::SeededRandom(n, range, seed)),
+                _ => Err("Seed requires a random pattern".to_string()),
+            };
+        }
+
         if let Ok(n) = s.parse::<usize>() {
             return Ok(TaskPattern::Exact(n));
         }
@@ -35,6 +52,9 @@ impl TaskPattern {
             if parts[1] == "random" {
                 return Ok(TaskPattern::Random(n, None));
             }
+            if parts[1] == "adaptive" {
+                return Ok(TaskPattern::Adaptive(n));
+            }
             let range = Self::parse_range(parts[1])?;
             return Ok(TaskPattern::Random(n, Some(range)));
         }
@@ -63,7 +83,24 @@ impl TaskPattern {
         }
     }
 
+    /// Selects the tasks of this pattern, picking random tasks with the
+    /// thread-local RNG.
     pub fn select_items<T: Clone>(&self, items: &[T]) -> Vec<T> {
+        self.select_items_with_rng(items, &mut thread_rng())
+    }
+
+    /// Selects the tasks of this pattern, picking random tasks with `rng`.
+    ///
+    /// A [`TaskPattern::SeededRandom`] ignores `rng` and always uses its own
+    /// seed. A [`TaskPattern::Adaptive`] pattern has no history here, so it
+    /// picks its tasks like a random pattern; use
+    /// [`ChallengeType::of_tasks_adaptive`](super::ChallengeType::of_tasks_adaptive)
+    /// to take the history into account.
+    pub fn select_items_with_rng<T: Clone, R: Rng + ?Sized>(
+        &self,
+        items: &[T],
+        rng: &mut R,
+    ) -> Vec<T> {
         match self {
             TaskPattern::Exact(n) => items.iter().take(*n).cloned().collect(),
             TaskPattern::Range(range) => {
@@ -72,21 +109,56 @@ impl TaskPattern {
                 items[start..=end].to_vec()
             }
             TaskPattern::Random(n, range_opt) => {
-                let range = range_opt
-                    .as_ref()
-                    .map(|r| {
-                        let start = *r.start().max(&0) as usize;
-                        let end = *r.end().min(&(items.len() as i32 - 1)) as usize;
-                        start..=end
-                    })
-                    .unwrap_or(0..=items.len() - 1);
-
-                let mut rng = thread_rng();
+                let range = Self::random_range(range_opt, items.len());
+                items[range].choose_multiple(rng, *n).cloned().collect()
+            }
+            TaskPattern::SeededRandom(n, range_opt, seed) => {
+                let range = Self::random_range(range_opt, items.len());
+                let mut rng = StdRng::seed_from_u64(*seed);
                 items[range]
                     .choose_multiple(&mut rng, *n)
                     .cloned()
                     .collect()
             }
+            TaskPattern::Adaptive(n) => items.choose_multiple(rng, *n).cloned().collect(),
+        }
+    }
+
+    fn random_range(range: &Option<RangeInclusive<i32>>, len: usize) -> RangeInclusive<usize> {
+        range
+            .as_ref()
+            .map(|r| {
+                let start = *r.start().max(&0) as usize;
+                let end = *r.end().min(&(len as i32 - 1)) as usize;
+                start..=end
+            })
+            .unwrap_or(0..=len - 1)
+    }
+
+    /// Returns whether the pattern picks its tasks at random.
+    pub fn is_random(&self) -> bool {
+        matches!(
+            self,
+            TaskPattern::Random(..) | TaskPattern::SeededRandom(..)
+        )
+    }
+
+    /// Returns the seed of a [`TaskPattern::SeededRandom`] pattern.
+    pub fn seed(&self) -> Option<u64> {
+        match self {
+            TaskPattern::SeededRandom(_, _, seed) => Some(*seed),
+            _ => None,
+        }
+    }
+
+    /// Returns the same pattern with its random selection fixed by `seed`.
+    /// Patterns that are not random are returned unchanged.
+    pub fn with_seed(&self, seed: u64) -> Self {
+        match self {
+            TaskPattern::Random(n, range) | TaskPattern::SeededRandom(n, range, _) => {
+                TaskPattern::SeededRandom(*n, range.clone(), seed)
+            }
+            pattern => pattern.clone(),
         }
     }
 
@@ -98,7 +170,9 @@ impl TaskPattern {
                 let end = *range.end() as usize;
                 end.saturating_sub(start) + 1
             }
-            TaskPattern::Random(n, _) => *n,
+            TaskPattern::Random(n, _)
+            | TaskPattern::SeededRandom(n, _, _)
+            | TaskPattern::Adaptive(n) => *n,
         }
     }
 
@@ -106,7 +180,9 @@ impl TaskPattern {
         match self {
             TaskPattern::Exact(n) => *n == 0,
             TaskPattern::Range(range) => range.is_empty(),
-            TaskPattern::Random(n, _) => *n == 0,
+            TaskPattern::Random(n, _)
+            | TaskPattern::SeededRandom(n, _, _)
+            | TaskPattern::Adaptive(n) => *n == 0,
         }
     }
 }
@@ -134,6 +210,10 @@ impl fmt::Display for TaskPattern {
                 }
             }
             TaskPattern::Random(n, None) => write!(f, "{}:random", n),
+            TaskPattern::SeededRandom(n, range, seed) => {
+                write!(f, "{}@{}", TaskPattern::Random(*n, range.clone()), seed)
+            }
+            TaskPattern::Adaptive(n) => write!(f, "{}:adaptive", n),
         }
     }
 }
@@ -152,6 +232,17 @@ impl Serialize for TaskPattern {
                 serializer.serialize_str(&format!("{}:{}..{}", n, range.start(), range.end()))
             }
             TaskPattern::Random(n, None) => serializer.serialize_str(&format!("{}:random", n)),
+            TaskPattern::SeededRandom(n, Some(range), seed) => serializer.serialize_str(&format!(
+                "{}:{}..{}@{}",
+                n,
+                range.start(),
+                range.end(),
+                seed
+            )),
+            TaskPattern::SeededRandom(n, None, seed) => {
+                serializer.serialize_str(&format!("{}:random@{}", n, seed))
+            }
+            TaskPattern::Adaptive(n) => serializer.serialize_str(&format!("{}:adaptive", n)),
         }
     }
 }
@@ -218,6 +309,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_seeded_random() {
+        assert_eq!(
+            TaskPattern::parse("5:random@42"),
+            Ok(TaskPattern::SeededRandom(5, None, 42))
+        );
+        assert_eq!(
+            TaskPattern::parse("5:1..10@7"),
+            Ok(TaskPattern::SeededRandom(5, Some(1..=10), 7))
+        );
+        assert_eq!(
+            TaskPattern::parse("5:random@abc"),
+            Err("Invalid seed".to_string())
+        );
+        assert_eq!(
+            TaskPattern::parse("1..10@7"),
+            Err("Seed requires a random pattern".to_string())
+        );
+    }
+
+    #[test]
+    fn test_adaptive() {
+        let pattern = TaskPattern::parse("10:adaptive").unwrap();
+        assert_eq!(pattern, TaskPattern::Adaptive(10));
+        assert_eq!(pattern.len(), 10);
+        assert!(!pattern.is_random());
+        assert_eq!(serde_json::to_string(&pattern).unwrap(), "\"10:adaptive\"");
+        assert_eq!(pattern.to_string(), "10:adaptive");
+
+        let items: Vec<i32> = (1..=20).collect();
+        assert_eq!(pattern.select_items(&items).len(), 10);
+    }
+
+    #[test]
+    fn test_seeded_selection_is_reproducible() {
+        let items: Vec<i32> = (1..=20).collect();
+
+        let pattern = TaskPattern::SeededRandom(5, Some(1..=10), 42);
+        let selected = pattern.select_items(&items);
+        assert_eq!(selected.len(), 5);
+        assert!(selected.iter().all(|&x| (2..=11).contains(&x)));
+        assert_eq!(pattern.select_items(&items), selected);
+
+        let random = TaskPattern::Random(5, None);
+        assert_eq!(
+            random.select_items_with_rng(&items, &mut StdRng::seed_from_u64(1)),
+            random.select_items_with_rng(&items, &mut StdRng::seed_from_u64(1))
+        );
+        assert_eq!(
+            random.with_seed(1).select_items(&items),
+            random.select_items_with_rng(&items, &mut StdRng::seed_from_u64(1))
+        );
+    }
+
+    #[test]
+    fn test_with_seed() {
+        assert_eq!(
+            TaskPattern::Random(5, None).with_seed(3),
+            TaskPattern::SeededRandom(5, None, 3)
+        );
+        assert_eq!(TaskPattern::Exact(5).with_seed(3), TaskPattern::Exact(5));
+        assert_eq!(TaskPattern::SeededRandom(5, None, 3).seed(), Some(3));
+        assert!(TaskPattern::SeededRandom(5, None, 3).is_random());
+        assert!(!TaskPattern::Range(1..=3).is_random());
+    }
+
     #[test]
     fn test_select_items() {
         let items: Vec<i32> = (1..=20).collect();
@@ -253,6 +410,13 @@ mod tests {
 
         let pattern = TaskPattern::Random(5, None);
         assert_eq!(serde_json::to_string(&pattern).unwrap(), "\"5:random\"");
+
+        let pattern = TaskPattern::SeededRandom(5, Some(1..=10), 42);
+        assert_eq!(serde_json::to_string(&pattern).unwrap(), "\"5:1..10@42\"");
+        assert_eq!(pattern.to_string(), "5:1..=10@42");
+
+        let pattern = TaskPattern::SeededRandom(5, None, 42);
+        assert_eq!(serde_json::to_string(&pattern).unwrap(), "\"5:random@42\"");
     }
 
     #[test]
@@ -268,6 +432,9 @@ mod tests {
 
         let pattern: TaskPattern = serde_json::from_str("\"5:random\"").unwrap();
         assert_eq!(pattern, TaskPattern::Random(5, None));
+
+        let pattern: TaskPattern = serde_json::from_str("\"5:1..10@42\"").unwrap();
+        assert_eq!(pattern, TaskPattern::SeededRandom(5, Some(1..=10), 42));
     }
 
     #[test]
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// How long a challenge and each of its tasks may take.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(default)]
+pub struct TimeLimit {
+    /// Seconds for the whole challenge, after which it is finished
+    pub challenge_seconds: Option<u32>,
+    /// Seconds for each task, after which the task is skipped
+    pub task_seconds: Option<u32>,
+    /// Seconds before a limit runs out at which the player is warned
+    pub warning_seconds: u32,
+}
+
+impl Default for TimeLimit {
+    fn default() -> Self {
+        TimeLimit {
+            challenge_seconds: None,
+            task_seconds: None,
+            warning_seconds: 10,
+        }
+    }
+}
+
+/// How much of a time limit is left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeStatus {
+    /// There is no limit
+    Unlimited,
+    /// More time is left than the warning period
+    Running { remaining_seconds: i64 },
+    /// The limit runs out within the warning period
+    Warning { remaining_seconds: i64 },
+    /// The limit ran out
+    Expired,
+}
+
+impl TimeLimit {
+    /// Returns the status of the challenge limit for a challenge started at
+    /// `started`.
+    pub fn challenge_status(&self, started: DateTime<Utc>, now: DateTime<Utc>) -> TimeStatus {
+        self.status(self.challenge_seconds, started, now)
+    }
+
+    /// Returns the status of the task limit for a task started at
+    /// `started`.
+    pub fn task_status(&self, started: DateTime<Utc>, now: DateTime<Utc>) -> TimeStatus {
+        self.status(self.task_seconds, started, now)
+    }
+
+    fn status(&self, limit: Option<u32>, started: DateTime<Utc>, now: DateTime<Utc>) -> TimeStatus {
+        let Some(limit) = limit else {
+            return TimeStatus::Unlimited;
+        };
+        let remaining_seconds = i64::from(limit) - (now - started).num_seconds();
+        if remaining_seconds <= 0 {
+            TimeStatus::Expired
+        } else if remaining_seconds <= i64::from(self.warning_seconds) {
+            TimeStatus::Warning { remaining_seconds }
+        } else {
+            TimeStatus::Running { remaining_seconds }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn unlimited_by_default() {
+        let limit = TimeLimit::default();
+        let now = Utc::now();
+        assert_eq!(limit.challenge_status(now, now), TimeStatus::Unlimited);
+        assert_eq!(limit.task_status(now, now), TimeStatus::Unlimited);
+    }
+
+    #[test]
+    fn status_over_time() {
+        let limit = TimeLimit {
+            challenge_seconds: Some(60),
+            task_seconds: Some(15),
+            warning_seconds: 10,
+        };
+        let started = Utc::now();
+        let after = |seconds| started + Duration::seconds(seconds);
+
+        assert_eq!(
+            limit.challenge_status(started, after(20)),
+            TimeStatus::Running {
+                remaining_seconds: 40
+            }
+        );
+        assert_eq!(
+            limit.task_status(started, after(5)),
+            TimeStatus::Warning {
+                remaining_seconds: 10
+            }
+        );
+        assert_eq!(limit.task_status(started, after(15)), TimeStatus::Expired);
+        assert_eq!(
+            limit.challenge_status(started, after(61)),
+            TimeStatus::Expired
+        );
+    }
+
+    #[test]
+    fn deserialize_time_limit() {
+        let limit: TimeLimit = serde_yaml::from_str("task_seconds: 20\n").unwrap();
+        assert_eq!(limit.task_seconds, Some(20));
+        assert_eq!(limit.challenge_seconds, None);
+        assert_eq!(limit.warning_seconds, 10);
+    }
+}
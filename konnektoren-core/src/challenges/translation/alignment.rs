@@ -0,0 +1,248 @@
+use crate::challenges::grading::{AnswerVerdict, GradingPolicy};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// How a single word of an answer relates to the reference translation.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenStatus {
+    /// The word is in the reference, in the right place
+    Matched,
+    /// The word is in the reference, but in the wrong place
+    Misplaced,
+    /// The word is in the reference, but not in the answer
+    Missing,
+    /// The word is in the answer, but not in the reference
+    Extra,
+}
+
+/// A word of the aligned answer together with its status.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct AlignedToken {
+    /// The word as written in the answer, or in the reference if it is missing
+    pub text: String,
+    pub status: TokenStatus,
+}
+
+/// The word-by-word comparison of an answer with one reference translation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct Alignment {
+    /// The words of the answer in order, with the missing words of the
+    /// reference inserted where they belong
+    pub tokens: Vec<AlignedToken>,
+    /// Whether a matched word was only accepted as a typo
+    pub has_typos: bool,
+}
+
+impl Alignment {
+    /// Aligns the answer with the reference. Words are matched along their
+    /// longest common subsequence; the remaining words of the answer are
+    /// misplaced if the reference still contains them, and extra otherwise.
+    pub fn new(reference: &str, answer: &str, grading: &GradingPolicy) -> Self {
+        let expected = tokenize(reference);
+        let given = tokenize(answer);
+        let grade = |e: &str, g: &str| grading.grade(e, g);
+
+        // Longest common subsequence table, filled from the back
+        let mut table = vec![vec![0usize; given.len() + 1]; expected.len() + 1];
+        for i in (0..expected.len()).rev() {
+            for j in (0..given.len()).rev() {
+                table[i][j] = if grade(expected[i], given[j]).is_accepted() {
+                    table[i + 1][j + 1] + 1
+                } else {
+                    table[i + 1][j].max(table[i][j + 1])
+                };
+            }
+        }
+
+        let mut matched_expected = vec![false; expected.len()];
+        // For every word of the answer: its status and, if matched, the
+        // position of its partner in the reference
+        let mut matched_given = vec![(TokenStatus::Extra, None); given.len()];
+        let mut has_typos = false;
+        let (mut i, mut j) = (0, 0);
+        while i < expected.len() && j < given.len() {
+            let verdict = grade(expected[i], given[j]);
+            if verdict.is_accepted() && table[i][j] == table[i + 1][j + 1] + 1 {
+                has_typos |= verdict == AnswerVerdict::CorrectWithTypo;
+                matched_expected[i] = true;
+                matched_given[j] = (TokenStatus::Matched, Some(i));
+                i += 1;
+                j += 1;
+            } else if table[i + 1][j] >= table[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        for (j, word) in given.iter().enumerate() {
+            if matched_given[j].0 == TokenStatus::Matched {
+                continue;
+            }
+            let misplaced = (0..expected.len())
+                .find(|&i| !matched_expected[i] && grade(expected[i], word).is_accepted());
+            if let Some(i) = misplaced {
+                matched_expected[i] = true;
+                matched_given[j].0 = TokenStatus::Misplaced;
+            }
+        }
+
+        // Missing words are inserted before the next matched word that
+        // follows them in the reference.
+        let mut tokens = Vec::with_capacity(given.len() + expected.len());
+        let missing = |tokens: &mut Vec<AlignedToken>, range: std::ops::Range<usize>| {
+            for i in range.filter(|&i| !matched_expected[i]) {
+                tokens.push(AlignedToken {
+                    text: expected[i].to_string(),
+                    status: TokenStatus::Missing,
+                });
+            }
+        };
+        let mut next_expected = 0;
+        for (word, (status, partner)) in given.iter().zip(matched_given) {
+            if let Some(i) = partner {
+                missing(&mut tokens, next_expected..i);
+                next_expected = i + 1;
+            }
+            tokens.push(AlignedToken {
+                text: word.to_string(),
+                status,
+            });
+        }
+        missing(&mut tokens, next_expected..expected.len());
+
+        Alignment { tokens, has_typos }
+    }
+
+    pub fn count(&self, status: TokenStatus) -> usize {
+        self.tokens.iter().filter(|t| t.status == status).count()
+    }
+
+    /// Returns the credit between `0.0` and `1.0`. Misplaced words earn half
+    /// a point, and extra words count against the answer like missing ones.
+    pub fn score(&self) -> f64 {
+        let total = self.tokens.len();
+        if total == 0 {
+            return 1.0;
+        }
+        let matched = self.count(TokenStatus::Matched) as f64;
+        let misplaced = self.count(TokenStatus::Misplaced) as f64;
+        (matched + misplaced / 2.0) / total as f64
+    }
+
+    pub fn verdict(&self) -> AnswerVerdict {
+        if self.tokens.iter().any(|t| t.status != TokenStatus::Matched) {
+            AnswerVerdict::Wrong
+        } else if self.has_typos {
+            AnswerVerdict::CorrectWithTypo
+        } else {
+            AnswerVerdict::Correct
+        }
+    }
+}
+
+/// Splits a sentence into words, dropping the punctuation around them.
+fn tokenize(sentence: &str) -> Vec<&str> {
+    sentence
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statuses(alignment: &Alignment) -> Vec<(&str, TokenStatus)> {
+        alignment
+            .tokens
+            .iter()
+            .map(|t| (t.text.as_str(), t.status))
+            .collect()
+    }
+
+    #[test]
+    fn exact_answer() {
+        let alignment = Alignment::new(
+            "Ich bin nach Berlin gefahren.",
+            "ich bin nach Berlin gefahren",
+            &GradingPolicy::tolerant(),
+        );
+        assert_eq!(alignment.verdict(), AnswerVerdict::Correct);
+        assert_eq!(alignment.score(), 1.0);
+    }
+
+    #[test]
+    fn missing_and_extra_words() {
+        use TokenStatus::*;
+        let alignment = Alignment::new(
+            "Ich bin nach Berlin gefahren",
+            "Ich bin gestern Berlin gefahren",
+            &GradingPolicy::tolerant(),
+        );
+        assert_eq!(
+            statuses(&alignment),
+            vec![
+                ("Ich", Matched),
+                ("bin", Matched),
+                ("gestern", Extra),
+                ("nach", Missing),
+                ("Berlin", Matched),
+                ("gefahren", Matched),
+            ]
+        );
+        assert_eq!(alignment.verdict(), AnswerVerdict::Wrong);
+        assert_eq!(alignment.score(), 4.0 / 6.0);
+    }
+
+    #[test]
+    fn misplaced_words() {
+        use TokenStatus::*;
+        let alignment = Alignment::new(
+            "Ich bin nach Berlin gefahren",
+            "Ich bin gefahren nach Berlin",
+            &GradingPolicy::tolerant(),
+        );
+        assert_eq!(
+            statuses(&alignment),
+            vec![
+                ("Ich", Matched),
+                ("bin", Matched),
+                ("gefahren", Misplaced),
+                ("nach", Matched),
+                ("Berlin", Matched),
+            ]
+        );
+        assert_eq!(alignment.score(), 4.5 / 5.0);
+    }
+
+    #[test]
+    fn typos_are_tolerated_per_word() {
+        let alignment = Alignment::new(
+            "Ich bin nach Berlin gefahren",
+            "Ich bin nach Berlin gefaren",
+            &GradingPolicy::tolerant(),
+        );
+        assert_eq!(alignment.verdict(), AnswerVerdict::CorrectWithTypo);
+
+        let alignment = Alignment::new(
+            "Ich bin nach Berlin gefahren",
+            "Ich bin nach Berlin gefaren",
+            &GradingPolicy::exact(),
+        );
+        assert_eq!(alignment.verdict(), AnswerVerdict::Wrong);
+    }
+
+    #[test]
+    fn empty_answer() {
+        let alignment = Alignment::new("Guten Tag", "", &GradingPolicy::tolerant());
+        assert_eq!(alignment.count(TokenStatus::Missing), 2);
+        assert_eq!(alignment.score(), 0.0);
+    }
+}
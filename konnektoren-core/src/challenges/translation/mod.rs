@@ -0,0 +1,181 @@
+mod alignment;
+
+pub use alignment::{AlignedToken, Alignment, TokenStatus};
+
+use crate::challenges::CefrLevel;
+use crate::challenges::grading::{AnswerVerdict, GradingPolicy};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct Translation {
+    /// Unique identifier for the challenge
+    pub id: String,
+    /// Display name of the challenge
+    pub name: String,
+    /// Description of the challenge
+    pub description: String,
+    /// Language code of the translations
+    pub lang: String,
+    /// List of sentences to translate
+    pub items: Vec<TranslationItem>,
+    /// How the words of an answer are compared with the reference
+    #[serde(default)]
+    pub grading: GradingPolicy,
+    /// CEFR level of the challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, e.g. "konnektoren"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Default for Translation {
+    fn default() -> Self {
+        let data = include_str!("../../../assets/translation_default.yml");
+        serde_yaml::from_str(data).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct TranslationItem {
+    /// The sentence to translate
+    pub sentence: String,
+    /// Reference translation of the sentence
+    pub translation: String,
+    /// Other accepted translations
+    #[serde(default)]
+    pub alternatives: Vec<String>,
+    /// Helpful hints
+    #[serde(default)]
+    pub hints: Vec<String>,
+    /// Explanation of the grammar rule
+    #[serde(default)]
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct TranslationAnswer {
+    /// Index of the item being answered
+    pub item_index: usize,
+    /// The translation written by the player
+    pub text: String,
+}
+
+impl TranslationItem {
+    /// Returns the reference translation and its alternatives.
+    pub fn references(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.translation.as_str())
+            .chain(self.alternatives.iter().map(|a| a.as_str()))
+    }
+
+    /// Aligns the answer with the reference it comes closest to.
+    pub fn align(&self, answer: &str, grading: &GradingPolicy) -> Alignment {
+        self.references()
+            .map(|reference| Alignment::new(reference, answer, grading))
+            .max_by(|a, b| a.score().total_cmp(&b.score()))
+            .unwrap_or_default()
+    }
+}
+
+impl Translation {
+    pub fn align_answer(&self, answer: &TranslationAnswer) -> Option<Alignment> {
+        self.items
+            .get(answer.item_index)
+            .map(|item| item.align(&answer.text, &self.grading))
+    }
+
+    pub fn grade_answer(&self, answer: &TranslationAnswer) -> AnswerVerdict {
+        self.align_answer(answer)
+            .map(|alignment| alignment.verdict())
+            .unwrap_or_default()
+    }
+
+    /// Returns the credit between `0.0` and `1.0` earned by an answer.
+    pub fn score_answer(&self, answer: &TranslationAnswer) -> f64 {
+        self.align_answer(answer)
+            .map(|alignment| alignment.score())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(item_index: usize, text: &str) -> TranslationAnswer {
+        TranslationAnswer {
+            item_index,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_translation_deserialization() {
+        let yaml = r#"
+        id: "travel"
+        name: "Travel"
+        description: "Translate the sentences"
+        lang: "de"
+        items:
+          - sentence: "I went to Berlin."
+            translation: "Ich bin nach Berlin gefahren."
+            alternatives:
+              - "Ich fuhr nach Berlin."
+        "#;
+
+        let translation: Translation = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(translation.items.len(), 1);
+        assert_eq!(translation.items[0].references().count(), 2);
+        assert!(translation.items[0].hints.is_empty());
+        assert_eq!(translation.grading, GradingPolicy::default());
+    }
+
+    #[test]
+    fn default_translation() {
+        let translation = Translation::default();
+        assert!(!translation.items.is_empty());
+        let item = &translation.items[0];
+        let reference = item.translation.clone();
+        assert_eq!(
+            translation.grade_answer(&answer(0, &reference)),
+            AnswerVerdict::Correct
+        );
+    }
+
+    #[test]
+    fn closest_reference_wins() {
+        let translation = Translation {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            description: "Test".to_string(),
+            lang: "de".to_string(),
+            items: vec![TranslationItem {
+                sentence: "I went to Berlin.".to_string(),
+                translation: "Ich bin nach Berlin gefahren.".to_string(),
+                alternatives: vec!["Ich fuhr nach Berlin.".to_string()],
+                ..Default::default()
+            }],
+            grading: GradingPolicy::tolerant(),
+            level: None,
+            tags: vec![],
+        };
+
+        assert_eq!(
+            translation.grade_answer(&answer(0, "Ich fuhr nach Berlin")),
+            AnswerVerdict::Correct
+        );
+        assert_eq!(
+            translation.score_answer(&answer(0, "Ich fuhr Berlin")),
+            0.75
+        );
+        assert_eq!(
+            translation.grade_answer(&answer(1, "Ich fuhr nach Berlin")),
+            AnswerVerdict::Wrong
+        );
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// What the player answered for a single vocabulary card.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum VocabularyResponse {
+    /// The player rated the flipped card as known or unknown (study mode)
+    Known(bool),
+    /// The player typed an answer (recall mode)
+    Typed(String),
+    /// The player picked the item with this id (recognition mode)
+    Selected(usize),
+}
+
+impl Default for VocabularyResponse {
+    fn default() -> Self {
+        VocabularyResponse::Known(false)
+    }
+}
+
+/// A player's answer to one [`VocabularyItem`](super::VocabularyItem).
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct VocabularyAnswer {
+    /// Id of the vocabulary item that was asked
+    pub item_id: usize,
+    /// The player's response
+    pub response: VocabularyResponse,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_answer() {
+        let answer = VocabularyAnswer::default();
+        assert_eq!(answer.item_id, 0);
+        assert_eq!(answer.response, VocabularyResponse::Known(false));
+    }
+
+    #[test]
+    fn roundtrip_json() {
+        let answer = VocabularyAnswer {
+            item_id: 3,
+            response: VocabularyResponse::Typed("the apple".to_string()),
+        };
+        let json = serde_json::to_string(&answer).unwrap();
+        assert_eq!(json, r#"{"item_id":3,"response":{"typed":"the apple"}}"#);
+        let restored: VocabularyAnswer = serde_json::from_str(&json).unwrap();
+        assert_eq!(answer, restored);
+    }
+}
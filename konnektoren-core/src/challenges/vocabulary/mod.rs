@@ -1,3 +1,14 @@
+mod answer;
+mod mode;
+
+pub use answer::{VocabularyAnswer, VocabularyResponse};
+pub use mode::{VocabularyDirection, VocabularyMode};
+
+use crate::challenges::CefrLevel;
+use crate::challenges::grading::{AnswerVerdict, GradingPolicy};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -18,6 +29,21 @@ pub struct Vocabulary {
     pub lang: String,
     /// List of vocabulary items
     pub items: Vec<VocabularyItem>,
+    /// How the set is practised
+    #[serde(default)]
+    pub mode: VocabularyMode,
+    /// Which side of an item is asked for
+    #[serde(default)]
+    pub direction: VocabularyDirection,
+    /// How typed answers are compared in recall mode
+    #[serde(default)]
+    pub grading: GradingPolicy,
+    /// CEFR level of the challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<CefrLevel>,
+    /// Topic tags, e.g. "konnektoren"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Default for Vocabulary {
@@ -45,6 +71,118 @@ pub struct VocabularyItem {
     pub phonetic: Option<String>,
 }
 
+impl VocabularyItem {
+    /// The side of the card shown to the player.
+    pub fn prompt(&self, direction: VocabularyDirection) -> Option<&str> {
+        match direction {
+            VocabularyDirection::TextToTranslation => Some(&self.text),
+            VocabularyDirection::TranslationToText => self.translation.as_deref(),
+        }
+    }
+
+    /// The side of the card the player has to come up with.
+    pub fn expected(&self, direction: VocabularyDirection) -> Option<&str> {
+        match direction {
+            VocabularyDirection::TextToTranslation => self.translation.as_deref(),
+            VocabularyDirection::TranslationToText => Some(&self.text),
+        }
+    }
+}
+
+impl Vocabulary {
+    /// The number of options shown on a recognition card.
+    pub const RECOGNITION_OPTIONS: usize = 4;
+
+    pub fn item_by_id(&self, id: usize) -> Option<&VocabularyItem> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    /// Returns the items that can be asked, i.e. the ones with a translation.
+    pub fn quiz_items(&self) -> impl Iterator<Item = &VocabularyItem> {
+        self.items.iter().filter(|item| item.translation.is_some())
+    }
+
+    /// Grades a single answer. Only the response of the set's mode counts,
+    /// so a recall or recognition card cannot be passed by rating it as
+    /// known.
+    pub fn grade_answer(&self, answer: &VocabularyAnswer) -> AnswerVerdict {
+        let Some(item) = self.item_by_id(answer.item_id) else {
+            return AnswerVerdict::Wrong;
+        };
+
+        match (self.mode, &answer.response) {
+            (VocabularyMode::Study, VocabularyResponse::Known(known)) => (*known).into(),
+            (VocabularyMode::Recall, VocabularyResponse::Typed(text)) => {
+                match item.expected(self.direction) {
+                    Some(expected) => self.grading.grade(expected, text),
+                    None => AnswerVerdict::Wrong,
+                }
+            }
+            (VocabularyMode::Recognition, VocabularyResponse::Selected(id)) if *id == item.id => {
+                AnswerVerdict::Correct
+            }
+            (VocabularyMode::Recognition, VocabularyResponse::Selected(id)) => {
+                // Items sharing the same expected answer are interchangeable.
+                let selected = self
+                    .item_by_id(*id)
+                    .and_then(|selected| selected.expected(self.direction));
+                match (item.expected(self.direction), selected) {
+                    (Some(expected), Some(selected)) => {
+                        self.grading.normalization.normalize(expected)
+                            == self.grading.normalization.normalize(selected)
+                    }
+                    _ => false,
+                }
+                .into()
+            }
+            _ => AnswerVerdict::Wrong,
+        }
+    }
+
+    /// Returns the answer options for a recognition card: the item itself
+    /// and up to `count - 1` other quiz items of the set, shuffled with
+    /// `seed` so the same seed always shows the same order.
+    pub fn recognition_options(
+        &self,
+        item_id: usize,
+        count: usize,
+        seed: u64,
+    ) -> Vec<&VocabularyItem> {
+        let Some(item) = self.item_by_id(item_id) else {
+            return vec![];
+        };
+        let start = self.items.iter().position(|i| i.id == item_id).unwrap_or(0);
+
+        let mut options: Vec<&VocabularyItem> = std::iter::once(item)
+            .chain(
+                self.items
+                    .iter()
+                    .cycle()
+                    .skip(start + 1)
+                    .take(self.items.len().saturating_sub(1))
+                    .filter(|other| other.translation.is_some()),
+            )
+            .take(count)
+            .collect();
+        options.shuffle(&mut StdRng::seed_from_u64(seed ^ item_id as u64));
+        options
+    }
+
+    /// Returns the options shown on the recognition card of a task, shuffled
+    /// with the seed of the challenge. [`ChallengeCommand::SolveOption`](crate::commands::ChallengeCommand::SolveOption)
+    /// selects from the same list.
+    pub fn task_options(&self, task_index: usize, seed: Option<u64>) -> Vec<&VocabularyItem> {
+        match self.items.get(task_index) {
+            Some(item) => self.recognition_options(
+                item.id,
+                Self::RECOGNITION_OPTIONS,
+                seed.unwrap_or_default(),
+            ),
+            None => vec![],
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +218,11 @@ mod tests {
             icon: icon.clone(),
             lang: lang.clone(),
             items: items.clone(),
+            mode: VocabularyMode::default(),
+            direction: VocabularyDirection::default(),
+            grading: GradingPolicy::default(),
+            level: None,
+            tags: vec![],
         };
 
         assert_eq!(vocabulary.id, id);
@@ -205,6 +348,11 @@ mod tests {
                 icon: None,
                 phonetic: None,
             }],
+            mode: VocabularyMode::default(),
+            direction: VocabularyDirection::default(),
+            grading: GradingPolicy::default(),
+            level: None,
+            tags: vec![],
         };
 
         let yaml = serde_yaml::to_string(&vocabulary).unwrap();
@@ -229,10 +377,163 @@ mod tests {
                 icon: None,
                 phonetic: None,
             }],
+            mode: VocabularyMode::default(),
+            direction: VocabularyDirection::default(),
+            grading: GradingPolicy::default(),
+            level: None,
+            tags: vec![],
         };
 
         let json = serde_json::to_string(&vocabulary).unwrap();
         let deserialized: Vocabulary = serde_json::from_str(&json).unwrap();
         assert_eq!(vocabulary, deserialized);
     }
+
+    fn recall_vocabulary(direction: VocabularyDirection) -> Vocabulary {
+        Vocabulary {
+            mode: VocabularyMode::Recall,
+            direction,
+            grading: GradingPolicy::tolerant(),
+            ..Vocabulary::default()
+        }
+    }
+
+    #[test]
+    fn grade_typed_answer() {
+        let vocabulary = recall_vocabulary(VocabularyDirection::TextToTranslation);
+        let answer = |text: &str| VocabularyAnswer {
+            item_id: 0,
+            response: VocabularyResponse::Typed(text.to_string()),
+        };
+
+        assert_eq!(
+            vocabulary.grade_answer(&answer("The Apple")),
+            AnswerVerdict::Correct
+        );
+        assert_eq!(
+            vocabulary.grade_answer(&answer("the aple")),
+            AnswerVerdict::CorrectWithTypo
+        );
+        assert_eq!(
+            vocabulary.grade_answer(&answer("the house")),
+            AnswerVerdict::Wrong
+        );
+    }
+
+    #[test]
+    fn grade_reverse_typed_answer() {
+        let vocabulary = recall_vocabulary(VocabularyDirection::TranslationToText);
+        let item = &vocabulary.items[0];
+        assert_eq!(item.prompt(vocabulary.direction), Some("the apple"));
+
+        let answer = VocabularyAnswer {
+            item_id: 0,
+            response: VocabularyResponse::Typed("der Apfel".to_string()),
+        };
+        assert_eq!(vocabulary.grade_answer(&answer), AnswerVerdict::Correct);
+    }
+
+    #[test]
+    fn grade_selected_and_known_answers() {
+        let recognition = Vocabulary {
+            mode: VocabularyMode::Recognition,
+            ..Vocabulary::default()
+        };
+        let vocabulary = Vocabulary::default();
+        let answer = |response| VocabularyAnswer {
+            item_id: 0,
+            response,
+        };
+
+        assert!(
+            recognition
+                .grade_answer(&answer(VocabularyResponse::Selected(0)))
+                .is_accepted()
+        );
+        assert!(
+            !recognition
+                .grade_answer(&answer(VocabularyResponse::Selected(1)))
+                .is_accepted()
+        );
+        assert!(
+            vocabulary
+                .grade_answer(&answer(VocabularyResponse::Known(true)))
+                .is_accepted()
+        );
+        assert!(
+            !vocabulary
+                .grade_answer(&answer(VocabularyResponse::Known(false)))
+                .is_accepted()
+        );
+        assert_eq!(
+            vocabulary.grade_answer(&VocabularyAnswer {
+                item_id: 999,
+                response: VocabularyResponse::Known(true),
+            }),
+            AnswerVerdict::Wrong
+        );
+    }
+
+    #[test]
+    fn responses_of_other_modes_are_wrong() {
+        let answer = |response| VocabularyAnswer {
+            item_id: 0,
+            response,
+        };
+        let known = answer(VocabularyResponse::Known(true));
+        let typed = answer(VocabularyResponse::Typed("the apple".to_string()));
+        let selected = answer(VocabularyResponse::Selected(0));
+
+        let recall = recall_vocabulary(VocabularyDirection::TextToTranslation);
+        assert_eq!(recall.grade_answer(&typed), AnswerVerdict::Correct);
+        assert_eq!(recall.grade_answer(&known), AnswerVerdict::Wrong);
+        assert_eq!(recall.grade_answer(&selected), AnswerVerdict::Wrong);
+
+        let recognition = Vocabulary {
+            mode: VocabularyMode::Recognition,
+            ..Vocabulary::default()
+        };
+        assert_eq!(recognition.grade_answer(&known), AnswerVerdict::Wrong);
+        assert_eq!(recognition.grade_answer(&typed), AnswerVerdict::Wrong);
+
+        let study = Vocabulary::default();
+        assert_eq!(study.grade_answer(&typed), AnswerVerdict::Wrong);
+        assert_eq!(study.grade_answer(&selected), AnswerVerdict::Wrong);
+    }
+
+    #[test]
+    fn recognition_options_contain_item() {
+        let vocabulary = Vocabulary::default();
+        let last = vocabulary.items.last().unwrap().id;
+
+        let options = vocabulary.recognition_options(last, 3, 1);
+        assert_eq!(options.len(), 3);
+        assert!(options.iter().any(|option| option.id == last));
+        assert!(
+            options
+                .iter()
+                .any(|option| option.id == vocabulary.items[0].id)
+        );
+        assert_eq!(options, vocabulary.recognition_options(last, 3, 1));
+
+        assert!(vocabulary.recognition_options(999, 3, 1).is_empty());
+        assert_eq!(
+            vocabulary.recognition_options(last, 100, 1).len(),
+            vocabulary.quiz_items().count()
+        );
+    }
+
+    #[test]
+    fn recognition_options_are_shuffled() {
+        let vocabulary = Vocabulary::default();
+        let first_position = |seed| {
+            vocabulary
+                .task_options(0, Some(seed))
+                .iter()
+                .position(|option| option.id == vocabulary.items[0].id)
+                .unwrap()
+        };
+        assert!((0..20).any(|seed| first_position(seed) != 0));
+        assert!(vocabulary.task_options(999, None).is_empty());
+    }
 }
@@ -0,0 +1,52 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// How a [`Vocabulary`](super::Vocabulary) set is practised.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum VocabularyMode {
+    /// Flashcards the player flips and rates as known or unknown.
+    /// Study sessions always count as completed.
+    #[default]
+    Study,
+    /// The player types the answer for each prompt.
+    Recall,
+    /// The player picks the answer among the other items of the set.
+    Recognition,
+}
+
+/// Which side of a [`VocabularyItem`](super::VocabularyItem) is shown as the prompt.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum VocabularyDirection {
+    /// Show the text, ask for the translation
+    #[default]
+    TextToTranslation,
+    /// Show the translation, ask for the text
+    TranslationToText,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults() {
+        assert_eq!(VocabularyMode::default(), VocabularyMode::Study);
+        assert_eq!(
+            VocabularyDirection::default(),
+            VocabularyDirection::TextToTranslation
+        );
+    }
+
+    #[test]
+    fn serialize_kebab_case() {
+        let yaml = serde_yaml::to_string(&VocabularyDirection::TranslationToText).unwrap();
+        assert_eq!(yaml.trim(), "translation-to-text");
+        let mode: VocabularyMode = serde_yaml::from_str("recognition").unwrap();
+        assert_eq!(mode, VocabularyMode::Recognition);
+    }
+}
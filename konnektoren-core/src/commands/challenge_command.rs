@@ -5,8 +5,9 @@ use super::command_type::CommandType;
 use crate::challenges::Timed;
 use crate::challenges::error::ChallengeError;
 use crate::challenges::{
-    Challenge, ChallengeInput, ChallengeResult, ChallengeType, ContextItemChoiceAnswers,
-    GapFillAnswer, MultipleChoiceOption, OrderingResult, Solvable, SortTableRow,
+    Challenge, ChallengeInput, ChallengeResult, ChallengeType, ErrorCorrectionAnswer,
+    MatchingAnswer, MultipleChoiceOption, Solvable, TranslationAnswer, Vocabulary,
+    VocabularyAnswer, VocabularyResponse, WordCorrection,
 };
 use crate::commands::error::{CommandError, Result};
 use crate::game::GamePath;
@@ -24,6 +25,21 @@ pub enum ChallengeCommand {
     PreviousTask,
     /// Command to solve a multiple choice option.
     SolveOption(usize),
+    /// Command to solve the current task with a typed answer.
+    SolveText(String),
+    /// Command to connect the left entry of the current matching task with
+    /// the right entry of the pair with the given id.
+    SolveMatch(usize),
+    /// Command to solve the current error correction task with the
+    /// corrections made to its sentence.
+    SolveCorrections(Vec<WordCorrection>),
+    /// Command to reveal the next hint for the current task.
+    RequestHint,
+    /// Command to check the time limits of the current challenge, published
+    /// regularly while a timed challenge is played. It leaves the state
+    /// untouched; the [`ChallengeTimerPlugin`](crate::controller::ChallengeTimerPlugin)
+    /// reacts to it.
+    CheckTime,
     /// Command to finish the challenge with a custom result.
     Finish(Option<ChallengeResult>),
 }
@@ -44,6 +60,13 @@ impl CommandTrait for ChallengeCommand {
             ChallengeCommand::NextTask => Self::next_task(state),
             ChallengeCommand::PreviousTask => Self::previous_task(state),
             ChallengeCommand::SolveOption(option_index) => Self::solve_option(state, *option_index),
+            ChallengeCommand::SolveText(text) => Self::solve_text(state, text),
+            ChallengeCommand::SolveMatch(right) => Self::solve_match(state, *right),
+            ChallengeCommand::SolveCorrections(corrections) => {
+                Self::solve_corrections(state, corrections)
+            }
+            ChallengeCommand::RequestHint => Self::request_hint(state),
+            ChallengeCommand::CheckTime => Ok(()),
             ChallengeCommand::Finish(result) => Self::finish_challenge(state, result),
         }
     }
@@ -55,8 +78,13 @@ impl CommandTrait for ChallengeCommand {
 }
 
 impl ChallengeCommand {
-    /// Starts a new challenge with the given challenge configuration.
+    /// Starts a new challenge with the given challenge configuration, unless
+    /// the challenge is still locked.
     fn start_challenge(state: &mut GameState, challenge: &Challenge) -> Result<()> {
+        state
+            .game
+            .ensure_unlocked(&challenge.challenge_config)
+            .map_err(CommandError::GameError)?;
         let mut challenge = challenge.clone();
         challenge.start();
         state.challenge = challenge;
@@ -87,52 +115,18 @@ impl ChallengeCommand {
             return Err(CommandError::ChallengeError(ChallengeError::NoMoreTasks));
         }
 
-        // QUICKFIX: If current task wasn't answered, add a default answer to keep indices aligned
+        // QUICKFIX: If current task wasn't answered, add an answer graded as
+        // wrong to keep indices aligned
         let result_len = state.challenge.challenge_result.len();
-        if result_len <= state.current_task_index {
-            // Add default answer based on challenge type
-            let default_input = match &state.challenge.challenge_type {
-                ChallengeType::MultipleChoice(mc) => {
-                    if let Some(first_option) = mc.options.first() {
-                        ChallengeInput::MultipleChoice(MultipleChoiceOption {
-                            id: first_option.id,
-                            name: first_option.name.clone(),
-                        })
-                    } else {
-                        return Err(CommandError::ChallengeError(
-                            ChallengeError::InvalidChallengeType,
-                        ));
-                    }
-                }
-                ChallengeType::ContextualChoice(cc) => {
-                    if let Some(first_item) = cc.items.first() {
-                        // Create default answer with empty choices
-                        let ids = vec![0; first_item.choices.len()];
-                        ChallengeInput::ContextualChoice(ContextItemChoiceAnswers { ids })
-                    } else {
-                        return Err(CommandError::ChallengeError(
-                            ChallengeError::InvalidChallengeType,
-                        ));
-                    }
-                }
-                ChallengeType::GapFill(_) => ChallengeInput::GapFill(GapFillAnswer {
-                    question_index: state.current_task_index,
-                    answers: vec![],
-                }),
-                ChallengeType::SortTable(_) => ChallengeInput::SortTable(SortTableRow::default()),
-                ChallengeType::Ordering(_) => ChallengeInput::Ordering(OrderingResult::default()),
-                _ => {
-                    // For other types, don't add default input
-                    state.current_task_index += 1;
-                    return Ok(());
-                }
-            };
-
+        if result_len <= state.current_task_index
+            && let Some(unanswered) = state.challenge.unanswered_input(state.current_task_index)
+        {
             state
                 .challenge
                 .challenge_result
-                .add_input(default_input)
+                .add_input(unanswered)
                 .map_err(CommandError::ChallengeError)?;
+            state.challenge.skip_task(state.current_task_index);
         }
 
         state.current_task_index += 1;
@@ -184,6 +178,19 @@ impl ChallengeCommand {
                     name: option.name.clone(),
                 })
             }
+            ChallengeType::Vocabulary(ref dataset) => {
+                // The index points into the options shown on the card
+                let options = dataset.task_options(state.current_task_index, state.challenge.seed);
+                let selected = options
+                    .get(option_index)
+                    .ok_or(CommandError::ChallengeError(
+                        ChallengeError::InvalidOptionId(option_index),
+                    ))?;
+                ChallengeInput::Vocabulary(VocabularyAnswer {
+                    item_id: Self::current_vocabulary_item(dataset, state.current_task_index)?,
+                    response: VocabularyResponse::Selected(selected.id),
+                })
+            }
             _ => {
                 return Err(CommandError::ChallengeError(
                     ChallengeError::InvalidChallengeType,
@@ -202,6 +209,131 @@ impl ChallengeCommand {
         Ok(())
     }
 
+    /// Solves the current task with a typed answer. Only challenges that
+    /// take free text, such as translations and vocabulary recall, accept it.
+    fn solve_text(state: &mut GameState, text: &str) -> Result<()> {
+        let challenge_input = match state.challenge.challenge_type {
+            ChallengeType::Translation(_) => ChallengeInput::Translation(TranslationAnswer {
+                item_index: state.current_task_index,
+                text: text.to_string(),
+            }),
+            ChallengeType::Vocabulary(ref dataset) => {
+                ChallengeInput::Vocabulary(VocabularyAnswer {
+                    item_id: Self::current_vocabulary_item(dataset, state.current_task_index)?,
+                    response: VocabularyResponse::Typed(text.to_string()),
+                })
+            }
+            _ => {
+                return Err(CommandError::ChallengeError(
+                    ChallengeError::InvalidChallengeType,
+                ));
+            }
+        };
+
+        state
+            .challenge
+            .solve(challenge_input, state.current_task_index)
+            .map_err(CommandError::ChallengeError)?;
+
+        // Attempt to move to the next task, but ignore "no more tasks" errors
+        let _ = Self::next_task(state);
+
+        Ok(())
+    }
+
+    /// Solves the current matching task by connecting the left entry of the
+    /// task's pair with the right entry of the pair `right`.
+    fn solve_match(state: &mut GameState, right: usize) -> Result<()> {
+        let ChallengeType::Matching(ref dataset) = state.challenge.challenge_type else {
+            return Err(CommandError::ChallengeError(
+                ChallengeError::InvalidChallengeType,
+            ));
+        };
+        let left = dataset
+            .pairs
+            .get(state.current_task_index)
+            .map(|pair| pair.id)
+            .ok_or_else(|| {
+                CommandError::ChallengeError(ChallengeError::InvalidInput(format!(
+                    "No matching pair for task {}",
+                    state.current_task_index
+                )))
+            })?;
+        if dataset.pair_by_id(right).is_none() {
+            return Err(CommandError::ChallengeError(
+                ChallengeError::InvalidOptionId(right),
+            ));
+        }
+
+        state
+            .challenge
+            .solve(
+                ChallengeInput::Matching(MatchingAnswer { left, right }),
+                state.current_task_index,
+            )
+            .map_err(CommandError::ChallengeError)?;
+
+        // Attempt to move to the next task, but ignore "no more tasks" errors
+        let _ = Self::next_task(state);
+
+        Ok(())
+    }
+
+    /// Solves the current error correction task with the corrections the
+    /// player made to its sentence.
+    fn solve_corrections(state: &mut GameState, corrections: &[WordCorrection]) -> Result<()> {
+        let ChallengeType::ErrorCorrection(ref dataset) = state.challenge.challenge_type else {
+            return Err(CommandError::ChallengeError(
+                ChallengeError::InvalidChallengeType,
+            ));
+        };
+        if dataset.sentences.get(state.current_task_index).is_none() {
+            return Err(CommandError::ChallengeError(ChallengeError::InvalidInput(
+                format!("No sentence for task {}", state.current_task_index),
+            )));
+        }
+
+        state
+            .challenge
+            .solve(
+                ChallengeInput::ErrorCorrection(ErrorCorrectionAnswer {
+                    sentence_index: state.current_task_index,
+                    corrections: corrections.to_vec(),
+                }),
+                state.current_task_index,
+            )
+            .map_err(CommandError::ChallengeError)?;
+
+        // Attempt to move to the next task, but ignore "no more tasks" errors
+        let _ = Self::next_task(state);
+
+        Ok(())
+    }
+
+    /// Returns the id of the vocabulary card asked in the task.
+    fn current_vocabulary_item(dataset: &Vocabulary, task_index: usize) -> Result<usize> {
+        dataset
+            .items
+            .get(task_index)
+            .map(|item| item.id)
+            .ok_or_else(|| {
+                CommandError::ChallengeError(ChallengeError::InvalidInput(format!(
+                    "No vocabulary card for task {}",
+                    task_index
+                )))
+            })
+    }
+
+    /// Reveals the next hint for the current task and records it on the
+    /// challenge, so the reward can take it into account.
+    fn request_hint(state: &mut GameState) -> Result<()> {
+        state
+            .challenge
+            .request_hint(state.current_task_index)
+            .map_err(CommandError::ChallengeError)?;
+        Ok(())
+    }
+
     /// Finishes the current challenge with a custom result.
     fn finish_challenge(
         state: &mut GameState,
@@ -220,6 +352,9 @@ impl ChallengeCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::challenges::{
+        ChallengeConfig, ErrorCorrection, Matching, Performance, Translation, VocabularyMode,
+    };
     use crate::game::GameState;
 
     #[test]
@@ -254,6 +389,225 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_solve_text() {
+        let mut state = GameState::default();
+        let result = ChallengeCommand::solve_text(&mut state, "Ich bin nach Berlin gefahren");
+        assert!(matches!(
+            result,
+            Err(CommandError::ChallengeError(
+                ChallengeError::InvalidChallengeType
+            ))
+        ));
+
+        let translation = Translation::default();
+        let reference = translation.items[0].translation.clone();
+        state.challenge = Challenge::new(
+            &ChallengeType::Translation(translation),
+            &ChallengeConfig::default(),
+        );
+        ChallengeCommand::solve_text(&mut state, &reference).unwrap();
+        assert!(matches!(
+            &state.challenge.challenge_result,
+            ChallengeResult::Translation(answers) if answers[0].text == reference
+        ));
+    }
+
+    #[test]
+    fn test_solve_vocabulary() {
+        let vocabulary = |mode| {
+            ChallengeType::Vocabulary(Vocabulary {
+                mode,
+                ..Vocabulary::default()
+            })
+        };
+        let first_item = Vocabulary::default().items[0].id;
+
+        let mut state = GameState::default();
+        state.challenge = Challenge::new(
+            &vocabulary(VocabularyMode::Recall),
+            &ChallengeConfig::default(),
+        );
+        ChallengeCommand::solve_text(&mut state, "the apple").unwrap();
+        assert!(matches!(
+            &state.challenge.challenge_result,
+            ChallengeResult::Vocabulary(answers) if answers[0] == VocabularyAnswer {
+                item_id: first_item,
+                response: VocabularyResponse::Typed("the apple".to_string()),
+            }
+        ));
+
+        let mut state = GameState::default();
+        state.challenge = Challenge::new(
+            &vocabulary(VocabularyMode::Recognition),
+            &ChallengeConfig::default(),
+        );
+        assert!(matches!(
+            ChallengeCommand::solve_option(&mut state, 999),
+            Err(CommandError::ChallengeError(
+                ChallengeError::InvalidOptionId(999)
+            ))
+        ));
+        ChallengeCommand::solve_option(&mut state, 0).unwrap();
+        assert!(matches!(
+            &state.challenge.challenge_result,
+            ChallengeResult::Vocabulary(answers) if answers[0].item_id == first_item
+        ));
+    }
+
+    #[test]
+    fn test_solve_vocabulary_with_displayed_option() {
+        let vocabulary = Vocabulary {
+            mode: VocabularyMode::Recognition,
+            ..Vocabulary::default()
+        };
+        let mut state = GameState::default();
+        state.challenge = Challenge::new(
+            &ChallengeType::Vocabulary(vocabulary.clone()),
+            &ChallengeConfig::default(),
+        );
+        state.challenge.seed = Some(7);
+        let first_item = vocabulary.items[0].id;
+        let displayed = vocabulary
+            .task_options(0, Some(7))
+            .iter()
+            .position(|option| option.id == first_item)
+            .unwrap();
+
+        ChallengeCommand::SolveOption(displayed)
+            .execute(&mut state)
+            .unwrap();
+        assert!(matches!(
+            &state.challenge.challenge_result,
+            ChallengeResult::Vocabulary(answers) if answers[0] == VocabularyAnswer {
+                item_id: first_item,
+                response: VocabularyResponse::Selected(first_item),
+            }
+        ));
+        assert_eq!(
+            state
+                .challenge
+                .performance(&state.challenge.challenge_result),
+            100 / vocabulary.quiz_items().count() as u32
+        );
+    }
+
+    #[test]
+    fn test_solve_match() {
+        let matching = Matching::default();
+        let mut state = GameState::default();
+        assert!(matches!(
+            ChallengeCommand::SolveMatch(0).execute(&mut state),
+            Err(CommandError::ChallengeError(
+                ChallengeError::InvalidChallengeType
+            ))
+        ));
+
+        state.challenge = Challenge::new(
+            &ChallengeType::Matching(matching.clone()),
+            &ChallengeConfig::default(),
+        );
+        assert!(matches!(
+            ChallengeCommand::SolveMatch(999).execute(&mut state),
+            Err(CommandError::ChallengeError(
+                ChallengeError::InvalidOptionId(999)
+            ))
+        ));
+
+        let first = matching.pairs[0].id;
+        ChallengeCommand::SolveMatch(first)
+            .execute(&mut state)
+            .unwrap();
+        assert!(matches!(
+            &state.challenge.challenge_result,
+            ChallengeResult::Matching(answers) if answers[0] == MatchingAnswer {
+                left: first,
+                right: first,
+            }
+        ));
+        assert_eq!(
+            state
+                .challenge
+                .performance(&state.challenge.challenge_result),
+            100 / matching.pairs.len() as u32
+        );
+    }
+
+    #[test]
+    fn test_solve_corrections() {
+        let error_correction = ErrorCorrection::default();
+        let mut state = GameState::default();
+        assert!(matches!(
+            ChallengeCommand::SolveCorrections(vec![]).execute(&mut state),
+            Err(CommandError::ChallengeError(
+                ChallengeError::InvalidChallengeType
+            ))
+        ));
+
+        state.challenge = Challenge::new(
+            &ChallengeType::ErrorCorrection(error_correction.clone()),
+            &ChallengeConfig::default(),
+        );
+        let corrections: Vec<WordCorrection> = error_correction.sentences[0]
+            .errors
+            .iter()
+            .map(|error| WordCorrection {
+                position: error.position,
+                text: error.correction.clone(),
+            })
+            .collect();
+        ChallengeCommand::SolveCorrections(corrections.clone())
+            .execute(&mut state)
+            .unwrap();
+        assert!(matches!(
+            &state.challenge.challenge_result,
+            ChallengeResult::ErrorCorrection(answers) if answers[0] == ErrorCorrectionAnswer {
+                sentence_index: 0,
+                corrections,
+            }
+        ));
+        assert_eq!(
+            state
+                .challenge
+                .performance(&state.challenge.challenge_result),
+            100 / error_correction.sentences.len() as u32
+        );
+
+        state.current_task_index = error_correction.sentences.len();
+        assert!(matches!(
+            ChallengeCommand::SolveCorrections(vec![]).execute(&mut state),
+            Err(CommandError::ChallengeError(ChallengeError::InvalidInput(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_request_hint() {
+        let mut state = GameState::default();
+        state.current_task_index = 1;
+
+        let hints = state.challenge.challenge_type.hints(1);
+        for _ in 0..hints.len() {
+            ChallengeCommand::RequestHint.execute(&mut state).unwrap();
+        }
+        assert_eq!(state.challenge.hints_used(), hints.len());
+        assert!(
+            state
+                .challenge
+                .hints
+                .iter()
+                .all(|usage| usage.task_index == 1)
+        );
+
+        let result = ChallengeCommand::RequestHint.execute(&mut state);
+        assert!(matches!(
+            result,
+            Err(CommandError::ChallengeError(ChallengeError::NoMoreHints))
+        ));
+        assert_eq!(state.challenge.hints_used(), hints.len());
+    }
+
     #[test]
     fn test_next_task() {
         let mut state = GameState::default();
@@ -332,6 +686,31 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_start_challenge() {
+        let mut state = GameState::default();
+        let challenge = state.game.create_challenge("articles-1").unwrap();
+
+        let result = ChallengeCommand::Start(challenge.clone()).execute(&mut state);
+        assert!(result.is_ok());
+        assert_eq!(state.challenge.challenge_config.id, "articles-1");
+        assert!(state.challenge.start_time.is_some());
+    }
+
+    #[test]
+    fn test_start_locked_challenge() {
+        let mut state = GameState::default();
+        let challenge = state.game.create_challenge("konnektoren-5").unwrap();
+
+        let result = ChallengeCommand::Start(challenge).execute(&mut state);
+        assert!(matches!(
+            result,
+            Err(CommandError::GameError(GameError::ChallengeLocked { ref id, .. }))
+                if id == "konnektoren-5"
+        ));
+        assert_eq!(state.challenge.challenge_config.id, "konnektoren-1");
+    }
+
     #[test]
     fn test_execute() {
         let mut state = GameState::default();
@@ -36,15 +36,18 @@ impl CommandBus {
             .len()
     }
 
+    /// Calls every listener of the command type. The listeners may publish
+    /// further commands, which are handled before this call returns.
     pub fn publish(&self, command: Command) {
-        let listeners = self
+        let handlers = self
             .listeners
             .lock()
-            .unwrap_or_else(|poisoned| poisoned.into_inner());
-        if let Some(handlers) = listeners.get(&command.get_type()) {
-            for handler in handlers {
-                handler(command.clone());
-            }
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&command.get_type())
+            .cloned()
+            .unwrap_or_default();
+        for handler in handlers {
+            handler(command.clone());
         }
     }
 }
@@ -72,4 +75,21 @@ mod tests {
         command_bus.publish(Command::Game(GameCommand::NextChallenge));
         assert_eq!(counter.load(Ordering::SeqCst), 1);
     }
+
+    #[test]
+    fn test_publish_from_listener() {
+        let command_bus = CommandBus::new();
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = counter.clone();
+        let command_bus_clone = command_bus.clone();
+        command_bus.subscribe(CommandType::Game, move |command| {
+            if let Command::Game(GameCommand::NextChallenge) = command {
+                command_bus_clone.publish(Command::Game(GameCommand::PreviousChallenge));
+            }
+            counter_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        command_bus.publish(Command::Game(GameCommand::NextChallenge));
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
 }
@@ -2,8 +2,10 @@
 
 use super::command::CommandTrait;
 use super::command_type::CommandType;
+use crate::challenges::ChallengeConfig;
 use crate::challenges::Timed;
 use crate::commands::error::{CommandError, Result};
+use crate::game::Game;
 use crate::game::GamePath;
 use crate::game::GameState;
 use crate::game::error::GameError;
@@ -41,7 +43,9 @@ impl CommandTrait for GameCommand {
 }
 
 impl GameCommand {
-    /// Moves the game state to the next challenge.
+    /// Moves the game state to the next challenge. If the path branches, the
+    /// first unlocked successor on the main path is chosen, then side quests,
+    /// then successors that were already completed.
     ///
     /// # Arguments
     ///
@@ -49,7 +53,8 @@ impl GameCommand {
     ///
     /// # Returns
     ///
-    /// A `Result` indicating success or containing an error if there are no more challenges.
+    /// A `Result` indicating success or containing an error if there are no more challenges
+    /// or every next challenge is still locked.
     pub fn next_challenge(state: &mut GameState) -> Result<()> {
         let current_game_path: &GamePath = state
             .game
@@ -57,18 +62,18 @@ impl GameCommand {
             .get(state.current_game_path)
             .ok_or(CommandError::GameError(GameError::GamePathNotFound))?;
 
-        if state.current_challenge_index + 1 >= current_game_path.challenge_ids().len() {
-            return Err(CommandError::GameError(GameError::InvalidGameState(
-                "No more challenges".to_string(),
-            )));
-        }
-        state.current_challenge_index += 1;
+        let successors = current_game_path
+            .challenges
+            .get(state.current_challenge_index)
+            .map(|current| current_game_path.successors(&current.id))
+            .unwrap_or_default();
+        let challenge_config = Self::choose_successor(&state.game, &successors)?;
 
-        let challenge_config = &current_game_path.challenges[state.current_challenge_index];
         state.challenge = state
             .game
             .create_challenge(&challenge_config.id)
             .map_err(CommandError::GameError)?;
+        state.current_challenge_index = Self::index_of(current_game_path, challenge_config)?;
 
         state.challenge.start();
         state.current_task_index = 0;
@@ -76,7 +81,8 @@ impl GameCommand {
         Ok(())
     }
 
-    /// Moves the game state to the previous challenge.
+    /// Moves the game state to the previous challenge. If several challenges
+    /// lead to the current one, a completed one is preferred.
     ///
     /// # Arguments
     ///
@@ -86,42 +92,85 @@ impl GameCommand {
     ///
     /// A `Result` indicating success or containing an error if there are no previous challenges.
     pub fn previous_challenge(state: &mut GameState) -> Result<()> {
-        if state.current_challenge_index == 0 {
-            return Err(CommandError::GameError(GameError::InvalidGameState(
-                "No previous challenges".to_string(),
-            )));
-        }
-
-        state.current_challenge_index -= 1;
-
         let current_game_path: &GamePath = state
             .game
             .game_paths
             .get(state.current_game_path)
             .ok_or(CommandError::GameError(GameError::GamePathNotFound))?;
 
-        let challenge_config = &current_game_path.challenges[state.current_challenge_index];
+        let predecessors = current_game_path
+            .challenges
+            .get(state.current_challenge_index)
+            .map(|current| current_game_path.predecessors(&current.id))
+            .unwrap_or_default();
+        let challenge_config = predecessors
+            .iter()
+            .find(|challenge| state.game.is_completed(&challenge.id))
+            .or(predecessors.first())
+            .copied()
+            .ok_or_else(|| {
+                CommandError::GameError(GameError::InvalidGameState(
+                    "No previous challenges".to_string(),
+                ))
+            })?;
 
-        match state.game.create_challenge(&challenge_config.id) {
-            Ok(challenge) => {
-                state.challenge = challenge;
-                state.challenge.start();
-                state.current_task_index = 0;
-                Ok(())
-            }
-            Err(err) => Err(CommandError::GameError(err)),
+        state.challenge = state
+            .game
+            .create_challenge(&challenge_config.id)
+            .map_err(CommandError::GameError)?;
+        state.current_challenge_index = Self::index_of(current_game_path, challenge_config)?;
+
+        state.challenge.start();
+        state.current_task_index = 0;
+
+        Ok(())
+    }
+
+    /// Chooses the challenge to continue with among the successors of the
+    /// current challenge.
+    fn choose_successor<'a>(
+        game: &Game,
+        successors: &[&'a ChallengeConfig],
+    ) -> Result<&'a ChallengeConfig> {
+        let first = successors.first().copied().ok_or_else(|| {
+            CommandError::GameError(GameError::InvalidGameState(
+                "No more challenges".to_string(),
+            ))
+        })?;
+        let unlocked = successors
+            .iter()
+            .copied()
+            .filter(|challenge| game.ensure_unlocked(challenge).is_ok())
+            .min_by_key(|challenge| (game.is_completed(&challenge.id), challenge.elective));
+        match unlocked {
+            Some(challenge) => Ok(challenge),
+            None => game
+                .ensure_unlocked(first)
+                .map(|()| first)
+                .map_err(CommandError::GameError),
         }
     }
+
+    fn index_of(game_path: &GamePath, challenge_config: &ChallengeConfig) -> Result<usize> {
+        game_path
+            .challenges
+            .iter()
+            .position(|challenge| challenge.id == challenge_config.id)
+            .ok_or_else(|| {
+                CommandError::GameError(GameError::ChallengeNotFound(challenge_config.id.clone()))
+            })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::game::GameState;
+    use crate::game::{GameState, LockReason, PathEdge};
 
     #[test]
     fn next_challenge() {
         let mut state = GameState::default();
+        state.game.xp = 100;
         let command = GameCommand::NextChallenge;
         let result = command.execute(&mut state);
         assert!(result.is_ok());
@@ -131,6 +180,7 @@ mod tests {
     #[test]
     fn previous_challenge() {
         let mut state = GameState::default();
+        state.game.xp = 100;
         let command = GameCommand::NextChallenge;
         let result = command.execute(&mut state);
         assert!(result.is_ok());
@@ -144,6 +194,7 @@ mod tests {
     #[test]
     fn next_challenge_no_more() {
         let mut state = GameState::default();
+        state.game.xp = 100;
         let command = GameCommand::NextChallenge;
         command.execute(&mut state).unwrap();
         command.execute(&mut state).unwrap();
@@ -187,6 +238,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn next_challenge_locked() {
+        let mut state = GameState::default();
+        let result = GameCommand::NextChallenge.execute(&mut state);
+        assert_eq!(state.current_challenge_index, 0);
+        assert_eq!(
+            result,
+            Err(CommandError::GameError(GameError::ChallengeLocked {
+                id: "konnektoren-2".to_string(),
+                reason: LockReason::InsufficientXp {
+                    required: 10,
+                    current: 0
+                },
+            }))
+        );
+    }
+
+    fn branching_state() -> GameState {
+        let mut game = Game::default();
+        let game_path = &mut game.game_paths[0];
+        game_path.edges = vec![
+            PathEdge::new("konnektoren-1", "articles-1"),
+            PathEdge::new("konnektoren-1", "past-tense-1"),
+            PathEdge::new("articles-1", "sentence-structure-1"),
+            PathEdge::new("past-tense-1", "sentence-structure-1"),
+        ];
+        game_path.challenges[5].elective = true;
+        GameState::new(game)
+    }
+
+    #[test]
+    fn next_challenge_in_branching_path() {
+        let mut state = branching_state();
+        GameCommand::NextChallenge.execute(&mut state).unwrap();
+        assert_eq!(state.challenge.challenge_config.id, "past-tense-1");
+        assert_eq!(state.current_challenge_index, 6);
+
+        GameCommand::NextChallenge.execute(&mut state).unwrap();
+        assert_eq!(state.challenge.challenge_config.id, "sentence-structure-1");
+        assert_eq!(state.current_challenge_index, 7);
+
+        let result = GameCommand::NextChallenge.execute(&mut state);
+        assert_eq!(
+            result,
+            Err(CommandError::GameError(GameError::InvalidGameState(
+                "No more challenges".to_string()
+            )))
+        );
+
+        GameCommand::PreviousChallenge.execute(&mut state).unwrap();
+        assert_eq!(state.challenge.challenge_config.id, "articles-1");
+        assert_eq!(state.current_challenge_index, 5);
+    }
+
+    #[test]
+    fn next_challenge_skips_locked_branch() {
+        let mut state = branching_state();
+        state.game.game_paths[0].challenges[6].unlock_points = 50;
+        GameCommand::NextChallenge.execute(&mut state).unwrap();
+        assert_eq!(state.challenge.challenge_config.id, "articles-1");
+    }
+
     #[test]
     fn test_game_path_not_found() {
         let mut state = GameState::default();
@@ -1,5 +1,5 @@
 use super::{ChallengeCommand, Command, CommandError, GameCommand};
-use crate::challenges::{ChallengeResult, CustomChallengeResult};
+use crate::challenges::{ChallengeResult, CustomChallengeResult, WordCorrection};
 use serde_json::Value;
 use wasm_bindgen::prelude::*;
 
@@ -54,6 +54,8 @@ impl TryFrom<Value> for ChallengeCommand {
         match value.get("action").and_then(|v| v.as_str()) {
             Some("NextTask") => Ok(ChallengeCommand::NextTask),
             Some("PreviousTask") => Ok(ChallengeCommand::PreviousTask),
+            Some("RequestHint") => Ok(ChallengeCommand::RequestHint),
+            Some("CheckTime") => Ok(ChallengeCommand::CheckTime),
             Some("SolveOption") => {
                 let option_index = value
                     .get("optionIndex")
@@ -64,6 +66,32 @@ impl TryFrom<Value> for ChallengeCommand {
                     })?;
                 Ok(ChallengeCommand::SolveOption(option_index as usize))
             }
+            Some("SolveText") => {
+                let text = value
+                    .get("text")
+                    .ok_or(CommandError::MissingData)?
+                    .as_str()
+                    .ok_or_else(|| {
+                        CommandError::InvalidData("text must be a string".to_string())
+                    })?;
+                Ok(ChallengeCommand::SolveText(text.to_string()))
+            }
+            Some("SolveMatch") => {
+                let right = value
+                    .get("right")
+                    .ok_or(CommandError::MissingData)?
+                    .as_u64()
+                    .ok_or_else(|| {
+                        CommandError::InvalidData("right must be a number".to_string())
+                    })?;
+                Ok(ChallengeCommand::SolveMatch(right as usize))
+            }
+            Some("SolveCorrections") => {
+                let corrections = value.get("corrections").ok_or(CommandError::MissingData)?;
+                let corrections: Vec<WordCorrection> = serde_json::from_value(corrections.clone())
+                    .map_err(|e| CommandError::ParseError(e.to_string()))?;
+                Ok(ChallengeCommand::SolveCorrections(corrections))
+            }
             Some("Finish") => {
                 let result = value.get("result").ok_or(CommandError::MissingData)?;
                 let result: CustomChallengeResult = serde_json::from_value(result.clone())
@@ -101,6 +129,22 @@ mod tests {
         assert_eq!(command, Command::Challenge(ChallengeCommand::NextTask));
     }
 
+    #[test]
+    fn test_parse_request_hint() {
+        let json = r#"{"type":"Challenge","action":"RequestHint"}"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let command = Command::try_from(value).unwrap();
+        assert_eq!(command, Command::Challenge(ChallengeCommand::RequestHint));
+    }
+
+    #[test]
+    fn test_parse_check_time() {
+        let json = r#"{"type":"Challenge","action":"CheckTime"}"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let command = Command::try_from(value).unwrap();
+        assert_eq!(command, Command::Challenge(ChallengeCommand::CheckTime));
+    }
+
     #[test]
     fn test_parse_challenge_command_with_option() {
         let json = r#"{"type":"Challenge","action":"SolveOption","optionIndex":0}"#;
@@ -112,6 +156,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_challenge_command_with_text() {
+        let json = r#"{"type":"Challenge","action":"SolveText","text":"Guten Tag"}"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let command = Command::try_from(value).unwrap();
+        assert_eq!(
+            command,
+            Command::Challenge(ChallengeCommand::SolveText("Guten Tag".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_challenge_command_with_match() {
+        let json = r#"{"type":"Challenge","action":"SolveMatch","right":2}"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let command = Command::try_from(value).unwrap();
+        assert_eq!(command, Command::Challenge(ChallengeCommand::SolveMatch(2)));
+    }
+
+    #[test]
+    fn test_parse_challenge_command_with_corrections() {
+        let json = r#"{"type":"Challenge","action":"SolveCorrections","corrections":[{"position":4,"text":"weil"}]}"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let command = Command::try_from(value).unwrap();
+        assert_eq!(
+            command,
+            Command::Challenge(ChallengeCommand::SolveCorrections(vec![WordCorrection {
+                position: 4,
+                text: "weil".to_string(),
+            }]))
+        );
+    }
+
     #[test]
     fn test_parse_challenge_command_with_result() {
         let json = r#"{"type":"Challenge","action":"Finish","result":{"id":"123","performance":0.0,"data":{}}}"#;
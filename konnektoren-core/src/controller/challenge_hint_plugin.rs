@@ -0,0 +1,216 @@
+use super::GameControllerTrait;
+use super::{ControllerPlugin, ControllerPluginError};
+use crate::challenges::Challenge;
+use crate::commands::{ChallengeCommand, Command, CommandType};
+use crate::controller::ControllerError;
+use crate::events::{ChallengeEvent, Event};
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+
+/// Publishes a [`ChallengeEvent::HintUsed`] for every hint revealed by a
+/// [`ChallengeCommand::RequestHint`]. The published hints are counted per
+/// challenge id and start time, so a restarted challenge starts over.
+pub struct ChallengeHintPlugin;
+
+/// The hints of a challenge that were already published.
+#[derive(Debug, Clone, PartialEq)]
+struct SeenHints {
+    /// Id and start time of the challenge, to notice a new challenge
+    challenge: (String, Option<DateTime<Utc>>),
+    count: usize,
+}
+
+impl SeenHints {
+    fn key(challenge: &Challenge) -> (String, Option<DateTime<Utc>>) {
+        (challenge.get_id(), challenge.start_time)
+    }
+}
+
+impl ChallengeHintPlugin {
+    /// Returns the events for the hints recorded after the first `seen` ones.
+    fn hint_events(challenge: &Challenge, seen: usize) -> Vec<ChallengeEvent> {
+        challenge
+            .hints
+            .iter()
+            .skip(seen)
+            .map(|usage| ChallengeEvent::HintUsed(usage.task_index))
+            .collect()
+    }
+
+    fn publish_hint_events(
+        game_controller: Arc<dyn GameControllerTrait>,
+        seen: &Mutex<Option<SeenHints>>,
+    ) -> Result<(), ControllerError> {
+        let events = {
+            let game_state = game_controller
+                .game_state()
+                .lock()
+                .map_err(|_| ControllerError::StateLock)?;
+            let mut seen = seen.lock().map_err(|_| ControllerError::StateLock)?;
+
+            let key = SeenHints::key(&game_state.challenge);
+            if seen.as_ref().is_some_and(|seen| seen.challenge != key) {
+                *seen = None;
+            }
+            let seen = seen.get_or_insert(SeenHints {
+                challenge: key,
+                count: 0,
+            });
+            let events = Self::hint_events(&game_state.challenge, seen.count);
+            seen.count = game_state.challenge.hints_used();
+            events
+        };
+
+        for event in events {
+            game_controller.event_bus().publish(Event::Challenge(event));
+        }
+        Ok(())
+    }
+}
+
+impl ControllerPlugin for ChallengeHintPlugin {
+    fn name(&self) -> &str {
+        "ChallengeHintPlugin"
+    }
+
+    fn init(&self) -> Result<(), ControllerPluginError> {
+        Ok(())
+    }
+
+    fn load(
+        &self,
+        game_controller: Arc<dyn GameControllerTrait>,
+    ) -> Result<(), ControllerPluginError> {
+        let game_controller_clone = game_controller.clone();
+        let seen = Mutex::new(None);
+        game_controller
+            .command_bus()
+            .subscribe(CommandType::Challenge, move |command| {
+                if let Command::Challenge(ChallengeCommand::RequestHint) = command
+                    && let Err(e) = Self::publish_hint_events(game_controller_clone.clone(), &seen)
+                {
+                    tracing::error!("Error publishing hint events: {:?}", e);
+                }
+            });
+
+        Ok(())
+    }
+
+    fn unload(
+        &self,
+        _game_controller: Arc<dyn GameControllerTrait>,
+    ) -> Result<(), ControllerPluginError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{ChallengeConfig, ChallengeType, Timed};
+    use crate::controller::game_controller::MockGameControllerTrait;
+    use crate::events::{EventBus, EventType};
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_hint_events() {
+        let mut challenge = Challenge::new(&ChallengeType::default(), &ChallengeConfig::default());
+        challenge.request_hint(0).unwrap();
+        challenge.request_hint(2).unwrap();
+
+        assert_eq!(
+            ChallengeHintPlugin::hint_events(&challenge, 0),
+            vec![ChallengeEvent::HintUsed(0), ChallengeEvent::HintUsed(2)]
+        );
+        assert_eq!(
+            ChallengeHintPlugin::hint_events(&challenge, 1),
+            vec![ChallengeEvent::HintUsed(2)]
+        );
+        assert!(ChallengeHintPlugin::hint_events(&challenge, 2).is_empty());
+        assert!(ChallengeHintPlugin::hint_events(&challenge, 5).is_empty());
+    }
+
+    #[test]
+    fn test_publish_hint_events() {
+        let mut mock_controller = MockGameControllerTrait::new();
+
+        let mut challenge = Challenge::new(&ChallengeType::default(), &ChallengeConfig::default());
+        challenge.request_hint(1).unwrap();
+        let game_state = Mutex::new(crate::game::GameState {
+            challenge,
+            ..Default::default()
+        });
+        mock_controller
+            .expect_game_state()
+            .return_const(Arc::new(game_state));
+
+        let event_bus = EventBus::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        event_bus.subscribe(EventType::Challenge, move |event| {
+            received_clone.lock().unwrap().push(event);
+        });
+        mock_controller.expect_event_bus().return_const(event_bus);
+
+        let seen = Mutex::new(None);
+        let controller: Arc<dyn GameControllerTrait> = Arc::new(mock_controller);
+        ChallengeHintPlugin::publish_hint_events(controller.clone(), &seen).unwrap();
+        ChallengeHintPlugin::publish_hint_events(controller, &seen).unwrap();
+
+        assert_eq!(seen.lock().unwrap().as_ref().unwrap().count, 1);
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![Event::Challenge(ChallengeEvent::HintUsed(1))]
+        );
+    }
+
+    #[test]
+    fn test_restarted_challenge_publishes_hints_again() {
+        let mut mock_controller = MockGameControllerTrait::new();
+
+        let mut challenge = Challenge::new(&ChallengeType::default(), &ChallengeConfig::default());
+        challenge.start();
+        challenge.request_hint(0).unwrap();
+        challenge.request_hint(1).unwrap();
+        let game_state = Arc::new(Mutex::new(crate::game::GameState {
+            challenge: challenge.clone(),
+            ..Default::default()
+        }));
+        mock_controller
+            .expect_game_state()
+            .return_const(game_state.clone());
+
+        let event_bus = EventBus::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        event_bus.subscribe(EventType::Challenge, move |event| {
+            received_clone.lock().unwrap().push(event);
+        });
+        mock_controller.expect_event_bus().return_const(event_bus);
+
+        let seen = Mutex::new(None);
+        let controller: Arc<dyn GameControllerTrait> = Arc::new(mock_controller);
+        ChallengeHintPlugin::publish_hint_events(controller.clone(), &seen).unwrap();
+
+        // The same challenge started again, with as many hints as before
+        let mut restarted = challenge.clone();
+        restarted.hints.clear();
+        restarted.start_time = challenge
+            .start_time
+            .map(|start| start + chrono::Duration::seconds(1));
+        restarted.request_hint(0).unwrap();
+        restarted.request_hint(1).unwrap();
+        game_state.lock().unwrap().challenge = restarted;
+        ChallengeHintPlugin::publish_hint_events(controller, &seen).unwrap();
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![
+                Event::Challenge(ChallengeEvent::HintUsed(0)),
+                Event::Challenge(ChallengeEvent::HintUsed(1)),
+                Event::Challenge(ChallengeEvent::HintUsed(0)),
+                Event::Challenge(ChallengeEvent::HintUsed(1)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,344 @@
+use super::GameControllerTrait;
+use super::{ControllerPlugin, ControllerPluginError};
+use crate::challenges::{Clock, SystemClock, TimeStatus};
+use crate::commands::{ChallengeCommand, Command, CommandType};
+use crate::controller::ControllerError;
+use crate::events::{ChallengeEvent, Event};
+use crate::game::GameState;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+
+/// Enforces the [`TimeLimit`](crate::challenges::TimeLimit) of the current
+/// challenge.
+///
+/// The limits are checked against the clock after every command, so a
+/// frontend publishes [`ChallengeCommand::CheckTime`] regularly while a timed
+/// challenge is played. When a task runs out of time it is skipped with a
+/// [`ChallengeCommand::NextTask`], which records a wrong answer for it, and
+/// recorded in [`Challenge::timed_out_tasks`](crate::challenges::Challenge::timed_out_tasks)
+/// so it earns nothing and is left out of the reviews;
+/// when the challenge runs out of time it is finished with its current
+/// result. Warnings and timeouts are published as [`ChallengeEvent`]s.
+pub struct ChallengeTimerPlugin {
+    clock: Arc<dyn Clock>,
+}
+
+impl ChallengeTimerPlugin {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        ChallengeTimerPlugin { clock }
+    }
+
+    fn handle_command(
+        game_controller: Arc<dyn GameControllerTrait>,
+        clock: &dyn Clock,
+        timer: &Mutex<Option<Timer>>,
+        command: &Command,
+    ) -> Result<(), ControllerError> {
+        let (events, follow_up) = {
+            let mut state = game_controller
+                .game_state()
+                .lock()
+                .map_err(|_| ControllerError::StateLock)?;
+            let mut timer = timer.lock().map_err(|_| ControllerError::StateLock)?;
+            let now = clock.now();
+
+            let key = Timer::key(&state);
+            if timer.as_ref().is_some_and(|timer| timer.challenge != key) {
+                *timer = None;
+            }
+            let timer = timer.get_or_insert_with(|| Timer::for_state(&state, now));
+            if let Command::Challenge(ChallengeCommand::Finish(_)) = command {
+                timer.finished = true;
+            }
+            if timer.finished {
+                return Ok(());
+            }
+            timer.check(&mut state, now)
+        };
+
+        for event in events {
+            game_controller.event_bus().publish(Event::Challenge(event));
+        }
+        if let Some(command) = follow_up {
+            game_controller.publish_command(Command::Challenge(command));
+        }
+        Ok(())
+    }
+}
+
+impl Default for ChallengeTimerPlugin {
+    fn default() -> Self {
+        ChallengeTimerPlugin::new(Arc::new(SystemClock))
+    }
+}
+
+/// The clock times the current challenge and task are measured from.
+#[derive(Debug, Clone, PartialEq)]
+struct Timer {
+    /// Id and start time of the timed challenge, to notice a new challenge
+    challenge: (String, Option<DateTime<Utc>>),
+    challenge_started: DateTime<Utc>,
+    task_index: usize,
+    task_started: DateTime<Utc>,
+    challenge_warned: bool,
+    task_warned: bool,
+    finished: bool,
+}
+
+impl Timer {
+    fn new(
+        challenge: (String, Option<DateTime<Utc>>),
+        task_index: usize,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Timer {
+            challenge,
+            challenge_started: now,
+            task_index,
+            task_started: now,
+            challenge_warned: false,
+            task_warned: false,
+            finished: false,
+        }
+    }
+
+    /// Times the challenge of the state from the moment it was started
+    /// rather than from the first command the plugin sees, so the first task
+    /// gets no extra time.
+    fn for_state(state: &GameState, now: DateTime<Utc>) -> Self {
+        let started = state
+            .challenge
+            .start_time
+            .filter(|started| *started <= now)
+            .unwrap_or(now);
+        let mut timer = Timer::new(Self::key(state), state.current_task_index, started);
+        if state.current_task_index != 0 {
+            // When a later task started is not known
+            timer.task_started = now;
+        }
+        timer
+    }
+
+    fn key(state: &GameState) -> (String, Option<DateTime<Utc>>) {
+        (state.challenge.get_id(), state.challenge.start_time)
+    }
+
+    /// Checks the time limits at `now` and returns the events to publish and
+    /// the command that skips the task or finishes the challenge, if time
+    /// ran out. Timed out tasks are recorded in the challenge.
+    fn check(
+        &mut self,
+        state: &mut GameState,
+        now: DateTime<Utc>,
+    ) -> (Vec<ChallengeEvent>, Option<ChallengeCommand>) {
+        if state.current_task_index != self.task_index {
+            self.task_index = state.current_task_index;
+            self.task_started = now;
+            self.task_warned = false;
+        }
+        let Some(limit) = state.challenge.challenge_config.time_limit else {
+            return (vec![], None);
+        };
+        let finish = ChallengeCommand::Finish(Some(state.challenge.challenge_result.clone()));
+        let mut events = vec![];
+
+        match limit.challenge_status(self.challenge_started, now) {
+            TimeStatus::Expired => {
+                self.finished = true;
+                events.push(ChallengeEvent::TimedOut);
+                return (events, Some(finish));
+            }
+            TimeStatus::Warning { remaining_seconds } if !self.challenge_warned => {
+                self.challenge_warned = true;
+                events.push(ChallengeEvent::TimeWarning(remaining_seconds));
+            }
+            _ => {}
+        }
+
+        let index = state.current_task_index;
+        match limit.task_status(self.task_started, now) {
+            TimeStatus::Expired => {
+                if !state.challenge.timed_out_tasks.contains(&index) {
+                    state.challenge.timed_out_tasks.push(index);
+                }
+                events.push(ChallengeEvent::TaskTimedOut(index));
+                if index + 1 < state.challenge.challenge_config.tasks.len() {
+                    return (events, Some(ChallengeCommand::NextTask));
+                }
+                self.finished = true;
+                return (events, Some(finish));
+            }
+            TimeStatus::Warning { .. } if !self.task_warned => {
+                self.task_warned = true;
+                events.push(ChallengeEvent::TaskTimeWarning(index));
+            }
+            _ => {}
+        }
+        (events, None)
+    }
+}
+
+impl ControllerPlugin for ChallengeTimerPlugin {
+    fn name(&self) -> &str {
+        "ChallengeTimerPlugin"
+    }
+
+    fn init(&self) -> Result<(), ControllerPluginError> {
+        Ok(())
+    }
+
+    fn load(
+        &self,
+        game_controller: Arc<dyn GameControllerTrait>,
+    ) -> Result<(), ControllerPluginError> {
+        let timer = Arc::new(Mutex::new(None));
+
+        for command_type in [CommandType::Game, CommandType::Challenge] {
+            let game_controller_clone = game_controller.clone();
+            let clock = self.clock.clone();
+            let timer = timer.clone();
+            game_controller
+                .command_bus()
+                .subscribe(command_type, move |command| {
+                    if let Err(e) = Self::handle_command(
+                        game_controller_clone.clone(),
+                        clock.as_ref(),
+                        &timer,
+                        &command,
+                    ) {
+                        tracing::error!("Error checking time limits: {:?}", e);
+                    }
+                });
+        }
+
+        Ok(())
+    }
+
+    fn unload(
+        &self,
+        _game_controller: Arc<dyn GameControllerTrait>,
+    ) -> Result<(), ControllerPluginError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{ChallengeResult, ManualClock, MultipleChoiceOption, TimeLimit};
+    use crate::controller::{ChallengeFinishPlugin, GameController};
+    use crate::events::EventType;
+    use crate::game::Game;
+    use crate::persistence::MemoryPersistence;
+    use chrono::Duration;
+
+    fn timed_state(time_limit: TimeLimit) -> GameState {
+        let mut game = Game::default();
+        game.game_paths[0].challenges[0].time_limit = Some(time_limit);
+        GameState::new(game)
+    }
+
+    #[test]
+    fn test_last_task_timeout_finishes() {
+        let mut state = timed_state(TimeLimit {
+            task_seconds: Some(15),
+            ..TimeLimit::default()
+        });
+        let last = state.challenge.challenge_config.tasks.len() - 1;
+        state.current_task_index = last;
+        let now = Utc::now();
+        let mut timer = Timer::new(Timer::key(&state), last, now);
+
+        let (events, command) = timer.check(&mut state, now + Duration::seconds(15));
+        assert_eq!(events, vec![ChallengeEvent::TaskTimedOut(last)]);
+        assert!(matches!(command, Some(ChallengeCommand::Finish(Some(_)))));
+        assert_eq!(state.challenge.timed_out_tasks, vec![last]);
+        assert!(timer.finished);
+    }
+
+    #[test]
+    fn test_timed_from_challenge_start() {
+        let mut state = timed_state(TimeLimit {
+            challenge_seconds: Some(60),
+            task_seconds: Some(15),
+            ..TimeLimit::default()
+        });
+        let started = Utc::now();
+        state.challenge.start_time = Some(started);
+        let now = started + Duration::seconds(16);
+        let mut timer = Timer::for_state(&state, now);
+        assert_eq!(timer.challenge_started, started);
+
+        let (events, command) = timer.check(&mut state, now);
+        assert_eq!(events, vec![ChallengeEvent::TaskTimedOut(0)]);
+        assert_eq!(command, Some(ChallengeCommand::NextTask));
+    }
+
+    #[test]
+    fn test_untimed_challenge() {
+        let mut state = GameState::default();
+        let now = Utc::now();
+        let mut timer = Timer::new(Timer::key(&state), 0, now);
+        assert_eq!(
+            timer.check(&mut state, now + Duration::hours(1)),
+            (vec![], None)
+        );
+    }
+
+    #[test]
+    fn test_timeouts_with_controller() {
+        let state = timed_state(TimeLimit {
+            challenge_seconds: Some(60),
+            task_seconds: Some(15),
+            warning_seconds: 10,
+        });
+        let clock = Arc::new(ManualClock::default());
+        let mut controller =
+            GameController::new(state.game, Arc::new(MemoryPersistence::default()));
+        controller.register_plugin(Arc::new(ChallengeFinishPlugin));
+        controller.register_plugin(Arc::new(ChallengeTimerPlugin::new(clock.clone())));
+        let controller = controller.init();
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        controller
+            .event_bus()
+            .subscribe(EventType::Challenge, move |event| {
+                received_clone.lock().unwrap().push(event);
+            });
+        let check_time =
+            || controller.publish_command(Command::Challenge(ChallengeCommand::CheckTime));
+
+        check_time();
+        clock.advance(Duration::seconds(6));
+        check_time();
+        clock.advance(Duration::seconds(10));
+        check_time();
+        {
+            let state = controller.game_state().lock().unwrap();
+            assert_eq!(state.current_task_index, 1);
+            assert_eq!(state.challenge.timed_out_tasks, vec![0]);
+            assert_eq!(
+                state.challenge.challenge_result,
+                ChallengeResult::MultipleChoice(vec![MultipleChoiceOption {
+                    id: usize::MAX,
+                    name: String::new(),
+                }])
+            );
+        }
+
+        clock.advance(Duration::seconds(50));
+        check_time();
+        check_time();
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![
+                Event::Challenge(ChallengeEvent::TaskTimeWarning(0)),
+                Event::Challenge(ChallengeEvent::TaskTimedOut(0)),
+                Event::Challenge(ChallengeEvent::TimedOut),
+            ]
+        );
+        let state = controller.game_state().lock().unwrap();
+        assert_eq!(state.game.challenge_history.len(), 1);
+    }
+}
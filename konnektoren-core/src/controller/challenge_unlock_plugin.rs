@@ -0,0 +1,175 @@
+use super::GameControllerTrait;
+use super::{ControllerPlugin, ControllerPluginError};
+use crate::commands::{ChallengeCommand, Command, CommandType};
+use crate::controller::ControllerError;
+use crate::events::{Event, GameEvent};
+use crate::game::{ChallengeState, Game};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// Publishes a [`GameEvent::ChallengeUnlocked`] for every challenge that
+/// unlocks after a [`ChallengeCommand::Finish`].
+///
+/// Register it after the [`ChallengeFinishPlugin`](super::ChallengeFinishPlugin)
+/// and the [`GameXpPlugin`](super::GameXpPlugin), so the finished challenge
+/// and its XP are already part of the game.
+pub struct ChallengeUnlockPlugin;
+
+impl ChallengeUnlockPlugin {
+    /// Returns the ids of all challenges that are not locked, in game path
+    /// order.
+    fn unlocked(game: &Game) -> Vec<String> {
+        game.game_paths
+            .iter()
+            .flat_map(|game_path| game_path.challenges.iter())
+            .filter(|config| game.challenge_state(config) != ChallengeState::Locked)
+            .map(|config| config.id.clone())
+            .collect()
+    }
+
+    /// Returns the events for the challenges unlocked since the `known` ones
+    /// and adds them to `known`.
+    fn unlock_events(game: &Game, known: &mut HashSet<String>) -> Vec<GameEvent> {
+        Self::unlocked(game)
+            .into_iter()
+            .filter(|id| known.insert(id.clone()))
+            .map(GameEvent::ChallengeUnlocked)
+            .collect()
+    }
+
+    fn publish_unlock_events(
+        game_controller: Arc<dyn GameControllerTrait>,
+        known: &Mutex<HashSet<String>>,
+    ) -> Result<(), ControllerError> {
+        let events = {
+            let game_state = game_controller
+                .game_state()
+                .lock()
+                .map_err(|_| ControllerError::StateLock)?;
+            let mut known = known.lock().map_err(|_| ControllerError::StateLock)?;
+            Self::unlock_events(&game_state.game, &mut known)
+        };
+
+        for event in events {
+            game_controller.event_bus().publish(Event::Game(event));
+        }
+        Ok(())
+    }
+}
+
+impl ControllerPlugin for ChallengeUnlockPlugin {
+    fn name(&self) -> &str {
+        "ChallengeUnlockPlugin"
+    }
+
+    fn init(&self) -> Result<(), ControllerPluginError> {
+        Ok(())
+    }
+
+    fn load(
+        &self,
+        game_controller: Arc<dyn GameControllerTrait>,
+    ) -> Result<(), ControllerPluginError> {
+        let known: HashSet<String> = match game_controller.game_state().lock() {
+            Ok(state) => Self::unlocked(&state.game).into_iter().collect(),
+            Err(_) => {
+                return Err(ControllerPluginError::LoadError(
+                    "Failed to lock game state".to_string(),
+                ));
+            }
+        };
+        let known = Mutex::new(known);
+
+        let game_controller_clone = game_controller.clone();
+        game_controller
+            .command_bus()
+            .subscribe(CommandType::Challenge, move |command| {
+                if let Command::Challenge(ChallengeCommand::Finish(_)) = command
+                    && let Err(e) =
+                        Self::publish_unlock_events(game_controller_clone.clone(), &known)
+                {
+                    tracing::error!("Error publishing unlock events: {:?}", e);
+                }
+            });
+
+        Ok(())
+    }
+
+    fn unload(
+        &self,
+        _game_controller: Arc<dyn GameControllerTrait>,
+    ) -> Result<(), ControllerPluginError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{ChallengeResult, ChallengeType, MultipleChoiceOption};
+    use crate::controller::{ChallengeFinishPlugin, GameController, GameXpPlugin};
+    use crate::events::EventType;
+    use crate::persistence::MemoryPersistence;
+
+    #[test]
+    fn test_unlock_events() {
+        let mut game = Game::default();
+        let mut known: HashSet<String> =
+            ChallengeUnlockPlugin::unlocked(&game).into_iter().collect();
+        assert!(!known.contains("konnektoren-2"));
+        assert!(ChallengeUnlockPlugin::unlock_events(&game, &mut known).is_empty());
+
+        game.xp = 20;
+        assert_eq!(
+            ChallengeUnlockPlugin::unlock_events(&game, &mut known),
+            vec![
+                GameEvent::ChallengeUnlocked("konnektoren-2".to_string()),
+                GameEvent::ChallengeUnlocked("konnektoren-3".to_string()),
+                GameEvent::ChallengeUnlocked("dialog-begruessung-quiz".to_string()),
+            ]
+        );
+        assert!(ChallengeUnlockPlugin::unlock_events(&game, &mut known).is_empty());
+    }
+
+    #[test]
+    fn test_publish_on_finish() {
+        let mut controller =
+            GameController::new(Game::default(), Arc::new(MemoryPersistence::default()));
+        controller.register_plugin(Arc::new(ChallengeFinishPlugin));
+        controller.register_plugin(Arc::new(GameXpPlugin));
+        controller.register_plugin(Arc::new(ChallengeUnlockPlugin));
+        let controller = controller.init();
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        controller
+            .event_bus()
+            .subscribe(EventType::Game, move |event| {
+                received_clone.lock().unwrap().push(event);
+            });
+
+        let result = {
+            let state = controller.game_state().lock().unwrap();
+            let ChallengeType::MultipleChoice(dataset) = &state.challenge.challenge_type else {
+                unreachable!()
+            };
+            ChallengeResult::MultipleChoice(
+                dataset
+                    .questions
+                    .iter()
+                    .map(|q| MultipleChoiceOption {
+                        id: q.option,
+                        name: String::new(),
+                    })
+                    .collect(),
+            )
+        };
+        controller.publish_command(Command::Challenge(ChallengeCommand::Finish(Some(result))));
+
+        let received = received.lock().unwrap();
+        assert!(received.contains(&Event::Game(GameEvent::ChallengeUnlocked(
+            "konnektoren-5".to_string()
+        ))));
+        assert_eq!(received.len(), 5);
+    }
+}
@@ -182,7 +182,10 @@ mod tests {
 
     #[test]
     fn test_handle_command() {
-        let game = Game::default();
+        let game = Game {
+            xp: 100,
+            ..Game::default()
+        };
         let persistence = Arc::new(MemoryPersistence::default());
         let controller = GameController::new(game, persistence).init();
 
@@ -195,7 +198,10 @@ mod tests {
 
     #[test]
     fn test_save_and_load_game_state() {
-        let game = Game::default();
+        let game = Game {
+            xp: 100,
+            ..Game::default()
+        };
         let persistence = Arc::new(MemoryPersistence::default());
         let controller = GameController::new(game, persistence).init();
 
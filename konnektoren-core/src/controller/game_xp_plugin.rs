@@ -1,6 +1,5 @@
 use super::{ControllerPlugin, ControllerPluginError, GameControllerTrait};
 use crate::challenges::ChallengeResult;
-use crate::challenges::Performance;
 use crate::commands::{ChallengeCommand, Command, CommandType};
 use crate::controller::ControllerError;
 use std::sync::Arc;
@@ -19,8 +18,7 @@ impl GameXpPlugin {
                 .map_err(|_| ControllerError::StateLock)?;
 
             game_state.challenge.challenge_result = challenge_result.clone();
-            let performance = game_state.challenge.performance(challenge_result);
-            let xp_reward = performance / 10;
+            let xp_reward = game_state.game.calculate_xp_reward(&game_state.challenge);
 
             game_state.game.xp += xp_reward;
         }
@@ -1,4 +1,7 @@
 mod challenge_finish_plugin;
+mod challenge_hint_plugin;
+mod challenge_timer_plugin;
+mod challenge_unlock_plugin;
 mod debug_plugin;
 pub mod error;
 mod game_controller;
@@ -6,6 +9,9 @@ mod game_xp_plugin;
 mod plugins;
 
 pub use challenge_finish_plugin::ChallengeFinishPlugin;
+pub use challenge_hint_plugin::ChallengeHintPlugin;
+pub use challenge_timer_plugin::ChallengeTimerPlugin;
+pub use challenge_unlock_plugin::ChallengeUnlockPlugin;
 pub use debug_plugin::DebugPlugin;
 pub use error::*;
 pub use game_controller::{GameController, GameControllerTrait};
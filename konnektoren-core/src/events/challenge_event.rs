@@ -5,6 +5,16 @@ use serde::{Deserialize, Serialize};
 pub enum ChallengeEvent {
     SolvedCorrect(usize),
     SolvedIncorrect(usize),
+    /// A hint was revealed for the task with the given index
+    HintUsed(usize),
+    /// The time of the challenge runs out in the given number of seconds
+    TimeWarning(i64),
+    /// The time of the challenge ran out and it was finished
+    TimedOut,
+    /// The time of the task with the given index runs out soon
+    TaskTimeWarning(usize),
+    /// The time of the task with the given index ran out and it was skipped
+    TaskTimedOut(usize),
     #[default]
     Started,
     Completed,
@@ -20,6 +30,11 @@ impl EventTrait for ChallengeEvent {
         match self {
             ChallengeEvent::SolvedCorrect(_) => "SolvedCorrect",
             ChallengeEvent::SolvedIncorrect(_) => "SolvedIncorrect",
+            ChallengeEvent::HintUsed(_) => "HintUsed",
+            ChallengeEvent::TimeWarning(_) => "TimeWarning",
+            ChallengeEvent::TimedOut => "TimedOut",
+            ChallengeEvent::TaskTimeWarning(_) => "TaskTimeWarning",
+            ChallengeEvent::TaskTimedOut(_) => "TaskTimedOut",
             ChallengeEvent::Started => "Started",
             ChallengeEvent::Completed => "Completed",
             ChallengeEvent::Error(_) => "Error",
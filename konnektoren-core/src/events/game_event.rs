@@ -2,10 +2,12 @@ use serde::{Deserialize, Serialize};
 
 use super::{EventType, event::EventTrait};
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub enum GameEvent {
     #[default]
     Started,
+    /// The challenge with the given id was unlocked
+    ChallengeUnlocked(String),
 }
 
 impl EventTrait for GameEvent {
@@ -16,6 +18,7 @@ impl EventTrait for GameEvent {
     fn get_action(&self) -> &str {
         match self {
             GameEvent::Started => "Started",
+            GameEvent::ChallengeUnlocked(_) => "ChallengeUnlocked",
         }
     }
 }
@@ -40,6 +43,9 @@ mod tests {
     fn test_get_action() {
         let event = GameEvent::Started;
         assert_eq!(event.get_action(), "Started");
+
+        let event = GameEvent::ChallengeUnlocked("konnektoren-2".to_string());
+        assert_eq!(event.get_action(), "ChallengeUnlocked");
     }
 
     #[test]
@@ -38,6 +38,16 @@ impl TryFrom<Value> for GameEvent {
     fn try_from(value: Value) -> Result<Self, Self::Error> {
         match value.get("action").and_then(|v| v.as_str()) {
             Some("Started") => Ok(GameEvent::Started),
+            Some("ChallengeUnlocked") => {
+                let challenge_id = value
+                    .get("challengeId")
+                    .ok_or(EventParseError::MissingData)?
+                    .as_str()
+                    .ok_or_else(|| {
+                        EventParseError::InvalidData("challengeId must be a string".to_string())
+                    })?;
+                Ok(GameEvent::ChallengeUnlocked(challenge_id.to_string()))
+            }
             Some(unknown_action) => Err(EventParseError::UnknownEventType(
                 unknown_action.to_string(),
             )),
@@ -71,6 +81,47 @@ impl TryFrom<Value> for ChallengeEvent {
                     })?;
                 Ok(ChallengeEvent::SolvedIncorrect(index as usize))
             }
+            Some("HintUsed") => {
+                let index = value
+                    .get("index")
+                    .ok_or(EventParseError::MissingData)?
+                    .as_u64()
+                    .ok_or_else(|| {
+                        EventParseError::InvalidData("index must be a number".to_string())
+                    })?;
+                Ok(ChallengeEvent::HintUsed(index as usize))
+            }
+            Some("TimeWarning") => {
+                let seconds = value
+                    .get("seconds")
+                    .ok_or(EventParseError::MissingData)?
+                    .as_i64()
+                    .ok_or_else(|| {
+                        EventParseError::InvalidData("seconds must be a number".to_string())
+                    })?;
+                Ok(ChallengeEvent::TimeWarning(seconds))
+            }
+            Some("TimedOut") => Ok(ChallengeEvent::TimedOut),
+            Some("TaskTimeWarning") => {
+                let index = value
+                    .get("index")
+                    .ok_or(EventParseError::MissingData)?
+                    .as_u64()
+                    .ok_or_else(|| {
+                        EventParseError::InvalidData("index must be a number".to_string())
+                    })?;
+                Ok(ChallengeEvent::TaskTimeWarning(index as usize))
+            }
+            Some("TaskTimedOut") => {
+                let index = value
+                    .get("index")
+                    .ok_or(EventParseError::MissingData)?
+                    .as_u64()
+                    .ok_or_else(|| {
+                        EventParseError::InvalidData("index must be a number".to_string())
+                    })?;
+                Ok(ChallengeEvent::TaskTimedOut(index as usize))
+            }
             Some("Error") => {
                 let message = value
                     .get("message")
@@ -103,6 +154,17 @@ mod tests {
         assert_eq!(event, Event::Game(GameEvent::Started));
     }
 
+    #[test]
+    fn test_parse_game_event_challenge_unlocked() {
+        let json = r#"{"type":"Game","action":"ChallengeUnlocked","challengeId":"konnektoren-2"}"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let event = Event::try_from(value).unwrap();
+        assert_eq!(
+            event,
+            Event::Game(GameEvent::ChallengeUnlocked("konnektoren-2".to_string()))
+        );
+    }
+
     #[test]
     fn test_parse_challenge_event() {
         let json = r#"{"type":"Challenge","action":"SolvedCorrect","index":2}"#;
@@ -119,6 +181,27 @@ mod tests {
         assert_eq!(event, Event::Challenge(ChallengeEvent::SolvedIncorrect(2)));
     }
 
+    #[test]
+    fn test_parse_challenge_event_hint_used() {
+        let json = r#"{"type":"Challenge","action":"HintUsed","index":1}"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let event = Event::try_from(value).unwrap();
+        assert_eq!(event, Event::Challenge(ChallengeEvent::HintUsed(1)));
+    }
+
+    #[test]
+    fn test_parse_challenge_event_timeouts() {
+        let json = r#"{"type":"Challenge","action":"TimeWarning","seconds":10}"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let event = Event::try_from(value).unwrap();
+        assert_eq!(event, Event::Challenge(ChallengeEvent::TimeWarning(10)));
+
+        let json = r#"{"type":"Challenge","action":"TaskTimedOut","index":3}"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let event = Event::try_from(value).unwrap();
+        assert_eq!(event, Event::Challenge(ChallengeEvent::TaskTimedOut(3)));
+    }
+
     #[test]
     fn test_parse_challenge_event_started() {
         let json = r#"{"type":"Challenge","action":"Started"}"#;
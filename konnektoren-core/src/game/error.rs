@@ -11,6 +11,15 @@ pub enum GameError {
     #[error("Invalid game state: {0}")]
     InvalidGameState(String),
 
+    #[error("Challenge {id} is locked: {reason}")]
+    ChallengeLocked {
+        id: String,
+        reason: crate::game::LockReason,
+    },
+
+    #[error("Map too small: {needed} challenges need a position, {available} cells are free")]
+    MapTooSmall { needed: usize, available: usize },
+
     #[error("Challenge error: {0}")]
     ChallengeError(#[from] crate::challenges::ChallengeError),
 }
@@ -1,7 +1,8 @@
 use super::GamePath;
 use crate::Xp;
 use crate::challenges::{
-    Challenge, ChallengeConfig, ChallengeFactory, ChallengeHistory, Performance,
+    Challenge, ChallengeConfig, ChallengeFactory, ChallengeHistory, ChallengeStats, RewardContext,
+    RewardPolicy,
 };
 use crate::game::error::{GameError, Result};
 use serde::{Deserialize, Serialize};
@@ -41,10 +42,36 @@ impl Game {
             .ok_or_else(|| GameError::ChallengeNotFound(challenge_config_id.to_string()))?;
 
         self.challenge_factory
-            .create_challenge(&challenge_config)
+            .create_challenge_for_history(&challenge_config, &self.challenge_history)
             .map_err(GameError::ChallengeError)
     }
 
+    /// Returns the reward policy of a challenge: its own override, otherwise
+    /// the policy of the game path it belongs to.
+    pub fn reward_policy(&self, challenge_config: &ChallengeConfig) -> RewardPolicy {
+        challenge_config
+            .rewards
+            .clone()
+            .or_else(|| {
+                self.find_game_path_index(&challenge_config.id)
+                    .map(|index| self.game_paths[index].rewards.clone())
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn calculate_stars(&self, challenge: &Challenge) -> u32 {
+        self.reward_policy(&challenge.challenge_config)
+            .stars_for(&self.reward_context(challenge))
+    }
+
+    /// Returns everything about the challenge that influences its reward.
+    pub fn reward_context(&self, challenge: &Challenge) -> RewardContext {
+        RewardContext {
+            first_attempt: self.is_first_attempt(challenge),
+            ..RewardContext::of(challenge)
+        }
+    }
+
     pub fn get_challenge_config(&self, challenge_config_id: &str) -> Option<ChallengeConfig> {
         self.game_paths
             .iter()
@@ -55,14 +82,53 @@ impl Game {
     }
 
     pub fn calculate_xp_reward(&self, challenge: &Challenge) -> Xp {
-        challenge.performance(&challenge.challenge_result)
-            * challenge.stars(&challenge.challenge_result)
+        self.reward_policy(&challenge.challenge_config)
+            .xp(&self.reward_context(challenge))
+    }
+
+    /// A challenge is the first attempt if its config was never played, or if
+    /// it is the attempt the history recorded first for its config.
+    fn is_first_attempt(&self, challenge: &Challenge) -> bool {
+        self.challenge_history
+            .challenges
+            .iter()
+            .find(|c| c.challenge_config.id == challenge.challenge_config.id)
+            .is_none_or(|first| first == challenge)
+    }
+}
+
+/// The statistics of a challenge with the reward policy of its game path.
+impl ChallengeStats for (&ChallengeConfig, &Game) {
+    fn challenges(&self) -> usize {
+        (self.0, &self.1.challenge_history).challenges()
+    }
+
+    fn completed_challenges(&self) -> usize {
+        (self.0, &self.1.challenge_history).completed_challenges()
+    }
+
+    fn reward_policy(&self) -> RewardPolicy {
+        self.1.reward_policy(self.0)
+    }
+
+    /// The stars of the best attempt, like [`Game::best_stars`].
+    fn stars(&self) -> u32 {
+        self.1.best_stars(&self.0.id).unwrap_or_default()
+    }
+
+    fn performance(&self) -> u32 {
+        (self.0, &self.1.challenge_history).performance()
+    }
+
+    fn solved(&self) -> bool {
+        (self.0, &self.1.challenge_history).solved()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::challenges::{ChallengeResult, ChallengeType, MultipleChoiceOption, Timed};
 
     #[test]
     fn create_challenge() {
@@ -97,6 +163,129 @@ mod tests {
         assert_eq!(xp, 0);
     }
 
+    #[test]
+    fn reward_policy_of_game_path() {
+        let mut game = Game::default();
+        game.game_paths[0].rewards.stars.three = 95;
+
+        let config = game.get_challenge_config("konnektoren-1").unwrap();
+        assert_eq!(game.reward_policy(&config).stars.three, 95);
+
+        // The policy is resolved by the game and not copied into the content
+        let challenge = game.create_challenge("konnektoren-1").unwrap();
+        assert_eq!(challenge.challenge_config.rewards, None);
+        assert_eq!(
+            game.reward_policy(&challenge.challenge_config).stars.three,
+            95
+        );
+
+        let overridden = ChallengeConfig {
+            rewards: Some(RewardPolicy::default()),
+            ..config
+        };
+        assert_eq!(game.reward_policy(&overridden).stars.three, 80);
+    }
+
+    #[test]
+    fn first_attempt_bonus() {
+        let mut game = Game::default();
+        game.game_paths[0].rewards.first_attempt_bonus = 7;
+
+        let mut challenge = game.create_challenge("konnektoren-1").unwrap();
+        challenge.start();
+        let ChallengeType::MultipleChoice(dataset) = &challenge.challenge_type else {
+            unreachable!()
+        };
+        challenge.challenge_result = ChallengeResult::MultipleChoice(
+            dataset
+                .questions
+                .iter()
+                .map(|q| MultipleChoiceOption {
+                    id: q.option,
+                    name: String::new(),
+                })
+                .collect(),
+        );
+        assert_eq!(game.calculate_xp_reward(&challenge), 10 + 7);
+
+        game.challenge_history.add_challenge(challenge.clone());
+        assert_eq!(game.calculate_xp_reward(&challenge), 10 + 7);
+
+        let mut second = challenge.clone();
+        second.start_time = challenge
+            .start_time
+            .map(|start| start + chrono::Duration::seconds(1));
+        assert_eq!(game.calculate_xp_reward(&second), 10);
+    }
+
+    #[test]
+    fn first_attempt_by_challenge_id() {
+        let mut game = Game::default();
+        game.game_paths[0].rewards.first_attempt_bonus = 7;
+        game.game_paths[0].rewards.stars.one = 0;
+
+        let first = game.create_challenge("konnektoren-1").unwrap();
+        let mut second = first.clone();
+        second.timed_out_tasks = vec![0];
+        game.challenge_history.add_challenge(first.clone());
+        game.challenge_history.add_challenge(second);
+
+        let rewards: Vec<Xp> = game
+            .challenge_history
+            .challenges
+            .iter()
+            .map(|challenge| game.calculate_xp_reward(challenge))
+            .collect();
+        assert_eq!(rewards, vec![7, 0]);
+
+        // A copy of the first attempt, e.g. after loading a saved game, is
+        // still the first attempt
+        let json = serde_json::to_string(&first).unwrap();
+        let restored: Challenge = serde_json::from_str(&json).unwrap();
+        assert_eq!(game.calculate_xp_reward(&restored), 7);
+    }
+
+    #[test]
+    fn hints_reduce_reward() {
+        let mut game = Game::default();
+        game.game_paths[0].rewards.hint_penalty = 2;
+        game.game_paths[0].rewards.hint_star_penalty = 25;
+
+        let mut challenge = game.create_challenge("konnektoren-1").unwrap();
+        let ChallengeType::MultipleChoice(dataset) = &challenge.challenge_type else {
+            unreachable!()
+        };
+        challenge.challenge_result = ChallengeResult::MultipleChoice(
+            dataset
+                .questions
+                .iter()
+                .map(|q| MultipleChoiceOption {
+                    id: q.option,
+                    name: String::new(),
+                })
+                .collect(),
+        );
+        assert_eq!(game.calculate_stars(&challenge), 3);
+
+        challenge.request_hint(0).unwrap();
+        assert_eq!(game.calculate_stars(&challenge), 2);
+        assert_eq!(game.calculate_xp_reward(&challenge), 10 - 2);
+    }
+
+    #[test]
+    fn challenge_stats_follow_game_path_policy() {
+        let mut game = Game::default();
+        game.game_paths[0].rewards.stars.one = 0;
+        let config = game.get_challenge_config("konnektoren-1").unwrap();
+        assert_eq!((&config, &game).stars(), 0);
+
+        let challenge = game.create_challenge("konnektoren-1").unwrap();
+        game.challenge_history.add_challenge(challenge.clone());
+        assert_eq!((&config, &game).stars(), 1);
+        assert_eq!((&config, &game).stars(), game.calculate_stars(&challenge));
+        assert_eq!((&config, &game.challenge_history).stars(), 0);
+    }
+
     #[test]
     fn get_challenge_config() {
         let game = Game::default();
@@ -1,5 +1,7 @@
 use crate::challenges::challenge_config::ChallengeConfig;
-use crate::game::Map;
+use crate::challenges::{ChallengeQuery, RewardPolicy};
+use crate::game::error::Result;
+use crate::game::{Map, MapLayout, PathEdge};
 #[cfg(feature = "schema")]
 use schemars::{JsonSchema, schema_for};
 use serde::{Deserialize, Serialize};
@@ -10,8 +12,15 @@ pub struct GamePath {
     pub id: String,
     pub name: String,
     pub challenges: Vec<ChallengeConfig>,
+    /// Connections between the challenges. Without edges the challenges
+    /// follow each other in the order they are listed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edges: Vec<PathEdge>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub map: Option<Map>,
+    /// How stars and XP are awarded for the challenges of this path
+    #[serde(default)]
+    pub rewards: RewardPolicy,
 }
 
 impl Default for GamePath {
@@ -35,26 +44,151 @@ impl GamePath {
             .collect()
     }
 
+    /// Inserts a challenge in front of another one. Edges leading to
+    /// `before_id` lead to the new challenge instead.
     pub fn insert_before(&mut self, before_id: &str, config: ChallengeConfig) {
         if let Some(pos) = self.challenges.iter().position(|c| c.id == before_id) {
+            if !self.edges.is_empty() {
+                for edge in self.edges.iter_mut().filter(|edge| edge.to == before_id) {
+                    edge.to = config.id.clone();
+                }
+                self.edges.push(PathEdge::new(&config.id, before_id));
+            }
             self.challenges.insert(pos, config);
         }
     }
 
+    /// Inserts a challenge behind another one. Edges starting at `after_id`
+    /// start at the new challenge instead.
     pub fn insert_after(&mut self, after_id: &str, config: ChallengeConfig) {
         if let Some(pos) = self.challenges.iter().position(|c| c.id == after_id) {
+            if !self.edges.is_empty() {
+                for edge in self.edges.iter_mut().filter(|edge| edge.from == after_id) {
+                    edge.from = config.id.clone();
+                }
+                self.edges.push(PathEdge::new(after_id, &config.id));
+            }
             self.challenges.insert(pos + 1, config);
         }
     }
 
+    /// Returns `true` if the path has no edges, so every learner walks the
+    /// challenges in the order they are listed.
+    pub fn is_linear(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Returns the edges of the path. A linear path connects every challenge
+    /// with the one listed after it.
+    pub fn connections(&self) -> Vec<PathEdge> {
+        if !self.is_linear() {
+            return self.edges.clone();
+        }
+        self.challenges
+            .windows(2)
+            .map(|pair| PathEdge::new(&pair[0].id, &pair[1].id))
+            .collect()
+    }
+
+    /// Returns the challenges that may follow the challenge, in edge order.
+    pub fn successors(&self, challenge_id: &str) -> Vec<&ChallengeConfig> {
+        self.connections()
+            .iter()
+            .filter(|edge| edge.from == challenge_id)
+            .filter_map(|edge| self.get_challenge_config(&edge.to))
+            .collect()
+    }
+
+    /// Returns the challenges the challenge may be reached from, in edge
+    /// order.
+    pub fn predecessors(&self, challenge_id: &str) -> Vec<&ChallengeConfig> {
+        self.connections()
+            .iter()
+            .filter(|edge| edge.to == challenge_id)
+            .filter_map(|edge| self.get_challenge_config(&edge.from))
+            .collect()
+    }
+
+    /// Returns the challenges without predecessors, where learners start.
+    pub fn start_challenges(&self) -> Vec<&ChallengeConfig> {
+        let connections = self.connections();
+        self.challenges
+            .iter()
+            .filter(|challenge| !connections.iter().any(|edge| edge.to == challenge.id))
+            .collect()
+    }
+
+    /// Returns the first successor of the challenge.
     pub fn next_challenge_id(&self, challenge_id: &str) -> Option<String> {
-        let mut iter = self.challenges.iter();
-        while let Some(challenge) = iter.next() {
-            if challenge.id == challenge_id {
-                return iter.next().map(|c| c.id.clone());
+        self.successors(challenge_id)
+            .first()
+            .map(|challenge| challenge.id.clone())
+    }
+
+    /// Returns the positions of both ends of every edge, for drawing the path
+    /// on a map. Edges with a challenge without position are left out.
+    pub fn edge_positions(&self) -> Vec<((i32, i32), (i32, i32))> {
+        self.connections()
+            .iter()
+            .filter_map(|edge| {
+                let from = self.get_challenge_config(&edge.from)?.position?;
+                let to = self.get_challenge_config(&edge.to)?.position?;
+                Some((from, to))
+            })
+            .collect()
+    }
+
+    /// Returns a copy of the path where every challenge has a position.
+    /// Challenges without one are placed by the layout of the map, see
+    /// [`MapLayout`]. Without a map they are placed on a square with room
+    /// for twice as many challenges as the path has.
+    pub fn laid_out(&self) -> Result<GamePath> {
+        let (width, height, layout) = match &self.map {
+            Some(map) => (map.width, map.height, map.layout),
+            None => {
+                let side = ((2 * self.challenges.len()) as f64).sqrt().ceil() as u32;
+                (side.max(1), side.max(1), None)
+            }
+        };
+        let layout = layout.unwrap_or_else(|| MapLayout::for_path(self));
+        let mut positions = layout.layout(self, width, height)?;
+
+        let mut game_path = self.clone();
+        for challenge in game_path.challenges.iter_mut() {
+            if let Some(position) = positions.remove(&challenge.id) {
+                challenge.position = Some(position);
             }
         }
-        None
+        Ok(game_path)
+    }
+
+    /// Returns the challenges whose level and tags match the query. Only the
+    /// configs are looked at; [`ChallengeFactory::level_of`] and
+    /// [`ChallengeFactory::tags_of`] include the level and tags of the
+    /// challenge types.
+    ///
+    /// [`ChallengeFactory::level_of`]: crate::challenges::ChallengeFactory::level_of
+    /// [`ChallengeFactory::tags_of`]: crate::challenges::ChallengeFactory::tags_of
+    pub fn challenges_matching(&self, query: &ChallengeQuery) -> Vec<&ChallengeConfig> {
+        self.challenges
+            .iter()
+            .filter(|challenge| query.matches(challenge.level, &challenge.tags))
+            .collect()
+    }
+
+    /// Returns a copy of the path with only the challenges matching the
+    /// query, e.g. to build a path for a single level. Edges to the removed
+    /// challenges are dropped.
+    pub fn filtered(&self, query: &ChallengeQuery) -> GamePath {
+        let mut game_path = self.clone();
+        game_path
+            .challenges
+            .retain(|challenge| query.matches(challenge.level, &challenge.tags));
+        let ids = game_path.challenge_ids();
+        game_path
+            .edges
+            .retain(|edge| ids.contains(&edge.from) && ids.contains(&edge.to));
+        game_path
     }
 }
 
@@ -112,6 +246,8 @@ mod tests {
             id: "test".to_string(),
             name: "Test".to_string(),
             map: None,
+            rewards: RewardPolicy::default(),
+            edges: vec![],
             challenges: ids
                 .iter()
                 .map(|id| ChallengeConfig {
@@ -126,6 +262,43 @@ mod tests {
         game_path.challenges.iter().map(|c| c.id.as_str()).collect()
     }
 
+    fn leveled_game_path() -> GamePath {
+        use crate::challenges::CefrLevel;
+        let mut game_path = make_game_path(&["a", "b", "c"]);
+        game_path.challenges[0].level = Some(CefrLevel::A1);
+        game_path.challenges[1].level = Some(CefrLevel::A2);
+        game_path.challenges[1].tags = vec!["konnektoren".to_string()];
+        game_path.challenges[2].tags = vec!["konnektoren".to_string()];
+        game_path
+    }
+
+    #[test]
+    fn test_challenges_matching() {
+        use crate::challenges::CefrLevel;
+        let game_path = leveled_game_path();
+        let query = ChallengeQuery::new()
+            .level(CefrLevel::A2)
+            .tag("konnektoren");
+        let ids: Vec<&str> = game_path
+            .challenges_matching(&query)
+            .iter()
+            .map(|c| c.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["b"]);
+
+        let query = ChallengeQuery::new().tag("konnektoren");
+        assert_eq!(game_path.challenges_matching(&query).len(), 2);
+    }
+
+    #[test]
+    fn test_filtered() {
+        use crate::challenges::CefrLevel;
+        let game_path = leveled_game_path();
+        let a1 = game_path.filtered(&ChallengeQuery::new().level(CefrLevel::A1));
+        assert_eq!(challenge_ids(&a1), vec!["a"]);
+        assert_eq!(a1.id, game_path.id);
+    }
+
     #[test]
     fn test_insert_before() {
         let mut game_path = make_game_path(&["a", "b", "c"]);
@@ -178,6 +351,162 @@ mod tests {
         assert_eq!(challenge_ids(&game_path), vec!["a", "b"]);
     }
 
+    /// `a` branches to `b` and `c`, which join again at `d`. `s` is a side
+    /// quest reachable from `a`.
+    fn branching_game_path() -> GamePath {
+        let mut game_path = make_game_path(&["a", "b", "c", "d", "s"]);
+        game_path.challenges[4].elective = true;
+        game_path.edges = vec![
+            PathEdge::new("a", "b"),
+            PathEdge::new("a", "c"),
+            PathEdge::new("b", "d"),
+            PathEdge::new("c", "d"),
+            PathEdge::new("a", "s"),
+        ];
+        game_path
+    }
+
+    fn ids<'a>(challenges: Vec<&'a ChallengeConfig>) -> Vec<&'a str> {
+        challenges.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_linear_connections() {
+        let game_path = GamePath::default();
+        assert!(game_path.is_linear());
+        assert_eq!(
+            game_path.connections().len(),
+            game_path.challenges.len() - 1
+        );
+        assert_eq!(
+            game_path.connections()[0],
+            PathEdge::new("konnektoren-1", "konnektoren-2")
+        );
+        assert_eq!(ids(game_path.start_challenges()), vec!["konnektoren-1"]);
+        assert_eq!(game_path.next_challenge_id("dialog-begruessung-quiz"), None);
+    }
+
+    #[test]
+    fn test_branching_game_path() {
+        let game_path = branching_game_path();
+        assert!(!game_path.is_linear());
+        assert_eq!(ids(game_path.successors("a")), vec!["b", "c", "s"]);
+        assert_eq!(ids(game_path.predecessors("d")), vec!["b", "c"]);
+        assert!(game_path.successors("d").is_empty());
+        assert_eq!(ids(game_path.start_challenges()), vec!["a"]);
+        assert_eq!(game_path.next_challenge_id("a"), Some("b".to_string()));
+    }
+
+    #[test]
+    fn test_deserialize_edges() {
+        let yaml = r#"
+id: branching
+name: Branching
+challenges:
+  - id: a
+    name: A
+    description: Start
+    challenge: konnektoren
+    tasks: 1
+    unlock_points: 0
+  - id: b
+    name: B
+    description: Side quest
+    challenge: konnektoren
+    tasks: 1
+    unlock_points: 0
+    elective: true
+edges:
+  - from: a
+    to: b
+"#;
+        let game_path: GamePath = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(game_path.edges, vec![PathEdge::new("a", "b")]);
+        assert!(game_path.challenges[1].elective);
+        assert!(!game_path.challenges[0].elective);
+    }
+
+    #[test]
+    fn test_insert_into_branching_game_path() {
+        let mut game_path = branching_game_path();
+        game_path.insert_after(
+            "b",
+            ChallengeConfig {
+                id: "x".to_string(),
+                ..ChallengeConfig::default()
+            },
+        );
+        assert_eq!(ids(game_path.successors("b")), vec!["x"]);
+        assert_eq!(ids(game_path.successors("x")), vec!["d"]);
+
+        game_path.insert_before(
+            "d",
+            ChallengeConfig {
+                id: "y".to_string(),
+                ..ChallengeConfig::default()
+            },
+        );
+        assert_eq!(ids(game_path.predecessors("y")), vec!["x", "c"]);
+        assert_eq!(ids(game_path.predecessors("d")), vec!["y"]);
+    }
+
+    #[test]
+    fn test_filtered_drops_edges() {
+        use crate::challenges::CefrLevel;
+        let mut game_path = branching_game_path();
+        for challenge in game_path.challenges.iter_mut() {
+            challenge.level = Some(CefrLevel::A1);
+        }
+        game_path.challenges[1].level = Some(CefrLevel::B1);
+
+        let a1 = game_path.filtered(&ChallengeQuery::new().level(CefrLevel::A1));
+        assert_eq!(challenge_ids(&a1), vec!["a", "c", "d", "s"]);
+        assert_eq!(a1.edges.len(), 3);
+        assert!(a1.successors("a").iter().all(|c| c.id != "b"));
+    }
+
+    #[test]
+    fn test_edge_positions() {
+        let mut game_path = branching_game_path();
+        for (i, challenge) in game_path.challenges.iter_mut().enumerate() {
+            challenge.position = Some((i as i32, 0));
+        }
+        game_path.challenges[4].position = None;
+        assert_eq!(
+            game_path.edge_positions(),
+            vec![
+                ((0, 0), (1, 0)),
+                ((0, 0), (2, 0)),
+                ((1, 0), (3, 0)),
+                ((2, 0), (3, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_laid_out() {
+        let game_path = GamePath::default();
+        assert_eq!(game_path.laid_out().unwrap(), game_path);
+
+        let mut game_path = branching_game_path();
+        for challenge in game_path.challenges.iter_mut() {
+            challenge.position = None;
+        }
+        game_path.challenges[0].position = Some((1, 1));
+        let laid_out = game_path.laid_out().unwrap();
+        assert_eq!(laid_out.challenges[0].position, Some((1, 1)));
+        assert!(laid_out.challenges.iter().all(|c| c.position.is_some()));
+        assert_eq!(laid_out.edge_positions().len(), game_path.edges.len());
+
+        game_path.map = Some(Map {
+            background: String::new(),
+            width: 2,
+            height: 2,
+            layout: Some(MapLayout::Grid),
+        });
+        assert!(game_path.laid_out().is_err());
+    }
+
     #[test]
     fn test_game_path_json_round_trip() {
         let game_path = GamePath::default();
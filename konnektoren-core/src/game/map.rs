@@ -1,11 +0,0 @@
-#[cfg(feature = "schema")]
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-#[cfg_attr(feature = "schema", derive(JsonSchema))]
-pub struct Map {
-    pub background: String,
-    pub width: u32,
-    pub height: u32,
-}
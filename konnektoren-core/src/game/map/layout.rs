@@ -0,0 +1,406 @@
+//! Positions for the challenges an author did not place on the map.
+//!
+//! Challenges are placed on the cells of a `width` x `height` map. Authored
+//! positions never move, and no placed challenge shares a cell with another
+//! challenge.
+
+use crate::game::GamePath;
+use crate::game::error::{GameError, Result};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Rounds of the force-directed simulation.
+const FORCE_ITERATIONS: usize = 100;
+
+/// Smallest distance between two challenges in the force-directed
+/// simulation, so forces stay finite.
+const MIN_DISTANCE: f64 = 0.01;
+
+type Position = (i32, i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum MapLayout {
+    /// Rows in alternating directions, so challenges that follow each other
+    /// stay next to each other
+    #[default]
+    Serpentine,
+    /// Rows from left to right
+    Grid,
+    /// Connected challenges pull each other closer while all challenges push
+    /// each other apart, for branching paths
+    ForceDirected,
+}
+
+impl MapLayout {
+    /// Returns the layout for a path whose map does not choose one:
+    /// force-directed for branching paths, serpentine for linear ones.
+    pub fn for_path(game_path: &GamePath) -> Self {
+        if game_path.is_linear() {
+            MapLayout::Serpentine
+        } else {
+            MapLayout::ForceDirected
+        }
+    }
+
+    /// Returns a position for every challenge of the path without one,
+    /// keyed by challenge id.
+    ///
+    /// Fails with [`GameError::MapTooSmall`] if there are fewer free cells
+    /// than challenges to place.
+    pub fn layout(
+        &self,
+        game_path: &GamePath,
+        width: u32,
+        height: u32,
+    ) -> Result<HashMap<String, Position>> {
+        let area = Area {
+            width: i32::try_from(width).unwrap_or(i32::MAX),
+            height: i32::try_from(height).unwrap_or(i32::MAX),
+        };
+        let authored: HashSet<Position> = game_path
+            .challenges
+            .iter()
+            .filter_map(|challenge| challenge.position)
+            .collect();
+        let missing: Vec<&str> = game_path
+            .challenges
+            .iter()
+            .filter(|challenge| challenge.position.is_none())
+            .map(|challenge| challenge.id.as_str())
+            .collect();
+
+        let available = area
+            .cells()
+            .saturating_sub(authored.iter().filter(|p| area.contains(**p)).count());
+        if missing.len() > available {
+            return Err(GameError::MapTooSmall {
+                needed: missing.len(),
+                available,
+            });
+        }
+
+        let targets: Vec<(f64, f64)> = match self {
+            MapLayout::Serpentine => area.slots(missing.len(), true),
+            MapLayout::Grid => area.slots(missing.len(), false),
+            MapLayout::ForceDirected => {
+                let start = area.place(&missing, &area.slots(missing.len(), true), &authored);
+                force_directed(game_path, &start, &area)
+            }
+        };
+        Ok(area.place(&missing, &targets, &authored))
+    }
+}
+
+struct Area {
+    width: i32,
+    height: i32,
+}
+
+impl Area {
+    fn cells(&self) -> usize {
+        self.width.max(0) as usize * self.height.max(0) as usize
+    }
+
+    fn contains(&self, (x, y): Position) -> bool {
+        (0..self.width).contains(&x) && (0..self.height).contains(&y)
+    }
+
+    /// Returns `count` cells spread evenly over the area, row by row.
+    fn slots(&self, count: usize, serpentine: bool) -> Vec<(f64, f64)> {
+        if count == 0 || self.cells() == 0 {
+            return vec![];
+        }
+        let ratio = self.width as f64 / self.height as f64;
+        let columns = ((count as f64 * ratio).sqrt().ceil() as i32).clamp(1, self.width);
+        let rows = (count.div_ceil(columns as usize) as i32).clamp(1, self.height);
+        let (step_x, step_y) = (self.width / columns, self.height / rows);
+
+        (0..rows)
+            .flat_map(|row| {
+                (0..columns).map(move |column| {
+                    let column = if serpentine && row % 2 == 1 {
+                        columns - 1 - column
+                    } else {
+                        column
+                    };
+                    (
+                        (column * step_x + step_x / 2) as f64,
+                        (row * step_y + step_y / 2) as f64,
+                    )
+                })
+            })
+            .take(count)
+            .collect()
+    }
+
+    /// Puts every challenge on the free cell closest to its target.
+    fn place(
+        &self,
+        ids: &[&str],
+        targets: &[(f64, f64)],
+        authored: &HashSet<Position>,
+    ) -> HashMap<String, Position> {
+        let mut occupied = authored.clone();
+        let mut positions = HashMap::new();
+        for (index, id) in ids.iter().enumerate() {
+            let target = targets
+                .get(index)
+                .or(targets.last())
+                .copied()
+                .unwrap_or((0.0, 0.0));
+            if let Some(cell) = self.nearest_free(target, &occupied) {
+                occupied.insert(cell);
+                positions.insert(id.to_string(), cell);
+            }
+        }
+        positions
+    }
+
+    /// Returns the free cell closest to the target, searching rings of
+    /// growing distance around it.
+    fn nearest_free(&self, (x, y): (f64, f64), occupied: &HashSet<Position>) -> Option<Position> {
+        let center = (
+            (x.round() as i32).clamp(0, self.width - 1),
+            (y.round() as i32).clamp(0, self.height - 1),
+        );
+        let distance = |(cx, cy): Position| (cx as f64 - x).powi(2) + (cy as f64 - y).powi(2);
+        (0..self.width.max(self.height)).find_map(|radius| {
+            ring(center, radius)
+                .into_iter()
+                .filter(|cell| self.contains(*cell) && !occupied.contains(cell))
+                .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+        })
+    }
+}
+
+/// Returns the cells at exactly `radius` steps (in any direction) from the
+/// center.
+fn ring((x, y): Position, radius: i32) -> Vec<Position> {
+    if radius == 0 {
+        return vec![(x, y)];
+    }
+    let mut cells = vec![];
+    for dx in -radius..=radius {
+        cells.push((x + dx, y - radius));
+        cells.push((x + dx, y + radius));
+    }
+    for dy in (-radius + 1)..radius {
+        cells.push((x - radius, y + dy));
+        cells.push((x + radius, y + dy));
+    }
+    cells
+}
+
+/// Moves the challenges without an authored position along the forces of
+/// a Fruchterman-Reingold simulation and returns their final spots, in the
+/// order of the challenges.
+fn force_directed(
+    game_path: &GamePath,
+    start: &HashMap<String, Position>,
+    area: &Area,
+) -> Vec<(f64, f64)> {
+    let challenges = &game_path.challenges;
+    let count = challenges.len();
+    let index: HashMap<&str, usize> = challenges
+        .iter()
+        .enumerate()
+        .map(|(i, challenge)| (challenge.id.as_str(), i))
+        .collect();
+    let edges: Vec<(usize, usize)> = game_path
+        .connections()
+        .iter()
+        .filter_map(|edge| {
+            Some((
+                *index.get(edge.from.as_str())?,
+                *index.get(edge.to.as_str())?,
+            ))
+        })
+        .collect();
+    let fixed: Vec<bool> = challenges.iter().map(|c| c.position.is_some()).collect();
+    let mut positions: Vec<(f64, f64)> = challenges
+        .iter()
+        .map(|challenge| {
+            let (x, y) = challenge
+                .position
+                .or_else(|| start.get(&challenge.id).copied())
+                .unwrap_or((0, 0));
+            (x as f64, y as f64)
+        })
+        .collect();
+
+    let k = (area.cells() as f64 / count.max(1) as f64).sqrt();
+    let mut temperature = area.width.max(area.height) as f64 / 10.0;
+    let cooling = temperature / FORCE_ITERATIONS as f64;
+
+    for _ in 0..FORCE_ITERATIONS {
+        let mut displacement = vec![(0.0, 0.0); count];
+        for i in 0..count {
+            for j in 0..count {
+                if i == j {
+                    continue;
+                }
+                let (dx, dy) = (
+                    positions[i].0 - positions[j].0,
+                    positions[i].1 - positions[j].1,
+                );
+                let distance = dx.hypot(dy).max(MIN_DISTANCE);
+                let force = k * k / distance;
+                displacement[i].0 += dx / distance * force;
+                displacement[i].1 += dy / distance * force;
+            }
+        }
+        for &(from, to) in &edges {
+            let (dx, dy) = (
+                positions[from].0 - positions[to].0,
+                positions[from].1 - positions[to].1,
+            );
+            let distance = dx.hypot(dy).max(MIN_DISTANCE);
+            let force = distance * distance / k;
+            displacement[from].0 -= dx / distance * force;
+            displacement[from].1 -= dy / distance * force;
+            displacement[to].0 += dx / distance * force;
+            displacement[to].1 += dy / distance * force;
+        }
+        for i in (0..count).filter(|i| !fixed[*i]) {
+            let (dx, dy) = displacement[i];
+            let length = dx.hypot(dy).max(MIN_DISTANCE);
+            let step = length.min(temperature);
+            positions[i].0 =
+                (positions[i].0 + dx / length * step).clamp(0.0, (area.width - 1) as f64);
+            positions[i].1 =
+                (positions[i].1 + dy / length * step).clamp(0.0, (area.height - 1) as f64);
+        }
+        temperature -= cooling;
+    }
+
+    positions
+        .into_iter()
+        .zip(fixed)
+        .filter(|(_, fixed)| !fixed)
+        .map(|(position, _)| position)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::ChallengeConfig;
+    use crate::game::PathEdge;
+
+    fn game_path(ids: &[&str]) -> GamePath {
+        GamePath {
+            challenges: ids
+                .iter()
+                .map(|id| ChallengeConfig {
+                    id: id.to_string(),
+                    position: None,
+                    ..ChallengeConfig::default()
+                })
+                .collect(),
+            edges: vec![],
+            map: None,
+            ..GamePath::default()
+        }
+    }
+
+    fn positions(game_path: &GamePath, layout: &HashMap<String, Position>) -> Vec<Position> {
+        game_path
+            .challenges
+            .iter()
+            .map(|challenge| challenge.position.unwrap_or_else(|| layout[&challenge.id]))
+            .collect()
+    }
+
+    fn assert_no_overlaps(positions: &[Position]) {
+        let unique: HashSet<_> = positions.iter().collect();
+        assert_eq!(unique.len(), positions.len(), "{:?}", positions);
+    }
+
+    #[test]
+    fn serpentine() {
+        let path = game_path(&["a", "b", "c", "d", "e", "f"]);
+        let layout = MapLayout::Serpentine.layout(&path, 3, 2).unwrap();
+        assert_eq!(
+            positions(&path, &layout),
+            vec![(0, 0), (1, 0), (2, 0), (2, 1), (1, 1), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn grid() {
+        let path = game_path(&["a", "b", "c", "d"]);
+        let layout = MapLayout::Grid.layout(&path, 4, 4).unwrap();
+        assert_eq!(
+            positions(&path, &layout),
+            vec![(1, 1), (3, 1), (1, 3), (3, 3)]
+        );
+    }
+
+    #[test]
+    fn authored_positions_stay_fixed() {
+        let mut path = game_path(&["a", "b", "c", "d"]);
+        path.challenges[0].position = Some((0, 0));
+        path.challenges[2].position = Some((9, 9));
+
+        let layout = MapLayout::Serpentine.layout(&path, 2, 2).unwrap();
+        assert_eq!(layout.len(), 2);
+        assert!(!layout.contains_key("a"));
+        let positions = positions(&path, &layout);
+        assert_eq!(positions[0], (0, 0));
+        assert_eq!(positions[2], (9, 9));
+        assert_no_overlaps(&positions);
+    }
+
+    #[test]
+    fn map_too_small() {
+        let mut path = game_path(&["a", "b", "c"]);
+        path.challenges[0].position = Some((0, 0));
+        assert_eq!(
+            MapLayout::Grid.layout(&path, 2, 1),
+            Err(GameError::MapTooSmall {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn force_directed() {
+        let mut path = game_path(&["a", "b", "c", "d", "e", "f"]);
+        path.challenges[0].position = Some((0, 5));
+        path.edges = vec![
+            PathEdge::new("a", "b"),
+            PathEdge::new("a", "c"),
+            PathEdge::new("b", "d"),
+            PathEdge::new("c", "d"),
+            PathEdge::new("d", "e"),
+            PathEdge::new("a", "f"),
+        ];
+        assert_eq!(MapLayout::for_path(&path), MapLayout::ForceDirected);
+
+        let layout = MapLayout::ForceDirected.layout(&path, 12, 12).unwrap();
+        let positions = positions(&path, &layout);
+        assert_eq!(positions[0], (0, 5));
+        assert_no_overlaps(&positions);
+        assert!(
+            positions
+                .iter()
+                .all(|(x, y)| (0..12).contains(x) && (0..12).contains(y))
+        );
+        assert_eq!(
+            layout,
+            MapLayout::ForceDirected.layout(&path, 12, 12).unwrap()
+        );
+    }
+
+    #[test]
+    fn ring_cells() {
+        assert_eq!(ring((0, 0), 0), vec![(0, 0)]);
+        assert_eq!(ring((0, 0), 1).len(), 8);
+        assert_eq!(ring((0, 0), 2).len(), 16);
+    }
+}
@@ -0,0 +1,20 @@
+//! The map a game path is drawn on.
+pub mod layout;
+
+pub use layout::MapLayout;
+
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct Map {
+    pub background: String,
+    pub width: u32,
+    pub height: u32,
+    /// How challenges without a position are placed, by default chosen by
+    /// the shape of the game path, see [`MapLayout::for_path`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<MapLayout>,
+}
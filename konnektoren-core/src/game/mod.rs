@@ -5,9 +5,13 @@ pub mod game;
 pub mod game_path;
 pub mod game_state;
 pub mod map;
+pub mod path_edge;
+pub mod unlock;
 
 pub use error::*;
 pub use game::Game;
 pub use game_path::GamePath;
 pub use game_state::GameState;
-pub use map::Map;
+pub use map::{Map, MapLayout};
+pub use path_edge::PathEdge;
+pub use unlock::{ChallengeState, LockReason};
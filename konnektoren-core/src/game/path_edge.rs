@@ -0,0 +1,35 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A connection from one challenge of a [`GamePath`](super::GamePath) to a
+/// challenge that may follow it. A challenge with several outgoing edges
+/// branches, one with several incoming edges joins branches again.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct PathEdge {
+    /// Id of the challenge the edge starts at
+    pub from: String,
+    /// Id of the challenge the edge leads to
+    pub to: String,
+}
+
+impl PathEdge {
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        PathEdge {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_edge() {
+        let edge: PathEdge = serde_yaml::from_str("from: a\nto: b\n").unwrap();
+        assert_eq!(edge, PathEdge::new("a", "b"));
+    }
+}
@@ -0,0 +1,245 @@
+//! Unlock rules of the challenges in the game paths.
+//!
+//! A challenge unlocks once the player has enough XP
+//! ([`ChallengeConfig::unlock_points`]), enough stars
+//! ([`ChallengeConfig::unlock_stars`]) and has completed every challenge in
+//! [`ChallengeConfig::requires`]. A challenge counts as completed once it was
+//! finished with at least one star.
+
+use super::Game;
+use crate::Xp;
+use crate::challenges::ChallengeConfig;
+use crate::game::error::{GameError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// The progress of a challenge in a game path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChallengeState {
+    /// At least one unlock rule is not met yet
+    Locked,
+    /// Every unlock rule is met, but the challenge was not completed yet
+    Unlocked,
+    /// The challenge was finished with at least one star
+    Completed,
+}
+
+/// An unlock rule a challenge is still waiting for.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum LockReason {
+    #[error("Insufficient XP: {current} of {required}")]
+    InsufficientXp { required: Xp, current: Xp },
+
+    #[error("Insufficient stars: {current} of {required}")]
+    InsufficientStars { required: u32, current: u32 },
+
+    #[error("Prerequisite not completed: {0}")]
+    MissingPrerequisite(String),
+}
+
+impl Game {
+    /// Returns the most stars earned in any attempt of the challenge, or
+    /// `None` if it was never played.
+    pub fn best_stars(&self, challenge_config_id: &str) -> Option<u32> {
+        self.challenge_history
+            .challenges
+            .iter()
+            .filter(|challenge| challenge.challenge_config.id == challenge_config_id)
+            .map(|challenge| self.calculate_stars(challenge))
+            .max()
+    }
+
+    /// Returns the stars of the best attempt of every played challenge,
+    /// summed up.
+    pub fn total_stars(&self) -> u32 {
+        let mut best: HashMap<&str, u32> = HashMap::new();
+        for challenge in &self.challenge_history.challenges {
+            let stars = self.calculate_stars(challenge);
+            let entry = best
+                .entry(challenge.challenge_config.id.as_str())
+                .or_default();
+            *entry = (*entry).max(stars);
+        }
+        best.values().sum()
+    }
+
+    pub fn is_completed(&self, challenge_config_id: &str) -> bool {
+        self.best_stars(challenge_config_id)
+            .is_some_and(|stars| stars > 0)
+    }
+
+    /// Returns every unlock rule of the challenge that is not met yet. The
+    /// challenge is unlocked if the list is empty.
+    pub fn lock_reasons(&self, challenge_config: &ChallengeConfig) -> Vec<LockReason> {
+        let mut reasons = vec![];
+
+        let required_xp = Xp::try_from(challenge_config.unlock_points).unwrap_or(Xp::MAX);
+        if self.xp < required_xp {
+            reasons.push(LockReason::InsufficientXp {
+                required: required_xp,
+                current: self.xp,
+            });
+        }
+
+        if challenge_config.unlock_stars > 0 {
+            let current = self.total_stars();
+            if current < challenge_config.unlock_stars {
+                reasons.push(LockReason::InsufficientStars {
+                    required: challenge_config.unlock_stars,
+                    current,
+                });
+            }
+        }
+
+        reasons.extend(
+            challenge_config
+                .requires
+                .iter()
+                .filter(|id| !self.is_completed(id))
+                .map(|id| LockReason::MissingPrerequisite(id.clone())),
+        );
+        reasons
+    }
+
+    pub fn challenge_state(&self, challenge_config: &ChallengeConfig) -> ChallengeState {
+        if self.is_completed(&challenge_config.id) {
+            ChallengeState::Completed
+        } else if self.lock_reasons(challenge_config).is_empty() {
+            ChallengeState::Unlocked
+        } else {
+            ChallengeState::Locked
+        }
+    }
+
+    /// Returns the state of every challenge in the game paths by id.
+    pub fn challenge_states(&self) -> HashMap<String, ChallengeState> {
+        self.game_paths
+            .iter()
+            .flat_map(|game_path| game_path.challenges.iter())
+            .map(|config| (config.id.clone(), self.challenge_state(config)))
+            .collect()
+    }
+
+    /// Fails with [`GameError::ChallengeLocked`] if the challenge may not be
+    /// played yet. Completed challenges can always be replayed.
+    pub fn ensure_unlocked(&self, challenge_config: &ChallengeConfig) -> Result<()> {
+        if self.is_completed(&challenge_config.id) {
+            return Ok(());
+        }
+        match self.lock_reasons(challenge_config).into_iter().next() {
+            Some(reason) => Err(GameError::ChallengeLocked {
+                id: challenge_config.id.clone(),
+                reason,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{ChallengeResult, ChallengeType, MultipleChoiceOption};
+
+    fn complete(game: &mut Game, id: &str) {
+        let mut challenge = game.create_challenge(id).unwrap();
+        let ChallengeType::MultipleChoice(dataset) = &challenge.challenge_type else {
+            unreachable!()
+        };
+        challenge.challenge_result = ChallengeResult::MultipleChoice(
+            dataset
+                .questions
+                .iter()
+                .map(|q| MultipleChoiceOption {
+                    id: q.option,
+                    name: String::new(),
+                })
+                .collect(),
+        );
+        game.challenge_history.add_challenge(challenge);
+    }
+
+    fn config(game: &Game, id: &str) -> ChallengeConfig {
+        game.get_challenge_config(id).unwrap()
+    }
+
+    #[test]
+    fn locked_by_xp() {
+        let mut game = Game::default();
+        let konnektoren_2 = config(&game, "konnektoren-2");
+        assert_eq!(
+            game.lock_reasons(&konnektoren_2),
+            vec![LockReason::InsufficientXp {
+                required: 10,
+                current: 0
+            }]
+        );
+        assert_eq!(game.challenge_state(&konnektoren_2), ChallengeState::Locked);
+
+        game.xp = 10;
+        assert!(game.lock_reasons(&konnektoren_2).is_empty());
+        assert_eq!(
+            game.challenge_state(&konnektoren_2),
+            ChallengeState::Unlocked
+        );
+    }
+
+    #[test]
+    fn locked_by_stars_and_prerequisites() {
+        let mut game = Game::default();
+        game.game_paths[0].challenges[0].requires = vec!["konnektoren-2".to_string()];
+        game.game_paths[0].challenges[5].unlock_stars = 3;
+        let konnektoren_1 = config(&game, "konnektoren-1");
+        let articles_1 = config(&game, "articles-1");
+
+        assert_eq!(
+            game.lock_reasons(&konnektoren_1),
+            vec![LockReason::MissingPrerequisite("konnektoren-2".to_string())]
+        );
+        assert_eq!(
+            game.lock_reasons(&articles_1),
+            vec![LockReason::InsufficientStars {
+                required: 3,
+                current: 0
+            }]
+        );
+
+        game.xp = 10;
+        complete(&mut game, "konnektoren-2");
+        assert_eq!(game.best_stars("konnektoren-2"), Some(3));
+        assert_eq!(game.total_stars(), 3);
+        assert!(game.lock_reasons(&konnektoren_1).is_empty());
+        assert!(game.lock_reasons(&articles_1).is_empty());
+    }
+
+    #[test]
+    fn challenge_states() {
+        let mut game = Game::default();
+        complete(&mut game, "konnektoren-1");
+        game.challenge_history
+            .add_challenge(game.create_challenge("articles-1").unwrap());
+
+        let states = game.challenge_states();
+        assert_eq!(states["konnektoren-1"], ChallengeState::Completed);
+        assert_eq!(states["konnektoren-2"], ChallengeState::Locked);
+        assert_eq!(states["articles-1"], ChallengeState::Unlocked);
+        assert_eq!(states.len(), 10);
+    }
+
+    #[test]
+    fn ensure_unlocked() {
+        let mut game = Game::default();
+        let konnektoren_2 = config(&game, "konnektoren-2");
+        let error = game.ensure_unlocked(&konnektoren_2).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Challenge konnektoren-2 is locked: Insufficient XP: 0 of 10"
+        );
+
+        game.xp = 10;
+        complete(&mut game, "konnektoren-2");
+        game.xp = 0;
+        assert!(game.ensure_unlocked(&konnektoren_2).is_ok());
+    }
+}
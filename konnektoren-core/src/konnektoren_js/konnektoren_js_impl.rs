@@ -1,8 +1,9 @@
-use crate::challenges::{Custom, CustomChallengeResult};
+use crate::challenges::{Challenge, Custom, CustomChallengeResult};
 use wasm_bindgen::JsValue;
 
 use super::{
     ChallengeHandler, CommandHandler, EventHandler, I18nHandler, JsExecutor, ResultHandler,
+    ScormHandler,
 };
 
 use web_sys::Window;
@@ -17,6 +18,7 @@ pub struct KonnektorenJs {
     i18n: I18nHandler<'static>,
     js_executor: &'static JsExecutor<'static>,
     result: ResultHandler<'static>,
+    scorm: ScormHandler<'static>,
 }
 
 impl KonnektorenJs {
@@ -47,6 +49,7 @@ impl KonnektorenJs {
         let event = EventHandler::new(static_window);
         let i18n = I18nHandler::new(static_window);
         let result = ResultHandler::new(js_executor);
+        let scorm = ScormHandler::new(js_executor);
 
         Self {
             challenge,
@@ -55,6 +58,7 @@ impl KonnektorenJs {
             i18n,
             js_executor,
             result,
+            scorm,
         }
     }
 
@@ -76,6 +80,16 @@ impl KonnektorenJs {
         self.result.set_result_data(result_data);
     }
 
+    /// Sets the LMS score of a finished challenge for the SCORM runtime
+    /// bridge.
+    ///
+    /// # Arguments
+    ///
+    /// * `challenge` - The finished `Challenge` to be scored.
+    pub fn set_lms_score(&self, challenge: &Challenge) {
+        self.scorm.set_lms_score(challenge);
+    }
+
     /// Exposes a function to send events from JavaScript to Rust.
     ///
     /// # Arguments
@@ -5,6 +5,7 @@ mod i18n;
 mod js_executor;
 mod konnektoren_js_impl;
 mod result;
+mod scorm;
 
 pub const KONNEKTOREN_NAMESPACE: &str = "konnektoren";
 
@@ -15,6 +16,7 @@ pub use i18n::I18nHandler;
 pub use js_executor::JsExecutor;
 pub use konnektoren_js_impl::KonnektorenJs;
 pub use result::ResultHandler;
+pub use scorm::ScormHandler;
 
 #[cfg(target_arch = "wasm32")]
 #[cfg(test)]
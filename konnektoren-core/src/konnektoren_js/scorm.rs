@@ -0,0 +1,71 @@
+use super::JsExecutor;
+use crate::challenges::{Challenge, LmsScore};
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+
+/// The [`LmsScore`] of a challenge together with the SCORM data model values
+/// the runtime bridge of a SCORM package reports.
+#[derive(Debug, Clone, Serialize)]
+struct JsLmsScore {
+    raw: u32,
+    stars: u32,
+    passed: bool,
+    scorm12: Vec<(&'static str, String)>,
+    scorm2004: Vec<(&'static str, String)>,
+}
+
+impl From<LmsScore> for JsLmsScore {
+    fn from(score: LmsScore) -> Self {
+        JsLmsScore {
+            raw: score.raw,
+            stars: score.stars,
+            passed: score.passed,
+            scorm12: score.scorm12(),
+            scorm2004: score.scorm2004(),
+        }
+    }
+}
+
+pub struct ScormHandler<'a> {
+    js_executor: &'a JsExecutor<'a>,
+}
+
+impl<'a> ScormHandler<'a> {
+    pub fn new(js_executor: &'a JsExecutor<'a>) -> Self {
+        Self { js_executor }
+    }
+
+    /// Sets `konnektoren.lmsScore` to the score of the challenge, so the
+    /// player can pass `lmsScore.scorm12` or `lmsScore.scorm2004` to
+    /// `KonnektorenScorm.report`.
+    pub fn set_lms_score(&self, challenge: &Challenge) {
+        let score = JsLmsScore::from(LmsScore::of(challenge));
+        let js_score = serde_wasm_bindgen::to_value(&score).unwrap();
+        let konnektoren_obj = self.js_executor.get_or_create_konnektoren_object();
+        js_sys::Reflect::set(&konnektoren_obj, &JsValue::from_str("lmsScore"), &js_score).unwrap();
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{ChallengeConfig, ChallengeType};
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn test_scorm_handler() {
+        let challenge = Challenge::new(&ChallengeType::default(), &ChallengeConfig::default());
+        let window = web_sys::window().unwrap();
+        let js_executor = JsExecutor::new(&window);
+        let handler = ScormHandler::new(&js_executor);
+        handler.set_lms_score(&challenge);
+
+        let konnektoren_obj = js_executor.get_or_create_konnektoren_object();
+        let score = js_sys::Reflect::get(&konnektoren_obj, &JsValue::from_str("lmsScore")).unwrap();
+        let raw = js_sys::Reflect::get(&score, &JsValue::from_str("raw")).unwrap();
+        assert_eq!(raw.as_f64(), Some(0.0));
+        let scorm2004 = js_sys::Reflect::get(&score, &JsValue::from_str("scorm2004")).unwrap();
+        assert!(js_sys::Array::is_array(&scorm2004));
+    }
+}
@@ -29,6 +29,8 @@ fn test_set_challenge_data() {
         task_ids: None,
         data: serde_json::json!({"key":"value"}),
         package_url: None,
+        level: None,
+        tags: vec![],
     };
     konnektoren_js.set_challenge_data(test_custom.clone());
 
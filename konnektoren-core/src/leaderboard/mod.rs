@@ -0,0 +1,347 @@
+//! Leaderboards rank the players of a game path by their best
+//! [`PerformanceRecord`] in a time window.
+//!
+//! Players are ranked by performance, faster times break ties. Players with
+//! the same performance and time share a rank.
+
+pub mod store;
+pub mod window;
+
+pub use store::{LeaderboardStore, MemoryLeaderboardStore};
+pub use window::LeaderboardWindow;
+
+use crate::challenges::{PerformanceRecord, Timed};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    /// Position of the player, starting at 1
+    pub rank: usize,
+    pub profile_name: String,
+    pub performance_percentage: u8,
+    pub time_milliseconds: u64,
+    /// When the ranked record was made
+    pub date: DateTime<Utc>,
+}
+
+impl From<&PerformanceRecord> for LeaderboardEntry {
+    fn from(record: &PerformanceRecord) -> Self {
+        LeaderboardEntry {
+            rank: 0,
+            profile_name: record.profile_name.clone(),
+            performance_percentage: record.performance_percentage,
+            time_milliseconds: record
+                .elapsed_time()
+                .map(|time| time.num_milliseconds() as u64)
+                .unwrap_or_default(),
+            date: record.date,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub game_path_id: String,
+    pub window: LeaderboardWindow,
+    /// One entry per player, best first
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+/// A page of a leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardPage {
+    /// Number of the page, starting at 0
+    pub page: usize,
+    pub page_size: usize,
+    /// Number of entries on all pages
+    pub total_entries: usize,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl LeaderboardPage {
+    pub fn page_count(&self) -> usize {
+        self.total_entries.div_ceil(self.page_size.max(1))
+    }
+}
+
+impl Leaderboard {
+    /// Ranks the best record of every player on the game path within the
+    /// window that contains `now`. Records of other game paths are ignored.
+    pub fn from_records(
+        game_path_id: &str,
+        window: LeaderboardWindow,
+        now: DateTime<Utc>,
+        records: &[PerformanceRecord],
+    ) -> Self {
+        let mut best: HashMap<&str, &PerformanceRecord> = HashMap::new();
+        for record in records.iter().filter(|record| {
+            record.game_path_id == game_path_id && window.contains(record.date, now)
+        }) {
+            best.entry(record.profile_name.as_str())
+                .and_modify(|current| *current = (*current).min(record))
+                .or_insert(record);
+        }
+
+        let mut ranked: Vec<&PerformanceRecord> = best.into_values().collect();
+        ranked.sort_by(|a, b| a.cmp(b).then_with(|| a.profile_name.cmp(&b.profile_name)));
+
+        let mut entries: Vec<LeaderboardEntry> = Vec::with_capacity(ranked.len());
+        for (index, record) in ranked.into_iter().enumerate() {
+            let mut entry = LeaderboardEntry::from(record);
+            entry.rank = match entries.last() {
+                Some(previous)
+                    if previous.performance_percentage == entry.performance_percentage
+                        && previous.time_milliseconds == entry.time_milliseconds =>
+                {
+                    previous.rank
+                }
+                _ => index + 1,
+            };
+            entries.push(entry);
+        }
+
+        Leaderboard {
+            game_path_id: game_path_id.to_string(),
+            window,
+            entries,
+        }
+    }
+
+    /// Returns the entry of the player, if the player is ranked.
+    pub fn entry(&self, profile_name: &str) -> Option<&LeaderboardEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.profile_name == profile_name)
+    }
+
+    /// Returns the entries of page `page` with `page_size` entries per page.
+    pub fn page(&self, page: usize, page_size: usize) -> LeaderboardPage {
+        let entries = self
+            .entries
+            .iter()
+            .skip(page.saturating_mul(page_size))
+            .take(page_size)
+            .cloned()
+            .collect();
+        LeaderboardPage {
+            page,
+            page_size,
+            total_entries: self.entries.len(),
+            entries,
+        }
+    }
+
+    /// Returns the player's entry together with up to `radius` entries
+    /// ranked directly above and below, or nothing if the player is not
+    /// ranked.
+    pub fn around(&self, profile_name: &str, radius: usize) -> Vec<LeaderboardEntry> {
+        let Some(position) = self
+            .entries
+            .iter()
+            .position(|entry| entry.profile_name == profile_name)
+        else {
+            return vec![];
+        };
+        let start = position.saturating_sub(radius);
+        let end = (position + radius + 1).min(self.entries.len());
+        self.entries[start..end].to_vec()
+    }
+
+    /// Replaces the names of all players but `viewer` with pseudonyms, see
+    /// [`pseudonym`].
+    pub fn pseudonymised(mut self, viewer: Option<&str>, secret: &[u8]) -> Self {
+        for entry in &mut self.entries {
+            if Some(entry.profile_name.as_str()) != viewer {
+                entry.profile_name = pseudonym(&entry.profile_name, secret);
+            }
+        }
+        self
+    }
+}
+
+/// Returns the pseudonym of a player name, keyed with the secret of the
+/// deployment. The secret has to be kept private, otherwise names can be
+/// recovered by hashing candidate names.
+pub fn pseudonym(profile_name: &str, secret: &[u8]) -> String {
+    let digest = hmac_sha256(secret, profile_name.as_bytes());
+    let hex: String = digest[..16]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("Player-{}", hex)
+}
+
+/// HMAC-SHA256 as specified in RFC 2104.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let padded = |pad: u8| block.map(|byte| byte ^ pad);
+    let inner = Sha256::new()
+        .chain_update(padded(0x36))
+        .chain_update(message)
+        .finalize();
+    Sha256::new()
+        .chain_update(padded(0x5c))
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 15, 18, 0, 0).unwrap()
+    }
+
+    fn record(
+        profile_name: &str,
+        performance_percentage: u8,
+        time_milliseconds: u64,
+        date: DateTime<Utc>,
+    ) -> PerformanceRecord {
+        PerformanceRecord {
+            game_path_id: "path".to_string(),
+            profile_name: profile_name.to_string(),
+            challenges_performance: vec![(
+                "challenge".to_string(),
+                performance_percentage,
+                time_milliseconds,
+            )],
+            total_challenges: 1,
+            performance_percentage,
+            date,
+        }
+    }
+
+    fn names(entries: &[LeaderboardEntry]) -> Vec<&str> {
+        entries
+            .iter()
+            .map(|entry| entry.profile_name.as_str())
+            .collect()
+    }
+
+    fn leaderboard() -> Leaderboard {
+        let records: Vec<PerformanceRecord> = ["a", "b", "c", "d", "e", "f"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| record(name, 100 - i as u8 * 10, 1000, now()))
+            .collect();
+        Leaderboard::from_records("path", LeaderboardWindow::AllTime, now(), &records)
+    }
+
+    #[test]
+    fn best_record_per_player() {
+        let records = vec![
+            record("anna", 70, 1000, now()),
+            record("anna", 90, 5000, now()),
+            record("ben", 80, 1000, now()),
+            PerformanceRecord {
+                game_path_id: "other".to_string(),
+                ..record("carl", 100, 1000, now())
+            },
+        ];
+        let leaderboard =
+            Leaderboard::from_records("path", LeaderboardWindow::AllTime, now(), &records);
+        assert_eq!(names(&leaderboard.entries), vec!["anna", "ben"]);
+        assert_eq!(leaderboard.entries[0].performance_percentage, 90);
+        assert_eq!(leaderboard.entries[0].time_milliseconds, 5000);
+    }
+
+    #[test]
+    fn ties_broken_by_time() {
+        let records = vec![
+            record("slow", 90, 3000, now()),
+            record("fast", 90, 1000, now()),
+            record("same", 90, 1000, now()),
+            record("best", 100, 9000, now()),
+        ];
+        let leaderboard =
+            Leaderboard::from_records("path", LeaderboardWindow::AllTime, now(), &records);
+        assert_eq!(
+            names(&leaderboard.entries),
+            vec!["best", "fast", "same", "slow"]
+        );
+        let ranks: Vec<usize> = leaderboard.entries.iter().map(|e| e.rank).collect();
+        assert_eq!(ranks, vec![1, 2, 2, 4]);
+    }
+
+    #[test]
+    fn windows() {
+        let records = vec![
+            record("today", 50, 1000, now()),
+            record("last-week", 60, 1000, now() - Duration::days(7)),
+            record("last-month", 70, 1000, now() - Duration::days(31)),
+        ];
+        let ranked = |window| {
+            let leaderboard = Leaderboard::from_records("path", window, now(), &records);
+            names(&leaderboard.entries)
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ranked(LeaderboardWindow::Weekly), vec!["today"]);
+        assert_eq!(
+            ranked(LeaderboardWindow::Monthly),
+            vec!["last-week", "today"]
+        );
+        assert_eq!(
+            ranked(LeaderboardWindow::AllTime),
+            vec!["last-month", "last-week", "today"]
+        );
+    }
+
+    #[test]
+    fn pagination() {
+        let leaderboard = leaderboard();
+        let page = leaderboard.page(1, 4);
+        assert_eq!(names(&page.entries), vec!["e", "f"]);
+        assert_eq!(page.total_entries, 6);
+        assert_eq!(page.page_count(), 2);
+        assert!(leaderboard.page(2, 4).entries.is_empty());
+        assert!(leaderboard.page(0, 0).entries.is_empty());
+    }
+
+    #[test]
+    fn players_around_me() {
+        let leaderboard = leaderboard();
+        assert_eq!(names(&leaderboard.around("c", 1)), vec!["b", "c", "d"]);
+        assert_eq!(names(&leaderboard.around("a", 2)), vec!["a", "b", "c"]);
+        assert_eq!(names(&leaderboard.around("f", 1)), vec!["e", "f"]);
+        assert!(leaderboard.around("nobody", 1).is_empty());
+    }
+
+    #[test]
+    fn pseudonymised() {
+        let secret = b"deployment secret";
+        let leaderboard = leaderboard().pseudonymised(Some("b"), secret);
+        assert_eq!(leaderboard.entries[0].profile_name, pseudonym("a", secret));
+        assert_eq!(leaderboard.entries[1].profile_name, "b");
+        assert!(leaderboard.entry("a").is_none());
+        assert_eq!(pseudonym("a", secret), pseudonym("a", secret));
+        assert_ne!(pseudonym("a", secret), pseudonym("c", secret));
+        assert_ne!(pseudonym("a", secret), pseudonym("a", b"other secret"));
+        assert_eq!(pseudonym("a", secret).len(), "Player-".len() + 32);
+    }
+
+    #[test]
+    fn hmac_sha256_test_vector() {
+        // RFC 4231, test case 2
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        let hex: String = mac.iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert_eq!(
+            hex,
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
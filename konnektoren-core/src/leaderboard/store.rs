@@ -0,0 +1,105 @@
+use super::{Leaderboard, LeaderboardWindow};
+use crate::challenges::PerformanceRecord;
+use crate::persistence::error::{PersistenceError, Result};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Stores the performance records leaderboards are built from.
+pub trait LeaderboardStore: Send + Sync {
+    fn add_record(&self, record: PerformanceRecord) -> Result<()>;
+    fn records(&self, game_path_id: &str) -> Result<Vec<PerformanceRecord>>;
+
+    /// Builds the leaderboard of a game path for the window that contains
+    /// `now`.
+    fn leaderboard(
+        &self,
+        game_path_id: &str,
+        window: LeaderboardWindow,
+        now: DateTime<Utc>,
+    ) -> Result<Leaderboard> {
+        let records = self.records(game_path_id)?;
+        Ok(Leaderboard::from_records(
+            game_path_id,
+            window,
+            now,
+            &records,
+        ))
+    }
+}
+
+/// Keeps the records in memory, grouped by game path.
+#[derive(Debug, Default)]
+pub struct MemoryLeaderboardStore {
+    records: Mutex<HashMap<String, Vec<PerformanceRecord>>>,
+}
+
+impl MemoryLeaderboardStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl LeaderboardStore for MemoryLeaderboardStore {
+    fn add_record(&self, record: PerformanceRecord) -> Result<()> {
+        let mut records = self.records.lock().map_err(|_| {
+            PersistenceError::AccessError("Failed to lock leaderboard records".to_string())
+        })?;
+        records
+            .entry(record.game_path_id.clone())
+            .or_default()
+            .push(record);
+        Ok(())
+    }
+
+    fn records(&self, game_path_id: &str) -> Result<Vec<PerformanceRecord>> {
+        let records = self.records.lock().map_err(|_| {
+            PersistenceError::AccessError("Failed to lock leaderboard records".to_string())
+        })?;
+        Ok(records.get(game_path_id).cloned().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        game_path_id: &str,
+        profile_name: &str,
+        performance_percentage: u8,
+    ) -> PerformanceRecord {
+        PerformanceRecord {
+            game_path_id: game_path_id.to_string(),
+            profile_name: profile_name.to_string(),
+            performance_percentage,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn records_per_game_path() {
+        let store = MemoryLeaderboardStore::new();
+        store.add_record(record("a", "anna", 80)).unwrap();
+        store.add_record(record("b", "ben", 90)).unwrap();
+        store.add_record(record("a", "ben", 70)).unwrap();
+
+        assert_eq!(store.records("a").unwrap().len(), 2);
+        assert_eq!(store.records("b").unwrap().len(), 1);
+        assert!(store.records("c").unwrap().is_empty());
+    }
+
+    #[test]
+    fn leaderboard_from_store() {
+        let store = MemoryLeaderboardStore::new();
+        store.add_record(record("a", "anna", 80)).unwrap();
+        store.add_record(record("a", "ben", 90)).unwrap();
+
+        let leaderboard = store
+            .leaderboard("a", LeaderboardWindow::AllTime, Utc::now())
+            .unwrap();
+        assert_eq!(leaderboard.game_path_id, "a");
+        assert_eq!(leaderboard.entries[0].profile_name, "ben");
+        assert_eq!(leaderboard.entries[1].profile_name, "anna");
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The time span of the records a leaderboard ranks. Weeks start on Monday
+/// and both weeks and months are calendar periods in UTC.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LeaderboardWindow {
+    /// Records of the current week
+    Weekly,
+    /// Records of the current month
+    Monthly,
+    /// All records
+    #[default]
+    AllTime,
+}
+
+impl LeaderboardWindow {
+    /// Returns when the window that contains `now` started, or `None` for
+    /// all-time leaderboards.
+    pub fn start(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let today = now.date_naive();
+        let first_day = match self {
+            LeaderboardWindow::Weekly => {
+                today - Duration::days(today.weekday().num_days_from_monday() as i64)
+            }
+            LeaderboardWindow::Monthly => today.with_day(1)?,
+            LeaderboardWindow::AllTime => return None,
+        };
+        first_day.and_hms_opt(0, 0, 0).map(|start| start.and_utc())
+    }
+
+    /// Returns true if a record made at `date` counts for the window that
+    /// contains `now`.
+    pub fn contains(&self, date: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        self.start(now).is_none_or(|start| date >= start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn window_start() {
+        // a Wednesday
+        let now = Utc.with_ymd_and_hms(2024, 5, 15, 18, 0, 0).unwrap();
+        assert_eq!(
+            LeaderboardWindow::Weekly.start(now),
+            Some(Utc.with_ymd_and_hms(2024, 5, 13, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            LeaderboardWindow::Monthly.start(now),
+            Some(Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(LeaderboardWindow::AllTime.start(now), None);
+    }
+
+    #[test]
+    fn contains() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 15, 18, 0, 0).unwrap();
+        let last_week = Utc.with_ymd_and_hms(2024, 5, 12, 23, 59, 59).unwrap();
+        assert!(!LeaderboardWindow::Weekly.contains(last_week, now));
+        assert!(LeaderboardWindow::Monthly.contains(last_week, now));
+        assert!(LeaderboardWindow::AllTime.contains(last_week, now));
+    }
+}
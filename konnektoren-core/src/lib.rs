@@ -6,6 +6,7 @@ pub mod controller;
 pub mod error;
 pub mod events;
 pub mod game;
+pub mod leaderboard;
 pub mod persistence;
 pub mod player_profile;
 pub mod session;
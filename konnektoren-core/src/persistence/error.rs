@@ -12,8 +12,17 @@ pub enum PersistenceError {
     #[error("State not found")]
     StateNotFound,
 
+    #[error("Corrupted state file: {0}")]
+    Corrupted(String),
+
     #[error("Access error: {0}")]
     AccessError(String),
+
+    #[error("Profile not found: {0}")]
+    ProfileNotFound(String),
+
+    #[error("Profile already exists: {0}")]
+    ProfileExists(String),
 }
 
 pub type Result<T> = std::result::Result<T, PersistenceError>;
@@ -0,0 +1,545 @@
+use super::{GameStatePersistence, PersistenceFactory, ProfileIndex, ProfileIndexPersistence};
+use crate::game::GameState;
+use crate::persistence::error::{PersistenceError, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// How the game state is encoded in the file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileFormat {
+    #[default]
+    Json,
+    Yaml,
+    MessagePack,
+}
+
+impl FileFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FileFormat::Json => "json",
+            FileFormat::Yaml => "yaml",
+            FileFormat::MessagePack => "msgpack",
+        }
+    }
+
+    fn serialize<T: Serialize>(&self, state: &T) -> Result<Vec<u8>> {
+        match self {
+            FileFormat::Json => serde_json::to_vec(state).map_err(|e| e.to_string()),
+            FileFormat::Yaml => serde_yaml::to_string(state)
+                .map(String::into_bytes)
+                .map_err(|e| e.to_string()),
+            FileFormat::MessagePack => rmp_serde::to_vec_named(state).map_err(|e| e.to_string()),
+        }
+        .map_err(PersistenceError::Serialization)
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, data: &[u8]) -> Result<T> {
+        match self {
+            FileFormat::Json => serde_json::from_slice(data).map_err(|e| e.to_string()),
+            FileFormat::Yaml => serde_yaml::from_slice(data).map_err(|e| e.to_string()),
+            FileFormat::MessagePack => rmp_serde::from_slice(data).map_err(|e| e.to_string()),
+        }
+        .map_err(PersistenceError::Serialization)
+    }
+}
+
+/// Saves the game state, or the [`ProfileIndex`] of a
+/// [`ProfileStore`](super::ProfileStore), to a file.
+///
+/// The state is written to a temporary file first, which is then renamed
+/// over the state file, so a crash never leaves a half written state behind.
+/// Before a save the previous state is kept as a backup (`state.json.1`,
+/// `state.json.2`, ...) with the oldest backup dropped. The checksum of each
+/// file is kept next to it (`state.json.sum`, `state.json.1.sum`, ...), so
+/// the files themselves stay plain JSON, YAML or MessagePack; if the state
+/// file is corrupted, the newest intact backup is loaded instead.
+#[derive(Debug)]
+pub struct FilePersistence {
+    path: PathBuf,
+    format: FileFormat,
+    backups: usize,
+    lock: Mutex<()>,
+}
+
+impl FilePersistence {
+    pub fn new(path: impl Into<PathBuf>, format: FileFormat) -> Self {
+        FilePersistence {
+            path: path.into(),
+            format,
+            backups: 3,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Sets how many backups are kept, none if `0`.
+    pub fn with_backups(mut self, backups: usize) -> Self {
+        self.backups = backups;
+        self
+    }
+
+    /// Creates the persistence of each profile of a
+    /// [`ProfileStore`](super::ProfileStore) as a file named
+    /// `profile-<id>.<extension>` in `dir`. Characters of the id other than
+    /// ASCII letters, digits, `-` and `_` are percent-encoded, so an id can
+    /// neither leave `dir` nor share a file with another id.
+    pub fn factory(dir: impl Into<PathBuf>, format: FileFormat) -> PersistenceFactory {
+        let dir = dir.into();
+        Arc::new(move |id: &str| -> Arc<dyn GameStatePersistence> {
+            Arc::new(FilePersistence::new(
+                dir.join(format!("profile-{}.{}", encode_id(id), format.extension())),
+                format,
+            ))
+        })
+    }
+
+    /// Creates the persistence of the profile index of a
+    /// [`ProfileStore`](super::ProfileStore) as `profiles.<extension>` in
+    /// `dir`, next to the game states created by [`factory`](Self::factory).
+    pub fn profile_index(
+        dir: impl Into<PathBuf>,
+        format: FileFormat,
+    ) -> Arc<dyn ProfileIndexPersistence> {
+        let path = dir.into().join(format!("profiles.{}", format.extension()));
+        Arc::new(FilePersistence::new(path, format))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path of the `index`th backup, the newest being 1.
+    pub fn backup_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", index));
+        PathBuf::from(path)
+    }
+
+    /// Returns the path of the file holding the checksum of the file at
+    /// `path`.
+    pub fn checksum_path(path: &Path) -> PathBuf {
+        let mut path = path.to_path_buf().into_os_string();
+        path.push(".sum");
+        PathBuf::from(path)
+    }
+
+    /// Returns a new temporary path next to the state file, unique so that
+    /// processes sharing the directory never write to the same file.
+    fn temp_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}.tmp", uuid::Uuid::new_v4().simple()));
+        PathBuf::from(path)
+    }
+
+    fn dir(&self) -> &Path {
+        self.path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+    }
+
+    /// Flushes the directory entry of the renamed state file to disk.
+    #[cfg(unix)]
+    fn sync_dir(&self) -> Result<()> {
+        fs::File::open(self.dir())?.sync_all()?;
+        Ok(())
+    }
+
+    /// Directories cannot be opened to be flushed on this platform.
+    #[cfg(not(unix))]
+    fn sync_dir(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Reads the state file at `path` and checks it against its checksum
+    /// file. A missing checksum file counts as corrupted, as the state file
+    /// cannot be checked without it.
+    fn read<T: DeserializeOwned>(&self, path: &Path) -> Result<T> {
+        let data = fs::read(path)?;
+        let corrupted = || PersistenceError::Corrupted(path.display().to_string());
+        let expected = fs::read_to_string(Self::checksum_path(path))
+            .ok()
+            .and_then(|sum| u64::from_str_radix(sum.trim(), 16).ok())
+            .ok_or_else(corrupted)?;
+        if checksum(&data) != expected {
+            return Err(corrupted());
+        }
+        self.format.deserialize(&data)
+    }
+
+    /// Moves the file at `from` and its checksum file to `to`.
+    fn rename_with_checksum(from: &Path, to: &Path) -> Result<()> {
+        let (sum_from, sum_to) = (Self::checksum_path(from), Self::checksum_path(to));
+        if sum_from.exists() {
+            fs::rename(sum_from, sum_to)?;
+        }
+        fs::rename(from, to)?;
+        Ok(())
+    }
+
+    /// Removes the file at `path` and its checksum file, if they exist.
+    fn remove_with_checksum(path: &Path) -> Result<()> {
+        for path in [path.to_path_buf(), Self::checksum_path(path)] {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves each backup one place back and keeps the current state file as
+    /// the newest backup. A corrupted state file is not kept, so it does not
+    /// push out intact backups.
+    fn rotate_backups<T: DeserializeOwned>(&self) -> Result<()> {
+        if self.backups == 0 || self.read::<T>(&self.path).is_err() {
+            return Ok(());
+        }
+        for index in (1..self.backups).rev() {
+            let backup = self.backup_path(index);
+            if backup.exists() {
+                Self::rename_with_checksum(&backup, &self.backup_path(index + 1))?;
+            }
+        }
+        fs::copy(&self.path, self.backup_path(1))?;
+        fs::copy(
+            Self::checksum_path(&self.path),
+            Self::checksum_path(&self.backup_path(1)),
+        )?;
+        Ok(())
+    }
+
+    fn save<T: Serialize + DeserializeOwned>(&self, state: &T) -> Result<()> {
+        let _lock = self
+            .lock
+            .lock()
+            .map_err(|_| PersistenceError::AccessError("Failed to lock state file".to_string()))?;
+        let data = self.format.serialize(state)?;
+        let sum = format!("{:016x}\n", checksum(&data));
+        fs::create_dir_all(self.dir())?;
+
+        // a crash between the two renames leaves a state file that does not
+        // match its checksum, so the backup kept by the rotation is loaded
+        let temp_path = self.temp_path();
+        let written = (|| -> Result<()> {
+            for (path, content) in [
+                (Self::checksum_path(&temp_path), sum.as_bytes()),
+                (temp_path.clone(), data.as_slice()),
+            ] {
+                let mut temp = fs::File::create(path)?;
+                temp.write_all(content)?;
+                temp.sync_all()?;
+            }
+            self.rotate_backups::<T>()?;
+            Self::rename_with_checksum(&temp_path, &self.path)?;
+            Ok(())
+        })();
+        if written.is_err() {
+            let _ = Self::remove_with_checksum(&temp_path);
+        }
+        written?;
+        self.sync_dir()
+    }
+
+    fn load<T: DeserializeOwned>(&self) -> Result<T> {
+        let _lock = self
+            .lock
+            .lock()
+            .map_err(|_| PersistenceError::AccessError("Failed to lock state file".to_string()))?;
+        let candidates = std::iter::once(self.path.clone())
+            .chain((1..=self.backups).map(|i| self.backup_path(i)));
+
+        let mut error = None;
+        for path in candidates.filter(|path| path.exists()) {
+            match self.read(&path) {
+                Ok(state) => {
+                    if path != self.path {
+                        tracing::warn!("Recovered state from backup {}", path.display());
+                    }
+                    return Ok(state);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load state from {}: {}", path.display(), e);
+                    error = error.or(Some(e));
+                }
+            }
+        }
+        Err(error.unwrap_or(PersistenceError::StateNotFound))
+    }
+}
+
+impl GameStatePersistence for FilePersistence {
+    fn save_game_state(&self, state: &GameState) -> Result<()> {
+        self.save(state)
+    }
+
+    fn load_game_state(&self) -> Result<GameState> {
+        self.load()
+    }
+
+    /// Removes the state file, its backups and their checksum files.
+    fn delete_game_state(&self) -> Result<()> {
+        let _lock = self
+            .lock
+            .lock()
+            .map_err(|_| PersistenceError::AccessError("Failed to lock state file".to_string()))?;
+        let paths = std::iter::once(self.path.clone())
+            .chain((1..=self.backups).map(|i| self.backup_path(i)));
+        for path in paths {
+            Self::remove_with_checksum(&path)?;
+        }
+        Ok(())
+    }
+}
+
+impl ProfileIndexPersistence for FilePersistence {
+    fn save_profile_index(&self, index: &ProfileIndex) -> Result<()> {
+        self.save(index)
+    }
+
+    fn load_profile_index(&self) -> Result<ProfileIndex> {
+        self.load()
+    }
+}
+
+/// Percent-encodes every byte of the id that is not an ASCII letter, digit,
+/// `-` or `_`.
+fn encode_id(id: &str) -> String {
+    id.bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// FNV-1a, enough to notice truncated or partly overwritten files.
+fn checksum(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn state(task_index: usize) -> GameState {
+        GameState {
+            current_task_index: task_index,
+            ..GameState::default()
+        }
+    }
+
+    #[test]
+    fn save_and_load_in_all_formats() {
+        let dir = TempDir::new().unwrap();
+        for format in [FileFormat::Json, FileFormat::Yaml, FileFormat::MessagePack] {
+            let path = dir.path().join(format!("state.{}", format.extension()));
+            let persistence = FilePersistence::new(path, format);
+            persistence.save_game_state(&state(2)).unwrap();
+            assert_eq!(persistence.load_game_state().unwrap(), state(2));
+        }
+    }
+
+    #[test]
+    fn state_files_are_plain_documents() {
+        let dir = TempDir::new().unwrap();
+        let json = FilePersistence::new(dir.path().join("state.json"), FileFormat::Json);
+        json.save_game_state(&state(2)).unwrap();
+        let saved: GameState = serde_json::from_slice(&fs::read(json.path()).unwrap()).unwrap();
+        assert_eq!(saved, state(2));
+
+        let yaml = FilePersistence::new(dir.path().join("state.yaml"), FileFormat::Yaml);
+        yaml.save_game_state(&state(3)).unwrap();
+        let saved: GameState = serde_yaml::from_slice(&fs::read(yaml.path()).unwrap()).unwrap();
+        assert_eq!(saved, state(3));
+    }
+
+    #[test]
+    fn missing_checksum_is_corrupted() {
+        let dir = TempDir::new().unwrap();
+        let persistence = FilePersistence::new(dir.path().join("state.json"), FileFormat::Json);
+        persistence.save_game_state(&state(1)).unwrap();
+        fs::remove_file(FilePersistence::checksum_path(persistence.path())).unwrap();
+        assert!(matches!(
+            persistence.load_game_state(),
+            Err(PersistenceError::Corrupted(_))
+        ));
+    }
+
+    #[test]
+    fn missing_state() {
+        let dir = TempDir::new().unwrap();
+        let persistence = FilePersistence::new(dir.path().join("state.json"), FileFormat::Json);
+        assert!(matches!(
+            persistence.load_game_state(),
+            Err(PersistenceError::StateNotFound)
+        ));
+    }
+
+    #[test]
+    fn rolling_backups() {
+        let dir = TempDir::new().unwrap();
+        let persistence =
+            FilePersistence::new(dir.path().join("state.json"), FileFormat::Json).with_backups(2);
+        for task_index in 0..4 {
+            persistence.save_game_state(&state(task_index)).unwrap();
+        }
+
+        assert_eq!(
+            persistence.read::<GameState>(persistence.path()).unwrap(),
+            state(3)
+        );
+        assert_eq!(
+            persistence
+                .read::<GameState>(&persistence.backup_path(1))
+                .unwrap(),
+            state(2)
+        );
+        assert_eq!(
+            persistence
+                .read::<GameState>(&persistence.backup_path(2))
+                .unwrap(),
+            state(1)
+        );
+        assert!(!persistence.backup_path(3).exists());
+        let files = fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(files, 6, "no temporary file is left behind");
+    }
+
+    #[test]
+    fn recover_from_backup() {
+        let dir = TempDir::new().unwrap();
+        let persistence = FilePersistence::new(dir.path().join("state.json"), FileFormat::Json);
+        persistence.save_game_state(&state(1)).unwrap();
+        persistence.save_game_state(&state(2)).unwrap();
+
+        let mut file = fs::read(persistence.path()).unwrap();
+        let last = file.len() - 2;
+        file[last] ^= 0xff;
+        fs::write(persistence.path(), file).unwrap();
+
+        assert!(matches!(
+            persistence.read::<GameState>(persistence.path()),
+            Err(PersistenceError::Corrupted(_))
+        ));
+        assert_eq!(persistence.load_game_state().unwrap(), state(1));
+
+        // the corrupted file does not replace the intact backup
+        persistence.save_game_state(&state(3)).unwrap();
+        assert_eq!(
+            persistence
+                .read::<GameState>(&persistence.backup_path(1))
+                .unwrap(),
+            state(1)
+        );
+    }
+
+    #[test]
+    fn all_files_corrupted() {
+        let dir = TempDir::new().unwrap();
+        let persistence = FilePersistence::new(dir.path().join("state.json"), FileFormat::Json);
+        fs::write(persistence.path(), "not a state").unwrap();
+        assert!(matches!(
+            persistence.load_game_state(),
+            Err(PersistenceError::Corrupted(_))
+        ));
+    }
+
+    #[test]
+    fn corrupted_names_the_file_read() {
+        let dir = TempDir::new().unwrap();
+        let persistence = FilePersistence::new(dir.path().join("state.json"), FileFormat::Json);
+        fs::write(persistence.backup_path(1), "not a state").unwrap();
+        match persistence.read::<GameState>(&persistence.backup_path(1)) {
+            Err(PersistenceError::Corrupted(path)) => {
+                assert_eq!(path, persistence.backup_path(1).display().to_string())
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn delete_state_and_backups() {
+        let dir = TempDir::new().unwrap();
+        let persistence = FilePersistence::new(dir.path().join("state.json"), FileFormat::Json);
+        persistence.save_game_state(&state(1)).unwrap();
+        persistence.save_game_state(&state(2)).unwrap();
+        persistence.delete_game_state().unwrap();
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn profile_store_with_files() {
+        use crate::persistence::ProfileStore;
+
+        let dir = TempDir::new().unwrap();
+        let open = || {
+            ProfileStore::new(
+                FilePersistence::profile_index(dir.path(), FileFormat::Yaml),
+                FilePersistence::factory(dir.path(), FileFormat::Yaml),
+            )
+            .unwrap()
+        };
+        let mut store = open();
+        let anna = store.create_profile("Anna").unwrap();
+        let mut session = store.load_session(&anna.id).unwrap();
+        session.game_state.current_task_index = 4;
+        session.player_profile.xp = 25;
+        store.save_session(&session).unwrap();
+
+        assert!(dir.path().join("profiles.yaml").exists());
+        assert!(
+            dir.path()
+                .join(format!("profile-{}.yaml", anna.id))
+                .exists()
+        );
+        let mut restored = open();
+        assert_eq!(restored.active_profile().unwrap().name, "Anna");
+        let session = restored.load_session(&anna.id).unwrap();
+        assert_eq!(session.player_profile.xp, 25);
+        assert_eq!(session.game_state.current_task_index, 4);
+    }
+
+    #[test]
+    fn factory_encodes_profile_ids() {
+        let dir = TempDir::new().unwrap();
+        let factory = FilePersistence::factory(dir.path().join("profiles"), FileFormat::Json);
+        factory("../evil").save_game_state(&state(1)).unwrap();
+        factory("profiles").save_game_state(&state(2)).unwrap();
+
+        assert!(
+            dir.path()
+                .join("profiles")
+                .join("profile-%2E%2E%2Fevil.json")
+                .exists()
+        );
+        assert!(!dir.path().join("evil.json").exists());
+        assert_eq!(factory("../evil").load_game_state().unwrap(), state(1));
+        assert_eq!(factory("profiles").load_game_state().unwrap(), state(2));
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        let dir = TempDir::new().unwrap();
+        let persistence = Arc::new(FilePersistence::new(
+            dir.path().join("state.msgpack"),
+            FileFormat::MessagePack,
+        ));
+        let handles: Vec<_> = (0..4)
+            .map(|task_index| {
+                let persistence = persistence.clone();
+                std::thread::spawn(move || persistence.save_game_state(&state(task_index)))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+        assert!(persistence.load_game_state().unwrap().current_task_index < 4);
+    }
+}
@@ -4,4 +4,10 @@ use crate::persistence::error::Result;
 pub trait GameStatePersistence: Send + Sync {
     fn save_game_state(&self, state: &GameState) -> Result<()>;
     fn load_game_state(&self) -> Result<GameState>;
+
+    /// Removes the saved game state. Persistences that cannot remove it
+    /// overwrite it with a new game state.
+    fn delete_game_state(&self) -> Result<()> {
+        self.save_game_state(&GameState::default())
+    }
 }
@@ -1,43 +1,39 @@
 use super::GameStatePersistence;
 use crate::game::GameState;
 use crate::persistence::error::{PersistenceError, Result};
-use std::cell::RefCell;
-use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Default)]
 pub struct MemoryPersistence {
-    game_state: Rc<RefCell<GameState>>,
+    game_state: Arc<Mutex<GameState>>,
 }
 
-unsafe impl Send for MemoryPersistence {}
-unsafe impl Sync for MemoryPersistence {}
-
 impl MemoryPersistence {
     pub fn new(game_state: GameState) -> Self {
         MemoryPersistence {
-            game_state: Rc::new(RefCell::new(game_state)),
+            game_state: Arc::new(Mutex::new(game_state)),
         }
     }
 }
 
 impl GameStatePersistence for MemoryPersistence {
     fn save_game_state(&self, state: &GameState) -> Result<()> {
-        match self.game_state.try_borrow_mut() {
+        match self.game_state.lock() {
             Ok(mut gs) => {
                 *gs = state.clone();
                 Ok(())
             }
             Err(_) => Err(PersistenceError::AccessError(
-                "Failed to get mutable borrow of game state".to_string(),
+                "Failed to lock game state".to_string(),
             )),
         }
     }
 
     fn load_game_state(&self) -> Result<GameState> {
         self.game_state
-            .try_borrow()
+            .lock()
             .map(|gs| gs.clone())
-            .map_err(|_| PersistenceError::AccessError("Failed to borrow game state".to_string()))
+            .map_err(|_| PersistenceError::AccessError("Failed to lock game state".to_string()))
     }
 }
 
@@ -67,12 +63,35 @@ mod tests {
     }
 
     #[test]
-    fn test_concurrent_access_errors() {
+    fn test_shared_between_threads() {
+        let persistence = Arc::new(MemoryPersistence::default());
+        let state = GameState {
+            current_task_index: 2,
+            ..GameState::default()
+        };
+
+        let writer = persistence.clone();
+        let saved = state.clone();
+        std::thread::spawn(move || writer.save_game_state(&saved))
+            .join()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(persistence.load_game_state().unwrap(), state);
+    }
+
+    #[test]
+    fn test_poisoned_lock_errors() {
         let persistence = MemoryPersistence::default();
         let state = GameState::default();
 
-        // Create a borrow that will cause subsequent borrows to fail
-        let _borrow = persistence.game_state.borrow_mut();
+        // Poison the lock by panicking while holding it
+        let game_state = persistence.game_state.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = game_state.lock().unwrap();
+            panic!("poison the lock");
+        })
+        .join();
 
         // This should fail with an AccessError
         let save_result = persistence.save_game_state(&state);
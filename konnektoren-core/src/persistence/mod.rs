@@ -1,7 +1,13 @@
 pub mod error;
+pub mod file_persistence;
 pub mod game_state_persistence;
 pub mod memory_persistence;
+pub mod profile_index_persistence;
+pub mod profile_store;
 
 pub use error::*;
+pub use file_persistence::{FileFormat, FilePersistence};
 pub use game_state_persistence::GameStatePersistence;
 pub use memory_persistence::MemoryPersistence;
+pub use profile_index_persistence::{MemoryProfileIndex, ProfileIndex, ProfileIndexPersistence};
+pub use profile_store::{PersistenceFactory, ProfileStore};
@@ -0,0 +1,67 @@
+use crate::persistence::error::{PersistenceError, Result};
+use crate::player_profile::PlayerProfile;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// The profiles of a [`ProfileStore`](super::ProfileStore) and the one that
+/// is active.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileIndex {
+    pub profiles: Vec<PlayerProfile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+}
+
+/// Saves the profile index of a [`ProfileStore`](super::ProfileStore), so
+/// the names, XP, streaks and daily goals of the profiles are kept next to
+/// their game states.
+pub trait ProfileIndexPersistence: Send + Sync {
+    fn save_profile_index(&self, index: &ProfileIndex) -> Result<()>;
+
+    /// Fails with [`PersistenceError::StateNotFound`] if no index was saved.
+    fn load_profile_index(&self) -> Result<ProfileIndex>;
+}
+
+/// Keeps the profile index in memory.
+#[derive(Debug, Default)]
+pub struct MemoryProfileIndex {
+    index: Mutex<Option<ProfileIndex>>,
+}
+
+impl ProfileIndexPersistence for MemoryProfileIndex {
+    fn save_profile_index(&self, index: &ProfileIndex) -> Result<()> {
+        let mut saved = self.index.lock().map_err(|_| {
+            PersistenceError::AccessError("Failed to lock profile index".to_string())
+        })?;
+        *saved = Some(index.clone());
+        Ok(())
+    }
+
+    fn load_profile_index(&self) -> Result<ProfileIndex> {
+        let saved = self.index.lock().map_err(|_| {
+            PersistenceError::AccessError("Failed to lock profile index".to_string())
+        })?;
+        saved.clone().ok_or(PersistenceError::StateNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_load() {
+        let persistence = MemoryProfileIndex::default();
+        assert!(matches!(
+            persistence.load_profile_index(),
+            Err(PersistenceError::StateNotFound)
+        ));
+
+        let index = ProfileIndex {
+            profiles: vec![PlayerProfile::new("123".to_string())],
+            active: Some("123".to_string()),
+        };
+        persistence.save_profile_index(&index).unwrap();
+        assert_eq!(persistence.load_profile_index().unwrap(), index);
+    }
+}
@@ -0,0 +1,346 @@
+use super::{
+    GameStatePersistence, MemoryPersistence, MemoryProfileIndex, ProfileIndex,
+    ProfileIndexPersistence,
+};
+use crate::game::GameState;
+use crate::persistence::error::{PersistenceError, Result};
+use crate::player_profile::PlayerProfile;
+use crate::session::Session;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Creates the persistence that holds the game state of the profile with
+/// the given id.
+pub type PersistenceFactory = Arc<dyn Fn(&str) -> Arc<dyn GameStatePersistence> + Send + Sync>;
+
+/// Manages several player profiles on one device, each with its own game
+/// state and challenge history.
+///
+/// Every profile gets its own [`GameStatePersistence`] from the factory, so
+/// the state of one profile is saved and loaded independently of the
+/// others. The profiles themselves are saved in the [`ProfileIndex`] after
+/// every change. One profile is active at a time; switching saves the
+/// session of the active profile and loads the session of the next one.
+pub struct ProfileStore {
+    profiles: Vec<PlayerProfile>,
+    active: Option<String>,
+    index: Arc<dyn ProfileIndexPersistence>,
+    persistence: HashMap<String, Arc<dyn GameStatePersistence>>,
+    persistence_factory: PersistenceFactory,
+}
+
+impl ProfileStore {
+    /// Opens the store with the profiles saved in the index, if any.
+    pub fn new(
+        index: Arc<dyn ProfileIndexPersistence>,
+        persistence_factory: PersistenceFactory,
+    ) -> Result<Self> {
+        let saved = match index.load_profile_index() {
+            Err(PersistenceError::StateNotFound) => ProfileIndex::default(),
+            saved => saved?,
+        };
+        Ok(ProfileStore {
+            profiles: saved.profiles,
+            active: saved.active,
+            index,
+            persistence: HashMap::new(),
+            persistence_factory,
+        })
+    }
+
+    /// Keeps the profiles and game states in memory only.
+    pub fn in_memory() -> Self {
+        ProfileStore {
+            profiles: vec![],
+            active: None,
+            index: Arc::new(MemoryProfileIndex::default()),
+            persistence: HashMap::new(),
+            persistence_factory: Arc::new(memory_persistence),
+        }
+    }
+
+    pub fn profiles(&self) -> &[PlayerProfile] {
+        &self.profiles
+    }
+
+    pub fn profile(&self, id: &str) -> Option<&PlayerProfile> {
+        self.profiles.iter().find(|profile| profile.id == id)
+    }
+
+    pub fn active_profile(&self) -> Option<&PlayerProfile> {
+        self.active.as_deref().and_then(|id| self.profile(id))
+    }
+
+    /// Returns the persistence of the profile's game state, e.g. to create a
+    /// [`GameController`](crate::controller::GameController) for it.
+    pub fn persistence(&mut self, id: &str) -> Result<Arc<dyn GameStatePersistence>> {
+        if self.profile(id).is_none() {
+            return Err(PersistenceError::ProfileNotFound(id.to_string()));
+        }
+        let factory = &self.persistence_factory;
+        Ok(self
+            .persistence
+            .entry(id.to_string())
+            .or_insert_with(|| factory(id))
+            .clone())
+    }
+
+    /// Creates a profile with a new game state. The first profile becomes
+    /// active. If the game state or the index cannot be saved, the store is
+    /// left as it was.
+    pub fn create_profile(&mut self, name: &str) -> Result<PlayerProfile> {
+        self.check_name_available(name)?;
+        let profile = PlayerProfile {
+            name: name.to_string(),
+            ..PlayerProfile::new(uuid::Uuid::new_v4().to_string())
+        };
+        let persistence = (self.persistence_factory)(&profile.id);
+        persistence.save_game_state(&GameState::default())?;
+
+        let activated = self.active.is_none();
+        self.profiles.push(profile.clone());
+        if activated {
+            self.active = Some(profile.id.clone());
+        }
+        if let Err(e) = self.save_index() {
+            self.profiles.pop();
+            if activated {
+                self.active = None;
+            }
+            if let Err(e) = persistence.delete_game_state() {
+                tracing::warn!("Failed to delete game state of {}: {}", profile.id, e);
+            }
+            return Err(e);
+        }
+        self.persistence.insert(profile.id.clone(), persistence);
+        Ok(profile)
+    }
+
+    pub fn rename_profile(&mut self, id: &str, name: &str) -> Result<()> {
+        if self.profile(id).is_some_and(|profile| profile.name == name) {
+            return Ok(());
+        }
+        self.check_name_available(name)?;
+        let profile = self
+            .profiles
+            .iter_mut()
+            .find(|profile| profile.id == id)
+            .ok_or_else(|| PersistenceError::ProfileNotFound(id.to_string()))?;
+        profile.name = name.to_string();
+        self.save_index()
+    }
+
+    /// Deletes the profile and its game state. If the profile was active,
+    /// the first remaining profile becomes active.
+    pub fn delete_profile(&mut self, id: &str) -> Result<PlayerProfile> {
+        let position = self
+            .profiles
+            .iter()
+            .position(|profile| profile.id == id)
+            .ok_or_else(|| PersistenceError::ProfileNotFound(id.to_string()))?;
+        self.persistence(id)?.delete_game_state()?;
+        self.persistence.remove(id);
+        let profile = self.profiles.remove(position);
+        if self.active.as_deref() == Some(id) {
+            self.active = self.profiles.first().map(|profile| profile.id.clone());
+        }
+        self.save_index()?;
+        Ok(profile)
+    }
+
+    /// Loads the session of a profile with its saved game state.
+    pub fn load_session(&mut self, id: &str) -> Result<Session> {
+        let profile = self
+            .profile(id)
+            .cloned()
+            .ok_or_else(|| PersistenceError::ProfileNotFound(id.to_string()))?;
+        let game_state = match self.persistence(id)?.load_game_state() {
+            Err(PersistenceError::StateNotFound) => GameState::default(),
+            game_state => game_state?,
+        };
+        Ok(Session {
+            game_state,
+            ..Session::new_with_profile(profile)
+        })
+    }
+
+    /// Saves the profile and game state of a session.
+    pub fn save_session(&mut self, session: &Session) -> Result<()> {
+        let id = session.player_profile.id.as_str();
+        let profile = self
+            .profiles
+            .iter_mut()
+            .find(|profile| profile.id == id)
+            .ok_or_else(|| PersistenceError::ProfileNotFound(id.to_string()))?;
+        *profile = session.player_profile.clone();
+        self.save_index()?;
+        self.persistence(id)?.save_game_state(&session.game_state)
+    }
+
+    /// Saves the session of the active profile and returns the session of
+    /// the profile with the given id, which becomes active.
+    pub fn switch_profile(&mut self, current: &Session, id: &str) -> Result<Session> {
+        if self.profile(id).is_none() {
+            return Err(PersistenceError::ProfileNotFound(id.to_string()));
+        }
+        if self.active.as_deref() == Some(current.player_profile.id.as_str()) {
+            self.save_session(current)?;
+        }
+        let session = self.load_session(id)?;
+        self.active = Some(id.to_string());
+        self.save_index()?;
+        Ok(session)
+    }
+
+    fn save_index(&self) -> Result<()> {
+        self.index.save_profile_index(&ProfileIndex {
+            profiles: self.profiles.clone(),
+            active: self.active.clone(),
+        })
+    }
+
+    fn check_name_available(&self, name: &str) -> Result<()> {
+        if self.profiles.iter().any(|profile| profile.name == name) {
+            return Err(PersistenceError::ProfileExists(name.to_string()));
+        }
+        Ok(())
+    }
+}
+
+fn memory_persistence(_id: &str) -> Arc<dyn GameStatePersistence> {
+    Arc::new(MemoryPersistence::default())
+}
+
+impl Default for ProfileStore {
+    fn default() -> Self {
+        ProfileStore::in_memory()
+    }
+}
+
+impl fmt::Debug for ProfileStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProfileStore")
+            .field("profiles", &self.profiles)
+            .field("active", &self.active)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_profiles() {
+        let mut store = ProfileStore::in_memory();
+        let anna = store.create_profile("Anna").unwrap();
+        let ben = store.create_profile("Ben").unwrap();
+
+        assert_ne!(anna.id, ben.id);
+        assert_eq!(store.profiles().len(), 2);
+        assert_eq!(store.active_profile(), Some(&anna));
+        assert!(matches!(
+            store.create_profile("Anna"),
+            Err(PersistenceError::ProfileExists(_))
+        ));
+    }
+
+    #[test]
+    fn create_profile_rolls_back_on_failed_save() {
+        struct FailingIndex;
+
+        impl ProfileIndexPersistence for FailingIndex {
+            fn save_profile_index(&self, _: &ProfileIndex) -> Result<()> {
+                Err(PersistenceError::AccessError("read-only".to_string()))
+            }
+
+            fn load_profile_index(&self) -> Result<ProfileIndex> {
+                Err(PersistenceError::StateNotFound)
+            }
+        }
+
+        let mut store =
+            ProfileStore::new(Arc::new(FailingIndex), Arc::new(memory_persistence)).unwrap();
+        assert!(matches!(
+            store.create_profile("Anna"),
+            Err(PersistenceError::AccessError(_))
+        ));
+        assert!(store.profiles().is_empty());
+        assert_eq!(store.active_profile(), None);
+        assert!(store.persistence.is_empty());
+    }
+
+    #[test]
+    fn rename_profile() {
+        let mut store = ProfileStore::in_memory();
+        let anna = store.create_profile("Anna").unwrap();
+        store.create_profile("Ben").unwrap();
+
+        store.rename_profile(&anna.id, "Annika").unwrap();
+        assert_eq!(store.profile(&anna.id).unwrap().name, "Annika");
+        store.rename_profile(&anna.id, "Annika").unwrap();
+        assert!(matches!(
+            store.rename_profile(&anna.id, "Ben"),
+            Err(PersistenceError::ProfileExists(_))
+        ));
+        assert!(matches!(
+            store.rename_profile("unknown", "Carl"),
+            Err(PersistenceError::ProfileNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn delete_active_profile() {
+        let mut store = ProfileStore::in_memory();
+        let anna = store.create_profile("Anna").unwrap();
+        let ben = store.create_profile("Ben").unwrap();
+
+        assert_eq!(store.delete_profile(&anna.id).unwrap(), anna);
+        assert_eq!(store.active_profile(), Some(&ben));
+        store.delete_profile(&ben.id).unwrap();
+        assert_eq!(store.active_profile(), None);
+        assert!(store.delete_profile(&ben.id).is_err());
+    }
+
+    #[test]
+    fn switch_keeps_game_states_apart() {
+        let mut store = ProfileStore::in_memory();
+        let anna = store.create_profile("Anna").unwrap();
+        let ben = store.create_profile("Ben").unwrap();
+
+        let mut session = store.load_session(&anna.id).unwrap();
+        session.game_state.current_task_index = 3;
+        session.player_profile.xp = 20;
+
+        let session = store.switch_profile(&session, &ben.id).unwrap();
+        assert_eq!(store.active_profile().unwrap().id, ben.id);
+        assert_eq!(session.player_profile.name, "Ben");
+        assert_eq!(session.game_state.current_task_index, 0);
+
+        let session = store.switch_profile(&session, &anna.id).unwrap();
+        assert_eq!(session.id, anna.id);
+        assert_eq!(session.player_profile.xp, 20);
+        assert_eq!(session.game_state.current_task_index, 3);
+    }
+
+    #[test]
+    fn restore_profiles() {
+        let index: Arc<dyn ProfileIndexPersistence> = Arc::new(MemoryProfileIndex::default());
+        let mut store = ProfileStore::new(index.clone(), Arc::new(memory_persistence)).unwrap();
+        assert!(store.profiles().is_empty());
+        let anna = store.create_profile("Anna").unwrap();
+        let ben = store.create_profile("Ben").unwrap();
+
+        let mut session = store.load_session(&anna.id).unwrap();
+        session.player_profile.xp = 30;
+        store.switch_profile(&session, &ben.id).unwrap();
+
+        let mut restored = ProfileStore::new(index, Arc::new(memory_persistence)).unwrap();
+        assert_eq!(restored.profiles().len(), 2);
+        assert_eq!(restored.active_profile().unwrap().id, ben.id);
+        assert_eq!(restored.profile(&anna.id).unwrap().xp, 30);
+        let session = restored.load_session(&ben.id).unwrap();
+        assert_eq!(session.game_state, GameState::default());
+    }
+}
@@ -0,0 +1,85 @@
+use super::streak::day_of;
+use crate::Xp;
+use crate::game::Game;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// What the player wants to reach every day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DailyGoal {
+    /// Earn this much XP
+    Xp(Xp),
+    /// Answer this many tasks
+    Tasks(u32),
+}
+
+/// What the player reached on one day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DailyProgress {
+    pub xp: Xp,
+    pub tasks: u32,
+}
+
+impl DailyProgress {
+    /// Sums up the XP and answered tasks of the challenges finished on `day`
+    /// in a time zone `utc_offset_minutes` ahead of UTC.
+    pub fn of_day(game: &Game, day: NaiveDate, utc_offset_minutes: i32) -> Self {
+        game.challenge_history
+            .challenges
+            .iter()
+            .filter(|challenge| {
+                challenge
+                    .end_time
+                    .is_some_and(|end_time| day_of(end_time, utc_offset_minutes) == day)
+            })
+            .fold(DailyProgress::default(), |progress, challenge| {
+                DailyProgress {
+                    xp: progress.xp + game.calculate_xp_reward(challenge),
+                    tasks: progress.tasks + challenge.challenge_result.len() as u32,
+                }
+            })
+    }
+}
+
+impl DailyGoal {
+    pub fn target(&self) -> u32 {
+        match self {
+            DailyGoal::Xp(xp) => *xp,
+            DailyGoal::Tasks(tasks) => *tasks,
+        }
+    }
+
+    /// Returns how far the progress is towards the goal.
+    pub fn reached(&self, progress: &DailyProgress) -> u32 {
+        match self {
+            DailyGoal::Xp(_) => progress.xp,
+            DailyGoal::Tasks(_) => progress.tasks,
+        }
+    }
+
+    pub fn is_met(&self, progress: &DailyProgress) -> bool {
+        self.reached(progress) >= self.target()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goals() {
+        let progress = DailyProgress { xp: 40, tasks: 12 };
+        assert!(!DailyGoal::Xp(50).is_met(&progress));
+        assert!(DailyGoal::Tasks(10).is_met(&progress));
+        assert_eq!(DailyGoal::Xp(50).reached(&progress), 40);
+    }
+
+    #[test]
+    fn deserialize_goal() {
+        let goal: DailyGoal = serde_yaml::from_str("!xp 50").unwrap();
+        assert_eq!(goal, DailyGoal::Xp(50));
+        let goal: DailyGoal = serde_yaml::from_str("!tasks 10").unwrap();
+        assert_eq!(goal, DailyGoal::Tasks(10));
+    }
+}
@@ -0,0 +1,148 @@
+//! Player profile module.
+
+pub mod daily_goal;
+pub mod streak;
+
+pub use daily_goal::{DailyGoal, DailyProgress};
+pub use streak::Streak;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::Xp;
+use crate::game::Game;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerProfile {
+    pub id: String,
+    pub name: String,
+    pub xp: Xp,
+    /// Offset of the player's time zone from UTC in minutes, so days start
+    /// at the player's midnight. The offset is fixed and does not follow
+    /// daylight saving time, so clients update it when the player's offset
+    /// changes
+    #[serde(default)]
+    pub utc_offset_minutes: i32,
+    #[serde(default)]
+    pub streak: Streak,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_goal: Option<DailyGoal>,
+}
+
+impl PlayerProfile {
+    pub fn new(id: String) -> Self {
+        PlayerProfile {
+            id,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the calendar day of `time` for the player.
+    pub fn day_of(&self, time: DateTime<Utc>) -> NaiveDate {
+        streak::day_of(time, self.utc_offset_minutes)
+    }
+
+    /// Updates the streak from the finished challenges of the game, see
+    /// [`Session::finish_challenge`](crate::session::Session::finish_challenge).
+    pub fn update_streak(&mut self, game: &Game, now: DateTime<Utc>) {
+        let active_days = streak::active_days(&game.challenge_history, self.utc_offset_minutes);
+        let today = self.day_of(now);
+        self.streak.update(&active_days, today);
+    }
+
+    /// Returns the XP and tasks of the challenges finished today.
+    pub fn daily_progress(&self, game: &Game, now: DateTime<Utc>) -> DailyProgress {
+        DailyProgress::of_day(game, self.day_of(now), self.utc_offset_minutes)
+    }
+
+    /// Returns `true` if the player set a daily goal and reached it today.
+    pub fn daily_goal_met(&self, game: &Game, now: DateTime<Utc>) -> bool {
+        self.daily_goal
+            .is_some_and(|goal| goal.is_met(&self.daily_progress(game, now)))
+    }
+}
+
+impl Default for PlayerProfile {
+    fn default() -> Self {
+        let mut generator = names::Generator::default();
+        let name = generator.next().unwrap();
+
+        PlayerProfile {
+            id: "".to_string(),
+            name,
+            xp: 0,
+            utc_offset_minutes: 0,
+            streak: Streak::default(),
+            daily_goal: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{ChallengeResult, ChallengeType, MultipleChoiceOption};
+    use chrono::{Duration, TimeZone};
+
+    fn finish(game: &mut Game, id: &str, end_time: DateTime<Utc>) {
+        let mut challenge = game.create_challenge(id).unwrap();
+        let ChallengeType::MultipleChoice(dataset) = &challenge.challenge_type else {
+            unreachable!()
+        };
+        challenge.challenge_result = ChallengeResult::MultipleChoice(
+            dataset
+                .questions
+                .iter()
+                .map(|q| MultipleChoiceOption {
+                    id: q.option,
+                    name: String::new(),
+                })
+                .collect(),
+        );
+        challenge.start_time = Some(end_time - Duration::minutes(5));
+        challenge.end_time = Some(end_time);
+        game.challenge_history.add_challenge(challenge);
+    }
+
+    #[test]
+    fn new_profile() {
+        let id = "123".to_string();
+        let profile = PlayerProfile::new(id.clone());
+        assert_eq!(profile.id, id);
+    }
+
+    #[test]
+    fn streak_and_daily_goal() {
+        let mut game = Game::default();
+        let now = Utc.with_ymd_and_hms(2024, 3, 3, 22, 30, 0).unwrap();
+        finish(&mut game, "konnektoren-1", now - Duration::days(2));
+        finish(&mut game, "konnektoren-1", now - Duration::days(1));
+        finish(&mut game, "konnektoren-1", now);
+
+        let mut profile = PlayerProfile {
+            daily_goal: Some(DailyGoal::Tasks(5)),
+            ..PlayerProfile::new("123".to_string())
+        };
+        profile.update_streak(&game, now);
+        assert_eq!(profile.streak.current, 3);
+        assert!(profile.daily_goal_met(&game, now));
+        assert_eq!(profile.daily_progress(&game, now).tasks, 10);
+
+        // 22:30 UTC is already the next day two hours ahead of UTC
+        profile.utc_offset_minutes = 120;
+        assert_eq!(
+            profile.day_of(now),
+            NaiveDate::from_ymd_opt(2024, 3, 4).unwrap()
+        );
+        profile.update_streak(&game, now);
+        assert_eq!(profile.streak.current, 3);
+    }
+
+    #[test]
+    fn deserialize_old_profile() {
+        let profile: PlayerProfile =
+            serde_json::from_str(r#"{"id":"123","name":"Anna","xp":10}"#).unwrap();
+        assert_eq!(profile.streak, Streak::default());
+        assert_eq!(profile.daily_goal, None);
+    }
+}
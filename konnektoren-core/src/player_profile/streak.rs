@@ -0,0 +1,189 @@
+//! Learning streaks: the days in a row on which the player finished at least
+//! one challenge.
+//!
+//! Days are calendar days in the time zone of the player. A missed day breaks
+//! the streak unless a freeze token covers it; frozen days keep the streak
+//! alive without making it longer.
+
+use crate::challenges::ChallengeHistory;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Streak {
+    /// Active days of the streak that is still going, up to today or
+    /// yesterday
+    pub current: u32,
+    /// Active days of the longest streak so far
+    pub longest: u32,
+    /// Tokens left to cover missed days
+    pub freeze_tokens: u32,
+    /// The days that were covered by a freeze token
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub frozen_days: Vec<NaiveDate>,
+}
+
+impl Streak {
+    /// Updates the streak from the days the player was active on, as of
+    /// `today`. The days missed since the last active or frozen day are
+    /// covered with freeze tokens if enough are left.
+    pub fn update(&mut self, active_days: &BTreeSet<NaiveDate>, today: NaiveDate) {
+        let last_covered = active_days
+            .iter()
+            .chain(self.frozen_days.iter())
+            .filter(|day| **day < today)
+            .max()
+            .copied();
+        if let Some(last_covered) = last_covered {
+            let missed: Vec<NaiveDate> = last_covered
+                .iter_days()
+                .skip(1)
+                .take_while(|day| *day < today)
+                .collect();
+            let needed = missed.len() as u32;
+            if needed > 0 && needed <= self.freeze_tokens {
+                self.freeze_tokens -= needed;
+                self.frozen_days.extend(missed);
+            }
+        }
+
+        self.current = self.current_run(active_days, today);
+        self.longest = self
+            .longest
+            .max(self.longest_run(active_days))
+            .max(self.current);
+    }
+
+    fn is_covered(&self, active_days: &BTreeSet<NaiveDate>, day: NaiveDate) -> bool {
+        active_days.contains(&day) || self.frozen_days.contains(&day)
+    }
+
+    /// Counts the active days of the run that ends today, or yesterday if
+    /// the player was not active yet today.
+    fn current_run(&self, active_days: &BTreeSet<NaiveDate>, today: NaiveDate) -> u32 {
+        let mut day = if self.is_covered(active_days, today) {
+            Some(today)
+        } else {
+            today.pred_opt()
+        };
+        let mut count = 0;
+        while let Some(current) = day.filter(|day| self.is_covered(active_days, *day)) {
+            if active_days.contains(&current) {
+                count += 1;
+            }
+            day = current.pred_opt();
+        }
+        count
+    }
+
+    fn longest_run(&self, active_days: &BTreeSet<NaiveDate>) -> u32 {
+        let covered: BTreeSet<NaiveDate> = active_days
+            .iter()
+            .chain(self.frozen_days.iter())
+            .copied()
+            .collect();
+        let mut longest = 0;
+        let mut count = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for day in covered {
+            if previous.and_then(|previous| previous.succ_opt()) != Some(day) {
+                count = 0;
+            }
+            if active_days.contains(&day) {
+                count += 1;
+            }
+            longest = longest.max(count);
+            previous = Some(day);
+        }
+        longest
+    }
+}
+
+/// Returns the calendar day of `time` in a time zone `utc_offset_minutes`
+/// ahead of UTC.
+pub fn day_of(time: DateTime<Utc>, utc_offset_minutes: i32) -> NaiveDate {
+    let offset = FixedOffset::east_opt(utc_offset_minutes.saturating_mul(60))
+        .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+    time.with_timezone(&offset).date_naive()
+}
+
+/// Returns the days on which at least one challenge of the history was
+/// finished.
+pub fn active_days(history: &ChallengeHistory, utc_offset_minutes: i32) -> BTreeSet<NaiveDate> {
+    history
+        .challenges
+        .iter()
+        .filter_map(|challenge| challenge.end_time)
+        .map(|end_time| day_of(end_time, utc_offset_minutes))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    fn days(ds: &[u32]) -> BTreeSet<NaiveDate> {
+        ds.iter().map(|d| day(*d)).collect()
+    }
+
+    #[test]
+    fn current_and_longest() {
+        let mut streak = Streak::default();
+        let active = days(&[1, 2, 3, 4, 7, 8]);
+
+        streak.update(&active, day(8));
+        assert_eq!(streak.current, 2);
+        assert_eq!(streak.longest, 4);
+
+        streak.update(&active, day(9));
+        assert_eq!(streak.current, 2, "today is not over yet");
+
+        streak.update(&active, day(10));
+        assert_eq!(streak.current, 0);
+        assert_eq!(streak.longest, 4);
+    }
+
+    #[test]
+    fn freeze_tokens_cover_missed_days() {
+        let mut streak = Streak {
+            freeze_tokens: 2,
+            ..Streak::default()
+        };
+        let active = days(&[1, 2, 5]);
+
+        streak.update(&active, day(5));
+        assert_eq!(streak.current, 3);
+        assert_eq!(streak.freeze_tokens, 0);
+        assert_eq!(streak.frozen_days, vec![day(3), day(4)]);
+
+        streak.update(&active, day(5));
+        assert_eq!(streak.freeze_tokens, 0, "days are only frozen once");
+    }
+
+    #[test]
+    fn too_few_freeze_tokens() {
+        let mut streak = Streak {
+            freeze_tokens: 1,
+            ..Streak::default()
+        };
+        streak.update(&days(&[1, 2, 5]), day(5));
+        assert_eq!(streak.current, 1);
+        assert_eq!(streak.longest, 2);
+        assert_eq!(streak.freeze_tokens, 1);
+    }
+
+    #[test]
+    fn days_in_time_zone() {
+        let time = Utc.with_ymd_and_hms(2024, 3, 1, 23, 30, 0).unwrap();
+        assert_eq!(day_of(time, 0), day(1));
+        assert_eq!(day_of(time, 60), day(2));
+        assert_eq!(day_of(time, -24 * 60 * 100), day(1));
+    }
+}
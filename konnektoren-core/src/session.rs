@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::{game::GameState, player_profile::PlayerProfile};
@@ -26,11 +27,26 @@ impl Session {
             game_state: GameState::default(),
         }
     }
+
+    /// Finishes the current challenge at `now` for sessions played without a
+    /// game controller: records it in the challenge history, awards its XP
+    /// and updates the streak of the player.
+    pub fn finish_challenge(&mut self, now: DateTime<Utc>) {
+        let state = &mut self.game_state;
+        state.challenge.end_time = Some(now);
+        state
+            .game
+            .challenge_history
+            .add_challenge(state.challenge.clone());
+        state.game.xp += state.game.calculate_xp_reward(&state.challenge);
+        self.player_profile.update_streak(&state.game, now);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::{Duration, TimeZone};
 
     #[test]
     fn new_session() {
@@ -43,4 +59,17 @@ mod tests {
             10
         );
     }
+
+    #[test]
+    fn finish_challenge_updates_streak() {
+        let mut session = Session::new("123".to_string());
+        let now = Utc.with_ymd_and_hms(2024, 3, 3, 12, 0, 0).unwrap();
+        session.game_state.challenge.start_time = Some(now - Duration::days(1));
+        session.finish_challenge(now - Duration::days(1));
+        session.finish_challenge(now);
+
+        assert_eq!(session.game_state.game.challenge_history.len(), 2);
+        assert_eq!(session.player_profile.streak.current, 2);
+        assert_eq!(session.player_profile.streak.longest, 2);
+    }
 }
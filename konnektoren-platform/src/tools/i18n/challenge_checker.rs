@@ -21,8 +21,9 @@ impl ChallengeI18nReport {
     }
 }
 
-/// Checks that all `name` and `description` fields of the challenges in a
-/// [`GamePath`] are translated for every builtin language.
+/// Checks that all `name` and `description` fields and the tags of the
+/// challenges in a [`GamePath`] are translated for every builtin language.
+/// CEFR levels are the same in every language and are not checked.
 ///
 /// Keys are the values from the path YAML. The convention is that the English
 /// translation file maps each key to its English text, so a missing key in any
@@ -88,6 +89,11 @@ fn extract_keys(path: &GamePath) -> Vec<String> {
     for challenge in &path.challenges {
         keys.push(challenge.name.clone());
         keys.push(challenge.description.clone());
+        for tag in &challenge.tags {
+            if !keys.contains(tag) {
+                keys.push(tag.clone());
+            }
+        }
     }
     keys
 }
@@ -108,4 +114,13 @@ mod tests {
         let report = checker.check(&GamePath::default());
         report.assert_complete();
     }
+
+    #[test]
+    fn tags_are_translation_keys() {
+        let mut path = GamePath::default();
+        path.challenges[0].tags = vec!["konnektoren".to_string()];
+        path.challenges[1].tags = vec!["konnektoren".to_string()];
+        let keys = extract_keys(&path);
+        assert_eq!(keys.iter().filter(|key| *key == "konnektoren").count(), 1);
+    }
 }
@@ -0,0 +1,201 @@
+use super::config::ImportConfig;
+use super::csv_importer::read_rows;
+use super::error::ImportError;
+use super::table::Table;
+use csv::ReaderBuilder;
+use konnektoren_core::challenges::ChallengeType;
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static LINE_BREAK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)<br\s*/?>").unwrap());
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+/// Imports a challenge from an Anki "Notes in Plain Text" export.
+///
+/// The separator, HTML and column names are read from the `#separator:`,
+/// `#html:` and `#columns:` lines at the top of the export, so the
+/// `delimiter` and `has_headers` of the config are ignored. Cloze notes like
+/// `{{c1::bin}}` can be imported as gap fill challenges.
+pub struct AnkiImporter {
+    config: ImportConfig,
+}
+
+impl AnkiImporter {
+    pub fn new(config: ImportConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn import_file<P: AsRef<Path>>(&self, path: P) -> Result<ChallengeType, ImportError> {
+        self.import_str(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn import_str(&self, data: &str) -> Result<ChallengeType, ImportError> {
+        let mut delimiter = '\t';
+        let mut html = false;
+        let mut columns = None;
+        let mut header_lines = 0;
+        for line in data.lines().take_while(|line| line.starts_with('#')) {
+            header_lines += 1;
+            let Some((key, value)) = line[1..].split_once(':') else {
+                continue;
+            };
+            match key.trim() {
+                "separator" => delimiter = separator(value.trim()).unwrap_or(delimiter),
+                "html" => html = value.trim() == "true",
+                "columns" => columns = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        let headers = columns
+            .map(|columns| {
+                columns
+                    .split(delimiter)
+                    .map(|header| header.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let body: String = data
+            .lines()
+            .skip(header_lines as usize)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut reader = ReaderBuilder::new()
+            .delimiter(delimiter as u8)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(body.as_bytes());
+        let mut rows = read_rows(reader.records(), header_lines)?;
+        if html {
+            for field in rows.iter_mut().flat_map(|row| row.fields.iter_mut()) {
+                *field = strip_html(field);
+            }
+        }
+        Table { headers, rows }.build(&self.config)
+    }
+}
+
+/// Returns the separator named in a `#separator:` line.
+fn separator(name: &str) -> Option<char> {
+    match name.to_lowercase().as_str() {
+        "tab" => Some('\t'),
+        "comma" => Some(','),
+        "semicolon" => Some(';'),
+        "pipe" => Some('|'),
+        "colon" => Some(':'),
+        "space" => Some(' '),
+        _ => {
+            let mut chars = name.chars();
+            chars.next().filter(|_| chars.next().is_none())
+        }
+    }
+}
+
+fn strip_html(field: &str) -> String {
+    let text = LINE_BREAK.replace_all(field, " ");
+    TAG.replace_all(&text, "")
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::import::{ImportTarget, RowError};
+
+    const CLOZE_EXPORT: &str = "#separator:tab\n#html:true\n#columns:Text\tBack Extra\n\
+        Ich {{c1::bin}} nach Berlin {{c2::gefahren}}.\tPerfekt mit <b>sein</b>\n\
+        Er {{c1::hat}}&nbsp;gearbeitet.<br>\t\n";
+
+    fn cloze() -> ImportConfig {
+        ImportConfig::new(
+            "perfekt",
+            "Perfekt",
+            "de",
+            ImportTarget::GapFill {
+                sentence: "Text".into(),
+                answers: None,
+                options: None,
+                translation: None,
+                explanation: Some("Back Extra".into()),
+                hints: None,
+            },
+        )
+    }
+
+    #[test]
+    fn import_cloze_notes() {
+        let ChallengeType::GapFill(dataset) =
+            AnkiImporter::new(cloze()).import_str(CLOZE_EXPORT).unwrap()
+        else {
+            panic!("expected a gap fill challenge");
+        };
+        assert_eq!(dataset.questions.len(), 2);
+        assert_eq!(dataset.questions[0].sentence, "Ich __ nach Berlin __.");
+        assert_eq!(dataset.questions[0].explanation, "Perfekt mit sein");
+        assert_eq!(dataset.questions[1].sentence, "Er __ gearbeitet.");
+        assert_eq!(dataset.questions[1].gaps[0].correct, "hat");
+    }
+
+    #[test]
+    fn import_basic_notes_without_header() {
+        let config = ImportConfig::new(
+            "basic",
+            "Basic",
+            "de",
+            ImportTarget::Vocabulary {
+                text: 0.into(),
+                translation: Some(1.into()),
+                phonetic: None,
+                mode: Default::default(),
+                direction: Default::default(),
+            },
+        );
+        let ChallengeType::Vocabulary(dataset) = AnkiImporter::new(config)
+            .import_str("der Apfel\tthe apple\ndas Haus\tthe house\n")
+            .unwrap()
+        else {
+            panic!("expected a vocabulary challenge");
+        };
+        assert_eq!(dataset.items[1].translation.as_deref(), Some("the house"));
+    }
+
+    #[test]
+    fn row_errors_count_header_lines() {
+        let export = "#separator:semicolon\n#html:false\nIch {{c1::bin}} müde.;\n__ ist kalt.;\n";
+        let mut config = cloze();
+        config.target = ImportTarget::GapFill {
+            sentence: 0.into(),
+            answers: None,
+            options: None,
+            translation: None,
+            explanation: None,
+            hints: None,
+        };
+        match AnkiImporter::new(config).import_str(export) {
+            Err(ImportError::InvalidRows(errors)) => assert_eq!(
+                errors,
+                vec![RowError {
+                    line: 4,
+                    message: "Missing answers".to_string()
+                }]
+            ),
+            other => panic!("expected invalid rows, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn separator_names() {
+        assert_eq!(separator("tab"), Some('\t'));
+        assert_eq!(separator("Semicolon"), Some(';'));
+        assert_eq!(separator("#"), Some('#'));
+        assert_eq!(separator("unknown"), None);
+    }
+}
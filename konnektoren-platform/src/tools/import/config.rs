@@ -0,0 +1,161 @@
+use konnektoren_core::challenges::{VocabularyDirection, VocabularyMode};
+use serde::{Deserialize, Serialize};
+
+/// A column of the imported file, either by its header or by its position
+/// starting at 0.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Column {
+    Index(usize),
+    Header(String),
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl From<&str> for Column {
+    fn from(header: &str) -> Self {
+        Column::Header(header.to_string())
+    }
+}
+
+/// The challenge type to create and which columns fill its fields.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ImportTarget {
+    /// One vocabulary item per row
+    Vocabulary {
+        text: Column,
+        #[serde(default)]
+        translation: Option<Column>,
+        #[serde(default)]
+        phonetic: Option<Column>,
+        #[serde(default)]
+        mode: VocabularyMode,
+        #[serde(default)]
+        direction: VocabularyDirection,
+    },
+    /// One question per row. The options of the challenge are the distinct
+    /// values of the `answer` column.
+    MultipleChoice {
+        question: Column,
+        answer: Column,
+        #[serde(default)]
+        help: Option<Column>,
+    },
+    /// One sentence per row, with gaps written as `__` or as Anki clozes
+    /// like `{{c1::bin}}`.
+    ///
+    /// Several answers or hints are separated by `|`, the options of a gap by
+    /// `;`, e.g. `bin;habe;war|gefahren;gefahrt`. Without an `answers` column
+    /// the answers are taken from the clozes.
+    GapFill {
+        sentence: Column,
+        #[serde(default)]
+        answers: Option<Column>,
+        #[serde(default)]
+        options: Option<Column>,
+        #[serde(default)]
+        translation: Option<Column>,
+        #[serde(default)]
+        explanation: Option<Column>,
+        #[serde(default)]
+        hints: Option<Column>,
+    },
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+fn default_has_headers() -> bool {
+    true
+}
+
+/// Describes how the rows of a CSV or TSV file become a challenge.
+///
+/// ```yaml
+/// id: "verbs"
+/// name: "Strong verbs"
+/// lang: "de"
+/// delimiter: "\t"
+/// target:
+///   type: vocabulary
+///   text: "German"
+///   translation: "English"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImportConfig {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub lang: String,
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    /// Whether the first row names the columns
+    #[serde(default = "default_has_headers")]
+    pub has_headers: bool,
+    pub target: ImportTarget,
+}
+
+impl ImportConfig {
+    pub fn new(id: &str, name: &str, lang: &str, target: ImportTarget) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            lang: lang.to_string(),
+            delimiter: default_delimiter(),
+            has_headers: default_has_headers(),
+            target,
+        }
+    }
+
+    /// Sets the delimiter to a tab.
+    pub fn tsv(mut self) -> Self {
+        self.delimiter = '\t';
+        self
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_from_yaml() {
+        let config = ImportConfig::from_yaml(
+            r#"
+id: "verbs"
+name: "Verbs"
+lang: "de"
+delimiter: "\t"
+target:
+  type: vocabulary
+  text: "German"
+  translation: 1
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.delimiter, '\t');
+        assert!(config.has_headers);
+        assert_eq!(
+            config.target,
+            ImportTarget::Vocabulary {
+                text: "German".into(),
+                translation: Some(1.into()),
+                phonetic: None,
+                mode: VocabularyMode::default(),
+                direction: VocabularyDirection::default(),
+            }
+        );
+    }
+}
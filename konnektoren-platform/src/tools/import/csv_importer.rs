@@ -0,0 +1,154 @@
+use super::config::ImportConfig;
+use super::error::ImportError;
+use super::table::{Row, Table};
+use csv::ReaderBuilder;
+use konnektoren_core::challenges::ChallengeType;
+use std::io::Read;
+use std::path::Path;
+
+/// Imports a challenge from a CSV or TSV file.
+///
+/// # Example
+/// ```rust,ignore
+/// use konnektoren_platform::tools::{CsvImporter, ImportConfig};
+///
+/// let config = ImportConfig::from_yaml(&std::fs::read_to_string("verbs.import.yml")?)?;
+/// let challenge_type = CsvImporter::new(config).import_file("verbs.tsv")?;
+/// factory.challenge_types.push(challenge_type);
+/// ```
+pub struct CsvImporter {
+    config: ImportConfig,
+}
+
+impl CsvImporter {
+    pub fn new(config: ImportConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn import_file<P: AsRef<Path>>(&self, path: P) -> Result<ChallengeType, ImportError> {
+        self.import_reader(std::fs::File::open(path)?)
+    }
+
+    pub fn import_str(&self, data: &str) -> Result<ChallengeType, ImportError> {
+        self.import_reader(data.as_bytes())
+    }
+
+    pub fn import_reader<R: Read>(&self, reader: R) -> Result<ChallengeType, ImportError> {
+        let mut reader = ReaderBuilder::new()
+            .delimiter(self.config.delimiter as u8)
+            .has_headers(self.config.has_headers)
+            .flexible(true)
+            .from_reader(reader);
+
+        let headers = if self.config.has_headers {
+            reader
+                .headers()?
+                .iter()
+                .map(|header| header.trim().to_string())
+                .collect()
+        } else {
+            vec![]
+        };
+        let rows = read_rows(reader.records(), 0)?;
+        Table { headers, rows }.build(&self.config)
+    }
+}
+
+/// Collects the records of a reader. `offset` is the number of lines before
+/// the first record that the reader did not see.
+pub(crate) fn read_rows<R: Read>(
+    records: csv::StringRecordsIter<'_, R>,
+    offset: u64,
+) -> Result<Vec<Row>, ImportError> {
+    records
+        .map(|record| {
+            let record = record?;
+            Ok(Row {
+                line: offset + record.position().map_or(0, |position| position.line()),
+                fields: record.iter().map(str::to_string).collect(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::import::{ImportTarget, RowError};
+
+    fn vocabulary() -> ImportConfig {
+        ImportConfig::new(
+            "verbs",
+            "Verbs",
+            "de",
+            ImportTarget::Vocabulary {
+                text: "German".into(),
+                translation: Some("English".into()),
+                phonetic: None,
+                mode: Default::default(),
+                direction: Default::default(),
+            },
+        )
+    }
+
+    #[test]
+    fn import_csv_vocabulary() {
+        let data = "German,English\nder Apfel,the apple\n\"das Haus, groß\",the big house\n";
+        let ChallengeType::Vocabulary(dataset) =
+            CsvImporter::new(vocabulary()).import_str(data).unwrap()
+        else {
+            panic!("expected a vocabulary challenge");
+        };
+        assert_eq!(dataset.id, "verbs");
+        assert_eq!(dataset.items.len(), 2);
+        assert_eq!(dataset.items[1].text, "das Haus, groß");
+        assert_eq!(
+            dataset.items[1].translation.as_deref(),
+            Some("the big house")
+        );
+    }
+
+    #[test]
+    fn import_tsv_without_headers() {
+        let mut config = vocabulary().tsv();
+        config.has_headers = false;
+        config.target = ImportTarget::Vocabulary {
+            text: 1.into(),
+            translation: Some(0.into()),
+            phonetic: None,
+            mode: Default::default(),
+            direction: Default::default(),
+        };
+        let ChallengeType::Vocabulary(dataset) = CsvImporter::new(config)
+            .import_str("the apple\tder Apfel\n")
+            .unwrap()
+        else {
+            panic!("expected a vocabulary challenge");
+        };
+        assert_eq!(dataset.items[0].text, "der Apfel");
+    }
+
+    #[test]
+    fn row_errors_name_the_line() {
+        let data = "German,English\nder Apfel,the apple\n,the house\n";
+        match CsvImporter::new(vocabulary()).import_str(data) {
+            Err(ImportError::InvalidRows(errors)) => assert_eq!(
+                errors,
+                vec![RowError {
+                    line: 3,
+                    message: "Missing text".to_string()
+                }]
+            ),
+            other => panic!("expected invalid rows, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn import_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("verbs.csv");
+        std::fs::write(&path, "German,English\ngehen,to go\n").unwrap();
+        let challenge_type = CsvImporter::new(vocabulary()).import_file(&path).unwrap();
+        assert_eq!(challenge_type.id(), "verbs");
+    }
+}
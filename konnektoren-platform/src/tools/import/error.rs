@@ -0,0 +1,32 @@
+use std::fmt;
+use thiserror::Error;
+
+/// A row that could not be turned into a task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    /// Line of the row in the imported file, starting at 1
+    pub line: u64,
+    pub message: String,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("CSV error: {0}")]
+    CsvError(#[from] csv::Error),
+    #[error("YAML error: {0}")]
+    YamlError(#[from] serde_yaml::Error),
+    #[error("Unknown column: {0}")]
+    UnknownColumn(String),
+    #[error("No rows to import")]
+    Empty,
+    #[error("{} invalid rows:\n{}", .0.len(), .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"))]
+    InvalidRows(Vec<RowError>),
+}
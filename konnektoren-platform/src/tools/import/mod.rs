@@ -0,0 +1,10 @@
+mod anki;
+mod config;
+mod csv_importer;
+mod error;
+mod table;
+
+pub use anki::AnkiImporter;
+pub use config::{Column, ImportConfig, ImportTarget};
+pub use csv_importer::CsvImporter;
+pub use error::{ImportError, RowError};
@@ -0,0 +1,456 @@
+use super::config::{Column, ImportConfig, ImportTarget};
+use super::error::{ImportError, RowError};
+use konnektoren_core::challenges::{
+    ChallengeType, Gap, GapFill, GapFillQuestion, GradingPolicy, MultipleChoice,
+    MultipleChoiceOption, Question, Vocabulary, VocabularyItem,
+};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Separates the answers, option lists and hints of the gaps in a cell.
+const GAP_SEPARATOR: char = '|';
+/// Separates the options of a single gap.
+const OPTION_SEPARATOR: char = ';';
+
+/// An Anki cloze such as `{{c1::bin}}` or `{{c1::bin::auxiliary}}`.
+static CLOZE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{c\d+::(.*?)(?:::(.*?))?\}\}").unwrap());
+
+pub(crate) struct Row {
+    /// Line of the row in the imported file, starting at 1
+    pub line: u64,
+    pub fields: Vec<String>,
+}
+
+impl Row {
+    /// Returns the trimmed value of a column, or `None` if it is empty.
+    fn get(&self, index: Option<usize>) -> Option<String> {
+        index
+            .and_then(|index| self.fields.get(index))
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn required(&self, index: usize, name: &str) -> Result<String, RowError> {
+        self.get(Some(index))
+            .ok_or_else(|| self.error(format!("Missing {}", name)))
+    }
+
+    fn error(&self, message: impl Into<String>) -> RowError {
+        RowError {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+}
+
+pub(crate) struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Row>,
+}
+
+impl Table {
+    fn column(&self, column: &Column) -> Result<usize, ImportError> {
+        match column {
+            Column::Index(index) => Ok(*index),
+            Column::Header(header) => self
+                .headers
+                .iter()
+                .position(|h| h == header)
+                .ok_or_else(|| ImportError::UnknownColumn(header.clone())),
+        }
+    }
+
+    fn optional(&self, column: &Option<Column>) -> Result<Option<usize>, ImportError> {
+        column.as_ref().map(|c| self.column(c)).transpose()
+    }
+
+    /// Turns the rows into the challenge type of the config. All invalid
+    /// rows are reported together.
+    pub fn build(&self, config: &ImportConfig) -> Result<ChallengeType, ImportError> {
+        if self.rows.is_empty() {
+            return Err(ImportError::Empty);
+        }
+        let mut errors = vec![];
+        let challenge_type = match &config.target {
+            ImportTarget::Vocabulary {
+                text,
+                translation,
+                phonetic,
+                mode,
+                direction,
+            } => {
+                let text = self.column(text)?;
+                let translation = self.optional(translation)?;
+                let phonetic = self.optional(phonetic)?;
+                let mut items = vec![];
+                for row in &self.rows {
+                    match row.required(text, "text") {
+                        Ok(text) => items.push(VocabularyItem {
+                            id: items.len(),
+                            text,
+                            translation: row.get(translation),
+                            icon: None,
+                            phonetic: row.get(phonetic),
+                        }),
+                        Err(error) => errors.push(error),
+                    }
+                }
+                ChallengeType::Vocabulary(Vocabulary {
+                    id: config.id.clone(),
+                    name: config.name.clone(),
+                    description: config.description.clone(),
+                    icon: None,
+                    lang: config.lang.clone(),
+                    items,
+                    mode: *mode,
+                    direction: *direction,
+                    grading: Default::default(),
+                    level: None,
+                    tags: vec![],
+                })
+            }
+            ImportTarget::MultipleChoice {
+                question,
+                answer,
+                help,
+            } => {
+                let question = self.column(question)?;
+                let answer = self.column(answer)?;
+                let help = self.optional(help)?;
+                let mut options: Vec<MultipleChoiceOption> = vec![];
+                let mut questions = vec![];
+                for row in &self.rows {
+                    let (text, answer) = match (
+                        row.required(question, "question"),
+                        row.required(answer, "answer"),
+                    ) {
+                        (Ok(text), Ok(answer)) => (text, answer),
+                        (Err(error), _) | (_, Err(error)) => {
+                            errors.push(error);
+                            continue;
+                        }
+                    };
+                    let option = match options.iter().find(|o| o.name == answer) {
+                        Some(option) => option.id,
+                        None => {
+                            options.push(MultipleChoiceOption {
+                                id: options.len(),
+                                name: answer,
+                            });
+                            options.len() - 1
+                        }
+                    };
+                    questions.push(Question {
+                        question: text,
+                        help: row.get(help).unwrap_or_default(),
+                        image: None,
+                        option,
+                    });
+                }
+                ChallengeType::MultipleChoice(MultipleChoice {
+                    id: config.id.clone(),
+                    name: config.name.clone(),
+                    lang: config.lang.clone(),
+                    options,
+                    questions,
+                    level: None,
+                    tags: vec![],
+                })
+            }
+            ImportTarget::GapFill {
+                sentence,
+                answers,
+                options,
+                translation,
+                explanation,
+                hints,
+            } => {
+                let columns = GapFillColumns {
+                    sentence: self.column(sentence)?,
+                    answers: self.optional(answers)?,
+                    options: self.optional(options)?,
+                    translation: self.optional(translation)?,
+                    explanation: self.optional(explanation)?,
+                    hints: self.optional(hints)?,
+                };
+                let mut questions = vec![];
+                for row in &self.rows {
+                    match columns.question(row) {
+                        Ok(question) => questions.push(question),
+                        Err(error) => errors.push(error),
+                    }
+                }
+                ChallengeType::GapFill(GapFill {
+                    id: config.id.clone(),
+                    name: config.name.clone(),
+                    description: config.description.clone(),
+                    lang: config.lang.clone(),
+                    questions,
+                    grading: GradingPolicy::exact(),
+                    level: None,
+                    tags: vec![],
+                })
+            }
+        };
+
+        if errors.is_empty() {
+            Ok(challenge_type)
+        } else {
+            Err(ImportError::InvalidRows(errors))
+        }
+    }
+}
+
+struct GapFillColumns {
+    sentence: usize,
+    answers: Option<usize>,
+    options: Option<usize>,
+    translation: Option<usize>,
+    explanation: Option<usize>,
+    hints: Option<usize>,
+}
+
+impl GapFillColumns {
+    fn question(&self, row: &Row) -> Result<GapFillQuestion, RowError> {
+        let (sentence, cloze_answers, cloze_hints) =
+            parse_clozes(&row.required(self.sentence, "sentence")?);
+        let answers = match row.get(self.answers) {
+            Some(answers) => split(&answers, GAP_SEPARATOR),
+            None => cloze_answers,
+        };
+        if answers.is_empty() {
+            return Err(row.error("Missing answers"));
+        }
+        let placeholders = sentence.matches(GapFill::GAP_PLACEHOLDER).count();
+        if answers.len() != placeholders {
+            return Err(row.error(format!(
+                "{} answers for {} gaps",
+                answers.len(),
+                placeholders
+            )));
+        }
+
+        let options: Vec<Vec<String>> = match row.get(self.options) {
+            Some(options) => split(&options, GAP_SEPARATOR)
+                .iter()
+                .map(|gap| split(gap, OPTION_SEPARATOR))
+                .collect(),
+            None => vec![vec![]; answers.len()],
+        };
+        if options.len() != answers.len() {
+            return Err(row.error(format!(
+                "{} option lists for {} gaps",
+                options.len(),
+                answers.len()
+            )));
+        }
+
+        let mut gaps = vec![];
+        for (position, (correct, options)) in answers.into_iter().zip(options).enumerate() {
+            if !options.is_empty() && !options.contains(&correct) {
+                return Err(row.error(format!(
+                    "'{}' is not one of the options of gap {}",
+                    correct,
+                    position + 1
+                )));
+            }
+            gaps.push(Gap {
+                position,
+                options,
+                correct,
+            });
+        }
+
+        let mut hints = row
+            .get(self.hints)
+            .map(|hints| split(&hints, GAP_SEPARATOR))
+            .unwrap_or_default();
+        hints.extend(cloze_hints);
+
+        Ok(GapFillQuestion {
+            sentence,
+            gaps,
+            hints,
+            translation: row.get(self.translation).unwrap_or_default(),
+            explanation: row.get(self.explanation).unwrap_or_default(),
+        })
+    }
+}
+
+fn split(value: &str, separator: char) -> Vec<String> {
+    value
+        .split(separator)
+        .map(|part| part.trim().to_string())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// Replaces the clozes of a sentence with gap placeholders and returns the
+/// sentence, the answers and the hints of the clozes.
+fn parse_clozes(sentence: &str) -> (String, Vec<String>, Vec<String>) {
+    let mut answers = vec![];
+    let mut hints = vec![];
+    for captures in CLOZE.captures_iter(sentence) {
+        answers.push(captures[1].trim().to_string());
+        if let Some(hint) = captures.get(2) {
+            hints.push(hint.as_str().trim().to_string());
+        }
+    }
+    let sentence = CLOZE
+        .replace_all(sentence, GapFill::GAP_PLACEHOLDER)
+        .into_owned();
+    (sentence, answers, hints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(rows: &[&[&str]]) -> Table {
+        Table {
+            headers: vec![],
+            rows: rows
+                .iter()
+                .enumerate()
+                .map(|(index, fields)| Row {
+                    line: index as u64 + 1,
+                    fields: fields.iter().map(|f| f.to_string()).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    fn gap_fill() -> ImportConfig {
+        ImportConfig::new(
+            "gaps",
+            "Gaps",
+            "de",
+            ImportTarget::GapFill {
+                sentence: 0.into(),
+                answers: Some(1.into()),
+                options: Some(2.into()),
+                translation: None,
+                explanation: None,
+                hints: None,
+            },
+        )
+    }
+
+    #[test]
+    fn parse_anki_clozes() {
+        let (sentence, answers, hints) =
+            parse_clozes("Ich {{c1::bin}} nach Berlin {{c2::gefahren::Partizip}}.");
+        assert_eq!(sentence, "Ich __ nach Berlin __.");
+        assert_eq!(answers, vec!["bin", "gefahren"]);
+        assert_eq!(hints, vec!["Partizip"]);
+    }
+
+    #[test]
+    fn gap_fill_rows() {
+        let table = table(&[
+            &[
+                "Ich __ nach Berlin __.",
+                "bin|gefahren",
+                "bin;habe|gefahren;gefahrt",
+            ],
+            &["Er {{c1::hat}} gearbeitet.", "", ""],
+        ]);
+        let ChallengeType::GapFill(dataset) = table.build(&gap_fill()).unwrap() else {
+            panic!("expected a gap fill challenge");
+        };
+        assert_eq!(dataset.questions.len(), 2);
+        assert_eq!(
+            dataset.questions[0].gaps[1].options,
+            vec!["gefahren", "gefahrt"]
+        );
+        assert_eq!(dataset.questions[1].sentence, "Er __ gearbeitet.");
+        assert_eq!(dataset.questions[1].gaps[0].correct, "hat");
+        assert!(dataset.questions[1].gaps[0].options.is_empty());
+    }
+
+    #[test]
+    fn invalid_rows_are_reported_together() {
+        let table = table(&[
+            &["Ich __ nach Berlin __.", "bin", ""],
+            &["Ich __ müde.", "bin", "habe;war"],
+            &["Ich __ müde.", "bin", "bin;war"],
+            &["", "bin", ""],
+        ]);
+        match table.build(&gap_fill()) {
+            Err(ImportError::InvalidRows(errors)) => assert_eq!(
+                errors,
+                vec![
+                    RowError {
+                        line: 1,
+                        message: "1 answers for 2 gaps".to_string()
+                    },
+                    RowError {
+                        line: 2,
+                        message: "'bin' is not one of the options of gap 1".to_string()
+                    },
+                    RowError {
+                        line: 4,
+                        message: "Missing sentence".to_string()
+                    },
+                ]
+            ),
+            other => panic!("expected invalid rows, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn multiple_choice_options_from_answers() {
+        let table = table(&[
+            &["Ich bleibe, ___ es regnet.", "weil"],
+            &["Ich komme, ___ du kommst.", "wenn"],
+            &["Er lacht, ___ er froh ist.", "weil"],
+        ]);
+        let config = ImportConfig::new(
+            "mc",
+            "MC",
+            "de",
+            ImportTarget::MultipleChoice {
+                question: 0.into(),
+                answer: 1.into(),
+                help: None,
+            },
+        );
+        let ChallengeType::MultipleChoice(dataset) = table.build(&config).unwrap() else {
+            panic!("expected a multiple choice challenge");
+        };
+        assert_eq!(
+            dataset.options.iter().map(|o| &o.name).collect::<Vec<_>>(),
+            vec!["weil", "wenn"]
+        );
+        assert_eq!(
+            dataset
+                .questions
+                .iter()
+                .map(|q| q.option)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 0]
+        );
+    }
+
+    #[test]
+    fn unknown_column() {
+        let config = ImportConfig::new(
+            "vocabulary",
+            "Vocabulary",
+            "de",
+            ImportTarget::Vocabulary {
+                text: "German".into(),
+                translation: None,
+                phonetic: None,
+                mode: Default::default(),
+                direction: Default::default(),
+            },
+        );
+        assert!(matches!(
+            table(&[&["Haus"]]).build(&config),
+            Err(ImportError::UnknownColumn(column)) if column == "German"
+        ));
+        assert!(matches!(table(&[]).build(&config), Err(ImportError::Empty)));
+    }
+}
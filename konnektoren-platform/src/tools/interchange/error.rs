@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum InterchangeError {
+    #[error("Challenge type cannot be exported: {0}")]
+    Unsupported(String),
+    #[error("No questions found")]
+    NoQuestions,
+    #[error("Invalid {format}: {message}")]
+    Invalid {
+        format: &'static str,
+        message: String,
+    },
+    #[error("XML error: {0}")]
+    XmlError(#[from] quick_xml::Error),
+    #[error("Formatting error: {0}")]
+    FmtError(#[from] std::fmt::Error),
+}
+
+impl From<quick_xml::events::attributes::AttrError> for InterchangeError {
+    fn from(error: quick_xml::events::attributes::AttrError) -> Self {
+        InterchangeError::XmlError(error.into())
+    }
+}
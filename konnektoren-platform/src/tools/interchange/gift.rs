@@ -0,0 +1,968 @@
+use super::report::{Conversion, ConversionReport};
+use super::{InterchangeError, InterchangeFormat, slug};
+use konnektoren_core::challenges::{
+    ChallengeType, Gap, GapFill, GapFillQuestion, GradingPolicy, MultipleChoice,
+    MultipleChoiceOption, Ordering, OrderingItem, Question, SortTable, SortTableColumn,
+    SortTableRow,
+};
+use std::fmt::Write;
+
+/// Characters with a meaning in GIFT that have to be escaped in text.
+const SPECIAL: [char; 6] = ['~', '=', '#', '{', '}', ':'];
+/// Question text of an exported ordering.
+const ORDERING_PROMPT: &str = "Put the elements in order";
+
+/// Moodle GIFT, a plain text format with one question per paragraph.
+///
+/// The category of an exported file is the id of the challenge and the
+/// comment on the first line its name. Gap fill sentences with several gaps
+/// become one missing word question per gap, titled `<id>-<task>.<gap>`, and
+/// are joined again on import. Sort tables become one matching question per
+/// column, orderings a matching of the elements to their positions.
+pub struct GiftFormat;
+
+impl InterchangeFormat for GiftFormat {
+    fn export(
+        &self,
+        challenge_type: &ChallengeType,
+    ) -> Result<Conversion<String>, InterchangeError> {
+        let mut report = ConversionReport::default();
+        let mut out = String::new();
+        writeln!(out, "// {}", challenge_type.name())?;
+        writeln!(out, "$CATEGORY: {}\n", challenge_type.id())?;
+        match challenge_type {
+            ChallengeType::MultipleChoice(dataset) => {
+                export_multiple_choice(dataset, &mut out, &mut report)?
+            }
+            ChallengeType::GapFill(dataset) => export_gap_fill(dataset, &mut out, &mut report)?,
+            ChallengeType::SortTable(dataset) => export_sort_table(dataset, &mut out, &mut report)?,
+            ChallengeType::Ordering(dataset) => export_ordering(dataset, &mut out, &mut report)?,
+            other => {
+                let kind: &'static str = other.into();
+                return Err(InterchangeError::Unsupported(kind.to_string()));
+            }
+        }
+        Ok(Conversion { value: out, report })
+    }
+
+    fn import(&self, data: &str) -> Result<Conversion<ChallengeType>, InterchangeError> {
+        let mut report = ConversionReport::default();
+        let file = parse(data, &mut report)?;
+        let Some(first) = file.questions.first() else {
+            return Err(InterchangeError::NoQuestions);
+        };
+        let id = file.category.clone().unwrap_or_else(|| "gift".to_string());
+        let name = file.name.clone().unwrap_or_else(|| id.clone());
+
+        let matching = first.is_matching();
+        let gap_fill = !matching
+            && file
+                .questions
+                .iter()
+                .any(|q| q.is_missing_word() || q.is_short_answer());
+        let mut questions = vec![];
+        for question in file.questions {
+            if question.is_matching() == matching {
+                questions.push(question);
+            } else {
+                report.note(
+                    question.location(),
+                    "Skipped, the question is of another kind than the first one",
+                );
+            }
+        }
+
+        let challenge_type = if matching {
+            if questions.iter().all(GiftQuestion::is_ordering) {
+                import_ordering(id, name, &questions)
+            } else {
+                import_sort_table(id, name, &questions, &mut report)
+            }
+        } else if gap_fill {
+            import_gap_fill(id, name, &questions, &mut report)
+        } else {
+            import_multiple_choice(id, name, &questions, &mut report)
+        };
+        Ok(Conversion {
+            value: challenge_type,
+            report,
+        })
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            c if c == '\\' || SPECIAL.contains(&c) => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some(c) => out.push(c),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Returns the byte index of the first occurrence of `pattern` that is not
+/// escaped with a backslash.
+fn find_unescaped(text: &str, pattern: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if text[index..].starts_with(pattern) {
+            return Some(index);
+        }
+    }
+    None
+}
+
+/// Writes the answers of a choice, marking the correct one with `=`.
+fn answer_block(options: &[&str], correct: &str, feedback: &str) -> String {
+    let mut block = String::from("{");
+    if !options.contains(&correct) {
+        block.push_str(&format!("={} ", escape(correct)));
+    }
+    for option in options {
+        let marker = if *option == correct { '=' } else { '~' };
+        block.push_str(&format!("{}{} ", marker, escape(option)));
+    }
+    if !feedback.is_empty() {
+        block.push_str(&format!("####{} ", escape(feedback)));
+    }
+    block.truncate(block.trim_end().len());
+    block.push('}');
+    block
+}
+
+fn matching_block<'a>(pairs: impl IntoIterator<Item = (&'a str, String)>) -> String {
+    let pairs: Vec<String> = pairs
+        .into_iter()
+        .map(|(left, right)| format!("={} -> {}", escape(left), escape(&right)))
+        .collect();
+    format!("{{{}}}", pairs.join(" "))
+}
+
+fn export_multiple_choice(
+    dataset: &MultipleChoice,
+    out: &mut String,
+    report: &mut ConversionReport,
+) -> Result<(), InterchangeError> {
+    let options: Vec<&str> = dataset.options.iter().map(|o| o.name.as_str()).collect();
+    for (index, question) in dataset.questions.iter().enumerate() {
+        if question.image.is_some() {
+            report.note(format!("questions[{}]", index), "Images are not supported");
+        }
+        let correct = dataset
+            .options
+            .iter()
+            .find(|o| o.id == question.option)
+            .map(|o| o.name.as_str())
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "::{}-{}::{} {}\n",
+            escape(&dataset.id),
+            index,
+            escape(&question.question),
+            answer_block(&options, correct, &question.help)
+        )?;
+    }
+    Ok(())
+}
+
+fn export_gap_fill(
+    dataset: &GapFill,
+    out: &mut String,
+    report: &mut ConversionReport,
+) -> Result<(), InterchangeError> {
+    if !dataset.description.is_empty() {
+        report.note("", "The description is not supported");
+    }
+    for (index, question) in dataset.questions.iter().enumerate() {
+        let location = format!("questions[{}]", index);
+        let parts: Vec<&str> = question.sentence.split(GapFill::GAP_PLACEHOLDER).collect();
+        let mut gaps: Vec<&Gap> = question.gaps.iter().collect();
+        gaps.sort_by_key(|gap| gap.position);
+        if gaps.len() + 1 != parts.len() {
+            report.note(location, "Skipped, the gaps do not match the sentence");
+            continue;
+        }
+        if !question.hints.is_empty() {
+            report.note(&location, "Hints are not supported");
+        }
+        if !question.translation.is_empty() {
+            report.note(&location, "Translations are not supported");
+        }
+        if gaps.len() > 1 {
+            report.note(
+                &location,
+                format!("Split into {} questions, one per gap", gaps.len()),
+            );
+        }
+
+        for (gap_index, gap) in gaps.iter().enumerate() {
+            let mut text = String::new();
+            for (part_index, part) in parts.iter().enumerate() {
+                text.push_str(&escape(part));
+                match gaps.get(part_index) {
+                    Some(_) if part_index == gap_index => {
+                        let options: Vec<&str> = gap.options.iter().map(String::as_str).collect();
+                        text.push_str(&answer_block(&options, &gap.correct, &question.explanation));
+                    }
+                    Some(other) => text.push_str(&escape(&other.correct)),
+                    None => {}
+                }
+            }
+            let title = if gaps.len() > 1 {
+                format!("{}-{}.{}", dataset.id, index, gap_index + 1)
+            } else {
+                format!("{}-{}", dataset.id, index)
+            };
+            writeln!(out, "::{}::{}\n", escape(&title), text.trim())?;
+        }
+    }
+    Ok(())
+}
+
+fn export_sort_table(
+    dataset: &SortTable,
+    out: &mut String,
+    report: &mut ConversionReport,
+) -> Result<(), InterchangeError> {
+    if !dataset.description.is_empty() {
+        report.note("", "The description is not supported");
+    }
+    if dataset.columns.iter().any(|c| !c.description.is_empty()) {
+        report.note("columns", "Column descriptions are not supported");
+    }
+    let Some(first) = dataset.columns.first() else {
+        return Ok(());
+    };
+    for (index, column) in dataset.columns.iter().enumerate().skip(1) {
+        let pairs = dataset.rows.iter().map(|row| {
+            (
+                row.values.first().map(String::as_str).unwrap_or_default(),
+                row.values.get(index).cloned().unwrap_or_default(),
+            )
+        });
+        writeln!(
+            out,
+            "::{}-{}::{} -> {} {}\n",
+            escape(&dataset.id),
+            escape(&column.id),
+            escape(&first.title),
+            escape(&column.title),
+            matching_block(pairs)
+        )?;
+    }
+    Ok(())
+}
+
+fn export_ordering(
+    dataset: &Ordering,
+    out: &mut String,
+    report: &mut ConversionReport,
+) -> Result<(), InterchangeError> {
+    if !dataset.description.is_empty() {
+        report.note("", "The description is not supported");
+    }
+    report.note(
+        "items",
+        "Orderings are exported as matching the elements to their positions",
+    );
+    for (index, item) in dataset.items.iter().enumerate() {
+        let pairs = item.elements.iter().enumerate().map(|(element, text)| {
+            let position = item
+                .correct_order
+                .iter()
+                .position(|&i| i == element)
+                .map_or(0, |position| position + 1);
+            (text.as_str(), position.to_string())
+        });
+        writeln!(
+            out,
+            "::{}-{}::{} {}\n",
+            escape(&dataset.id),
+            index,
+            ORDERING_PROMPT,
+            matching_block(pairs)
+        )?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum GiftAnswer {
+    Right(String),
+    Wrong(String),
+    Pair(String, String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct GiftQuestion {
+    /// Position of the question in the file
+    index: usize,
+    title: Option<String>,
+    /// Text before the answers
+    before: String,
+    /// Text after the answers, only set for missing word questions
+    after: String,
+    answers: Vec<GiftAnswer>,
+    /// General feedback, written after `####`
+    feedback: String,
+}
+
+impl GiftQuestion {
+    fn location(&self) -> String {
+        format!("questions[{}]", self.index)
+    }
+
+    fn is_matching(&self) -> bool {
+        self.answers
+            .iter()
+            .all(|answer| matches!(answer, GiftAnswer::Pair(..)))
+    }
+
+    fn is_missing_word(&self) -> bool {
+        !self.after.trim().is_empty()
+    }
+
+    fn is_short_answer(&self) -> bool {
+        self.answers
+            .iter()
+            .all(|answer| matches!(answer, GiftAnswer::Right(_)))
+    }
+
+    /// A matching of elements to the positions `1..=n`.
+    fn is_ordering(&self) -> bool {
+        let mut positions: Vec<usize> = self
+            .pairs()
+            .filter_map(|(_, right)| right.parse().ok())
+            .collect();
+        positions.sort_unstable();
+        positions.len() == self.answers.len() && positions.iter().copied().eq(1..=positions.len())
+    }
+
+    fn rights(&self) -> impl Iterator<Item = &str> {
+        self.answers.iter().filter_map(|answer| match answer {
+            GiftAnswer::Right(text) => Some(text.as_str()),
+            _ => None,
+        })
+    }
+
+    fn choices(&self) -> impl Iterator<Item = &str> {
+        self.answers.iter().filter_map(|answer| match answer {
+            GiftAnswer::Right(text) | GiftAnswer::Wrong(text) => Some(text.as_str()),
+            GiftAnswer::Pair(..) => None,
+        })
+    }
+
+    fn pairs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.answers.iter().filter_map(|answer| match answer {
+            GiftAnswer::Pair(left, right) => Some((left.as_str(), right.as_str())),
+            _ => None,
+        })
+    }
+}
+
+struct GiftFile {
+    category: Option<String>,
+    /// The first comment of the file
+    name: Option<String>,
+    questions: Vec<GiftQuestion>,
+}
+
+fn parse(data: &str, report: &mut ConversionReport) -> Result<GiftFile, InterchangeError> {
+    let mut file = GiftFile {
+        category: None,
+        name: None,
+        questions: vec![],
+    };
+    let mut blocks: Vec<String> = vec![];
+    let mut block = String::new();
+    for line in data.lines().map(str::trim) {
+        if let Some(comment) = line.strip_prefix("//") {
+            if file.name.is_none() && blocks.is_empty() && block.is_empty() {
+                file.name = Some(comment.trim().to_string());
+            }
+        } else if let Some(category) = line.strip_prefix("$CATEGORY:") {
+            let category = category.trim();
+            file.category = Some(category.rsplit('/').next().unwrap_or(category).to_string());
+        } else if line.is_empty() {
+            if !block.is_empty() {
+                blocks.push(std::mem::take(&mut block));
+            }
+        } else {
+            if !block.is_empty() {
+                block.push('\n');
+            }
+            block.push_str(line);
+        }
+    }
+    if !block.is_empty() {
+        blocks.push(block);
+    }
+
+    for (index, block) in blocks.iter().enumerate() {
+        if let Some(question) = parse_question(block, index, report)? {
+            file.questions.push(question);
+        }
+    }
+    Ok(file)
+}
+
+fn parse_question(
+    block: &str,
+    index: usize,
+    report: &mut ConversionReport,
+) -> Result<Option<GiftQuestion>, InterchangeError> {
+    let location = format!("questions[{}]", index);
+    let mut text = block.trim();
+    let mut title = None;
+    if let Some(rest) = text.strip_prefix("::")
+        && let Some(end) = find_unescaped(rest, "::")
+    {
+        title = Some(unescape(&rest[..end]).trim().to_string());
+        text = rest[end + 2..].trim_start();
+    }
+    for format in ["[plain]", "[moodle]", "[html]", "[markdown]"] {
+        if let Some(rest) = text.strip_prefix(format) {
+            text = rest.trim_start();
+        }
+    }
+
+    let Some(open) = find_unescaped(text, "{") else {
+        report.note(location, "Skipped, the text has no answers");
+        return Ok(None);
+    };
+    let close = find_unescaped(&text[open..], "}")
+        .map(|close| open + close)
+        .ok_or_else(|| InterchangeError::Invalid {
+            format: "GIFT",
+            message: format!("question {} has no closing brace", index + 1),
+        })?;
+
+    let mut body = &text[open + 1..close];
+    let mut feedback = String::new();
+    if let Some(start) = find_unescaped(body, "####") {
+        feedback = unescape(&body[start + 4..]).trim().to_string();
+        body = &body[..start];
+    }
+    let kind = body.trim();
+    if kind.is_empty() || kind.starts_with('#') || ["T", "F", "TRUE", "FALSE"].contains(&kind) {
+        report.note(
+            location,
+            "Skipped, only choice, missing word and matching questions are supported",
+        );
+        return Ok(None);
+    }
+
+    let mut answers = vec![];
+    for (marker, answer) in answer_tokens(body) {
+        let mut answer = answer;
+        if let Some(start) = find_unescaped(answer, "#") {
+            report.note(&location, "Answer feedback is not supported");
+            answer = &answer[..start];
+        }
+        let mut answer = answer.trim();
+        if let Some(weighted) = answer.strip_prefix('%')
+            && let Some(end) = weighted.find('%')
+        {
+            report.note(&location, "Answer weights are not supported");
+            answer = weighted[end + 1..].trim();
+        }
+        answers.push(match (marker, find_unescaped(answer, "->")) {
+            ('=', Some(arrow)) => GiftAnswer::Pair(
+                unescape(&answer[..arrow]).trim().to_string(),
+                unescape(&answer[arrow + 2..]).trim().to_string(),
+            ),
+            ('=', None) => GiftAnswer::Right(unescape(answer).trim().to_string()),
+            _ => GiftAnswer::Wrong(unescape(answer).trim().to_string()),
+        });
+    }
+
+    Ok(Some(GiftQuestion {
+        index,
+        title,
+        before: unescape(&text[..open]),
+        after: unescape(&text[close + 1..]),
+        answers,
+        feedback,
+    }))
+}
+
+/// Splits the answers of a question at the unescaped `=` and `~` markers.
+fn answer_tokens(body: &str) -> Vec<(char, &str)> {
+    let mut tokens = vec![];
+    let mut start: Option<(char, usize)> = None;
+    let mut escaped = false;
+    for (index, c) in body.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '=' | '~' => {
+                if let Some((marker, start)) = start {
+                    tokens.push((marker, &body[start..index]));
+                }
+                start = Some((c, index + 1));
+            }
+            _ => {}
+        }
+    }
+    if let Some((marker, start)) = start {
+        tokens.push((marker, &body[start..]));
+    }
+    tokens
+}
+
+fn import_multiple_choice(
+    id: String,
+    name: String,
+    questions: &[GiftQuestion],
+    report: &mut ConversionReport,
+) -> ChallengeType {
+    let mut options: Vec<MultipleChoiceOption> = vec![];
+    let mut imported = vec![];
+    let first_choices: Vec<&str> = questions
+        .first()
+        .map(|q| q.choices().collect())
+        .unwrap_or_default();
+    for question in questions {
+        for choice in question.choices() {
+            if !options.iter().any(|o| o.name == choice) {
+                options.push(MultipleChoiceOption {
+                    id: options.len(),
+                    name: choice.to_string(),
+                });
+            }
+        }
+        let mut choices: Vec<&str> = question.choices().collect();
+        let mut expected = first_choices.clone();
+        choices.sort_unstable();
+        expected.sort_unstable();
+        if choices != expected {
+            report.note(
+                question.location(),
+                "The options of all questions are merged",
+            );
+        }
+        let mut rights = question.rights();
+        let Some(right) = rights.next() else {
+            report.note(question.location(), "Skipped, no correct answer");
+            continue;
+        };
+        if rights.next().is_some() {
+            report.note(question.location(), "Only the first correct answer is kept");
+        }
+        imported.push(Question {
+            question: question.before.trim().to_string(),
+            help: question.feedback.clone(),
+            image: None,
+            option: options
+                .iter()
+                .position(|o| o.name == right)
+                .unwrap_or_default(),
+        });
+    }
+    ChallengeType::MultipleChoice(MultipleChoice {
+        id,
+        name,
+        lang: String::new(),
+        options,
+        questions: imported,
+        level: None,
+        tags: vec![],
+    })
+}
+
+fn import_gap_fill(
+    id: String,
+    name: String,
+    questions: &[GiftQuestion],
+    report: &mut ConversionReport,
+) -> ChallengeType {
+    let mut imported: Vec<GapFillQuestion> = vec![];
+    let mut index = 0;
+    while index < questions.len() {
+        let group = split_group(&questions[index..]);
+        let group = &questions[index..index + group.max(1)];
+        index += group.len();
+
+        let gaps: Vec<Gap> = group
+            .iter()
+            .enumerate()
+            .map(|(position, question)| {
+                let mut rights = question.rights();
+                let correct = rights.next().unwrap_or_default().to_string();
+                if rights.next().is_some() {
+                    report.note(
+                        question.location(),
+                        "Alternative correct answers are not supported",
+                    );
+                }
+                let options = if question.is_short_answer() {
+                    vec![]
+                } else {
+                    question.choices().map(str::to_string).collect()
+                };
+                Gap {
+                    position,
+                    options,
+                    correct,
+                }
+            })
+            .collect();
+        let corrects: Vec<&str> = gaps.iter().map(|gap| gap.correct.as_str()).collect();
+
+        match join_sentence(group, &corrects) {
+            Some(sentence) => imported.push(GapFillQuestion {
+                sentence,
+                gaps,
+                hints: vec![],
+                translation: String::new(),
+                explanation: group[0].feedback.clone(),
+            }),
+            None => {
+                for (question, mut gap) in group.iter().zip(gaps) {
+                    if group.len() > 1 {
+                        report.note(
+                            question.location(),
+                            "Could not be joined with the other gaps of its sentence",
+                        );
+                    }
+                    gap.position = 0;
+                    imported.push(GapFillQuestion {
+                        sentence: format!(
+                            "{}{}{}",
+                            question.before,
+                            GapFill::GAP_PLACEHOLDER,
+                            question.after
+                        )
+                        .trim()
+                        .to_string(),
+                        gaps: vec![gap],
+                        hints: vec![],
+                        translation: String::new(),
+                        explanation: question.feedback.clone(),
+                    });
+                }
+            }
+        }
+    }
+    ChallengeType::GapFill(GapFill {
+        id,
+        name,
+        description: String::new(),
+        lang: String::new(),
+        questions: imported,
+        grading: GradingPolicy::exact(),
+        level: None,
+        tags: vec![],
+    })
+}
+
+/// Returns how many questions, starting with the first, are the gaps of one
+/// sentence, i.e. are titled `<task>.1`, `<task>.2` and so on.
+fn split_group(questions: &[GiftQuestion]) -> usize {
+    let part = |question: &GiftQuestion| {
+        question.title.as_ref().and_then(|title| {
+            let (task, gap) = title.rsplit_once('.')?;
+            Some((task.to_string(), gap.parse::<usize>().ok()?))
+        })
+    };
+    let Some((task, 1)) = questions.first().and_then(part) else {
+        return 1;
+    };
+    questions
+        .iter()
+        .enumerate()
+        .take_while(|(index, question)| part(question) == Some((task.clone(), *index + 1)))
+        .count()
+}
+
+/// Joins the missing word questions of one sentence. Each question repeats
+/// the sentence with the answers of the other gaps filled in.
+fn join_sentence(group: &[GiftQuestion], corrects: &[&str]) -> Option<String> {
+    let mut sentence = group[0].before.clone();
+    let mut previous = group[0].before.clone();
+    for (index, question) in group.iter().enumerate().skip(1) {
+        let filled = format!("{}{}", previous, corrects[index - 1]);
+        let between = question.before.strip_prefix(&filled)?;
+        sentence.push_str(GapFill::GAP_PLACEHOLDER);
+        sentence.push_str(between);
+        previous = question.before.clone();
+    }
+    sentence.push_str(GapFill::GAP_PLACEHOLDER);
+    sentence.push_str(&group[group.len() - 1].after);
+    Some(sentence.trim().to_string())
+}
+
+fn import_sort_table(
+    id: String,
+    name: String,
+    questions: &[GiftQuestion],
+    report: &mut ConversionReport,
+) -> ChallengeType {
+    let column = |title: &str, index: usize| SortTableColumn {
+        id: if slug(title).is_empty() {
+            format!("column-{}", index + 1)
+        } else {
+            slug(title)
+        },
+        title: title.to_string(),
+        description: String::new(),
+    };
+    let titles = |question: &GiftQuestion| {
+        question
+            .before
+            .split_once("->")
+            .map(|(left, right)| (left.trim().to_string(), right.trim().to_string()))
+    };
+
+    let first_title = titles(&questions[0])
+        .map(|(left, _)| left)
+        .unwrap_or_default();
+    let mut columns = vec![column(&first_title, 0)];
+    let mut rows: Vec<SortTableRow> = questions[0]
+        .pairs()
+        .enumerate()
+        .map(|(id, (left, _))| SortTableRow {
+            id,
+            values: vec![left.to_string()],
+        })
+        .collect();
+
+    for (index, question) in questions.iter().enumerate() {
+        let title = titles(question)
+            .map(|(_, right)| right)
+            .or_else(|| question.title.clone())
+            .unwrap_or_default();
+        columns.push(column(&title, index + 1));
+        let pairs: Vec<(&str, &str)> = question.pairs().collect();
+        for (row_index, row) in rows.iter_mut().enumerate() {
+            let value = match pairs.get(row_index) {
+                Some((left, right)) if *left == row.values[0] => Some(*right),
+                _ => pairs
+                    .iter()
+                    .find(|(left, _)| *left == row.values[0])
+                    .map(|(_, right)| *right),
+            };
+            if value.is_none() {
+                report.note(
+                    question.location(),
+                    format!("No match for '{}'", row.values[0]),
+                );
+            }
+            row.values.push(value.unwrap_or_default().to_string());
+        }
+    }
+    ChallengeType::SortTable(SortTable {
+        id,
+        name,
+        description: String::new(),
+        columns,
+        rows,
+        level: None,
+        tags: vec![],
+    })
+}
+
+fn import_ordering(id: String, name: String, questions: &[GiftQuestion]) -> ChallengeType {
+    let items = questions
+        .iter()
+        .map(|question| {
+            let pairs: Vec<(&str, usize)> = question
+                .pairs()
+                .map(|(left, right)| (left, right.parse().unwrap_or_default()))
+                .collect();
+            let mut correct_order: Vec<usize> = (0..pairs.len()).collect();
+            correct_order.sort_by_key(|&index| pairs[index].1);
+            OrderingItem {
+                elements: pairs.iter().map(|(left, _)| left.to_string()).collect(),
+                correct_order,
+            }
+        })
+        .collect();
+    ChallengeType::Ordering(Ordering {
+        id,
+        name,
+        description: String::new(),
+        items,
+        level: None,
+        tags: vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(challenge_type: &ChallengeType) -> (ChallengeType, ConversionReport) {
+        let exported = GiftFormat.export(challenge_type).unwrap();
+        let imported = GiftFormat.import(&exported.value).unwrap();
+        (imported.value, exported.report)
+    }
+
+    #[test]
+    fn escape_special_characters() {
+        let text = "a=b {c} ~d #e: \\f";
+        assert_eq!(escape(text), "a\\=b \\{c\\} \\~d \\#e\\: \\\\f");
+        assert_eq!(unescape(&escape(text)), text);
+    }
+
+    #[test]
+    fn multiple_choice_round_trip() {
+        let challenge_type = ChallengeType::default();
+        let (imported, report) = round_trip(&challenge_type);
+        let (ChallengeType::MultipleChoice(original), ChallengeType::MultipleChoice(imported)) =
+            (&challenge_type, &imported)
+        else {
+            panic!("expected multiple choice challenges");
+        };
+        assert!(report.is_lossless());
+        assert_eq!(imported.id, original.id);
+        assert_eq!(imported.name, original.name);
+        assert_eq!(imported.questions.len(), original.questions.len());
+        let answer = |dataset: &MultipleChoice, question: &Question| {
+            dataset
+                .options
+                .iter()
+                .find(|o| o.id == question.option)
+                .map(|o| o.name.clone())
+        };
+        for (original_question, imported_question) in
+            original.questions.iter().zip(&imported.questions)
+        {
+            assert_eq!(imported_question.question, original_question.question);
+            assert_eq!(imported_question.help, original_question.help);
+            assert_eq!(
+                answer(imported, imported_question),
+                answer(original, original_question)
+            );
+        }
+    }
+
+    #[test]
+    fn gap_fill_round_trip() {
+        let original = GapFill::default();
+        let (imported, report) = round_trip(&ChallengeType::GapFill(original.clone()));
+        let ChallengeType::GapFill(imported) = imported else {
+            panic!("expected a gap fill challenge");
+        };
+        for (original, imported) in original.questions.iter().zip(&imported.questions) {
+            assert_eq!(imported.sentence, original.sentence);
+            assert_eq!(imported.gaps, original.gaps);
+            assert_eq!(imported.explanation, original.explanation);
+        }
+        assert!(
+            report
+                .notes
+                .iter()
+                .any(|note| note.message == "Hints are not supported")
+        );
+    }
+
+    #[test]
+    fn sort_table_round_trip() {
+        let original = SortTable::default();
+        let (imported, report) = round_trip(&ChallengeType::SortTable(original.clone()));
+        let ChallengeType::SortTable(imported) = imported else {
+            panic!("expected a sort table challenge");
+        };
+        assert_eq!(imported.rows, original.rows);
+        assert_eq!(
+            imported.columns.iter().map(|c| &c.id).collect::<Vec<_>>(),
+            original.columns.iter().map(|c| &c.id).collect::<Vec<_>>()
+        );
+        assert!(!report.is_lossless());
+    }
+
+    #[test]
+    fn ordering_round_trip() {
+        let original = Ordering::default();
+        let (imported, _) = round_trip(&ChallengeType::Ordering(original.clone()));
+        let ChallengeType::Ordering(imported) = imported else {
+            panic!("expected an ordering challenge");
+        };
+        assert_eq!(imported.items, original.items);
+    }
+
+    #[test]
+    fn import_moodle_questions() {
+        let data = r#"
+// Konnektoren aus Moodle
+$CATEGORY: $course$/top/konnektoren
+
+::Q1:: Ich bleibe zu Hause, ... es regnet. {
+    =weil#Richtig
+    ~obwohl
+    ~%50%denn
+}
+
+::Q2:: Wie viel ist 2 + 2? {#4}
+
+::Q3:: Er kommt, ... er krank ist. {=obwohl ~weil ~denn ####Gegensatz}
+"#;
+        let imported = GiftFormat.import(data).unwrap();
+        let ChallengeType::MultipleChoice(dataset) = imported.value else {
+            panic!("expected a multiple choice challenge");
+        };
+        assert_eq!(dataset.id, "konnektoren");
+        assert_eq!(dataset.name, "Konnektoren aus Moodle");
+        assert_eq!(
+            dataset.options.iter().map(|o| &o.name).collect::<Vec<_>>(),
+            vec!["weil", "obwohl", "denn"]
+        );
+        assert_eq!(dataset.questions.len(), 2);
+        assert_eq!(dataset.questions[1].option, 1);
+        assert_eq!(dataset.questions[1].help, "Gegensatz");
+
+        let messages: Vec<&str> = imported
+            .report
+            .notes
+            .iter()
+            .map(|note| note.message.as_str())
+            .collect();
+        assert!(messages.contains(&"Answer feedback is not supported"));
+        assert!(messages.contains(&"Answer weights are not supported"));
+        assert!(
+            messages.contains(
+                &"Skipped, only choice, missing word and matching questions are supported"
+            )
+        );
+    }
+
+    #[test]
+    fn unsupported_challenge_type() {
+        let result = GiftFormat.export(&ChallengeType::Informative(Default::default()));
+        assert!(matches!(
+            result,
+            Err(InterchangeError::Unsupported(kind)) if kind == "informative"
+        ));
+    }
+}
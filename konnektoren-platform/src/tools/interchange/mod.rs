@@ -0,0 +1,36 @@
+mod error;
+mod gift;
+mod qti;
+mod report;
+mod xml;
+
+pub use error::InterchangeError;
+pub use gift::GiftFormat;
+pub use qti::QtiFormat;
+pub use report::{Conversion, ConversionNote, ConversionReport};
+
+use konnektoren_core::challenges::ChallengeType;
+
+/// A question format used by learning management systems.
+///
+/// Multiple choice, gap fill, sort table and ordering challenges can be
+/// exported and imported again. Whatever a format cannot represent, such as
+/// hints or images, is listed in the [`ConversionReport`].
+pub trait InterchangeFormat {
+    fn export(
+        &self,
+        challenge_type: &ChallengeType,
+    ) -> Result<Conversion<String>, InterchangeError>;
+
+    fn import(&self, data: &str) -> Result<Conversion<ChallengeType>, InterchangeError>;
+}
+
+/// Turns a title into an identifier, e.g. `"Past Tense"` into `"past-tense"`.
+fn slug(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
@@ -0,0 +1,840 @@
+use super::report::{Conversion, ConversionReport};
+use super::xml::{Element, Node, normalize};
+use super::{InterchangeError, InterchangeFormat, slug};
+use konnektoren_core::challenges::{
+    ChallengeType, Gap, GapFill, GapFillQuestion, GradingPolicy, MultipleChoice,
+    MultipleChoiceOption, Ordering, OrderingItem, Question, SortTable, SortTableColumn,
+    SortTableRow,
+};
+use quick_xml::escape::escape;
+use std::fmt::Write;
+
+/// Prompt of an exported ordering.
+const ORDERING_PROMPT: &str = "Put the elements in order";
+const NAMESPACE: &str = "http://www.imsglobal.org/xsd/imsqti_v2p1";
+
+/// IMS Question and Test Interoperability 2.1.
+///
+/// A challenge is exported as one `assessmentItem` with an interaction per
+/// task: a `choiceInteraction` per multiple choice question, inline choice
+/// and text entry interactions inside the sentences of a gap fill, a
+/// `matchInteraction` per sort table column and an `orderInteraction` per
+/// ordering item. On import the first supported interaction decides the
+/// challenge type.
+pub struct QtiFormat;
+
+/// A `responseDeclaration` with its correct values.
+struct Response {
+    identifier: String,
+    cardinality: &'static str,
+    base_type: &'static str,
+    values: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    MultipleChoice,
+    GapFill,
+    SortTable,
+    Ordering,
+}
+
+impl Kind {
+    fn of(interaction: &Element) -> Option<Kind> {
+        match interaction.name.as_str() {
+            "choiceInteraction" => Some(Kind::MultipleChoice),
+            "inlineChoiceInteraction" | "textEntryInteraction" => Some(Kind::GapFill),
+            "matchInteraction" => Some(Kind::SortTable),
+            "orderInteraction" => Some(Kind::Ordering),
+            _ => None,
+        }
+    }
+}
+
+impl InterchangeFormat for QtiFormat {
+    fn export(
+        &self,
+        challenge_type: &ChallengeType,
+    ) -> Result<Conversion<String>, InterchangeError> {
+        let mut report = ConversionReport::default();
+        let mut responses = vec![];
+        let mut body = String::new();
+        match challenge_type {
+            ChallengeType::MultipleChoice(dataset) => {
+                export_multiple_choice(dataset, &mut responses, &mut body, &mut report)?
+            }
+            ChallengeType::GapFill(dataset) => {
+                export_gap_fill(dataset, &mut responses, &mut body, &mut report)?
+            }
+            ChallengeType::SortTable(dataset) => {
+                export_sort_table(dataset, &mut responses, &mut body, &mut report)?
+            }
+            ChallengeType::Ordering(dataset) => {
+                export_ordering(dataset, &mut responses, &mut body, &mut report)?
+            }
+            other => {
+                let kind: &'static str = other.into();
+                return Err(InterchangeError::Unsupported(kind.to_string()));
+            }
+        }
+
+        let mut out = String::new();
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            out,
+            r#"<assessmentItem xmlns="{}" identifier="{}" title="{}" adaptive="false" timeDependent="false">"#,
+            NAMESPACE,
+            escape(challenge_type.id()),
+            escape(challenge_type.name())
+        )?;
+        for response in &responses {
+            writeln!(
+                out,
+                r#"  <responseDeclaration identifier="{}" cardinality="{}" baseType="{}">"#,
+                response.identifier, response.cardinality, response.base_type
+            )?;
+            writeln!(out, "    <correctResponse>")?;
+            for value in &response.values {
+                writeln!(out, "      <value>{}</value>", escape(value))?;
+            }
+            writeln!(out, "    </correctResponse>")?;
+            writeln!(out, "  </responseDeclaration>")?;
+        }
+        writeln!(
+            out,
+            r#"  <outcomeDeclaration identifier="SCORE" cardinality="single" baseType="float"/>"#
+        )?;
+        writeln!(out, "  <itemBody>")?;
+        out.push_str(&body);
+        writeln!(out, "  </itemBody>")?;
+        writeln!(out, "</assessmentItem>")?;
+        Ok(Conversion { value: out, report })
+    }
+
+    fn import(&self, data: &str) -> Result<Conversion<ChallengeType>, InterchangeError> {
+        let mut report = ConversionReport::default();
+        let root = Element::parse(data)?;
+        if root.name != "assessmentItem" {
+            return Err(InterchangeError::Invalid {
+                format: "QTI",
+                message: format!("expected an assessmentItem, found {}", root.name),
+            });
+        }
+        let id = root.attribute("identifier").unwrap_or("qti").to_string();
+        let name = root
+            .attribute("title")
+            .map(str::to_string)
+            .unwrap_or_else(|| id.clone());
+        let responses = Responses::of(&root);
+        let Some(body) = root.child("itemBody") else {
+            return Err(InterchangeError::NoQuestions);
+        };
+
+        let interactions: Vec<&Element> = body
+            .descendants()
+            .into_iter()
+            .filter(|element| element.name.ends_with("Interaction"))
+            .collect();
+        let Some(kind) = interactions.iter().find_map(|i| Kind::of(i)) else {
+            return Err(InterchangeError::NoQuestions);
+        };
+        let mut tasks = vec![];
+        for (index, interaction) in interactions.into_iter().enumerate() {
+            let location = format!("interactions[{}]", index);
+            match Kind::of(interaction) {
+                Some(other) if other == kind => tasks.push((location, interaction)),
+                Some(_) => report.note(
+                    location,
+                    "Skipped, the interaction is of another kind than the first one",
+                ),
+                None => report.note(
+                    location,
+                    format!("Skipped, {} is not supported", interaction.name),
+                ),
+            }
+        }
+
+        let challenge_type = match kind {
+            Kind::MultipleChoice => {
+                import_multiple_choice(id, name, &tasks, &responses, &mut report)
+            }
+            Kind::GapFill => import_gap_fill(id, name, body, &responses, &mut report),
+            Kind::SortTable => import_sort_table(id, name, &tasks, &responses, &mut report),
+            Kind::Ordering => import_ordering(id, name, &tasks, &responses),
+        };
+        Ok(Conversion {
+            value: challenge_type,
+            report,
+        })
+    }
+}
+
+fn export_multiple_choice(
+    dataset: &MultipleChoice,
+    responses: &mut Vec<Response>,
+    body: &mut String,
+    report: &mut ConversionReport,
+) -> Result<(), InterchangeError> {
+    for (index, question) in dataset.questions.iter().enumerate() {
+        let location = format!("questions[{}]", index);
+        if question.image.is_some() {
+            report.note(&location, "Images are not supported");
+        }
+        if !question.help.is_empty() {
+            report.note(&location, "Help texts are not supported");
+        }
+        let identifier = format!("RESPONSE_{}", index);
+        writeln!(
+            body,
+            r#"    <choiceInteraction responseIdentifier="{}" shuffle="false" maxChoices="1">"#,
+            identifier
+        )?;
+        writeln!(
+            body,
+            "      <prompt>{}</prompt>",
+            escape(&question.question)
+        )?;
+        for option in &dataset.options {
+            writeln!(
+                body,
+                r#"      <simpleChoice identifier="CHOICE_{}">{}</simpleChoice>"#,
+                option.id,
+                escape(&option.name)
+            )?;
+        }
+        writeln!(body, "    </choiceInteraction>")?;
+        responses.push(Response {
+            identifier,
+            cardinality: "single",
+            base_type: "identifier",
+            values: vec![format!("CHOICE_{}", question.option)],
+        });
+    }
+    Ok(())
+}
+
+fn export_gap_fill(
+    dataset: &GapFill,
+    responses: &mut Vec<Response>,
+    body: &mut String,
+    report: &mut ConversionReport,
+) -> Result<(), InterchangeError> {
+    if !dataset.description.is_empty() {
+        report.note("", "The description is not supported");
+    }
+    for (index, question) in dataset.questions.iter().enumerate() {
+        let location = format!("questions[{}]", index);
+        let parts: Vec<&str> = question.sentence.split(GapFill::GAP_PLACEHOLDER).collect();
+        let mut gaps: Vec<&Gap> = question.gaps.iter().collect();
+        gaps.sort_by_key(|gap| gap.position);
+        if gaps.len() + 1 != parts.len() {
+            report.note(location, "Skipped, the gaps do not match the sentence");
+            continue;
+        }
+        if !question.hints.is_empty() {
+            report.note(&location, "Hints are not supported");
+        }
+        if !question.translation.is_empty() {
+            report.note(&location, "Translations are not supported");
+        }
+        if !question.explanation.is_empty() {
+            report.note(&location, "Explanations are not supported");
+        }
+
+        let mut paragraph = String::new();
+        for (gap_index, part) in parts.iter().enumerate() {
+            paragraph.push_str(&escape(*part));
+            let Some(gap) = gaps.get(gap_index) else {
+                continue;
+            };
+            let identifier = format!("RESPONSE_{}_{}", index, gap_index);
+            if gap.options.is_empty() {
+                write!(
+                    paragraph,
+                    r#"<textEntryInteraction responseIdentifier="{}" expectedLength="{}"/>"#,
+                    identifier,
+                    gap.correct.chars().count()
+                )?;
+                responses.push(Response {
+                    identifier,
+                    cardinality: "single",
+                    base_type: "string",
+                    values: vec![gap.correct.clone()],
+                });
+                continue;
+            }
+            let mut options = gap.options.clone();
+            if !options.contains(&gap.correct) {
+                options.push(gap.correct.clone());
+            }
+            write!(
+                paragraph,
+                r#"<inlineChoiceInteraction responseIdentifier="{}" shuffle="false">"#,
+                identifier
+            )?;
+            for (option_index, option) in options.iter().enumerate() {
+                write!(
+                    paragraph,
+                    r#"<inlineChoice identifier="CHOICE_{}">{}</inlineChoice>"#,
+                    option_index,
+                    escape(option)
+                )?;
+            }
+            paragraph.push_str("</inlineChoiceInteraction>");
+            let correct = options
+                .iter()
+                .position(|option| *option == gap.correct)
+                .unwrap_or_default();
+            responses.push(Response {
+                identifier,
+                cardinality: "single",
+                base_type: "identifier",
+                values: vec![format!("CHOICE_{}", correct)],
+            });
+        }
+        writeln!(body, "    <p>{}</p>", paragraph)?;
+    }
+    Ok(())
+}
+
+fn export_sort_table(
+    dataset: &SortTable,
+    responses: &mut Vec<Response>,
+    body: &mut String,
+    report: &mut ConversionReport,
+) -> Result<(), InterchangeError> {
+    if !dataset.description.is_empty() {
+        report.note("", "The description is not supported");
+    }
+    if dataset.columns.iter().any(|c| !c.description.is_empty()) {
+        report.note("columns", "Column descriptions are not supported");
+    }
+    let Some(first) = dataset.columns.first() else {
+        return Ok(());
+    };
+    let value =
+        |row: &SortTableRow, index: usize| row.values.get(index).cloned().unwrap_or_default();
+    for (index, column) in dataset.columns.iter().enumerate().skip(1) {
+        let identifier = format!("RESPONSE_{}", index);
+        writeln!(
+            body,
+            r#"    <matchInteraction responseIdentifier="{}" shuffle="false" maxAssociations="{}">"#,
+            identifier,
+            dataset.rows.len()
+        )?;
+        writeln!(
+            body,
+            "      <prompt>{} -&gt; {}</prompt>",
+            escape(&first.title),
+            escape(&column.title)
+        )?;
+        for (prefix, values) in [("ROW", 0), ("VALUE", index)] {
+            writeln!(body, "      <simpleMatchSet>")?;
+            for (row_index, row) in dataset.rows.iter().enumerate() {
+                writeln!(
+                    body,
+                    r#"        <simpleAssociableChoice identifier="{}_{}" matchMax="1">{}</simpleAssociableChoice>"#,
+                    prefix,
+                    row_index,
+                    escape(&value(row, values))
+                )?;
+            }
+            writeln!(body, "      </simpleMatchSet>")?;
+        }
+        writeln!(body, "    </matchInteraction>")?;
+        responses.push(Response {
+            identifier,
+            cardinality: "multiple",
+            base_type: "directedPair",
+            values: (0..dataset.rows.len())
+                .map(|row| format!("ROW_{} VALUE_{}", row, row))
+                .collect(),
+        });
+    }
+    Ok(())
+}
+
+fn export_ordering(
+    dataset: &Ordering,
+    responses: &mut Vec<Response>,
+    body: &mut String,
+    report: &mut ConversionReport,
+) -> Result<(), InterchangeError> {
+    if !dataset.description.is_empty() {
+        report.note("", "The description is not supported");
+    }
+    for (index, item) in dataset.items.iter().enumerate() {
+        let identifier = format!("RESPONSE_{}", index);
+        writeln!(
+            body,
+            r#"    <orderInteraction responseIdentifier="{}" shuffle="false">"#,
+            identifier
+        )?;
+        writeln!(body, "      <prompt>{}</prompt>", ORDERING_PROMPT)?;
+        for (element_index, element) in item.elements.iter().enumerate() {
+            writeln!(
+                body,
+                r#"      <simpleChoice identifier="ELEMENT_{}">{}</simpleChoice>"#,
+                element_index,
+                escape(element)
+            )?;
+        }
+        writeln!(body, "    </orderInteraction>")?;
+        responses.push(Response {
+            identifier,
+            cardinality: "ordered",
+            base_type: "identifier",
+            values: item
+                .correct_order
+                .iter()
+                .map(|element| format!("ELEMENT_{}", element))
+                .collect(),
+        });
+    }
+    Ok(())
+}
+
+/// The correct values of the response declarations of an item.
+struct Responses(Vec<(String, Vec<String>)>);
+
+impl Responses {
+    fn of(item: &Element) -> Self {
+        Responses(
+            item.children_named("responseDeclaration")
+                .map(|declaration| {
+                    let values = declaration
+                        .child("correctResponse")
+                        .map(|correct| correct.children_named("value").map(Element::text).collect())
+                        .unwrap_or_default();
+                    (
+                        declaration
+                            .attribute("identifier")
+                            .unwrap_or_default()
+                            .to_string(),
+                        values,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn values(&self, interaction: &Element) -> &[String] {
+        let identifier = interaction.attribute("responseIdentifier");
+        self.0
+            .iter()
+            .find(|(id, _)| Some(id.as_str()) == identifier)
+            .map(|(_, values)| values.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the identifiers and texts of the choices of an interaction.
+fn choices(parent: &Element, name: &str) -> Vec<(String, String)> {
+    parent
+        .children_named(name)
+        .map(|choice| {
+            (
+                choice
+                    .attribute("identifier")
+                    .unwrap_or_default()
+                    .to_string(),
+                choice.text(),
+            )
+        })
+        .collect()
+}
+
+fn choice_text<'a>(choices: &'a [(String, String)], identifier: &str) -> Option<&'a str> {
+    choices
+        .iter()
+        .find(|(id, _)| id == identifier)
+        .map(|(_, text)| text.as_str())
+}
+
+fn import_multiple_choice(
+    id: String,
+    name: String,
+    tasks: &[(String, &Element)],
+    responses: &Responses,
+    report: &mut ConversionReport,
+) -> ChallengeType {
+    let mut options: Vec<MultipleChoiceOption> = vec![];
+    let mut questions = vec![];
+    for (location, interaction) in tasks {
+        let choices = choices(interaction, "simpleChoice");
+        for (_, text) in &choices {
+            if !options.iter().any(|o| &o.name == text) {
+                options.push(MultipleChoiceOption {
+                    id: options.len(),
+                    name: text.clone(),
+                });
+            }
+        }
+        if choices.len() != options.len() {
+            report.note(location, "The options of all questions are merged");
+        }
+        let values = responses.values(interaction);
+        let Some(right) = values
+            .first()
+            .and_then(|value| choice_text(&choices, value))
+        else {
+            report.note(location, "Skipped, no correct answer");
+            continue;
+        };
+        if values.len() > 1 {
+            report.note(location, "Only the first correct answer is kept");
+        }
+        questions.push(Question {
+            question: interaction
+                .child("prompt")
+                .map(Element::text)
+                .unwrap_or_default(),
+            help: String::new(),
+            image: None,
+            option: options
+                .iter()
+                .position(|o| o.name == right)
+                .unwrap_or_default(),
+        });
+    }
+    ChallengeType::MultipleChoice(MultipleChoice {
+        id,
+        name,
+        lang: String::new(),
+        options,
+        questions,
+        level: None,
+        tags: vec![],
+    })
+}
+
+fn is_gap(element: &Element) -> bool {
+    Kind::of(element) == Some(Kind::GapFill)
+}
+
+fn import_gap_fill(
+    id: String,
+    name: String,
+    body: &Element,
+    responses: &Responses,
+    report: &mut ConversionReport,
+) -> ChallengeType {
+    // Every element holding gaps, usually a paragraph, is one sentence
+    let containers = std::iter::once(body)
+        .chain(body.descendants())
+        .filter(|element| element.elements().any(is_gap));
+    let mut questions = vec![];
+    for (index, container) in containers.enumerate() {
+        let mut sentence = String::new();
+        let mut gaps = vec![];
+        for child in &container.children {
+            match child {
+                Node::Text(text) => sentence.push_str(text),
+                Node::Element(element) if is_gap(element) => {
+                    sentence.push_str(GapFill::GAP_PLACEHOLDER);
+                    let values = responses.values(element);
+                    let (options, correct) = if element.name == "textEntryInteraction" {
+                        (vec![], values.first().cloned())
+                    } else {
+                        let choices = choices(element, "inlineChoice");
+                        let correct = values
+                            .first()
+                            .and_then(|value| choice_text(&choices, value))
+                            .map(str::to_string);
+                        (choices.into_iter().map(|(_, text)| text).collect(), correct)
+                    };
+                    if correct.is_none() {
+                        report.note(
+                            format!("questions[{}]", index),
+                            format!("Gap {} has no correct answer", gaps.len() + 1),
+                        );
+                    }
+                    gaps.push(Gap {
+                        position: gaps.len(),
+                        options,
+                        correct: correct.unwrap_or_default(),
+                    });
+                }
+                Node::Element(element) => {
+                    sentence.push(' ');
+                    sentence.push_str(&element.text());
+                    sentence.push(' ');
+                }
+            }
+        }
+        questions.push(GapFillQuestion {
+            sentence: normalize(&sentence),
+            gaps,
+            hints: vec![],
+            translation: String::new(),
+            explanation: String::new(),
+        });
+    }
+    ChallengeType::GapFill(GapFill {
+        id,
+        name,
+        description: String::new(),
+        lang: String::new(),
+        questions,
+        grading: GradingPolicy::exact(),
+        level: None,
+        tags: vec![],
+    })
+}
+
+fn import_sort_table(
+    id: String,
+    name: String,
+    tasks: &[(String, &Element)],
+    responses: &Responses,
+    report: &mut ConversionReport,
+) -> ChallengeType {
+    let column = |title: &str, index: usize| SortTableColumn {
+        id: if slug(title).is_empty() {
+            format!("column-{}", index + 1)
+        } else {
+            slug(title)
+        },
+        title: title.to_string(),
+        description: String::new(),
+    };
+    let titles = |interaction: &Element| {
+        let prompt = interaction
+            .child("prompt")
+            .map(Element::text)
+            .unwrap_or_default();
+        prompt
+            .split_once("->")
+            .map(|(left, right)| (left.trim().to_string(), right.trim().to_string()))
+            .unwrap_or((String::new(), prompt))
+    };
+    let sets = |interaction: &Element| {
+        let mut sets = interaction
+            .children_named("simpleMatchSet")
+            .map(|set| choices(set, "simpleAssociableChoice"));
+        (
+            sets.next().unwrap_or_default(),
+            sets.next().unwrap_or_default(),
+        )
+    };
+
+    let first = tasks[0].1;
+    let mut columns = vec![column(&titles(first).0, 0)];
+    let mut rows: Vec<SortTableRow> = sets(first)
+        .0
+        .into_iter()
+        .enumerate()
+        .map(|(id, (_, text))| SortTableRow {
+            id,
+            values: vec![text],
+        })
+        .collect();
+
+    for (index, (location, interaction)) in tasks.iter().enumerate() {
+        columns.push(column(&titles(interaction).1, index + 1));
+        let (sources, targets) = sets(interaction);
+        let pairs: Vec<(&str, &str)> = responses
+            .values(interaction)
+            .iter()
+            .filter_map(|pair| pair.split_once(' '))
+            .collect();
+        for row in rows.iter_mut() {
+            let value = sources
+                .iter()
+                .find(|(_, text)| *text == row.values[0])
+                .and_then(|(source, _)| pairs.iter().find(|(left, _)| *left == source.as_str()))
+                .and_then(|(_, target)| choice_text(&targets, target.trim()));
+            if value.is_none() {
+                report.note(location, format!("No match for '{}'", row.values[0]));
+            }
+            row.values.push(value.unwrap_or_default().to_string());
+        }
+    }
+    ChallengeType::SortTable(SortTable {
+        id,
+        name,
+        description: String::new(),
+        columns,
+        rows,
+        level: None,
+        tags: vec![],
+    })
+}
+
+fn import_ordering(
+    id: String,
+    name: String,
+    tasks: &[(String, &Element)],
+    responses: &Responses,
+) -> ChallengeType {
+    let items = tasks
+        .iter()
+        .map(|(_, interaction)| {
+            let choices = choices(interaction, "simpleChoice");
+            OrderingItem {
+                correct_order: responses
+                    .values(interaction)
+                    .iter()
+                    .filter_map(|value| choices.iter().position(|(id, _)| id == value))
+                    .collect(),
+                elements: choices.into_iter().map(|(_, text)| text).collect(),
+            }
+        })
+        .collect();
+    ChallengeType::Ordering(Ordering {
+        id,
+        name,
+        description: String::new(),
+        items,
+        level: None,
+        tags: vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(challenge_type: &ChallengeType) -> (ChallengeType, ConversionReport) {
+        let exported = QtiFormat.export(challenge_type).unwrap();
+        let imported = QtiFormat.import(&exported.value).unwrap();
+        (imported.value, exported.report)
+    }
+
+    #[test]
+    fn multiple_choice_round_trip() {
+        let challenge_type = ChallengeType::default();
+        let (imported, report) = round_trip(&challenge_type);
+        let (ChallengeType::MultipleChoice(original), ChallengeType::MultipleChoice(imported)) =
+            (&challenge_type, &imported)
+        else {
+            panic!("expected multiple choice challenges");
+        };
+        assert_eq!(imported.id, original.id);
+        assert_eq!(imported.name, original.name);
+        assert_eq!(imported.options, original.options);
+        assert_eq!(imported.questions.len(), original.questions.len());
+        for (original, imported) in original.questions.iter().zip(&imported.questions) {
+            assert_eq!(imported.question, original.question);
+            assert_eq!(imported.option, original.option);
+        }
+        assert!(
+            report
+                .notes
+                .iter()
+                .all(|note| note.message == "Help texts are not supported")
+        );
+    }
+
+    #[test]
+    fn gap_fill_round_trip() {
+        let original = GapFill::default();
+        let (imported, report) = round_trip(&ChallengeType::GapFill(original.clone()));
+        let ChallengeType::GapFill(imported) = imported else {
+            panic!("expected a gap fill challenge");
+        };
+        assert_eq!(imported.questions.len(), original.questions.len());
+        for (original, imported) in original.questions.iter().zip(&imported.questions) {
+            assert_eq!(imported.sentence, original.sentence);
+            assert_eq!(imported.gaps, original.gaps);
+        }
+        assert!(
+            report
+                .notes
+                .iter()
+                .any(|note| note.message == "Hints are not supported")
+        );
+    }
+
+    #[test]
+    fn sort_table_round_trip() {
+        let original = SortTable::default();
+        let (imported, _) = round_trip(&ChallengeType::SortTable(original.clone()));
+        let ChallengeType::SortTable(imported) = imported else {
+            panic!("expected a sort table challenge");
+        };
+        assert_eq!(imported.rows, original.rows);
+        assert_eq!(
+            imported
+                .columns
+                .iter()
+                .map(|c| &c.title)
+                .collect::<Vec<_>>(),
+            original
+                .columns
+                .iter()
+                .map(|c| &c.title)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn ordering_round_trip() {
+        let original = Ordering::default();
+        let (imported, _) = round_trip(&ChallengeType::Ordering(original.clone()));
+        let ChallengeType::Ordering(imported) = imported else {
+            panic!("expected an ordering challenge");
+        };
+        assert_eq!(imported.items, original.items);
+    }
+
+    #[test]
+    fn import_text_entry_item() {
+        let data = r#"<?xml version="1.0" encoding="UTF-8"?>
+<assessmentItem xmlns="http://www.imsglobal.org/xsd/imsqti_v2p1"
+    identifier="verben" title="Verben &amp; Zeiten" adaptive="false" timeDependent="false">
+  <responseDeclaration identifier="R1" cardinality="single" baseType="string">
+    <correctResponse><value>bin</value></correctResponse>
+  </responseDeclaration>
+  <responseDeclaration identifier="R2" cardinality="single" baseType="identifier">
+    <correctResponse><value>B</value></correctResponse>
+  </responseDeclaration>
+  <itemBody>
+    <p>Ich <textEntryInteraction responseIdentifier="R1"/> gestern <em>nach Hause</em>
+       <inlineChoiceInteraction responseIdentifier="R2">
+         <inlineChoice identifier="A">gehen</inlineChoice>
+         <inlineChoice identifier="B">gegangen</inlineChoice>
+       </inlineChoiceInteraction>.</p>
+    <extendedTextInteraction responseIdentifier="R3"/>
+  </itemBody>
+</assessmentItem>"#;
+        let imported = QtiFormat.import(data).unwrap();
+        let ChallengeType::GapFill(dataset) = imported.value else {
+            panic!("expected a gap fill challenge");
+        };
+        assert_eq!(dataset.id, "verben");
+        assert_eq!(dataset.name, "Verben & Zeiten");
+        assert_eq!(dataset.questions.len(), 1);
+        let question = &dataset.questions[0];
+        assert_eq!(question.sentence, "Ich __ gestern nach Hause __.");
+        assert_eq!(question.gaps[0].correct, "bin");
+        assert!(question.gaps[0].options.is_empty());
+        assert_eq!(question.gaps[1].correct, "gegangen");
+        assert_eq!(question.gaps[1].options, vec!["gehen", "gegangen"]);
+        assert_eq!(
+            imported.report.notes[0].message,
+            "Skipped, extendedTextInteraction is not supported"
+        );
+    }
+
+    #[test]
+    fn import_errors() {
+        assert!(matches!(
+            QtiFormat.import("<assessmentTest/>"),
+            Err(InterchangeError::Invalid { .. })
+        ));
+        assert!(matches!(
+            QtiFormat.import("<assessmentItem><itemBody><p>Text</p></itemBody></assessmentItem>"),
+            Err(InterchangeError::NoQuestions)
+        ));
+    }
+
+    #[test]
+    fn unsupported_challenge_type() {
+        let result = QtiFormat.export(&ChallengeType::Informative(Default::default()));
+        assert!(matches!(
+            result,
+            Err(InterchangeError::Unsupported(kind)) if kind == "informative"
+        ));
+    }
+}
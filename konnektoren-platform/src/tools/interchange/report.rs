@@ -0,0 +1,47 @@
+use serde::Serialize;
+use std::fmt;
+
+/// Something that could not be carried over by an import or export.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ConversionNote {
+    /// The task the note is about, e.g. `questions[2]`, or empty for the
+    /// whole challenge
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConversionNote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.location.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.location, self.message)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct ConversionReport {
+    pub notes: Vec<ConversionNote>,
+}
+
+impl ConversionReport {
+    /// Returns true if everything could be represented.
+    pub fn is_lossless(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    pub fn note(&mut self, location: impl Into<String>, message: impl Into<String>) {
+        self.notes.push(ConversionNote {
+            location: location.into(),
+            message: message.into(),
+        });
+    }
+}
+
+/// The result of an import or export together with what got lost on the way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion<T> {
+    pub value: T,
+    pub report: ConversionReport,
+}
@@ -0,0 +1,161 @@
+use super::InterchangeError;
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+}
+
+/// A parsed XML element. Namespace prefixes are dropped from all names.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    /// Parses a document and returns its root element.
+    pub fn parse(xml: &str) -> Result<Element, InterchangeError> {
+        let invalid = |message: &str| InterchangeError::Invalid {
+            format: "XML",
+            message: message.to_string(),
+        };
+        let mut reader = Reader::from_str(xml);
+        // The bottom of the stack collects the nodes of the document
+        let mut stack = vec![Element::default()];
+        loop {
+            let node = match reader.read_event()? {
+                Event::Start(start) => {
+                    stack.push(element(&start)?);
+                    continue;
+                }
+                Event::End(_) => match stack.pop() {
+                    Some(element) if !stack.is_empty() => Node::Element(element),
+                    _ => return Err(invalid("unexpected closing tag")),
+                },
+                Event::Empty(start) => Node::Element(element(&start)?),
+                Event::Text(text) => Node::Text(text.unescape()?.into_owned()),
+                Event::CData(data) => {
+                    Node::Text(String::from_utf8_lossy(&data.into_inner()).into_owned())
+                }
+                Event::Eof => break,
+                _ => continue,
+            };
+            if let Some(parent) = stack.last_mut() {
+                parent.children.push(node);
+            }
+        }
+        if stack.len() != 1 {
+            return Err(invalid("unclosed element"));
+        }
+        stack
+            .pop()
+            .and_then(|document| document.elements().next().cloned())
+            .ok_or_else(|| invalid("no root element"))
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.elements().filter(move |element| element.name == name)
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children_named(name).next()
+    }
+
+    /// Returns all elements below this one, in document order.
+    pub fn descendants(&self) -> Vec<&Element> {
+        let mut descendants = vec![];
+        for element in self.elements() {
+            descendants.push(element);
+            descendants.extend(element.descendants());
+        }
+        descendants
+    }
+
+    /// Returns the text of the element and all elements below it, with runs
+    /// of whitespace collapsed into single spaces.
+    pub fn text(&self) -> String {
+        normalize(&self.raw_text())
+    }
+
+    fn raw_text(&self) -> String {
+        self.children
+            .iter()
+            .map(|child| match child {
+                Node::Element(element) => element.raw_text(),
+                Node::Text(text) => text.clone(),
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn element(start: &BytesStart) -> Result<Element, InterchangeError> {
+    let mut attributes = vec![];
+    for attribute in start.attributes() {
+        let attribute = attribute?;
+        attributes.push((
+            String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned(),
+            attribute.unescape_value()?.into_owned(),
+        ));
+    }
+    Ok(Element {
+        name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+        attributes,
+        children: vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_document() {
+        let root = Element::parse(
+            r#"<?xml version="1.0"?>
+<qti:item xmlns:qti="urn:qti" qti:id="a&amp;b">
+  <p>Ich <b>bin</b> <gap id="1"/> &lt;hier&gt;</p>
+</qti:item>"#,
+        )
+        .unwrap();
+        assert_eq!(root.name, "item");
+        assert_eq!(root.attribute("id"), Some("a&b"));
+        let p = root.child("p").unwrap();
+        assert_eq!(p.text(), "Ich bin <hier>");
+        assert_eq!(
+            root.descendants()
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["p", "b", "gap"]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Element::parse("<a><b></a>").is_err());
+        assert!(Element::parse("<a>").is_err());
+        assert!(Element::parse("just text").is_err());
+    }
+}
@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LintError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Directory walk error: {0}")]
+    WalkError(#[from] walkdir::Error),
+    #[error("JSON serialization error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("YAML serialization error: {0}")]
+    YamlError(#[from] serde_yaml::Error),
+    #[error("Formatting error: {0}")]
+    FmtError(#[from] std::fmt::Error),
+}
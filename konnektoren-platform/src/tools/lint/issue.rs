@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+/// The check that found a [`LintIssue`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintRule {
+    /// The file is not valid YAML or does not match the expected structure
+    InvalidYaml,
+    /// Two challenges share the same id
+    DuplicateChallengeId,
+    /// A challenge config refers to a challenge that does not exist
+    UnknownChallenge,
+    /// A challenge config uses a variant that does not fit the challenge type
+    VariantMismatch,
+    /// A challenge is not used by any game path
+    UnusedChallenge,
+    /// A question refers to an option that does not exist
+    InvalidOption,
+    /// The correct answer is not one of the options
+    CorrectAnswerNotInOptions,
+    /// The gaps do not match the `__` placeholders of the sentence
+    GapPosition,
+    /// A dialog turn is spoken by an unknown speaker
+    UnknownSpeaker,
+    /// The correct option of a dialog turn does not exist
+    CorrectOptionOutOfRange,
+    /// The jumps between dialog turns are broken
+    DialogStructure,
+    /// A marked mistake does not match the words of the sentence
+    ErrorPosition,
+    /// A tag is empty or given more than once
+    InvalidTag,
+    /// A challenge config sets another CEFR level than its challenge type
+    LevelMismatch,
+    /// A challenge requires an unknown challenge, itself, or is part of a
+    /// cycle of prerequisites and can never unlock
+    InvalidPrerequisite,
+    /// An edge of a game path connects an unknown challenge or a challenge
+    /// with itself
+    InvalidEdge,
+    /// A time limit of zero seconds, or a task limit longer than the limit
+    /// of the whole challenge
+    InvalidTimeLimit,
+}
+
+impl LintRule {
+    pub fn severity(&self) -> LintSeverity {
+        match self {
+            LintRule::UnusedChallenge | LintRule::LevelMismatch => LintSeverity::Warning,
+            _ => LintSeverity::Error,
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            LintRule::InvalidYaml => "invalid-yaml",
+            LintRule::DuplicateChallengeId => "duplicate-challenge-id",
+            LintRule::UnknownChallenge => "unknown-challenge",
+            LintRule::VariantMismatch => "variant-mismatch",
+            LintRule::UnusedChallenge => "unused-challenge",
+            LintRule::InvalidOption => "invalid-option",
+            LintRule::CorrectAnswerNotInOptions => "correct-answer-not-in-options",
+            LintRule::GapPosition => "gap-position",
+            LintRule::UnknownSpeaker => "unknown-speaker",
+            LintRule::CorrectOptionOutOfRange => "correct-option-out-of-range",
+            LintRule::DialogStructure => "dialog-structure",
+            LintRule::ErrorPosition => "error-position",
+            LintRule::InvalidTag => "invalid-tag",
+            LintRule::LevelMismatch => "level-mismatch",
+            LintRule::InvalidPrerequisite => "invalid-prerequisite",
+            LintRule::InvalidEdge => "invalid-edge",
+            LintRule::InvalidTimeLimit => "invalid-time-limit",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Where an issue was found: the file, or `challenge:<id>` and
+/// `game-path:<id>` for content that was not loaded from a file, and the
+/// path of the field inside it, e.g. `questions[3].option`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LintLocation {
+    pub source: String,
+    pub field: String,
+}
+
+impl LintLocation {
+    pub fn new(source: impl Into<String>, field: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            field: field.into(),
+        }
+    }
+}
+
+impl fmt::Display for LintLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.field.is_empty() {
+            write!(f, "{}", self.source)
+        } else {
+            write!(f, "{}: {}", self.source, self.field)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LintIssue {
+    pub rule: LintRule,
+    pub severity: LintSeverity,
+    pub location: LintLocation,
+    pub message: String,
+}
+
+impl LintIssue {
+    pub fn new(rule: LintRule, location: LintLocation, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            severity: rule.severity(),
+            location,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} [{}]", self.location, self.message, self.rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unused_challenges_are_warnings() {
+        assert_eq!(LintRule::UnusedChallenge.severity(), LintSeverity::Warning);
+        assert_eq!(LintRule::UnknownSpeaker.severity(), LintSeverity::Error);
+    }
+
+    #[test]
+    fn rule_display_matches_serde() {
+        let json = serde_json::to_string(&LintRule::CorrectAnswerNotInOptions).unwrap();
+        assert_eq!(json, format!("\"{}\"", LintRule::CorrectAnswerNotInOptions));
+    }
+
+    #[test]
+    fn issue_display() {
+        let issue = LintIssue::new(
+            LintRule::InvalidOption,
+            LintLocation::new("articles.yml", "questions[2].option"),
+            "Option 7 does not exist",
+        );
+        assert_eq!(
+            issue.to_string(),
+            "articles.yml: questions[2].option: Option 7 does not exist [invalid-option]"
+        );
+    }
+}
@@ -0,0 +1,939 @@
+use super::error::LintError;
+use super::issue::{LintIssue, LintLocation, LintRule, LintSeverity};
+use konnektoren_core::challenges::{
+    ChallengeConfig, ChallengeFactory, ChallengeType, ChallengeVariant, ContextualChoice, Dialog,
+    ErrorCorrection, GapFill, MultipleChoice, TimeLimit,
+};
+use konnektoren_core::game::GamePath;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct LintReport {
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &LintIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == LintSeverity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &LintIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == LintSeverity::Warning)
+    }
+
+    pub fn assert_no_errors(&self) {
+        let errors: Vec<String> = self.errors().map(ToString::to_string).collect();
+        assert!(
+            errors.is_empty(),
+            "content lint errors:\n{}",
+            errors.join("\n")
+        );
+    }
+}
+
+/// Checks challenges and game paths for mistakes that parse fine but break
+/// the game, such as a question pointing at an option that does not exist or
+/// a game path referring to an unknown challenge.
+///
+/// # Example
+/// ```rust,ignore
+/// use konnektoren_platform::tools::ContentLinter;
+/// use konnektoren_core::challenges::ChallengeFactory;
+///
+/// let report = ContentLinter::new(ChallengeFactory::new())
+///     .load_dir("assets/")?
+///     .lint();
+/// report.assert_no_errors();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ContentLinter {
+    challenges: Vec<(ChallengeType, String)>,
+    game_paths: Vec<(GamePath, String)>,
+    load_issues: Vec<LintIssue>,
+}
+
+impl ContentLinter {
+    /// Creates a linter for the challenges of the factory. Their issues are
+    /// reported at `challenge:<id>`.
+    pub fn new(factory: ChallengeFactory) -> Self {
+        factory
+            .challenge_types
+            .into_iter()
+            .fold(Self::default(), |linter, challenge_type| {
+                let source = format!("challenge:{}", challenge_type.id());
+                linter.with_challenge(challenge_type, source)
+            })
+    }
+
+    pub fn with_challenge(
+        mut self,
+        challenge_type: ChallengeType,
+        source: impl Into<String>,
+    ) -> Self {
+        self.challenges.push((challenge_type, source.into()));
+        self
+    }
+
+    pub fn with_game_path(mut self, game_path: GamePath, source: impl Into<String>) -> Self {
+        self.game_paths.push((game_path, source.into()));
+        self
+    }
+
+    /// Loads all `.yml` and `.yaml` files below `dir`.
+    ///
+    /// Files starting with a tag like `!multiple-choice` are challenges, files
+    /// with a `challenges` list are game paths, and all other YAML files are
+    /// skipped. Files that fail to parse are reported when linting.
+    pub fn load_dir<P: AsRef<Path>>(mut self, dir: P) -> Result<Self, LintError> {
+        for entry in WalkDir::new(dir).sort_by_file_name() {
+            let entry = entry?;
+            let path = entry.path();
+            if !entry.file_type().is_file()
+                || path
+                    .extension()
+                    .is_none_or(|ext| ext != "yml" && ext != "yaml")
+            {
+                continue;
+            }
+            let content = std::fs::read_to_string(path)?;
+            self = self.load_str(&content, path.display().to_string());
+        }
+        Ok(self)
+    }
+
+    fn load_str(mut self, content: &str, source: String) -> Self {
+        let invalid = |error: serde_yaml::Error, source: String| {
+            LintIssue::new(
+                LintRule::InvalidYaml,
+                LintLocation::new(source, ""),
+                error.to_string(),
+            )
+        };
+        if content.trim_start().starts_with('!') {
+            return match serde_yaml::from_str::<ChallengeType>(content) {
+                Ok(challenge_type) => self.with_challenge(challenge_type, source),
+                Err(error) => {
+                    self.load_issues.push(invalid(error, source));
+                    self
+                }
+            };
+        }
+        match serde_yaml::from_str::<serde_yaml::Value>(content) {
+            Ok(value) if value.get("challenges").is_some() => {
+                match serde_yaml::from_value::<GamePath>(value) {
+                    Ok(game_path) => return self.with_game_path(game_path, source),
+                    Err(error) => self.load_issues.push(invalid(error, source)),
+                }
+            }
+            Ok(_) => tracing::debug!("skipping {}, neither a challenge nor a game path", source),
+            Err(error) => self.load_issues.push(invalid(error, source)),
+        }
+        self
+    }
+
+    /// Checks all loaded challenges and game paths. Challenges that no game
+    /// path uses are reported as warnings, as long as there is a game path.
+    pub fn lint(&self) -> LintReport {
+        let mut issues = self.load_issues.clone();
+
+        let mut ids = HashSet::new();
+        for (challenge_type, source) in &self.challenges {
+            if !ids.insert(challenge_type.id()) {
+                issues.push(LintIssue::new(
+                    LintRule::DuplicateChallengeId,
+                    LintLocation::new(source, "id"),
+                    format!(
+                        "Challenge id '{}' is used more than once",
+                        challenge_type.id()
+                    ),
+                ));
+            }
+            issues.extend(Self::check_challenge_type(challenge_type, source));
+        }
+
+        for (game_path, source) in &self.game_paths {
+            issues.extend(self.check_game_path(game_path, source));
+        }
+
+        if !self.game_paths.is_empty() {
+            let used: HashSet<&str> = self
+                .game_paths
+                .iter()
+                .flat_map(|(game_path, _)| &game_path.challenges)
+                .map(|config| config.challenge.as_str())
+                .collect();
+            for (challenge_type, source) in &self.challenges {
+                if !used.contains(challenge_type.id()) {
+                    issues.push(LintIssue::new(
+                        LintRule::UnusedChallenge,
+                        LintLocation::new(source, "id"),
+                        format!(
+                            "Challenge '{}' is not used by any game path",
+                            challenge_type.id()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        LintReport { issues }
+    }
+
+    /// Checks that every challenge config of the path refers to a known
+    /// challenge with a fitting variant.
+    pub fn check_game_path(&self, game_path: &GamePath, source: &str) -> Vec<LintIssue> {
+        let mut issues = vec![];
+        let mut ids = HashSet::new();
+        for (index, config) in game_path.challenges.iter().enumerate() {
+            let location =
+                |field: &str| LintLocation::new(source, format!("challenges[{}].{}", index, field));
+            if !ids.insert(config.id.as_str()) {
+                issues.push(LintIssue::new(
+                    LintRule::DuplicateChallengeId,
+                    location("id"),
+                    format!("Challenge config id '{}' is used more than once", config.id),
+                ));
+            }
+            let challenge_type = self
+                .challenges
+                .iter()
+                .map(|(challenge_type, _)| challenge_type)
+                .find(|challenge_type| challenge_type.id() == config.challenge);
+            match (challenge_type, &config.variant) {
+                (None, _) => issues.push(LintIssue::new(
+                    LintRule::UnknownChallenge,
+                    location("challenge"),
+                    format!("Unknown challenge '{}'", config.challenge),
+                )),
+                (Some(challenge_type), Some(variant)) if !variant_fits(variant, challenge_type) => {
+                    let name: &'static str = variant.clone().into();
+                    issues.push(LintIssue::new(
+                        LintRule::VariantMismatch,
+                        location("variant"),
+                        format!(
+                            "Variant '{}' does not fit challenge '{}'",
+                            name, config.challenge
+                        ),
+                    ));
+                }
+                _ => {}
+            }
+            if let (Some(level), Some(challenge_type)) = (config.level, challenge_type)
+                && let Some(expected) = challenge_type.level()
+                && level != expected
+            {
+                issues.push(LintIssue::new(
+                    LintRule::LevelMismatch,
+                    location("level"),
+                    format!(
+                        "Level {} differs from level {} of challenge '{}'",
+                        level, expected, config.challenge
+                    ),
+                ));
+            }
+            issues.extend(check_tags(
+                &config.tags,
+                source,
+                &format!("challenges[{}].tags", index),
+            ));
+            if let Some(time_limit) = &config.time_limit {
+                issues.extend(check_time_limit(
+                    time_limit,
+                    source,
+                    &format!("challenges[{}].time_limit", index),
+                ));
+            }
+        }
+        issues.extend(check_prerequisites(game_path, source));
+        issues.extend(check_edges(game_path, source));
+        issues
+    }
+
+    /// Checks the tasks of a single challenge.
+    pub fn check_challenge_type(challenge_type: &ChallengeType, source: &str) -> Vec<LintIssue> {
+        let mut issues = check_tags(challenge_type.tags(), source, "tags");
+        issues.extend(match challenge_type {
+            ChallengeType::MultipleChoice(dataset) => check_multiple_choice(dataset, source),
+            ChallengeType::ContextualChoice(dataset) => check_contextual_choice(dataset, source),
+            ChallengeType::GapFill(dataset) => check_gap_fill(dataset, source),
+            ChallengeType::Dialog(dataset) => check_dialog(dataset, source),
+            ChallengeType::ErrorCorrection(dataset) => check_error_correction(dataset, source),
+            _ => vec![],
+        });
+        issues
+    }
+}
+
+/// Checks that every prerequisite exists in the game path and that no
+/// challenge depends on itself, directly or through other challenges.
+fn check_prerequisites(game_path: &GamePath, source: &str) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    for (index, config) in game_path.challenges.iter().enumerate() {
+        for (i, required) in config.requires.iter().enumerate() {
+            let message = if *required == config.id {
+                format!("Challenge '{}' requires itself", config.id)
+            } else if game_path.get_challenge_config(required).is_none() {
+                format!("Unknown prerequisite '{}'", required)
+            } else {
+                continue;
+            };
+            issues.push(LintIssue::new(
+                LintRule::InvalidPrerequisite,
+                LintLocation::new(source, format!("challenges[{}].requires[{}]", index, i)),
+                message,
+            ));
+        }
+        if requires_transitively(game_path, config, &config.id) {
+            issues.push(LintIssue::new(
+                LintRule::InvalidPrerequisite,
+                LintLocation::new(source, format!("challenges[{}].requires", index)),
+                format!("Challenge '{}' is part of a prerequisite cycle", config.id),
+            ));
+        }
+    }
+    issues
+}
+
+fn check_edges(game_path: &GamePath, source: &str) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    for (index, edge) in game_path.edges.iter().enumerate() {
+        for (field, id) in [("from", &edge.from), ("to", &edge.to)] {
+            if game_path.get_challenge_config(id).is_none() {
+                issues.push(LintIssue::new(
+                    LintRule::InvalidEdge,
+                    LintLocation::new(source, format!("edges[{}].{}", index, field)),
+                    format!("Unknown challenge '{}'", id),
+                ));
+            }
+        }
+        if edge.from == edge.to {
+            issues.push(LintIssue::new(
+                LintRule::InvalidEdge,
+                LintLocation::new(source, format!("edges[{}]", index)),
+                format!("Challenge '{}' is connected with itself", edge.from),
+            ));
+        }
+    }
+    issues
+}
+
+fn check_time_limit(time_limit: &TimeLimit, source: &str, field: &str) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    for (name, seconds) in [
+        ("challenge_seconds", time_limit.challenge_seconds),
+        ("task_seconds", time_limit.task_seconds),
+    ] {
+        if seconds == Some(0) {
+            issues.push(LintIssue::new(
+                LintRule::InvalidTimeLimit,
+                LintLocation::new(source, format!("{}.{}", field, name)),
+                "A time limit of zero seconds can never be met",
+            ));
+        }
+    }
+    if let (Some(challenge), Some(task)) = (time_limit.challenge_seconds, time_limit.task_seconds)
+        && task > challenge
+    {
+        issues.push(LintIssue::new(
+            LintRule::InvalidTimeLimit,
+            LintLocation::new(source, format!("{}.task_seconds", field)),
+            format!(
+                "The task limit of {}s is longer than the challenge limit of {}s",
+                task, challenge
+            ),
+        ));
+    }
+    issues
+}
+
+/// Returns `true` if `id` is reachable through the prerequisites of other
+/// challenges of `config`.
+fn requires_transitively(game_path: &GamePath, config: &ChallengeConfig, id: &str) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack: Vec<&str> = config
+        .requires
+        .iter()
+        .map(String::as_str)
+        .filter(|required| *required != id)
+        .collect();
+    while let Some(current) = stack.pop() {
+        if current == id {
+            return true;
+        }
+        if !visited.insert(current) {
+            continue;
+        }
+        if let Some(required) = game_path.get_challenge_config(current) {
+            stack.extend(required.requires.iter().map(String::as_str));
+        }
+    }
+    false
+}
+
+fn check_tags(tags: &[String], source: &str, field: &str) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    let mut seen = HashSet::new();
+    for (index, tag) in tags.iter().enumerate() {
+        let location = LintLocation::new(source, format!("{}[{}]", field, index));
+        if tag.trim().is_empty() {
+            issues.push(LintIssue::new(
+                LintRule::InvalidTag,
+                location,
+                "Tag is empty",
+            ));
+        } else if !seen.insert(tag.trim().to_lowercase()) {
+            issues.push(LintIssue::new(
+                LintRule::InvalidTag,
+                location,
+                format!("Tag '{}' is given more than once", tag),
+            ));
+        }
+    }
+    issues
+}
+
+fn variant_fits(variant: &ChallengeVariant, challenge_type: &ChallengeType) -> bool {
+    use ChallengeVariant as V;
+    match challenge_type {
+        ChallengeType::MultipleChoice(_) => matches!(
+            variant,
+            V::MultipleChoice | V::MultipleChoiceCircle | V::MultipleChoice4
+        ),
+        ChallengeType::ContextualChoice(_) => matches!(variant, V::ContextualChoice),
+        ChallengeType::SortTable(_) => matches!(variant, V::SortTable),
+        ChallengeType::Informative(_) => {
+            matches!(variant, V::InformativeText | V::InformativeMarkdown)
+        }
+        ChallengeType::Custom(_) => matches!(variant, V::Custom | V::CustomPackage),
+        ChallengeType::Dialog(_) => matches!(variant, V::DialogObserver | V::DialogQuiz),
+        ChallengeType::Matching(_) => matches!(variant, V::Matching),
+        ChallengeType::Translation(_) => matches!(variant, V::Translation),
+        ChallengeType::ErrorCorrection(_) => matches!(variant, V::ErrorCorrection),
+        ChallengeType::GapFill(_)
+        | ChallengeType::Ordering(_)
+        | ChallengeType::Vocabulary(_)
+        | ChallengeType::Placeholder(_) => false,
+    }
+}
+
+fn check_multiple_choice(dataset: &MultipleChoice, source: &str) -> Vec<LintIssue> {
+    let options: HashSet<usize> = dataset.options.iter().map(|option| option.id).collect();
+    dataset
+        .questions
+        .iter()
+        .enumerate()
+        .filter(|(_, question)| !options.contains(&question.option))
+        .map(|(index, question)| {
+            LintIssue::new(
+                LintRule::InvalidOption,
+                LintLocation::new(source, format!("questions[{}].option", index)),
+                format!("Option {} does not exist", question.option),
+            )
+        })
+        .collect()
+}
+
+fn check_contextual_choice(dataset: &ContextualChoice, source: &str) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    for (index, item) in dataset.items.iter().enumerate() {
+        for (choice_index, choice) in item.choices.iter().enumerate() {
+            if !choice.options.contains(&choice.correct_answer) {
+                issues.push(LintIssue::new(
+                    LintRule::CorrectAnswerNotInOptions,
+                    LintLocation::new(
+                        source,
+                        format!("items[{}].choices[{}].correct_answer", index, choice_index),
+                    ),
+                    format!("'{}' is not one of the options", choice.correct_answer),
+                ));
+            }
+        }
+    }
+    issues
+}
+
+fn check_gap_fill(dataset: &GapFill, source: &str) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    for (index, question) in dataset.questions.iter().enumerate() {
+        let placeholders = question.sentence.matches(GapFill::GAP_PLACEHOLDER).count();
+        if question.gaps.len() != placeholders {
+            issues.push(LintIssue::new(
+                LintRule::GapPosition,
+                LintLocation::new(source, format!("questions[{}].gaps", index)),
+                format!(
+                    "{} gaps for {} placeholders in the sentence",
+                    question.gaps.len(),
+                    placeholders
+                ),
+            ));
+        }
+
+        let mut positions = HashSet::new();
+        for (gap_index, gap) in question.gaps.iter().enumerate() {
+            let field = |name: &str| format!("questions[{}].gaps[{}].{}", index, gap_index, name);
+            if gap.position >= placeholders {
+                issues.push(LintIssue::new(
+                    LintRule::GapPosition,
+                    LintLocation::new(source, field("position")),
+                    format!(
+                        "Position {} is out of range, the sentence has {} placeholders",
+                        gap.position, placeholders
+                    ),
+                ));
+            } else if !positions.insert(gap.position) {
+                issues.push(LintIssue::new(
+                    LintRule::GapPosition,
+                    LintLocation::new(source, field("position")),
+                    format!("Position {} is used by more than one gap", gap.position),
+                ));
+            }
+            // Gaps without options take a typed answer
+            if !gap.options.is_empty() && !gap.options.contains(&gap.correct) {
+                issues.push(LintIssue::new(
+                    LintRule::CorrectAnswerNotInOptions,
+                    LintLocation::new(source, field("correct")),
+                    format!("'{}' is not one of the options", gap.correct),
+                ));
+            }
+        }
+    }
+    issues
+}
+
+fn check_dialog(dataset: &Dialog, source: &str) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    for (index, turn) in dataset.turns.iter().enumerate() {
+        if dataset.speaker_by_id(&turn.speaker).is_none() {
+            issues.push(LintIssue::new(
+                LintRule::UnknownSpeaker,
+                LintLocation::new(source, format!("turns[{}].speaker", index)),
+                format!("Unknown speaker '{}'", turn.speaker),
+            ));
+        }
+        let options = turn.options.as_ref().map_or(0, |options| options.len());
+        if let Some(correct_option) = turn.correct_option
+            && correct_option >= options
+        {
+            issues.push(LintIssue::new(
+                LintRule::CorrectOptionOutOfRange,
+                LintLocation::new(source, format!("turns[{}].correct_option", index)),
+                format!(
+                    "Option {} is out of range, the turn has {} options",
+                    correct_option, options
+                ),
+            ));
+        }
+    }
+    if let Err(error) = dataset.validate() {
+        issues.push(LintIssue::new(
+            LintRule::DialogStructure,
+            LintLocation::new(source, "turns"),
+            error.to_string(),
+        ));
+    }
+    issues
+}
+
+fn check_error_correction(dataset: &ErrorCorrection, source: &str) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    for (index, sentence) in dataset.sentences.iter().enumerate() {
+        let words = sentence.words();
+        for (error_index, error) in sentence.errors.iter().enumerate() {
+            let end = error.position + error.text.split_whitespace().count();
+            let found = words.get(error.position..end).map(|words| words.join(" "));
+            let message = match found {
+                Some(found) if found == error.text => continue,
+                Some(found) => format!(
+                    "Expected '{}' at position {}, found '{}'",
+                    error.text, error.position, found
+                ),
+                None => format!(
+                    "Position {} is out of range, the sentence has {} words",
+                    error.position,
+                    words.len()
+                ),
+            };
+            issues.push(LintIssue::new(
+                LintRule::ErrorPosition,
+                LintLocation::new(
+                    source,
+                    format!("sentences[{}].errors[{}].position", index, error_index),
+                ),
+                message,
+            ));
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnektoren_core::challenges::CefrLevel;
+    use konnektoren_core::game::PathEdge;
+
+    fn challenge(yaml: &str) -> ChallengeType {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn rules(issues: &[LintIssue]) -> Vec<LintRule> {
+        issues.iter().map(|issue| issue.rule).collect()
+    }
+
+    #[test]
+    fn default_content_has_no_errors() {
+        let report = ContentLinter::new(ChallengeFactory::default())
+            .with_game_path(GamePath::default(), "konnektoren_path.yml")
+            .lint();
+        report.assert_no_errors();
+        assert_eq!(report.warnings().count(), 0);
+    }
+
+    #[test]
+    fn multiple_choice_with_unknown_option() {
+        let challenge_type = challenge(
+            r#"!multiple-choice
+id: "mc"
+name: "MC"
+lang: "de"
+options:
+  - id: 0
+    name: "und"
+questions:
+  - question: "Ich komme, ___ du kommst."
+    help: ""
+    option: 0
+  - question: "Ich bleibe, ___ es regnet."
+    help: ""
+    option: 3
+"#,
+        );
+        let issues = ContentLinter::check_challenge_type(&challenge_type, "mc.yml");
+        assert_eq!(rules(&issues), vec![LintRule::InvalidOption]);
+        assert_eq!(
+            issues[0].location,
+            LintLocation::new("mc.yml", "questions[1].option")
+        );
+    }
+
+    #[test]
+    fn gap_fill_with_wrong_positions() {
+        let challenge_type = challenge(
+            r#"!gap-fill
+id: "gaps"
+name: "Gaps"
+description: ""
+lang: "de"
+questions:
+  - sentence: "Ich __ nach Berlin gefahren."
+    gaps:
+      - position: 0
+        options: ["bin", "habe"]
+        correct: "war"
+      - position: 1
+        options: []
+        correct: "gestern"
+    hints: []
+    translation: ""
+    explanation: ""
+"#,
+        );
+        let issues = ContentLinter::check_challenge_type(&challenge_type, "gaps.yml");
+        assert_eq!(
+            rules(&issues),
+            vec![
+                LintRule::GapPosition,
+                LintRule::CorrectAnswerNotInOptions,
+                LintRule::GapPosition,
+            ]
+        );
+        assert_eq!(issues[2].location.field, "questions[0].gaps[1].position");
+    }
+
+    #[test]
+    fn dialog_with_unknown_speaker_and_option() {
+        let challenge_type = challenge(
+            r#"!dialog
+id: "dialog"
+name: "Dialog"
+description: ""
+lang: "de"
+speakers:
+  - id: "anna"
+    name: "Anna"
+  - id: "ben"
+    name: "Ben"
+turns:
+  - speaker: "anna"
+    text: "Hallo!"
+  - speaker: "carl"
+    text: "Hallo Anna!"
+    options: ["Hallo Anna!", "Tschüss!"]
+    correct_option: 2
+"#,
+        );
+        let issues = ContentLinter::check_challenge_type(&challenge_type, "dialog.yml");
+        assert_eq!(
+            rules(&issues),
+            vec![LintRule::UnknownSpeaker, LintRule::CorrectOptionOutOfRange]
+        );
+        assert_eq!(issues[1].location.field, "turns[1].correct_option");
+    }
+
+    #[test]
+    fn error_correction_with_wrong_position() {
+        let challenge_type = challenge(
+            r#"!error-correction
+id: "errors"
+name: "Errors"
+description: ""
+lang: "de"
+sentences:
+  - sentence: "Der Mädchen spielt im Garten."
+    errors:
+      - position: 1
+        text: "Der"
+        correction: "Das"
+        kind: article
+      - position: 9
+        text: "Garten"
+        correction: "Garten"
+        kind: other
+"#,
+        );
+        let issues = ContentLinter::check_challenge_type(&challenge_type, "errors.yml");
+        assert_eq!(
+            rules(&issues),
+            vec![LintRule::ErrorPosition, LintRule::ErrorPosition]
+        );
+        assert_eq!(
+            issues[0].message,
+            "Expected 'Der' at position 1, found 'Mädchen'"
+        );
+    }
+
+    #[test]
+    fn game_path_with_unknown_challenge_and_variant() {
+        let mut game_path = GamePath::default();
+        game_path.challenges = vec![
+            ChallengeConfig {
+                id: "one".to_string(),
+                challenge: "missing".to_string(),
+                ..ChallengeConfig::default()
+            },
+            ChallengeConfig {
+                id: "two".to_string(),
+                challenge: "konnektoren".to_string(),
+                variant: Some(ChallengeVariant::DialogQuiz),
+                ..ChallengeConfig::default()
+            },
+            ChallengeConfig {
+                id: "two".to_string(),
+                challenge: "konnektoren".to_string(),
+                variant: Some(ChallengeVariant::MultipleChoice4),
+                ..ChallengeConfig::default()
+            },
+        ];
+        let linter = ContentLinter::new(ChallengeFactory::default());
+        let issues = linter.check_game_path(&game_path, "path.yml");
+        assert_eq!(
+            rules(&issues),
+            vec![
+                LintRule::UnknownChallenge,
+                LintRule::VariantMismatch,
+                LintRule::DuplicateChallengeId,
+            ]
+        );
+        assert_eq!(
+            issues[1].location,
+            LintLocation::new("path.yml", "challenges[1].variant")
+        );
+    }
+
+    #[test]
+    fn tags_and_levels() {
+        let mut factory = ChallengeFactory::default();
+        if let ChallengeType::MultipleChoice(dataset) = &mut factory.challenge_types[0] {
+            dataset.level = Some(CefrLevel::B1);
+            dataset.tags = vec!["konnektoren".to_string(), " ".to_string()];
+        }
+        let mut game_path = GamePath::default();
+        game_path.challenges = vec![ChallengeConfig {
+            challenge: "konnektoren".to_string(),
+            level: Some(CefrLevel::B2),
+            tags: vec!["grammar".to_string(), "Grammar".to_string()],
+            ..ChallengeConfig::default()
+        }];
+
+        let issues = ContentLinter::check_challenge_type(&factory.challenge_types[0], "mc.yml");
+        assert_eq!(rules(&issues), vec![LintRule::InvalidTag]);
+        assert_eq!(issues[0].location, LintLocation::new("mc.yml", "tags[1]"));
+
+        let linter = ContentLinter::new(factory);
+        let issues = linter.check_game_path(&game_path, "path.yml");
+        assert_eq!(
+            rules(&issues),
+            vec![LintRule::LevelMismatch, LintRule::InvalidTag]
+        );
+        assert_eq!(issues[0].severity, LintSeverity::Warning);
+        assert_eq!(
+            issues[1].location,
+            LintLocation::new("path.yml", "challenges[0].tags[1]")
+        );
+    }
+
+    #[test]
+    fn prerequisites() {
+        let linter = ContentLinter::new(ChallengeFactory::default());
+        let mut game_path = GamePath::default();
+        game_path.challenges[0].requires = vec!["konnektoren-1".to_string()];
+        game_path.challenges[1].requires = vec!["missing".to_string()];
+        game_path.challenges[2].requires = vec!["konnektoren-4".to_string()];
+        game_path.challenges[3].requires = vec!["konnektoren-3".to_string()];
+        game_path.challenges[4].requires = vec!["konnektoren-2".to_string()];
+
+        let issues = linter.check_game_path(&game_path, "path.yml");
+        assert!(
+            issues
+                .iter()
+                .all(|issue| issue.rule == LintRule::InvalidPrerequisite)
+        );
+        let locations: Vec<_> = issues
+            .iter()
+            .map(|issue| issue.location.field.as_str())
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                "challenges[0].requires[0]",
+                "challenges[1].requires[0]",
+                "challenges[2].requires",
+                "challenges[3].requires",
+            ]
+        );
+    }
+
+    #[test]
+    fn edges() {
+        let linter = ContentLinter::new(ChallengeFactory::default());
+        let mut game_path = GamePath::default();
+        game_path.edges = vec![
+            PathEdge::new("konnektoren-1", "konnektoren-2"),
+            PathEdge::new("konnektoren-2", "missing"),
+            PathEdge::new("konnektoren-3", "konnektoren-3"),
+        ];
+
+        let issues = linter.check_game_path(&game_path, "path.yml");
+        assert_eq!(rules(&issues), vec![LintRule::InvalidEdge; 2]);
+        assert_eq!(
+            issues[0].location,
+            LintLocation::new("path.yml", "edges[1].to")
+        );
+        assert_eq!(
+            issues[1].location,
+            LintLocation::new("path.yml", "edges[2]")
+        );
+    }
+
+    #[test]
+    fn time_limits() {
+        let linter = ContentLinter::new(ChallengeFactory::default());
+        let mut game_path = GamePath::default();
+        game_path.challenges[0].time_limit = Some(TimeLimit {
+            challenge_seconds: Some(60),
+            task_seconds: Some(15),
+            ..TimeLimit::default()
+        });
+        game_path.challenges[1].time_limit = Some(TimeLimit {
+            challenge_seconds: Some(10),
+            task_seconds: Some(0),
+            ..TimeLimit::default()
+        });
+        game_path.challenges[2].time_limit = Some(TimeLimit {
+            challenge_seconds: Some(10),
+            task_seconds: Some(20),
+            ..TimeLimit::default()
+        });
+
+        let issues = linter.check_game_path(&game_path, "path.yml");
+        assert_eq!(rules(&issues), vec![LintRule::InvalidTimeLimit; 2]);
+        assert_eq!(
+            issues[0].location,
+            LintLocation::new("path.yml", "challenges[1].time_limit.task_seconds")
+        );
+        assert_eq!(
+            issues[1].location,
+            LintLocation::new("path.yml", "challenges[2].time_limit.task_seconds")
+        );
+    }
+
+    #[test]
+    fn unused_challenges_are_reported_with_a_game_path() {
+        let linter = ContentLinter::new(ChallengeFactory::default());
+        assert_eq!(linter.lint().warnings().count(), 0);
+
+        let mut game_path = GamePath::default();
+        game_path
+            .challenges
+            .retain(|c| c.challenge == "konnektoren");
+        let report = linter.with_game_path(game_path, "path.yml").lint();
+        assert!(!report.has_errors());
+        assert_eq!(report.warnings().count(), 4);
+        assert_eq!(
+            report.warnings().next().unwrap().location,
+            LintLocation::new("challenge:articles", "id")
+        );
+    }
+
+    #[test]
+    fn load_dir_reports_file_locations() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("articles.yml"),
+            include_str!("../../../../konnektoren-core/assets/articles-1.yml"),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("path.yml"),
+            r#"id: "path"
+name: "Path"
+challenges:
+  - id: "articles-1"
+    name: "Articles"
+    description: ""
+    challenge: "articles"
+    variant: sort-table
+    tasks: 5
+    unlock_points: 0
+"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("broken.yml"), "!multiple-choice\nid: [").unwrap();
+        std::fs::write(dir.path().join("other.yml"), "title: not content\n").unwrap();
+
+        let report = ContentLinter::default()
+            .load_dir(dir.path())
+            .unwrap()
+            .lint();
+        let path_file = dir.path().join("path.yml").display().to_string();
+        assert_eq!(
+            rules(&report.issues),
+            vec![LintRule::InvalidYaml, LintRule::VariantMismatch]
+        );
+        assert_eq!(
+            report.issues[1].location,
+            LintLocation::new(path_file, "challenges[0].variant")
+        );
+    }
+}
@@ -0,0 +1,11 @@
+mod error;
+mod issue;
+mod linter;
+mod report_format;
+
+pub use error::LintError;
+pub use issue::{LintIssue, LintLocation, LintRule, LintSeverity};
+pub use linter::{ContentLinter, LintReport};
+pub use report_format::{
+    LintHumanFormatter, LintJsonFormatter, LintReportFormatter, LintYamlFormatter,
+};
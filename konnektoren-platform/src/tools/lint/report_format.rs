@@ -0,0 +1,140 @@
+use super::error::LintError;
+use super::issue::LintIssue;
+use super::linter::LintReport;
+use serde::Serialize;
+
+pub trait LintReportFormatter {
+    fn format(&self, report: &LintReport) -> Result<String, LintError>;
+}
+
+pub struct LintYamlFormatter;
+pub struct LintJsonFormatter;
+pub struct LintHumanFormatter;
+
+/// The report with its issue counts, as written by the YAML and JSON
+/// formatters.
+#[derive(Serialize)]
+struct LintSummary<'a> {
+    errors: usize,
+    warnings: usize,
+    issues: &'a [LintIssue],
+}
+
+impl<'a> From<&'a LintReport> for LintSummary<'a> {
+    fn from(report: &'a LintReport) -> Self {
+        Self {
+            errors: report.errors().count(),
+            warnings: report.warnings().count(),
+            issues: &report.issues,
+        }
+    }
+}
+
+impl LintReportFormatter for LintYamlFormatter {
+    fn format(&self, report: &LintReport) -> Result<String, LintError> {
+        serde_yaml::to_string(&LintSummary::from(report)).map_err(LintError::YamlError)
+    }
+}
+
+impl LintReportFormatter for LintJsonFormatter {
+    fn format(&self, report: &LintReport) -> Result<String, LintError> {
+        serde_json::to_string_pretty(&LintSummary::from(report)).map_err(LintError::JsonError)
+    }
+}
+
+impl LintReportFormatter for LintHumanFormatter {
+    fn format(&self, report: &LintReport) -> Result<String, LintError> {
+        use std::fmt::Write;
+        let mut out = String::new();
+
+        writeln!(out, "\nContent Lint Report").map_err(LintError::FmtError)?;
+        writeln!(out, "===================").map_err(LintError::FmtError)?;
+
+        for (title, issues) in [
+            ("Errors", report.errors().collect::<Vec<_>>()),
+            ("Warnings", report.warnings().collect::<Vec<_>>()),
+        ] {
+            if issues.is_empty() {
+                continue;
+            }
+            writeln!(out, "\n{}:", title).map_err(LintError::FmtError)?;
+            writeln!(out, "-------------------").map_err(LintError::FmtError)?;
+            for issue in issues {
+                writeln!(out, "  - {}", issue).map_err(LintError::FmtError)?;
+            }
+        }
+
+        writeln!(out, "\nSummary:").map_err(LintError::FmtError)?;
+        writeln!(out, "--------").map_err(LintError::FmtError)?;
+        writeln!(out, "Errors: {}", report.errors().count()).map_err(LintError::FmtError)?;
+        writeln!(out, "Warnings: {}", report.warnings().count()).map_err(LintError::FmtError)?;
+        writeln!(
+            out,
+            "Overall status: {}",
+            if report.has_errors() {
+                "❌ Content has errors"
+            } else {
+                "✅ Content is valid"
+            }
+        )
+        .map_err(LintError::FmtError)?;
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::lint::{LintLocation, LintRule};
+
+    fn report() -> LintReport {
+        LintReport {
+            issues: vec![
+                LintIssue::new(
+                    LintRule::UnknownSpeaker,
+                    LintLocation::new("dialog.yml", "turns[1].speaker"),
+                    "Unknown speaker 'carl'",
+                ),
+                LintIssue::new(
+                    LintRule::UnusedChallenge,
+                    LintLocation::new("challenge:articles", "id"),
+                    "Challenge 'articles' is not used by any game path",
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn human_formatter() {
+        let out = LintHumanFormatter.format(&report()).unwrap();
+        assert!(out.contains(
+            "  - dialog.yml: turns[1].speaker: Unknown speaker 'carl' [unknown-speaker]"
+        ));
+        assert!(out.contains("Warnings:"));
+        assert!(out.contains("❌ Content has errors"));
+
+        let out = LintHumanFormatter.format(&LintReport::default()).unwrap();
+        assert!(!out.contains("Errors:"));
+        assert!(out.contains("✅ Content is valid"));
+    }
+
+    #[test]
+    fn json_formatter() {
+        let out = LintJsonFormatter.format(&report()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["errors"], 1);
+        assert_eq!(value["warnings"], 1);
+        assert_eq!(value["issues"][0]["rule"], "unknown-speaker");
+        assert_eq!(value["issues"][0]["location"]["field"], "turns[1].speaker");
+    }
+
+    #[test]
+    fn yaml_formatter() {
+        let formatter: Box<dyn LintReportFormatter> = Box::new(LintYamlFormatter);
+        let out = formatter.format(&report()).unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&out).unwrap();
+        assert_eq!(value["errors"], 1);
+        assert_eq!(value["issues"][1]["severity"], "warning");
+    }
+}
@@ -1,5 +1,9 @@
 mod i18n;
+mod import;
+mod interchange;
+mod lint;
 pub mod manifest;
+mod scorm;
 
 #[cfg(feature = "schema")]
 mod schema;
@@ -9,10 +13,26 @@ pub use i18n::{
     I18nReport, I18nReportError, I18nReportFormatter, I18nYamlFormatter, LanguageStats,
 };
 
+pub use import::{
+    AnkiImporter, Column, CsvImporter, ImportConfig, ImportError, ImportTarget, RowError,
+};
+
+pub use interchange::{
+    Conversion, ConversionNote, ConversionReport, GiftFormat, InterchangeError, InterchangeFormat,
+    QtiFormat,
+};
+
+pub use lint::{
+    ContentLinter, LintError, LintHumanFormatter, LintIssue, LintJsonFormatter, LintLocation,
+    LintReport, LintReportFormatter, LintRule, LintSeverity, LintYamlFormatter,
+};
+
 pub use manifest::{ManifestExporter, ManifestToolError};
 
 #[cfg(feature = "manifest")]
 pub use manifest::ManifestBuilder;
 
+pub use scorm::{ScormError, ScormExporter, ScormVersion};
+
 #[cfg(feature = "schema")]
 pub use schema::{SchemaExporter, SchemaFormat};
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ScormError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Directory walk error: {0}")]
+    WalkError(#[from] walkdir::Error),
+    #[error("ZIP error: {0}")]
+    ZipError(#[from] zip::result::ZipError),
+    #[error("YAML serialization error: {0}")]
+    YamlError(#[from] serde_yaml::Error),
+    #[error("Formatting error: {0}")]
+    FmtError(#[from] std::fmt::Error),
+    #[error("Challenge type not found: {0}")]
+    ChallengeNotFound(String),
+    #[error("The game path has no challenges")]
+    EmptyGamePath,
+    #[error("No player script set to run the challenges")]
+    MissingPlayer,
+}
@@ -0,0 +1,367 @@
+use super::ScormError;
+use super::manifest::{Sco, ScormVersion, write_manifest};
+use konnektoren_core::challenges::ChallengeFactory;
+use konnektoren_core::game::GamePath;
+use quick_xml::escape::escape;
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::io::{Seek, Write};
+use std::path::Path;
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// The runtime bridge every launch page loads.
+const BRIDGE: &str = "scorm.js";
+
+/// Exports a [`GamePath`] as a SCORM package.
+///
+/// Every challenge of the path becomes a SCO in `challenges/<id>/` with its
+/// `config.yml` and `challenge.yml`, the same layout a package read by
+/// `PackageReader` has, and a launch page. The launch page loads `scorm.js`,
+/// which talks to the LMS, and the player script, which runs the challenge
+/// and reports its [`LmsScore`](konnektoren_core::challenges::LmsScore).
+///
+/// The reward policy of the path is written into each `config.yml` and its
+/// one-star threshold becomes the mastery score in the manifest, so the LMS
+/// and the game agree on when a challenge is passed.
+pub struct ScormExporter {
+    factory: ChallengeFactory,
+    version: ScormVersion,
+    /// Shared files by their path below `assets/`
+    assets: BTreeMap<String, Vec<u8>>,
+    /// Path and content of the player script, required to export
+    player: Option<(String, Vec<u8>)>,
+}
+
+impl ScormExporter {
+    pub fn new(factory: ChallengeFactory) -> Self {
+        ScormExporter {
+            factory,
+            version: ScormVersion::default(),
+            assets: BTreeMap::new(),
+            player: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: ScormVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets the script that runs the challenges in the launch pages. It
+    /// reports the `konnektoren.lmsScore` that `KonnektorenJs::set_lms_score`
+    /// sets when a challenge is finished.
+    pub fn with_player(mut self, path: impl Into<String>, content: Vec<u8>) -> Self {
+        self.player = Some((path.into(), content));
+        self
+    }
+
+    /// Adds a file below `assets/`, e.g. an image used by a question.
+    pub fn with_asset(mut self, path: impl Into<String>, content: Vec<u8>) -> Self {
+        self.assets.insert(path.into(), content);
+        self
+    }
+
+    /// Adds all files of a directory below `assets/`.
+    pub fn with_asset_dir<P: AsRef<Path>>(mut self, dir: P) -> Result<Self, ScormError> {
+        let dir = dir.as_ref();
+        for entry in WalkDir::new(dir).sort_by_file_name() {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+            let path = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            self.assets.insert(path, std::fs::read(entry.path())?);
+        }
+        Ok(self)
+    }
+
+    /// Writes the package as a zip archive. Fails without a player, since
+    /// the launch pages could not run the challenges.
+    pub fn export<W: Write + Seek>(
+        &self,
+        game_path: &GamePath,
+        writer: W,
+    ) -> Result<W, ScormError> {
+        if game_path.challenges.is_empty() {
+            return Err(ScormError::EmptyGamePath);
+        }
+        let (player, player_content) = self.player.as_ref().ok_or(ScormError::MissingPlayer)?;
+
+        let mut files: Vec<(String, Vec<u8>)> = vec![
+            (BRIDGE.to_string(), include_bytes!("scorm.js").to_vec()),
+            (
+                "game_path.yml".to_string(),
+                serde_yaml::to_string(game_path)?.into_bytes(),
+            ),
+            (player.clone(), player_content.clone()),
+        ];
+        for (path, content) in &self.assets {
+            files.push((format!("assets/{}", path), content.clone()));
+        }
+        let shared: Vec<String> = files.iter().map(|(path, _)| path.clone()).collect();
+
+        let mut scos = vec![];
+        let mut directories = HashSet::new();
+        for config in &game_path.challenges {
+            let challenge_type = self
+                .factory
+                .challenge_types
+                .iter()
+                .find(|challenge_type| challenge_type.id() == config.challenge)
+                .ok_or_else(|| ScormError::ChallengeNotFound(config.challenge.clone()))?;
+            let rewards = config
+                .rewards
+                .clone()
+                .unwrap_or_else(|| game_path.rewards.clone());
+            let mastery_score = rewards.stars.one;
+            let mut config = config.clone();
+            config.rewards = Some(rewards);
+
+            let dir = format!(
+                "challenges/{}",
+                unique_directory(&config.id, &mut directories)
+            );
+            let launch = format!("{}/index.html", dir);
+            let sco_files = vec![
+                (
+                    launch.clone(),
+                    launch_page(&config.name, player, self.version).into_bytes(),
+                ),
+                (
+                    format!("{}/config.yml", dir),
+                    serde_yaml::to_string(&config)?.into_bytes(),
+                ),
+                (
+                    format!("{}/challenge.yml", dir),
+                    serde_yaml::to_string(challenge_type)?.into_bytes(),
+                ),
+            ];
+            scos.push(Sco {
+                title: config.name.clone(),
+                href: launch,
+                files: sco_files.iter().map(|(path, _)| path.clone()).collect(),
+                mastery_score,
+            });
+            files.extend(sco_files);
+        }
+
+        let manifest =
+            write_manifest(self.version, &game_path.id, &game_path.name, &scos, &shared)?;
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut zip = ZipWriter::new(writer);
+        zip.start_file("imsmanifest.xml", options)?;
+        zip.write_all(manifest.as_bytes())?;
+        for (path, content) in files {
+            zip.start_file(path, options)?;
+            zip.write_all(&content)?;
+        }
+        Ok(zip.finish()?)
+    }
+
+    pub fn export_file<P: AsRef<Path>>(
+        &self,
+        game_path: &GamePath,
+        path: P,
+    ) -> Result<(), ScormError> {
+        self.export(game_path, File::create(path)?)?;
+        Ok(())
+    }
+}
+
+/// Turns a challenge id into a directory name.
+fn directory(id: &str) -> String {
+    let name: String = id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if name.is_empty() {
+        "challenge".to_string()
+    } else {
+        name
+    }
+}
+
+/// Turns a challenge id into a directory name not in `used` yet, adding a
+/// number if the ids of two challenges give the same name, e.g. `a b` and
+/// `a-b`, and records it in `used`.
+fn unique_directory(id: &str, used: &mut HashSet<String>) -> String {
+    let name = directory(id);
+    let unique = (1..)
+        .map(|n| match n {
+            1 => name.clone(),
+            n => format!("{}-{}", name, n),
+        })
+        .find(|candidate| !used.contains(candidate))
+        .unwrap_or(name);
+    used.insert(unique.clone());
+    unique
+}
+
+/// The launch page names the SCORM version, so the bridge talks to the
+/// runtime API of that version only.
+fn launch_page(title: &str, player: &str, version: ScormVersion) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html data-scorm-version="{}">
+  <head>
+    <meta charset="utf-8">
+    <title>{}</title>
+    <script src="../../{}"></script>
+  </head>
+  <body data-config="config.yml" data-challenge="challenge.yml">
+    <main id="konnektoren"></main>
+    <script src="../../{}"></script>
+  </body>
+</html>
+"#,
+        version.runtime(),
+        escape(title),
+        BRIDGE,
+        escape(player)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnektoren_core::challenges::ChallengeConfig;
+    use std::io::{Cursor, Read};
+    use zip::ZipArchive;
+
+    fn export(exporter: &ScormExporter, game_path: &GamePath) -> ZipArchive<Cursor<Vec<u8>>> {
+        let data = exporter
+            .export(game_path, Cursor::new(vec![]))
+            .unwrap()
+            .into_inner();
+        ZipArchive::new(Cursor::new(data)).unwrap()
+    }
+
+    fn read(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> String {
+        let mut content = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        content
+    }
+
+    #[test]
+    fn export_game_path() {
+        let game_path = GamePath::default();
+        let exporter = ScormExporter::new(ChallengeFactory::default())
+            .with_player("player.js", b"// player".to_vec())
+            .with_asset("images/logo.png", vec![0, 1, 2]);
+        let mut archive = export(&exporter, &game_path);
+
+        let manifest = read(&mut archive, "imsmanifest.xml");
+        assert!(manifest.contains("<schemaversion>2004 4th Edition</schemaversion>"));
+        assert_eq!(
+            manifest.matches("<item ").count(),
+            game_path.challenges.len()
+        );
+        assert!(manifest.contains("<imsss:minNormalizedMeasure>0.4</imsss:minNormalizedMeasure>"));
+        // Every file listed in the manifest is part of the package
+        for href in manifest.split("href=\"").skip(1) {
+            let href = &href[..href.find('"').unwrap()];
+            assert!(archive.by_name(href).is_ok(), "missing {}", href);
+        }
+
+        let first = &game_path.challenges[0];
+        let dir = format!("challenges/{}", directory(&first.id));
+        let config: ChallengeConfig =
+            serde_yaml::from_str(&read(&mut archive, &format!("{}/config.yml", dir))).unwrap();
+        assert_eq!(config.rewards, Some(game_path.rewards.clone()));
+        let page = read(&mut archive, &format!("{}/index.html", dir));
+        assert!(page.contains(r#"<script src="../../player.js"></script>"#));
+        assert!(page.contains(r#"<html data-scorm-version="2004">"#));
+        assert!(archive.by_name("assets/images/logo.png").is_ok());
+    }
+
+    #[test]
+    fn export_scorm12() {
+        let mut game_path = GamePath::default();
+        game_path.rewards.stars.one = 50;
+        let exporter = ScormExporter::new(ChallengeFactory::default())
+            .with_version(ScormVersion::Scorm12)
+            .with_player("player.js", vec![]);
+        let mut archive = export(&exporter, &game_path);
+
+        let manifest = read(&mut archive, "imsmanifest.xml");
+        assert!(manifest.contains("<schemaversion>1.2</schemaversion>"));
+        assert!(manifest.contains(r#"adlcp:scormtype="sco""#));
+        assert!(manifest.contains("<adlcp:masteryscore>50</adlcp:masteryscore>"));
+        let page = read(
+            &mut archive,
+            &format!(
+                "challenges/{}/index.html",
+                directory(&game_path.challenges[0].id)
+            ),
+        );
+        assert!(page.contains(r#"<html data-scorm-version="1.2">"#));
+    }
+
+    #[test]
+    fn colliding_ids_get_their_own_directories() {
+        let mut game_path = GamePath::default();
+        game_path.challenges.truncate(2);
+        game_path.challenges[0].id = "a b".to_string();
+        game_path.challenges[1].id = "a-b".to_string();
+        let exporter =
+            ScormExporter::new(ChallengeFactory::default()).with_player("player.js", vec![]);
+        let mut archive = export(&exporter, &game_path);
+
+        let first: ChallengeConfig =
+            serde_yaml::from_str(&read(&mut archive, "challenges/a-b/config.yml")).unwrap();
+        let second: ChallengeConfig =
+            serde_yaml::from_str(&read(&mut archive, "challenges/a-b-2/config.yml")).unwrap();
+        assert_eq!(first.id, "a b");
+        assert_eq!(second.id, "a-b");
+
+        let mut used = HashSet::new();
+        assert_eq!(unique_directory("", &mut used), "challenge");
+        assert_eq!(unique_directory("a-b-2", &mut used), "a-b-2");
+        assert_eq!(unique_directory("a b", &mut used), "a-b");
+        assert_eq!(unique_directory("a/b", &mut used), "a-b-3");
+    }
+
+    #[test]
+    fn export_errors() {
+        let exporter = ScormExporter::new(ChallengeFactory::default());
+        assert!(matches!(
+            exporter.export(&GamePath::default(), Cursor::new(vec![])),
+            Err(ScormError::MissingPlayer)
+        ));
+
+        let exporter = ScormExporter::new(ChallengeFactory::new()).with_player("player.js", vec![]);
+        let game_path = GamePath::default();
+        match exporter.export(&game_path, Cursor::new(vec![])) {
+            Err(ScormError::ChallengeNotFound(challenge)) => {
+                assert_eq!(challenge, game_path.challenges[0].challenge)
+            }
+            other => panic!("Unexpected result: {:?}", other.map(|_| ())),
+        }
+
+        let game_path = GamePath {
+            challenges: vec![],
+            ..Default::default()
+        };
+        assert!(matches!(
+            exporter.export(&game_path, Cursor::new(vec![])),
+            Err(ScormError::EmptyGamePath)
+        ));
+    }
+}
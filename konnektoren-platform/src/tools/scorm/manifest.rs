@@ -0,0 +1,134 @@
+use quick_xml::escape::escape;
+use std::fmt::{self, Write};
+
+/// The SCORM edition a package is built for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScormVersion {
+    Scorm12,
+    #[default]
+    Scorm2004,
+}
+
+impl ScormVersion {
+    /// The name of the runtime API in the launch pages, also the value of
+    /// `KonnektorenScorm.version`.
+    pub fn runtime(&self) -> &'static str {
+        match self {
+            ScormVersion::Scorm12 => "1.2",
+            ScormVersion::Scorm2004 => "2004",
+        }
+    }
+}
+
+/// A shareable content object, one per challenge of the game path.
+pub(crate) struct Sco {
+    pub title: String,
+    /// The launch page
+    pub href: String,
+    pub files: Vec<String>,
+    /// Performance in percent needed to pass
+    pub mastery_score: u32,
+}
+
+/// Writes the `imsmanifest.xml` of a package. `shared` lists the files used
+/// by every SCO, like the runtime bridge and the assets.
+pub(crate) fn write_manifest(
+    version: ScormVersion,
+    identifier: &str,
+    title: &str,
+    scos: &[Sco],
+    shared: &[String],
+) -> Result<String, fmt::Error> {
+    let mut out = String::new();
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    match version {
+        ScormVersion::Scorm12 => writeln!(
+            out,
+            r#"<manifest identifier="{}" version="1.0" xmlns="http://www.imsproject.org/xsd/imscp_rootv1p1p2" xmlns:adlcp="http://www.adlnet.org/xsd/adlcp_rootv1p2" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://www.imsproject.org/xsd/imscp_rootv1p1p2 imscp_rootv1p1p2.xsd http://www.adlnet.org/xsd/adlcp_rootv1p2 adlcp_rootv1p2.xsd">"#,
+            escape(identifier)
+        )?,
+        ScormVersion::Scorm2004 => writeln!(
+            out,
+            r#"<manifest identifier="{}" version="1.0" xmlns="http://www.imsglobal.org/xsd/imscp_v1p1" xmlns:adlcp="http://www.adlnet.org/xsd/adlcp_v1p3" xmlns:imsss="http://www.imsglobal.org/xsd/imsss" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://www.imsglobal.org/xsd/imscp_v1p1 imscp_v1p1.xsd http://www.adlnet.org/xsd/adlcp_v1p3 adlcp_v1p3.xsd http://www.imsglobal.org/xsd/imsss imsss_v1p0.xsd">"#,
+            escape(identifier)
+        )?,
+    }
+    writeln!(out, "  <metadata>")?;
+    writeln!(out, "    <schema>ADL SCORM</schema>")?;
+    let schema_version = match version {
+        ScormVersion::Scorm12 => "1.2",
+        ScormVersion::Scorm2004 => "2004 4th Edition",
+    };
+    writeln!(out, "    <schemaversion>{}</schemaversion>", schema_version)?;
+    writeln!(out, "  </metadata>")?;
+
+    writeln!(out, r#"  <organizations default="ORG">"#)?;
+    writeln!(out, r#"    <organization identifier="ORG">"#)?;
+    writeln!(out, "      <title>{}</title>", escape(title))?;
+    for (index, sco) in scos.iter().enumerate() {
+        writeln!(
+            out,
+            r#"      <item identifier="ITEM_{}" identifierref="RES_{}">"#,
+            index, index
+        )?;
+        writeln!(out, "        <title>{}</title>", escape(&sco.title))?;
+        match version {
+            ScormVersion::Scorm12 => writeln!(
+                out,
+                "        <adlcp:masteryscore>{}</adlcp:masteryscore>",
+                sco.mastery_score
+            )?,
+            ScormVersion::Scorm2004 => {
+                writeln!(out, "        <imsss:sequencing>")?;
+                writeln!(out, "          <imsss:objectives>")?;
+                writeln!(
+                    out,
+                    r#"            <imsss:primaryObjective objectiveID="PASSED" satisfiedByMeasure="true">"#
+                )?;
+                writeln!(
+                    out,
+                    "              <imsss:minNormalizedMeasure>{}</imsss:minNormalizedMeasure>",
+                    sco.mastery_score as f64 / 100.0
+                )?;
+                writeln!(out, "            </imsss:primaryObjective>")?;
+                writeln!(out, "          </imsss:objectives>")?;
+                writeln!(out, "        </imsss:sequencing>")?;
+            }
+        }
+        writeln!(out, "      </item>")?;
+    }
+    writeln!(out, "    </organization>")?;
+    writeln!(out, "  </organizations>")?;
+
+    let scorm_type = match version {
+        ScormVersion::Scorm12 => "adlcp:scormtype",
+        ScormVersion::Scorm2004 => "adlcp:scormType",
+    };
+    writeln!(out, "  <resources>")?;
+    for (index, sco) in scos.iter().enumerate() {
+        writeln!(
+            out,
+            r#"    <resource identifier="RES_{}" type="webcontent" {}="sco" href="{}">"#,
+            index,
+            scorm_type,
+            escape(&sco.href)
+        )?;
+        for file in &sco.files {
+            writeln!(out, r#"      <file href="{}"/>"#, escape(file))?;
+        }
+        writeln!(out, r#"      <dependency identifierref="SHARED"/>"#)?;
+        writeln!(out, "    </resource>")?;
+    }
+    writeln!(
+        out,
+        r#"    <resource identifier="SHARED" type="webcontent" {}="asset">"#,
+        scorm_type
+    )?;
+    for file in shared {
+        writeln!(out, r#"      <file href="{}"/>"#, escape(file))?;
+    }
+    writeln!(out, "    </resource>")?;
+    writeln!(out, "  </resources>")?;
+    writeln!(out, "</manifest>")?;
+    Ok(out)
+}
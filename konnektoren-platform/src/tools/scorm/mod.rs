@@ -0,0 +1,7 @@
+mod error;
+mod exporter;
+mod manifest;
+
+pub use error::ScormError;
+pub use exporter::ScormExporter;
+pub use manifest::ScormVersion;
@@ -0,0 +1,83 @@
+// Connects a Konnektoren challenge to the SCORM runtime of the LMS.
+//
+// The launch page names the SCORM version of the package in its
+// `data-scorm-version` attribute and only the runtime API of that version is
+// used. The player reports the result of a challenge with
+// `KonnektorenScorm.report(values)`, where `values` are the pairs of data
+// model element and value of `konnektoren.lmsScore.scorm12` or
+// `konnektoren.lmsScore.scorm2004`, depending on `KonnektorenScorm.version`.
+(function () {
+  "use strict";
+
+  function findApi(name) {
+    var win = window;
+    for (var depth = 0; win && depth < 10; depth++) {
+      if (win[name]) {
+        return win[name];
+      }
+      if (win.parent === win) {
+        break;
+      }
+      win = win.parent;
+    }
+    return (window.opener && window.opener[name]) || null;
+  }
+
+  var version = document.documentElement.getAttribute("data-scorm-version");
+  var api2004 = version === "2004" ? findApi("API_1484_11") : null;
+  var api12 = version === "1.2" ? findApi("API") : null;
+  var terminated = false;
+
+  var scorm = {
+    version: api2004 || api12 ? version : null,
+
+    set: function (element, value) {
+      if (api2004) {
+        api2004.SetValue(element, String(value));
+      } else if (api12) {
+        api12.LMSSetValue(element, String(value));
+      }
+    },
+
+    commit: function () {
+      if (api2004) {
+        api2004.Commit("");
+      } else if (api12) {
+        api12.LMSCommit("");
+      }
+    },
+
+    report: function (values) {
+      values.forEach(function (pair) {
+        scorm.set(pair[0], pair[1]);
+      });
+      scorm.commit();
+    },
+
+    terminate: function () {
+      if (terminated) {
+        return;
+      }
+      terminated = true;
+      if (api2004) {
+        api2004.Terminate("");
+      } else if (api12) {
+        api12.LMSFinish("");
+      }
+    },
+  };
+
+  if (api2004) {
+    api2004.Initialize("");
+    if (api2004.GetValue("cmi.completion_status") !== "completed") {
+      scorm.set("cmi.completion_status", "incomplete");
+    }
+  } else if (api12) {
+    api12.LMSInitialize("");
+    if (api12.LMSGetValue("cmi.core.lesson_status") === "not attempted") {
+      scorm.set("cmi.core.lesson_status", "incomplete");
+    }
+  }
+  window.addEventListener("pagehide", scorm.terminate);
+  window.KonnektorenScorm = scorm;
+})();
@@ -0,0 +1,29 @@
+//! Integration test that lints the default challenges and game path.
+//!
+//! Run with:
+//! ```bash
+//! cargo test -p konnektoren-platform --features tools --test content_lint -- --nocapture
+//! ```
+
+use konnektoren_core::challenges::ChallengeFactory;
+use konnektoren_core::game::GamePath;
+use konnektoren_platform::tools::{ContentLinter, LintHumanFormatter, LintReportFormatter};
+
+#[test]
+fn default_content_is_valid() {
+    let report = ContentLinter::new(ChallengeFactory::default())
+        .with_game_path(GamePath::default(), "konnektoren_path.yml")
+        .lint();
+
+    println!("{}", LintHumanFormatter.format(&report).unwrap());
+    report.assert_no_errors();
+}
+
+#[test]
+fn core_assets_are_valid() {
+    let assets = concat!(env!("CARGO_MANIFEST_DIR"), "/../konnektoren-core/assets");
+    let report = ContentLinter::default().load_dir(assets).unwrap().lint();
+
+    println!("{}", LintHumanFormatter.format(&report).unwrap());
+    report.assert_no_errors();
+}
@@ -1,7 +1,10 @@
 use cucumber::World;
 use konnektoren_core::error::Result;
 pub mod steps;
+use konnektoren_core::challenges::ManualClock;
 use konnektoren_core::controller::GameController;
+use konnektoren_core::leaderboard::MemoryLeaderboardStore;
+use konnektoren_core::persistence::ProfileStore;
 use konnektoren_core::prelude::*;
 use std::sync::Arc;
 
@@ -19,6 +22,9 @@ pub struct BddWorld {
     pub unlocked_achievements: Vec<AchievementDefinition>,
     pub achievement_notification: Option<AchievementDefinition>,
     pub controller: Option<Arc<GameController>>,
+    pub clock: Option<Arc<ManualClock>>,
+    pub leaderboard_store: MemoryLeaderboardStore,
+    pub profile_store: ProfileStore,
 }
 
 impl Default for BddWorld {
@@ -47,6 +53,9 @@ impl Default for BddWorld {
             unlocked_achievements: Vec::new(),
             achievement_notification: None,
             controller: None,
+            clock: None,
+            leaderboard_store: MemoryLeaderboardStore::default(),
+            profile_store: ProfileStore::default(),
         }
     }
 }
@@ -6,3 +6,9 @@ Feature: Achievement System
     When the user completes 10 more challenges
     Then the "Challenge Champion" achievement should be unlocked
     And the achievement count should be 1
+
+  Scenario: Learning streak achievement
+    Given a user who finished a challenge on each of the last 7 days
+    When the achievements of the player are evaluated
+    Then the player's current streak should be 7 days
+    And the "Week Streak" achievement should be unlocked
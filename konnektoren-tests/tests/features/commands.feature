@@ -2,12 +2,29 @@ Feature: Commands
 
   Scenario: Next challenge
     Given A new Session with id "1"
+    And a user with 100 XP
     And the current challenge is "konnektoren-1"
     When the next challenge is requested
     Then the current challenge is "konnektoren-2"
 
+  Scenario: Next challenge is locked
+    Given A new Session with id "1"
+    And the current challenge is "konnektoren-1"
+    When the next challenge is requested
+    Then an error should be raised with message "Challenge konnektoren-2 is locked: Insufficient XP: 0 of 10"
+    And the current challenge is "konnektoren-1"
+
+  Scenario: Next challenge in a branching path
+    Given A new Session with id "1"
+    And the game path has an edge from "konnektoren-1" to "articles-1"
+    And the game path has an edge from "konnektoren-1" to "past-tense-1"
+    And the challenge "articles-1" is a side quest
+    When the next challenge is requested
+    Then the current challenge is "past-tense-1"
+
   Scenario: Previous challenge
     Given A new Session with id "1"
+    And a user with 100 XP
     And the current challenge is "konnektoren-1"
     When the next challenge is requested
     And the next challenge is requested
@@ -60,3 +77,17 @@ Feature: Commands
     And the current task index is 0
     When the previous task is requested
     Then an error should be raised with message "No previous tasks"
+
+  Scenario: Request hints
+    Given A new Session with id "1"
+    And the current task index is 0
+    When a hint is requested
+    And a hint is requested
+    Then 2 hints should be used
+
+  Scenario: Request a hint when all are revealed
+    Given A new Session with id "1"
+    And the current task index is 0
+    When all hints of the current task are requested
+    And a hint is requested
+    Then an error should be raised with message "No more hints"
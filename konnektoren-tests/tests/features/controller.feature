@@ -3,6 +3,7 @@ Feature: Controller
 
   Scenario: Controller Executes Next Challenge Command
     Given a new controller is initialized
+    And the controller's game has 100 XP
     When the controller executes the "NextChallenge" game command
     Then the controller's current challenge index should be 1
 
@@ -42,3 +43,17 @@ Feature: Controller
     Given a new controller is initialized
     When the controller executes the "Finish" challenge command
     Then the controller's challenge history should have 1 entry
+
+  Scenario: Controller Refuses Locked Challenges
+    Given a new controller is initialized
+    When the controller executes the "NextChallenge" game command
+    Then a controller error should be raised with message "Challenge konnektoren-2 is locked"
+
+  Scenario: Controller Skips a Task When Its Time Runs Out
+    Given a new controller with a time limit of 15 seconds per task is initialized
+    When 10 seconds pass
+    Then the controller's task index should be exactly 0
+    And the controller's challenge should have 0 timed out tasks
+    When 6 seconds pass
+    Then the controller's task index should be exactly 1
+    And the controller's challenge should have 1 timed out task
@@ -27,6 +27,22 @@ Feature: Dialog Challenge
     When the player answers interactive turn 1 with option 2
     Then the answer should be incorrect
 
+  Scenario: Branching dialog is scored along the path taken
+    Given a branching Dialog challenge is loaded
+    When the player answers turn 1 with option 1
+    And the player answers turn 1 with option 0
+    And the player answers turn 4 with option 0
+    Then the dialog performance should be 66
+    And the dialog should end with "bought-bread"
+    And 3 dialog answers should be recorded
+
+  Scenario: Branching dialog reaches a different ending
+    Given a branching Dialog challenge is loaded
+    When the player answers turn 1 with option 0
+    And the player answers turn 4 with option 1
+    Then the dialog performance should be 50
+    And the dialog should end with "sold-out"
+
   Scenario: Out-of-bounds turn index returns an error
     When the player answers turn 999 with option 0
     Then solving the dialog turn should return an error
@@ -11,6 +11,13 @@ Feature: Game Progression
     And attempts to access a challenge requiring 10 XP
     Then access should be granted
 
+  Scenario: Unlocking Challenges With Prerequisites
+    Given a user with 100 XP
+    When the user attempts to access a challenge requiring "konnektoren-1"
+    Then access should be denied with message "Prerequisite not completed: konnektoren-1"
+    When the user completes "konnektoren-1" without mistakes
+    Then access should be granted
+
   Scenario: Game Path Completion
     Given a user has completed all but one challenge in a path
     When the user completes the final challenge
@@ -0,0 +1,22 @@
+Feature: Leaderboard
+
+  Scenario: Players are ranked by performance and time
+    Given "anna" scored 80% in 60 seconds on the game path "konnektoren"
+    And "ben" scored 90% in 90 seconds on the game path "konnektoren"
+    And "carl" scored 80% in 45 seconds on the game path "konnektoren"
+    And "dora" scored 100% in 30 seconds on the game path "artikel"
+    Then the all-time leaderboard of "konnektoren" should rank "ben, carl, anna"
+    And "anna" should be ranked 3 on the all-time leaderboard of "konnektoren"
+
+  Scenario: Only the best record of a player counts
+    Given "anna" scored 50% in 60 seconds on the game path "konnektoren"
+    And "anna" scored 70% in 80 seconds on the game path "konnektoren"
+    Then the all-time leaderboard of "konnektoren" should rank "anna"
+    And the all-time leaderboard of "konnektoren" should show 70% for "anna"
+
+  Scenario: Players around me
+    Given "anna" scored 100% in 60 seconds on the game path "konnektoren"
+    And "ben" scored 90% in 60 seconds on the game path "konnektoren"
+    And "carl" scored 80% in 60 seconds on the game path "konnektoren"
+    And "dora" scored 70% in 60 seconds on the game path "konnektoren"
+    Then the players around "carl" on "konnektoren" should be "ben, carl, dora"
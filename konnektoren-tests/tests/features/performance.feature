@@ -12,6 +12,12 @@ Feature: Performance Calculation and Recording
     Then the challenge should show 100% performance
     And the performance record should show 100% for that challenge
 
+  Scenario: Vocabulary Recall Challenge Shows Exact Performance
+    Given a user starts a vocabulary recall challenge with 4 words
+    When the user translates 3 words correctly and 1 incorrectly
+    Then the challenge should show 75% performance
+    And the performance record should show 75% for that challenge
+
   Scenario: Multiple Choice Challenge Shows Exact Performance
     Given a user starts a multiple choice challenge with 10 questions
     When the user answers 9 questions correctly and 1 incorrectly
@@ -89,10 +95,37 @@ Feature: Performance Calculation and Recording
     When the user orders 4 items correctly and 4 incorrectly
     Then the challenge should show 50% performance
 
+  Scenario: Ordering Challenge With Partial Credit
+    Given an ordering challenge with 8 items
+    And the challenge uses the "partial-credit" scoring policy
+    When the user orders 4 items correctly and 4 incorrectly
+    Then the challenge should show 66% performance
+
+  Scenario: Ordering Challenge With Kendall Tau Scoring
+    Given an ordering challenge with 8 items
+    And the challenge uses the "kendall-tau" scoring policy
+    When the user orders 4 items correctly and 4 incorrectly
+    Then the challenge should show 50% performance
+
   Scenario: SortTable Challenge Shows 50%
     Given a sort table challenge with 10 rows
     When the user sorts 5 rows correctly and 5 incorrectly
     Then the challenge should show 50% performance
+
+  Scenario: Matching Challenge Shows 75%
+    Given a matching challenge with 8 pairs
+    When the user matches 6 pairs correctly and 2 incorrectly
+    Then the challenge should show 75% performance
+
+  Scenario: Translation Challenge Gives Partial Credit For Missing Words
+    Given a translation challenge with 4 sentences
+    When the user translates 2 sentences correctly and 2 with a missing word
+    Then the challenge should show 87% performance
+
+  Scenario: Error Correction Credits Found And Fixed Mistakes Separately
+    Given an error correction challenge with 4 sentences
+    When the user fixes 2 mistakes and only finds 2
+    Then the challenge should show 75% performance
   # ============================================================================
   # Edge Cases
   # ============================================================================
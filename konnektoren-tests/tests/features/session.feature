@@ -20,3 +20,13 @@ Feature: Session feature
     And the player profile xp is 100
     When the player profile xp is increased by 50
     Then the player profile xp should be 150
+
+  Scenario: Switch between profiles on a shared device
+    Given a profile store with the profiles "Anna, Ben"
+    And the session of the profile "Anna" is loaded
+    And the player profile xp is 100
+    When the profile is switched to "Ben"
+    Then the player profile name should be "Ben"
+    And the player profile xp should be 0
+    When the profile is switched to "Anna"
+    Then the player profile xp should be 100
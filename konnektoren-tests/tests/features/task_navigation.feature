@@ -52,6 +52,18 @@ Feature: Task Navigation
     When all tasks in the range are solved
     Then 5 tasks should be completed
 
+  Scenario: Seeded random task selection is reproducible
+    Given A new Session with id "1"
+    And a konnektoren challenge with random pattern "5:10..25@42" is loaded
+    Then the task count should be 5
+    And the challenge should have the seed 42
+    And loading the challenge again with its seed selects the same tasks
+
+  Scenario: Random task selection can be replayed
+    Given A new Session with id "1"
+    And a konnektoren challenge with random pattern "5:random" is loaded
+    Then loading the challenge again with its seed selects the same tasks
+
   Scenario: Exact task count
     Given A new Session with id "1"
     And a konnektoren challenge with exact pattern "10" is loaded
@@ -7,12 +7,14 @@ use konnektoren_core::challenges::{Challenge, ChallengeConfig, ChallengeHistory,
 #[given(expr = "a user with {int} XP")]
 async fn a_user_with_xp(world: &mut BddWorld, xp: u32) {
     world.session.player_profile.xp = xp;
+    world.session.game_state.game.xp = xp;
     world.game.xp = xp;
 }
 
 #[when(expr = "the user earns {int} more XP")]
 async fn the_user_earns_more_xp(world: &mut BddWorld, additional_xp: u32) {
     world.session.player_profile.xp += additional_xp;
+    world.session.game_state.game.xp += additional_xp;
     world.game.xp += additional_xp;
 
     // Re-evaluate achievements after XP change
@@ -50,6 +52,41 @@ async fn a_user_has_completed_challenges(world: &mut BddWorld, count: usize) {
     }
 }
 
+#[given(expr = "a user who finished a challenge on each of the last {int} days")]
+async fn a_user_who_finished_a_challenge_each_day(world: &mut BddWorld, days: i64) {
+    let now = chrono::Utc::now();
+    world.game.challenge_history = ChallengeHistory::new();
+    for days_ago in 0..days {
+        let mut challenge = Challenge::new(
+            &ChallengeType::default(),
+            &ChallengeConfig {
+                id: format!("challenge_{}", days_ago),
+                ..Default::default()
+            },
+        );
+        challenge.start_time = Some(now - chrono::Duration::days(days_ago));
+        challenge.end_time = challenge.start_time;
+        world.game.challenge_history.add_challenge(challenge);
+    }
+}
+
+#[when(expr = "the achievements of the player are evaluated")]
+async fn the_achievements_of_the_player_are_evaluated(world: &mut BddWorld) {
+    let achievements_data = include_str!("../../../konnektoren-core/assets/achievements.yml");
+    let evaluator = AchievementEvaluator::new(achievements_data).unwrap();
+    world
+        .session
+        .player_profile
+        .update_streak(&world.game, chrono::Utc::now());
+    let unlocked = evaluator.evaluate_for_player(&world.game, &world.session.player_profile);
+    world.unlocked_achievements = unlocked.iter().map(|&a| a.clone()).collect();
+}
+
+#[then(expr = "the player's current streak should be {int} days")]
+async fn the_players_current_streak_should_be(world: &mut BddWorld, days: u32) {
+    assert_eq!(world.session.player_profile.streak.current, days);
+}
+
 #[given(expr = "the user has {int} achievements")]
 async fn the_user_has_achievements(world: &mut BddWorld, _count: usize) {
     // This step just confirms the initial state
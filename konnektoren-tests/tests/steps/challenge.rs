@@ -112,6 +112,8 @@ async fn a_multiple_choice_challenge_is_set_up_with_a_question_of_option(
         lang,
         options,
         questions,
+        level: None,
+        tags: vec![],
     };
     world.challenge_type = ChallengeType::MultipleChoice(dataset);
     let challenge = Challenge::new(&world.challenge_type, &ChallengeConfig::default());
@@ -2,7 +2,7 @@ use crate::BddWorld;
 use cucumber::{given, then, when};
 use konnektoren_core::commands::{ChallengeCommand, Command, CommandTrait, GameCommand};
 use konnektoren_core::error::KonnektorenError;
-use konnektoren_core::game::GamePath;
+use konnektoren_core::game::{GamePath, PathEdge};
 
 #[when(expr = "the next challenge is requested")]
 async fn the_next_challenge_is_requested(world: &mut BddWorld) {
@@ -52,6 +52,25 @@ async fn the_current_task_index_is_the_last_task_index(world: &mut BddWorld) {
     world.session.game_state.current_task_index = max_tasks - 1;
 }
 
+#[given(expr = "the game path has an edge from {string} to {string}")]
+async fn the_game_path_has_an_edge(world: &mut BddWorld, from: String, to: String) {
+    let game_path =
+        &mut world.session.game_state.game.game_paths[world.session.game_state.current_game_path];
+    game_path.edges.push(PathEdge::new(from, to));
+}
+
+#[given(expr = "the challenge {string} is a side quest")]
+async fn the_challenge_is_a_side_quest(world: &mut BddWorld, id: String) {
+    let game_path =
+        &mut world.session.game_state.game.game_paths[world.session.game_state.current_game_path];
+    let challenge_config = game_path
+        .challenges
+        .iter_mut()
+        .find(|challenge| challenge.id == id)
+        .expect("Challenge should be part of the game path");
+    challenge_config.elective = true;
+}
+
 #[given(expr = "the current challenge is the first challenge")]
 async fn the_current_challenge_is_the_first_challenge(world: &mut BddWorld) {
     world.session.game_state.current_challenge_index = 0;
@@ -123,3 +142,32 @@ async fn the_previous_task_is_requested(world: &mut BddWorld) {
 async fn the_current_task_index_is(world: &mut BddWorld, index: usize) {
     world.session.game_state.current_task_index = index;
 }
+
+#[when(expr = "a hint is requested")]
+async fn a_hint_is_requested(world: &mut BddWorld) {
+    let command = Command::Challenge(ChallengeCommand::RequestHint);
+    let state = &mut world.session.game_state;
+
+    world.last_command_result = command.execute(state).map_err(KonnektorenError::Command);
+}
+
+#[when(expr = "all hints of the current task are requested")]
+async fn all_hints_of_the_current_task_are_requested(world: &mut BddWorld) {
+    let state = &mut world.session.game_state;
+    let hints = state
+        .challenge
+        .challenge_type
+        .hints(state.current_task_index)
+        .len();
+    for _ in 0..hints {
+        let command = Command::Challenge(ChallengeCommand::RequestHint);
+        if let Err(e) = command.execute(state) {
+            panic!("Failed to request hint: {}", e);
+        }
+    }
+}
+
+#[then(expr = "{int} hint(s) should be used")]
+async fn hints_should_be_used(world: &mut BddWorld, expected: usize) {
+    assert_eq!(world.session.game_state.challenge.hints_used(), expected);
+}
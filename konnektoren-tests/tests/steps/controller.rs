@@ -1,7 +1,10 @@
 use crate::BddWorld;
 use cucumber::{given, then, when};
+use konnektoren_core::challenges::{ManualClock, TimeLimit};
 use konnektoren_core::commands::{ChallengeCommand, Command, CommandTrait, GameCommand};
-use konnektoren_core::controller::{GameController, GameControllerTrait};
+use konnektoren_core::controller::{
+    ChallengeFinishPlugin, ChallengeTimerPlugin, GameController, GameControllerTrait,
+};
 use konnektoren_core::error::KonnektorenError;
 use konnektoren_core::persistence::MemoryPersistence;
 use konnektoren_core::prelude::*;
@@ -17,6 +20,58 @@ async fn a_new_controller_is_initialized(world: &mut BddWorld) {
     world.controller = Some(controller);
 }
 
+#[given(expr = "a new controller with a time limit of {int} seconds per task is initialized")]
+async fn a_new_controller_with_task_time_limit(world: &mut BddWorld, seconds: u32) {
+    let mut game = Game::default();
+    game.game_paths[0].challenges[0].time_limit = Some(TimeLimit {
+        task_seconds: Some(seconds),
+        ..TimeLimit::default()
+    });
+    let clock = Arc::new(ManualClock::default());
+    let persistence = Arc::new(MemoryPersistence::default());
+    let mut controller = GameController::new(game, persistence);
+    controller.register_plugin(Arc::new(ChallengeFinishPlugin));
+    controller.register_plugin(Arc::new(ChallengeTimerPlugin::new(clock.clone())));
+    let controller = controller.init();
+    controller.publish_command(Command::Challenge(ChallengeCommand::CheckTime));
+    world.controller = Some(controller);
+    world.clock = Some(clock);
+}
+
+#[when(expr = "{int} seconds pass")]
+async fn seconds_pass(world: &mut BddWorld, seconds: i64) {
+    let clock = world.clock.as_ref().expect("Clock not initialized");
+    let controller = world
+        .controller
+        .as_ref()
+        .expect("Controller not initialized");
+    clock.advance(chrono::Duration::seconds(seconds));
+    controller.publish_command(Command::Challenge(ChallengeCommand::CheckTime));
+}
+
+#[then(expr = "the controller's challenge should have {int} timed out task(s)")]
+async fn the_controllers_challenge_should_have_timed_out_tasks(
+    world: &mut BddWorld,
+    expected: usize,
+) {
+    let controller = world
+        .controller
+        .as_ref()
+        .expect("Controller not initialized");
+    let state = controller.game_state().lock().unwrap();
+    assert_eq!(state.challenge.timed_out_tasks.len(), expected);
+}
+
+#[then(expr = "the controller's task index should be exactly {int}")]
+async fn the_controllers_task_index_should_be_exactly(world: &mut BddWorld, expected: usize) {
+    let controller = world
+        .controller
+        .as_ref()
+        .expect("Controller not initialized");
+    let state = controller.game_state().lock().unwrap();
+    assert_eq!(state.current_task_index, expected);
+}
+
 #[when(expr = "the controller executes the {string} game command")]
 async fn the_controller_executes_game_command(world: &mut BddWorld, command_name: String) {
     if let Some(controller) = &world.controller {
@@ -138,6 +193,18 @@ async fn the_controller_executes_challenge_command_with_option(
     }
 }
 
+#[given(expr = "the controller's game has {int} XP")]
+async fn the_controllers_game_has_xp(world: &mut BddWorld, xp: u32) {
+    if let Some(controller) = &world.controller {
+        match controller.game_state().lock() {
+            Ok(mut state) => state.game.xp = xp,
+            Err(_) => panic!("Failed to lock game state"),
+        }
+    } else {
+        panic!("Controller not initialized");
+    }
+}
+
 #[given(expr = "the controller's current challenge index is {int}")]
 async fn the_controllers_current_challenge_index_is(world: &mut BddWorld, index: usize) {
     if let Some(controller) = &world.controller {
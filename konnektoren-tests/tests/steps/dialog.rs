@@ -38,6 +38,18 @@ async fn a_dialog_challenge_in_observer_mode_is_loaded(world: &mut BddWorld) {
     world.challenge = Some(challenge);
 }
 
+#[given(expr = "a branching Dialog challenge is loaded")]
+async fn a_branching_dialog_challenge_is_loaded(world: &mut BddWorld) {
+    let dialog: Dialog = serde_yaml::from_str(include_str!(
+        "../../../konnektoren-core/assets/dialog_beim_baecker.yml"
+    ))
+    .expect("branching dialog asset should parse");
+    let challenge_type = ChallengeType::Dialog(dialog);
+    let challenge = Challenge::new(&challenge_type, &ChallengeConfig::default());
+    world.challenge_type = challenge_type;
+    world.challenge = Some(challenge);
+}
+
 // ── When ──────────────────────────────────────────────────────────────────────
 
 #[when(expr = "the dialog is observed")]
@@ -151,6 +163,22 @@ async fn the_dialog_performance_should_be(world: &mut BddWorld, expected: u32) {
     );
 }
 
+#[then(expr = "the dialog should end with {string}")]
+async fn the_dialog_should_end_with(world: &mut BddWorld, expected: String) {
+    let dialog = match &world.challenge_type {
+        ChallengeType::Dialog(dialog) => dialog,
+        other => panic!("expected Dialog challenge, got {:?}", other),
+    };
+    let result = world.challenge_result.as_ref().expect("no result recorded");
+    match result {
+        ChallengeResult::Dialog(answers) => {
+            let path = dialog.path(answers);
+            assert_eq!(path.ending.as_deref(), Some(expected.as_str()));
+        }
+        other => panic!("expected Dialog result, got {:?}", other),
+    }
+}
+
 #[then(expr = "{int} dialog answers should be recorded")]
 async fn n_dialog_answers_should_be_recorded(world: &mut BddWorld, expected: usize) {
     let result = world.challenge_result.as_ref().expect("no result recorded");
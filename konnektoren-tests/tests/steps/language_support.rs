@@ -1,5 +1,6 @@
 use crate::BddWorld;
 use cucumber::{given, then, when};
+use konnektoren_core::challenges::ScoringPolicy;
 use konnektoren_core::prelude::*;
 use konnektoren_platform::i18n::{I18nConfig, JsonTranslationAsset, Language};
 use rust_embed::RustEmbed;
@@ -83,6 +84,9 @@ async fn a_challenge_with_language_set_to(world: &mut BddWorld, language_code: S
         lang: language_code.clone(),
         options,
         questions,
+        level: None,
+        tags: vec![],
+        time_limit: None,
     };
 
     world.challenge_type = ChallengeType::MultipleChoice(mc_dataset);
@@ -102,9 +106,17 @@ async fn a_challenge_with_language_set_to(world: &mut BddWorld, language_code: S
         challenge: "language_test".to_string(),
         tasks: 2.into(),
         unlock_points: 0,
+        unlock_stars: 0,
+        requires: vec![],
+        elective: false,
         variant: None,
         position: None,
         icon: None,
+        scoring: ScoringPolicy::default(),
+        rewards: None,
+        level: None,
+        tags: vec![],
+        time_limit: None,
     };
 
     let challenge = Challenge::new(&world.challenge_type, &challenge_config);
@@ -216,6 +228,9 @@ async fn the_language_is_changed_to_english(world: &mut BddWorld) {
                 lang: "en".to_string(),
                 options,
                 questions,
+                level: None,
+                tags: vec![],
+                time_limit: None,
             };
 
             world.challenge_type = ChallengeType::MultipleChoice(new_dataset);
@@ -235,9 +250,17 @@ async fn the_language_is_changed_to_english(world: &mut BddWorld) {
                 challenge: challenge.challenge_config.challenge.clone(),
                 tasks: challenge.challenge_config.tasks.clone(),
                 unlock_points: challenge.challenge_config.unlock_points,
+                unlock_stars: challenge.challenge_config.unlock_stars,
+                requires: challenge.challenge_config.requires.clone(),
+                elective: challenge.challenge_config.elective,
                 variant: challenge.challenge_config.variant.clone(),
                 position: challenge.challenge_config.position,
                 icon: challenge.challenge_config.icon.clone(),
+                scoring: ScoringPolicy::default(),
+                rewards: None,
+                level: None,
+                tags: vec![],
+                time_limit: None,
             };
 
             let new_challenge = Challenge::new(&world.challenge_type, &challenge_config);
@@ -0,0 +1,96 @@
+use crate::BddWorld;
+use chrono::Utc;
+use cucumber::{given, then};
+use konnektoren_core::challenges::PerformanceRecord;
+use konnektoren_core::leaderboard::{Leaderboard, LeaderboardStore, LeaderboardWindow};
+
+fn all_time_leaderboard(world: &BddWorld, game_path_id: &str) -> Leaderboard {
+    world
+        .leaderboard_store
+        .leaderboard(game_path_id, LeaderboardWindow::AllTime, Utc::now())
+        .expect("Failed to build leaderboard")
+}
+
+fn names(names: &str) -> Vec<String> {
+    names
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .collect()
+}
+
+#[given(expr = "{string} scored {int}% in {int} seconds on the game path {string}")]
+async fn player_scored(
+    world: &mut BddWorld,
+    profile_name: String,
+    performance: u8,
+    seconds: u64,
+    game_path_id: String,
+) {
+    let record = PerformanceRecord {
+        game_path_id,
+        profile_name,
+        challenges_performance: vec![("challenge".to_string(), performance, seconds * 1000)],
+        total_challenges: 1,
+        performance_percentage: performance,
+        date: Utc::now(),
+    };
+    world
+        .leaderboard_store
+        .add_record(record)
+        .expect("Failed to add record");
+}
+
+#[then(expr = "the all-time leaderboard of {string} should rank {string}")]
+async fn leaderboard_should_rank(world: &mut BddWorld, game_path_id: String, expected: String) {
+    let leaderboard = all_time_leaderboard(world, &game_path_id);
+    let ranked: Vec<String> = leaderboard
+        .entries
+        .into_iter()
+        .map(|entry| entry.profile_name)
+        .collect();
+    assert_eq!(ranked, names(&expected));
+}
+
+#[then(expr = "{string} should be ranked {int} on the all-time leaderboard of {string}")]
+async fn player_should_be_ranked(
+    world: &mut BddWorld,
+    profile_name: String,
+    rank: usize,
+    game_path_id: String,
+) {
+    let leaderboard = all_time_leaderboard(world, &game_path_id);
+    let entry = leaderboard
+        .entry(&profile_name)
+        .expect("Player is not ranked");
+    assert_eq!(entry.rank, rank);
+}
+
+#[then(expr = "the all-time leaderboard of {string} should show {int}% for {string}")]
+async fn leaderboard_should_show_performance(
+    world: &mut BddWorld,
+    game_path_id: String,
+    performance: u8,
+    profile_name: String,
+) {
+    let leaderboard = all_time_leaderboard(world, &game_path_id);
+    let entry = leaderboard
+        .entry(&profile_name)
+        .expect("Player is not ranked");
+    assert_eq!(entry.performance_percentage, performance);
+}
+
+#[then(expr = "the players around {string} on {string} should be {string}")]
+async fn players_around(
+    world: &mut BddWorld,
+    profile_name: String,
+    game_path_id: String,
+    expected: String,
+) {
+    let leaderboard = all_time_leaderboard(world, &game_path_id);
+    let around: Vec<String> = leaderboard
+        .around(&profile_name, 1)
+        .into_iter()
+        .map(|entry| entry.profile_name)
+        .collect();
+    assert_eq!(around, names(&expected));
+}
@@ -7,6 +7,7 @@ pub mod dialog;
 pub mod game;
 pub mod game_progression;
 pub mod language_support;
+pub mod leaderboard;
 pub mod performance;
 pub mod player_profile;
 pub mod session;
@@ -1,10 +1,14 @@
 use crate::BddWorld;
 use cucumber::{given, then, when};
 use konnektoren_core::challenges::{
-    Challenge, ChallengeConfig, ChallengeResult, ChallengeType, Choice, ContextItem,
-    ContextItemChoiceAnswers, ContextualChoice, Informative, Ordering, OrderingItem,
-    OrderingResult, Performance, PerformanceRecord, Solvable, SortTable, SortTableColumn,
-    SortTableRow, Timed, Vocabulary, VocabularyItem,
+    Challenge, ChallengeConfig, ChallengeInput, ChallengeResult, ChallengeType, Choice,
+    ContextItem, ContextItemChoiceAnswers, ContextualChoice, ErrorCorrection,
+    ErrorCorrectionAnswer, ErrorKind, FaultySentence, GradingPolicy, Informative, Matching,
+    MatchingAnswer, MatchingPair, Ordering, OrderingItem, OrderingResult, Performance,
+    PerformanceRecord, ScoringPolicy, SentenceError, Solvable, SortTable, SortTableColumn,
+    SortTableRow, Timed, Translation, TranslationAnswer, TranslationItem, Vocabulary,
+    VocabularyAnswer, VocabularyDirection, VocabularyItem, VocabularyMode, VocabularyResponse,
+    WordCorrection,
 };
 use konnektoren_core::prelude::*;
 
@@ -36,6 +40,11 @@ async fn user_starts_vocabulary_challenge(world: &mut BddWorld) {
             icon: None,
             phonetic: None,
         }],
+        mode: VocabularyMode::Study,
+        direction: VocabularyDirection::default(),
+        grading: GradingPolicy::default(),
+        level: None,
+        tags: vec![],
     };
 
     world.challenge_type = ChallengeType::Vocabulary(vocabulary);
@@ -47,7 +56,62 @@ async fn user_starts_vocabulary_challenge(world: &mut BddWorld) {
 async fn user_completes_vocabulary_challenge(world: &mut BddWorld) {
     if let Some(challenge) = &mut world.challenge {
         challenge.start();
-        challenge.challenge_result = ChallengeResult::Vocabulary;
+        challenge.challenge_result = ChallengeResult::Vocabulary(vec![]);
+        challenge.update_end_time();
+    }
+}
+
+#[given(expr = "a user starts a vocabulary recall challenge with {int} words")]
+async fn user_starts_vocabulary_recall_challenge(world: &mut BddWorld, word_count: usize) {
+    let items = (0..word_count)
+        .map(|i| VocabularyItem {
+            id: i,
+            text: format!("Wort {}", i),
+            translation: Some(format!("word {}", i)),
+            icon: None,
+            phonetic: None,
+        })
+        .collect();
+
+    let vocabulary = Vocabulary {
+        id: "test-vocab-recall".to_string(),
+        name: "Test Vocabulary Recall".to_string(),
+        description: "Test".to_string(),
+        icon: None,
+        lang: "de".to_string(),
+        items,
+        mode: VocabularyMode::Recall,
+        direction: VocabularyDirection::TextToTranslation,
+        grading: GradingPolicy::tolerant(),
+        level: None,
+        tags: vec![],
+    };
+
+    world.challenge_type = ChallengeType::Vocabulary(vocabulary);
+    let challenge = Challenge::new(&world.challenge_type, &ChallengeConfig::default());
+    world.challenge = Some(challenge);
+}
+
+#[when(expr = "the user translates {int} words correctly and {int} incorrectly")]
+async fn user_translates_words(world: &mut BddWorld, correct_count: usize, incorrect_count: usize) {
+    if let Some(challenge) = &mut world.challenge {
+        challenge.start();
+
+        for i in 0..(correct_count + incorrect_count) {
+            let text = if i < correct_count {
+                format!("Word {}", i)
+            } else {
+                "something else".to_string()
+            };
+            let _ = challenge.solve(
+                ChallengeInput::Vocabulary(VocabularyAnswer {
+                    item_id: i,
+                    response: VocabularyResponse::Typed(text),
+                }),
+                i,
+            );
+        }
+
         challenge.update_end_time();
     }
 }
@@ -85,6 +149,8 @@ async fn user_starts_multiple_choice_challenge(world: &mut BddWorld, question_co
         lang: "en".to_string(),
         options,
         questions,
+        level: None,
+        tags: vec![],
     };
 
     world.challenge_type = ChallengeType::MultipleChoice(mc);
@@ -367,6 +433,9 @@ async fn contextual_choice_challenge_with_n_items(world: &mut BddWorld, item_cou
         name: "Test Contextual Choice".to_string(),
         description: "Test".to_string(),
         items,
+        grading: GradingPolicy::default(),
+        level: None,
+        tags: vec![],
     };
 
     world.challenge_type = ChallengeType::ContextualChoice(contextual_choice);
@@ -419,6 +488,8 @@ async fn ordering_challenge_with_n_items(world: &mut BddWorld, item_count: usize
         name: "Test Ordering".to_string(),
         description: "Test".to_string(),
         items,
+        level: None,
+        tags: vec![],
     };
 
     world.challenge_type = ChallengeType::Ordering(ordering);
@@ -454,6 +525,19 @@ async fn user_orders_items_correctly_and_incorrectly(
     }
 }
 
+#[given(expr = "the challenge uses the {string} scoring policy")]
+async fn challenge_uses_scoring_policy(world: &mut BddWorld, policy: String) {
+    let scoring = match policy.as_str() {
+        "exact" => ScoringPolicy::Exact,
+        "partial-credit" => ScoringPolicy::PartialCredit,
+        "kendall-tau" => ScoringPolicy::KendallTau,
+        _ => panic!("Unknown scoring policy: {}", policy),
+    };
+    if let Some(challenge) = &mut world.challenge {
+        challenge.challenge_config.scoring = scoring;
+    }
+}
+
 // ============================================================================
 // SortTable Challenge Steps
 // ============================================================================
@@ -480,6 +564,8 @@ async fn sort_table_challenge_with_n_rows(world: &mut BddWorld, row_count: usize
         description: "Test".to_string(),
         columns,
         rows,
+        level: None,
+        tags: vec![],
     };
 
     world.challenge_type = ChallengeType::SortTable(sort_table);
@@ -517,6 +603,189 @@ async fn user_sorts_rows_correctly_and_incorrectly(
     }
 }
 
+// ============================================================================
+// Matching Challenge Steps
+// ============================================================================
+
+#[given(expr = "a matching challenge with {int} pairs")]
+async fn matching_challenge_with_n_pairs(world: &mut BddWorld, pair_count: usize) {
+    let pairs = (0..pair_count)
+        .map(|i| MatchingPair {
+            id: i,
+            left: format!("left-{}", i),
+            right: format!("right-{}", i),
+            image: None,
+        })
+        .collect();
+
+    let matching = Matching {
+        id: "test-matching".to_string(),
+        name: "Test Matching".to_string(),
+        description: "Test".to_string(),
+        left_label: None,
+        right_label: None,
+        pairs,
+        level: None,
+        tags: vec![],
+    };
+
+    world.challenge_type = ChallengeType::Matching(matching);
+    let challenge = Challenge::new(&world.challenge_type, &ChallengeConfig::default());
+    world.challenge = Some(challenge);
+}
+
+#[when(expr = "the user matches {int} pairs correctly and {int} incorrectly")]
+async fn user_matches_pairs_correctly_and_incorrectly(
+    world: &mut BddWorld,
+    correct_count: usize,
+    incorrect_count: usize,
+) {
+    let total = correct_count + incorrect_count;
+    if let Some(challenge) = &mut world.challenge {
+        challenge.start();
+
+        for i in 0..total {
+            let right = if i < correct_count {
+                i
+            } else {
+                (i + 1) % total
+            };
+            challenge
+                .solve(
+                    ChallengeInput::Matching(MatchingAnswer { left: i, right }),
+                    i,
+                )
+                .expect("Matching input should be accepted");
+        }
+
+        challenge.update_end_time();
+    }
+}
+
+// ============================================================================
+// Translation Challenge Steps
+// ============================================================================
+
+#[given(expr = "a translation challenge with {int} sentences")]
+async fn translation_challenge_with_n_sentences(world: &mut BddWorld, sentence_count: usize) {
+    let items = (0..sentence_count)
+        .map(|i| TranslationItem {
+            sentence: format!("I see the house {}", i),
+            translation: format!("Ich sehe das Haus{}", i),
+            ..Default::default()
+        })
+        .collect();
+
+    let translation = Translation {
+        id: "test-translation".to_string(),
+        name: "Test Translation".to_string(),
+        description: "Test".to_string(),
+        lang: "de".to_string(),
+        items,
+        grading: GradingPolicy::default(),
+        level: None,
+        tags: vec![],
+    };
+
+    world.challenge_type = ChallengeType::Translation(translation);
+    let challenge = Challenge::new(&world.challenge_type, &ChallengeConfig::default());
+    world.challenge = Some(challenge);
+}
+
+#[when(expr = "the user translates {int} sentences correctly and {int} with a missing word")]
+async fn user_translates_sentences(
+    world: &mut BddWorld,
+    correct_count: usize,
+    incomplete_count: usize,
+) {
+    if let Some(challenge) = &mut world.challenge {
+        challenge.start();
+
+        for i in 0..correct_count + incomplete_count {
+            let text = if i < correct_count {
+                format!("Ich sehe das Haus{}", i)
+            } else {
+                format!("Ich sehe Haus{}", i)
+            };
+            challenge
+                .solve(
+                    ChallengeInput::Translation(TranslationAnswer {
+                        item_index: i,
+                        text,
+                    }),
+                    i,
+                )
+                .expect("Translation input should be accepted");
+        }
+
+        challenge.update_end_time();
+    }
+}
+
+// ============================================================================
+// Error Correction Challenge Steps
+// ============================================================================
+
+#[given(expr = "an error correction challenge with {int} sentences")]
+async fn error_correction_challenge_with_n_sentences(world: &mut BddWorld, sentence_count: usize) {
+    let sentences = (0..sentence_count)
+        .map(|i| FaultySentence {
+            sentence: format!("Der Mädchen Nummer {} spielt.", i),
+            errors: vec![SentenceError {
+                position: 0,
+                text: "Der".to_string(),
+                correction: "Das".to_string(),
+                kind: ErrorKind::Article,
+            }],
+            ..Default::default()
+        })
+        .collect();
+
+    let error_correction = ErrorCorrection {
+        id: "test-error-correction".to_string(),
+        name: "Test Error Correction".to_string(),
+        description: "Test".to_string(),
+        lang: "de".to_string(),
+        sentences,
+        grading: GradingPolicy::default(),
+        level: None,
+        tags: vec![],
+    };
+
+    world.challenge_type = ChallengeType::ErrorCorrection(error_correction);
+    let challenge = Challenge::new(&world.challenge_type, &ChallengeConfig::default());
+    world.challenge = Some(challenge);
+}
+
+#[when(expr = "the user fixes {int} mistakes and only finds {int}")]
+async fn user_fixes_and_finds_mistakes(
+    world: &mut BddWorld,
+    fixed_count: usize,
+    found_count: usize,
+) {
+    if let Some(challenge) = &mut world.challenge {
+        challenge.start();
+
+        for i in 0..fixed_count + found_count {
+            let text = if i < fixed_count { "Das" } else { "Die" };
+            challenge
+                .solve(
+                    ChallengeInput::ErrorCorrection(ErrorCorrectionAnswer {
+                        sentence_index: i,
+                        corrections: vec![WordCorrection {
+                            position: 0,
+                            text: text.to_string(),
+                        }],
+                    }),
+                    i,
+                )
+                .expect("Error correction input should be accepted");
+        }
+
+        challenge.update_end_time();
+    }
+}
+
 // ============================================================================
 // Performance Assertion Steps
 // ============================================================================
@@ -1,7 +1,8 @@
 use crate::BddWorld;
+use konnektoren_core::persistence::ProfileStore;
 use konnektoren_core::prelude::*;
 
-use cucumber::given;
+use cucumber::{given, when};
 
 #[given(expr = "A new Session with id {string}")]
 async fn a_new_session_with_id(world: &mut BddWorld, id: String) {
@@ -14,3 +15,42 @@ async fn a_new_session_with_id_and_name(world: &mut BddWorld, id: String, name:
     player_profile.name = name;
     world.session = Session::new_with_profile(player_profile);
 }
+
+fn profile_id(world: &BddWorld, name: &str) -> String {
+    world
+        .profile_store
+        .profiles()
+        .iter()
+        .find(|profile| profile.name == name)
+        .map(|profile| profile.id.clone())
+        .expect("Profile not found")
+}
+
+#[given(expr = "a profile store with the profiles {string}")]
+async fn a_profile_store_with_profiles(world: &mut BddWorld, names: String) {
+    world.profile_store = ProfileStore::in_memory();
+    for name in names.split(',') {
+        world
+            .profile_store
+            .create_profile(name.trim())
+            .expect("Failed to create profile");
+    }
+}
+
+#[given(expr = "the session of the profile {string} is loaded")]
+async fn the_session_of_the_profile_is_loaded(world: &mut BddWorld, name: String) {
+    let id = profile_id(world, &name);
+    world.session = world
+        .profile_store
+        .load_session(&id)
+        .expect("Failed to load session");
+}
+
+#[when(expr = "the profile is switched to {string}")]
+async fn the_profile_is_switched_to(world: &mut BddWorld, name: String) {
+    let id = profile_id(world, &name);
+    world.session = world
+        .profile_store
+        .switch_profile(&world.session, &id)
+        .expect("Failed to switch profile");
+}
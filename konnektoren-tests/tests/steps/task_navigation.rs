@@ -1,7 +1,7 @@
 use crate::BddWorld;
 use cucumber::{given, then, when};
 use konnektoren_core::challenges::{
-    Solvable,
+    GradingPolicy, ScoringPolicy, Solvable,
     contextual_choice::{Choice, ContextItem, ContextItemChoiceAnswers, ContextualChoice},
     task_pattern::TaskPattern,
 };
@@ -36,8 +36,16 @@ async fn load_konnektoren_challenge_with_pattern(world: &mut BddWorld, pattern:
         variant: None,
         tasks: task_pattern.clone(),
         unlock_points: 0,
+        unlock_stars: 0,
+        requires: vec![],
+        elective: false,
         position: Some((0, 0)),
         icon: None,
+        scoring: ScoringPolicy::default(),
+        rewards: None,
+        level: None,
+        tags: vec![],
+        time_limit: None,
     };
 
     // Load the default konnektoren challenge type
@@ -69,6 +77,25 @@ async fn load_konnektoren_challenge_with_pattern(world: &mut BddWorld, pattern:
         [current_challenge_index] = challenge_config;
 }
 
+#[then(expr = "the challenge should have the seed {int}")]
+async fn the_challenge_should_have_the_seed(world: &mut BddWorld, seed: u64) {
+    assert_eq!(world.session.game_state.challenge.seed, Some(seed));
+}
+
+#[then(expr = "loading the challenge again with its seed selects the same tasks")]
+async fn loading_the_challenge_again_selects_the_same_tasks(world: &mut BddWorld) {
+    let challenge = &world.session.game_state.challenge;
+    let seed = challenge
+        .seed
+        .expect("Random challenges should have a seed");
+    let factory = world.factory.as_ref().expect("Factory should be loaded");
+
+    let replay = factory
+        .create_challenge_with_seed(&challenge.challenge_config, seed)
+        .expect("Failed to create challenge");
+    assert_eq!(replay.challenge_type, challenge.challenge_type);
+}
+
 #[then(expr = "the current task should be valid")]
 async fn the_current_task_should_be_valid(world: &mut BddWorld) {
     let challenge = world.session.game_state.challenge.challenge_type.clone();
@@ -199,10 +226,13 @@ async fn tasks_should_be_completed(world: &mut BddWorld, expected_count: usize)
         ChallengeResult::Informative => {
             assert_eq!(0, expected_count, "Informative challenges don't have tasks");
         }
-        ChallengeResult::Vocabulary => {
+        ChallengeResult::Vocabulary(answers) => {
             assert_eq!(
-                0, expected_count,
-                "Vocabulary challenges are always complete"
+                answers.len(),
+                expected_count,
+                "Expected {} completed tasks, but got {}",
+                expected_count,
+                answers.len()
             );
         }
         ChallengeResult::Custom(_) => {
@@ -221,6 +251,33 @@ async fn tasks_should_be_completed(world: &mut BddWorld, expected_count: usize)
                 answers.len()
             );
         }
+        ChallengeResult::Matching(answers) => {
+            assert_eq!(
+                answers.len(),
+                expected_count,
+                "Expected {} completed tasks, but got {}",
+                expected_count,
+                answers.len()
+            );
+        }
+        ChallengeResult::Translation(answers) => {
+            assert_eq!(
+                answers.len(),
+                expected_count,
+                "Expected {} translated sentences, but got {}",
+                expected_count,
+                answers.len()
+            );
+        }
+        ChallengeResult::ErrorCorrection(answers) => {
+            assert_eq!(
+                answers.len(),
+                expected_count,
+                "Expected {} corrected sentences, but got {}",
+                expected_count,
+                answers.len()
+            );
+        }
     }
 }
 
@@ -547,6 +604,10 @@ async fn a_contextual_choice_challenge_with_items(world: &mut BddWorld, item_cou
         name: "Test Contextual Choice".to_string(),
         description: "Test navigation".to_string(),
         items,
+        grading: GradingPolicy::default(),
+        level: None,
+        tags: vec![],
+        time_limit: None,
     };
 
     let challenge_type = ChallengeType::ContextualChoice(contextual_choice);
@@ -560,8 +621,16 @@ async fn a_contextual_choice_challenge_with_items(world: &mut BddWorld, item_cou
         variant: None,
         tasks: item_count.into(),
         unlock_points: 0,
+        unlock_stars: 0,
+        requires: vec![],
+        elective: false,
         position: Some((0, 0)),
         icon: None,
+        scoring: ScoringPolicy::default(),
+        rewards: None,
+        level: None,
+        tags: vec![],
+        time_limit: None,
     };
 
     // Create factory and add the challenge type
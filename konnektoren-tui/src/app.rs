@@ -9,10 +9,12 @@ use crate::{
 use crate::tui::Tui;
 
 #[cfg(feature = "crossterm")]
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
 use konnektoren_core::{
+    challenges::ChallengeType,
     commands::{ChallengeCommand, Command, CommandTrait, GameCommand},
+    persistence::{FileFormat, FilePersistence, ProfileStore},
     session::Session,
 };
 use ratatui::{
@@ -24,13 +26,23 @@ use ratatui::{
     text::Line,
     widgets::{Block, Borders, Paragraph, Widget},
 };
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Profiles shared by every app of the process, e.g. all SSH clients.
+pub type SharedProfileStore = Arc<Mutex<ProfileStore>>;
 
 #[derive(Debug, Default)]
 pub struct App {
     title: String,
     username: Option<String>,
     session: Session,
+    input: String,
+    /// Explains why the last navigation failed, e.g. a locked challenge
+    message: Option<String>,
     show_map: bool,
+    /// Where the session is saved, none if it is played without saving
+    profiles: Option<SharedProfileStore>,
     exit: bool,
 }
 
@@ -43,10 +55,54 @@ impl App {
         }
     }
 
+    /// Opens the profiles saved in `dir`.
+    pub fn open_profiles(dir: impl Into<PathBuf>) -> Result<SharedProfileStore> {
+        let dir = dir.into();
+        let store = ProfileStore::new(
+            FilePersistence::profile_index(&dir, FileFormat::Json),
+            FilePersistence::factory(&dir, FileFormat::Json),
+        )?;
+        Ok(Arc::new(Mutex::new(store)))
+    }
+
+    /// Plays the saved session of the profile named `name`, which is created
+    /// on the first start. The session is saved whenever a challenge is
+    /// finished and on exit.
+    pub fn with_profile(profiles: SharedProfileStore, name: &str) -> Result<Self> {
+        let session = {
+            let mut store = lock(&profiles)?;
+            let id = match store.profiles().iter().find(|profile| profile.name == name) {
+                Some(profile) => profile.id.clone(),
+                None => store.create_profile(name)?.id,
+            };
+            store.load_session(&id)?
+        };
+        Ok(App {
+            username: Some(name.to_string()),
+            session,
+            profiles: Some(profiles),
+            ..Self::new()
+        })
+    }
+
     pub fn set_username(&mut self, username: String) {
         self.username = Some(username);
     }
 
+    /// Saves the session to its profile, if it has one.
+    pub fn save(&self) -> Result<()> {
+        if let Some(profiles) = &self.profiles {
+            lock(profiles)?.save_session(&self.session)?;
+        }
+        Ok(())
+    }
+
+    fn save_or_log(&self) {
+        if let Err(err) = self.save() {
+            tracing::error!("Failed to save session: {}", err);
+        }
+    }
+
     #[cfg(feature = "crossterm")]
     pub fn run(&mut self, terminal: &mut Tui) -> Result<()> {
         terminal.clear().map_err(Error::IoError)?;
@@ -67,6 +123,7 @@ impl App {
 
     pub fn exit(&mut self) {
         self.exit = true;
+        self.save_or_log();
     }
 
     pub fn next_question(&mut self) {
@@ -85,8 +142,12 @@ impl App {
 
     pub fn next_challenge(&mut self) {
         let command = Command::Game(GameCommand::NextChallenge);
-        if let Err(err) = command.execute(&mut self.session.game_state) {
-            tracing::error!("Failed to execute next challenge command: {}", err);
+        match command.execute(&mut self.session.game_state) {
+            Ok(()) => self.message = None,
+            Err(err) => {
+                tracing::error!("Failed to execute next challenge command: {}", err);
+                self.message = Some(err.to_string());
+            }
         }
     }
 
@@ -104,12 +165,65 @@ impl App {
             .map_err(Error::CommandError)
     }
 
+    /// Finishes the current challenge, which records it in the history and
+    /// updates the player's streak.
+    pub fn finish_challenge(&mut self) {
+        self.session.finish_challenge(chrono::Utc::now());
+        self.message = None;
+        self.save_or_log();
+    }
+
     pub fn toggle_map(&mut self) {
         self.show_map = !self.show_map;
     }
 
+    /// Returns `true` if the current challenge is answered by typing.
+    pub fn accepts_text(&self) -> bool {
+        !self.show_map
+            && matches!(
+                self.session.game_state.challenge.challenge_type,
+                ChallengeType::Translation(_)
+            )
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.input.push(c);
+    }
+
+    pub fn pop_char(&mut self) {
+        self.input.pop();
+    }
+
+    pub fn submit_text(&mut self) -> Result<()> {
+        let text = std::mem::take(&mut self.input);
+        let command = Command::Challenge(ChallengeCommand::SolveText(text));
+        command
+            .execute(&mut self.session.game_state)
+            .map_err(Error::CommandError)
+    }
+
+    /// While an answer is typed, plain keys go into the input. Keys pressed
+    /// with Alt or Ctrl, the arrows, Tab and Esc keep their controls.
     #[cfg(feature = "crossterm")]
     fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<()> {
+        let with_modifier = key_event
+            .modifiers
+            .intersects(KeyModifiers::ALT | KeyModifiers::CONTROL);
+        if self.accepts_text() && !with_modifier {
+            match key_event.code {
+                KeyCode::Char(c) => {
+                    self.push_char(c);
+                    return Ok(());
+                }
+                KeyCode::Backspace => {
+                    self.pop_char();
+                    return Ok(());
+                }
+                KeyCode::Enter => return self.submit_text(),
+                _ => {}
+            }
+        }
+
         match key_event.code {
             KeyCode::Char('q') | KeyCode::Esc => self.exit(),
             KeyCode::Left | KeyCode::Char('h') => self.previous_question(),
@@ -127,6 +241,7 @@ impl App {
             KeyCode::Char('8') => self.solve_option(8)?,
             KeyCode::Char('9') => self.solve_option(9)?,
             KeyCode::Char('m') => self.toggle_map(),
+            KeyCode::Char('f') => self.finish_challenge(),
             _ => {}
         }
         Ok(())
@@ -146,6 +261,12 @@ impl App {
     }
 }
 
+fn lock(profiles: &SharedProfileStore) -> Result<std::sync::MutexGuard<'_, ProfileStore>> {
+    profiles
+        .lock()
+        .map_err(|_| Error::StateError("Failed to lock profiles".to_string()))
+}
+
 impl Widget for &App {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let username_display = self
@@ -161,15 +282,20 @@ impl Widget for &App {
             "<Right>".blue().bold(),
             " Map ".into(),
             "<M>".blue().bold(),
+            " Finish ".into(),
+            "<F>".blue().bold(),
             " Quit ".into(),
             "<Q> ".blue().bold(),
         ]);
 
-        let block = Block::default()
+        let mut block = Block::default()
             .title(Line::from(username_display).bold().centered())
             .title_bottom(instructions.centered())
             .borders(Borders::ALL)
             .border_set(border::THICK);
+        if let Some(message) = &self.message {
+            block = block.title_bottom(Line::from(format!(" {} ", message)).red().left_aligned());
+        }
 
         Paragraph::new(":")
             .centered()
@@ -181,10 +307,12 @@ impl Widget for &App {
             vertical: 1,
         });
 
+        let states = self.session.game_state.game.challenge_states();
         if self.show_map {
             let map = MapWidget::new(
                 &self.session.game_state.game.game_paths[0],
                 self.session.game_state.current_challenge_index,
+                &states,
             );
             map.render(inner_area, buf);
         } else {
@@ -194,6 +322,7 @@ impl Widget for &App {
             let tabs = ChallengeTabs::new(
                 &self.session.game_state.game.game_paths[0],
                 self.session.game_state.current_challenge_index,
+                &states,
             );
             tabs.render(tab_area, buf);
 
@@ -201,6 +330,7 @@ impl Widget for &App {
                 challenge: &self.session.game_state.challenge,
                 show_help: true,
                 current_question: self.session.game_state.current_task_index,
+                input: &self.input,
             };
             challenge_widget.render(challenge_area, buf);
         }
@@ -220,4 +350,123 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn next_challenge_locked() {
+        let mut app = App::default();
+        app.next_challenge();
+        assert_eq!(app.session.game_state.current_challenge_index, 0);
+        assert!(app.message.as_ref().is_some_and(|m| m.contains("locked")));
+
+        app.session.game_state.game.xp = 10;
+        app.next_challenge();
+        assert_eq!(app.session.game_state.current_challenge_index, 1);
+        assert_eq!(app.message, None);
+    }
+
+    #[test]
+    fn finish_challenge() {
+        let mut app = App::default();
+        app.finish_challenge();
+        assert_eq!(app.session.game_state.game.challenge_history.len(), 1);
+        assert_eq!(app.session.player_profile.streak.current, 1);
+    }
+
+    #[test]
+    fn save_and_restore_profile() -> Result<()> {
+        let profiles = Arc::new(Mutex::new(ProfileStore::in_memory()));
+        let mut app = App::with_profile(profiles.clone(), "anna")?;
+        app.finish_challenge();
+
+        let app = App::with_profile(profiles.clone(), "anna")?;
+        assert_eq!(app.session.game_state.game.challenge_history.len(), 1);
+        assert_eq!(app.session.player_profile.streak.current, 1);
+        assert_eq!(app.username.as_deref(), Some("anna"));
+
+        let app = App::with_profile(profiles.clone(), "ben")?;
+        assert!(app.session.game_state.game.challenge_history.is_empty());
+        assert_eq!(profiles.lock().unwrap().profiles().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "crossterm")]
+    fn type_translation() -> Result<()> {
+        use konnektoren_core::challenges::{
+            Challenge, ChallengeConfig, ChallengeResult, Translation,
+        };
+
+        let mut app = App::default();
+        assert!(!app.accepts_text());
+
+        app.session.game_state.challenge = Challenge::new(
+            &ChallengeType::Translation(Translation::default()),
+            &ChallengeConfig::default(),
+        );
+        assert!(app.accepts_text());
+
+        for c in "Hallo!".chars() {
+            app.handle_key_event(KeyCode::Char(c).into())?;
+        }
+        app.handle_key_event(KeyCode::Backspace.into())?;
+        assert_eq!(app.input, "Hallo");
+        assert!(!app.exit);
+
+        app.handle_key_event(KeyCode::Enter.into())?;
+        assert!(app.input.is_empty());
+        assert!(matches!(
+            &app.session.game_state.challenge.challenge_result,
+            ChallengeResult::Translation(answers) if answers[0].text == "Hallo"
+        ));
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('m'), KeyModifiers::ALT))?;
+        assert!(app.show_map);
+        assert!(app.input.is_empty());
+        app.handle_key_event(KeyCode::Char('m').into())?;
+        assert!(!app.show_map);
+
+        app.handle_key_event(KeyCode::Char('m').into())?;
+        assert_eq!(app.input, "m");
+        app.handle_key_event(KeyCode::Esc.into())?;
+        assert!(app.exit);
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_translation_past_the_last_sentence() {
+        use konnektoren_core::challenges::{Challenge, ChallengeConfig, Translation};
+
+        let mut app = App::default();
+        let translation = Translation::default();
+        app.session.game_state.current_task_index = translation.items.len();
+        app.session.game_state.challenge = Challenge::new(
+            &ChallengeType::Translation(translation),
+            &ChallengeConfig::default(),
+        );
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, 20));
+        (&app).render(buf.area, &mut buf);
+    }
+
+    #[test]
+    fn render_unsupported_challenge() {
+        use crate::{options_widget::OptionsWidget, results_widget::ResultsWidget};
+        use konnektoren_core::challenges::{Challenge, ChallengeConfig, Matching};
+
+        let mut app = App::default();
+        app.session.game_state.challenge = Challenge::new(
+            &ChallengeType::Matching(Matching::default()),
+            &ChallengeConfig::default(),
+        );
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, 20));
+        (&app).render(buf.area, &mut buf);
+        let rendered: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(rendered.contains("unsupported in the TUI"));
+
+        let challenge = &app.session.game_state.challenge;
+        OptionsWidget::new(challenge).render(buf.area, &mut buf);
+        ResultsWidget::new(challenge).render(buf.area, &mut buf);
+    }
 }
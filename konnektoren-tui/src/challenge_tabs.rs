@@ -1,28 +1,44 @@
-use konnektoren_core::game::GamePath;
+use konnektoren_core::game::{ChallengeState, GamePath};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::Color,
+    style::{Color, Stylize},
+    text::Line,
     widgets::{Tabs, Widget},
 };
+use std::collections::HashMap;
 
 pub struct ChallengeTabs<'a> {
     game_path: &'a GamePath,
     selected_tab_index: usize,
+    states: &'a HashMap<String, ChallengeState>,
 }
 
 impl<'a> ChallengeTabs<'a> {
-    pub fn new(game_path: &'a GamePath, selected_tab_index: usize) -> Self {
+    pub fn new(
+        game_path: &'a GamePath,
+        selected_tab_index: usize,
+        states: &'a HashMap<String, ChallengeState>,
+    ) -> Self {
         ChallengeTabs {
             game_path,
             selected_tab_index,
+            states,
         }
     }
 }
 
 impl Widget for ChallengeTabs<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let titles = self.game_path.challenge_ids();
+        let titles =
+            self.game_path
+                .challenge_ids()
+                .into_iter()
+                .map(|id| match self.states.get(&id) {
+                    Some(ChallengeState::Locked) => Line::from(id).dark_gray(),
+                    Some(ChallengeState::Completed) => Line::from(id).green(),
+                    _ => Line::from(id),
+                });
         let highlight_style = (Color::default(), Color::Red);
         let selected_tab_index = self.selected_tab_index;
         Tabs::new(titles)
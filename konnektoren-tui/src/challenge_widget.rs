@@ -14,6 +14,8 @@ pub struct ChallengeWidget<'a> {
     pub challenge: &'a Challenge,
     pub show_help: bool,
     pub current_question: usize,
+    /// The text typed so far for challenges answered by typing
+    pub input: &'a str,
 }
 
 impl Widget for ChallengeWidget<'_> {
@@ -61,7 +63,70 @@ impl Widget for ChallengeWidget<'_> {
                 let results = ResultsWidget::new(self.challenge);
                 results.render(layout2[1], buf);
             }
-            _ => panic!("Invalid challenge type"),
+            ChallengeType::Translation(ref dataset) => {
+                let layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(vec![
+                        Constraint::Percentage(35),
+                        Constraint::Length(3),
+                        Constraint::Min(0),
+                    ])
+                    .split(area);
+
+                let title = format!(
+                    " Translate ({}/{}) ",
+                    self.current_question + 1,
+                    self.challenge.challenge_config.tasks
+                );
+
+                let block = Block::bordered()
+                    .title(title.bold())
+                    .border_set(border::ROUNDED);
+
+                let Some(item) = dataset.items.get(self.current_question) else {
+                    Paragraph::new("No sentence left to translate")
+                        .centered()
+                        .block(block)
+                        .render(area, buf);
+                    return;
+                };
+
+                let mut lines = vec![Line::from(item.sentence.as_str())];
+                if self.show_help {
+                    lines.extend(
+                        item.hints
+                            .iter()
+                            .map(|hint| Line::from(hint.as_str()).dim()),
+                    );
+                }
+
+                Paragraph::new(Text::from(lines))
+                    .centered()
+                    .block(block)
+                    .render(layout[0], buf);
+
+                let input_block = Block::bordered()
+                    .title(" Your translation ".bold())
+                    .title_bottom(Line::from(" Submit <Enter> Controls <Alt>+<Key> ").centered())
+                    .border_set(border::ROUNDED);
+
+                Paragraph::new(format!("{}_", self.input))
+                    .block(input_block)
+                    .render(layout[1], buf);
+
+                let results = ResultsWidget::new(self.challenge);
+                results.render(layout[2], buf);
+            }
+            _ => {
+                let block = Block::bordered()
+                    .title(format!(" {} ", self.challenge.challenge_config.name).bold())
+                    .border_set(border::ROUNDED);
+
+                Paragraph::new("This challenge type is unsupported in the TUI")
+                    .centered()
+                    .block(block)
+                    .render(area, buf);
+            }
         }
     }
 }
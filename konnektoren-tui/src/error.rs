@@ -8,6 +8,9 @@ pub enum Error {
     #[error("Command execution error: {0}")]
     CommandError(#[from] konnektoren_core::commands::CommandError),
 
+    #[error("Persistence error: {0}")]
+    PersistenceError(#[from] konnektoren_core::persistence::PersistenceError),
+
     #[error("UI error: {0}")]
     UiError(String),
 
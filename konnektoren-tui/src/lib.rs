@@ -13,7 +13,7 @@ mod tui;
 pub mod ssh_server;
 
 pub mod prelude {
-    pub use crate::app::App;
+    pub use crate::app::{App, SharedProfileStore};
 
     #[cfg(feature = "crossterm")]
     pub use crate::tui::{Tui, init, restore};
@@ -5,7 +5,10 @@ use konnektoren_tui::prelude::{App, init, restore};
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing_subscriber::fmt::init();
 
-    let mut app = App::new();
+    // Profiles are kept in KONNEKTOREN_DATA_DIR, one per user name
+    let dir = std::env::var("KONNEKTOREN_DATA_DIR").unwrap_or_else(|_| "data".to_string());
+    let name = std::env::var("USER").unwrap_or_else(|_| "player".to_string());
+    let mut app = App::with_profile(App::open_profiles(dir)?, &name)?;
     let mut terminal = init()?;
     app.run(&mut terminal)?;
     restore()?;
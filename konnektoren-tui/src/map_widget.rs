@@ -1,14 +1,16 @@
-use konnektoren_core::game::GamePath;
+use konnektoren_core::game::{ChallengeState, GamePath};
 use ratatui::{
     prelude::*,
     style::Styled,
     symbols::Marker,
     widgets::{Block, canvas::Line, canvas::*},
 };
+use std::collections::HashMap;
 
 pub struct MapWidget<'a> {
     current_challenge: usize,
-    path: &'a GamePath,
+    path: GamePath,
+    states: &'a HashMap<String, ChallengeState>,
 }
 
 impl MapWidget<'_> {
@@ -39,10 +41,33 @@ impl MapWidget<'_> {
 }
 
 impl<'a> MapWidget<'a> {
-    pub fn new(path: &'a GamePath, current_challenge: usize) -> Self {
+    /// Challenges without a position are drawn where the layout of the
+    /// path's map puts them.
+    pub fn new(
+        path: &GamePath,
+        current_challenge: usize,
+        states: &'a HashMap<String, ChallengeState>,
+    ) -> Self {
         MapWidget {
-            path,
+            path: path.laid_out().unwrap_or_else(|_| path.clone()),
             current_challenge,
+            states,
+        }
+    }
+
+    fn challenge_color(&self, index: usize) -> Color {
+        if self.current_challenge == index {
+            return Color::Red;
+        }
+        let state = self
+            .path
+            .challenges
+            .get(index)
+            .and_then(|challenge| self.states.get(&challenge.id));
+        match state {
+            Some(ChallengeState::Locked) => Color::DarkGray,
+            Some(ChallengeState::Completed) => Color::Green,
+            _ => Color::Yellow,
         }
     }
 
@@ -58,11 +83,7 @@ impl<'a> MapWidget<'a> {
     }
 
     fn draw_challenge(&self, index: usize, challenge: &str, x: f64, y: f64, ctx: &mut Context) {
-        let color = if self.current_challenge == index {
-            Color::Red
-        } else {
-            Color::Yellow
-        };
+        let color = self.challenge_color(index);
 
         ctx.draw(&Rectangle {
             x,
@@ -87,19 +108,17 @@ impl<'a> MapWidget<'a> {
             .block(Block::bordered().title(title))
             .marker(Marker::Braille)
             .paint(|ctx| {
-                let mut last: Option<(f64, f64)> = None;
+                for ((x1, y1), (x2, y2)) in self.path.edge_positions() {
+                    ctx.draw(&Line {
+                        x1: x1 as f64 * 10.0,
+                        y1: y1 as f64 * 10.0,
+                        x2: x2 as f64 * 10.0,
+                        y2: y2 as f64 * 10.0,
+                        color: Color::Yellow,
+                    });
+                }
                 for (index, (name, x, y)) in challenges.iter().enumerate() {
                     self.draw_challenge(index, name, *x, *y, ctx);
-                    if let Some((x1, y1)) = last {
-                        ctx.draw(&Line {
-                            x1,
-                            y1,
-                            x2: *x,
-                            y2: *y,
-                            color: Color::Yellow,
-                        });
-                    }
-                    last = Some((*x, *y));
                 }
             })
             .x_bounds(x_bounds)
@@ -135,6 +154,37 @@ impl Widget for MapWidget<'_> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_challenge_color() {
+        let path = GamePath::default();
+        let states = HashMap::from([
+            ("konnektoren-2".to_string(), ChallengeState::Completed),
+            ("konnektoren-3".to_string(), ChallengeState::Locked),
+            ("konnektoren-4".to_string(), ChallengeState::Unlocked),
+        ]);
+        let map = MapWidget::new(&path, 0, &states);
+
+        assert_eq!(map.challenge_color(0), Color::Red);
+        assert_eq!(map.challenge_color(1), Color::Green);
+        assert_eq!(map.challenge_color(2), Color::DarkGray);
+        assert_eq!(map.challenge_color(3), Color::Yellow);
+    }
+
+    #[test]
+    fn test_lays_out_missing_positions() {
+        let mut path = GamePath::default();
+        path.challenges[1].position = None;
+        let states = HashMap::new();
+        let map = MapWidget::new(&path, 0, &states);
+
+        let position = map.path.challenges[1].position.unwrap();
+        assert!(
+            path.challenges
+                .iter()
+                .all(|challenge| challenge.position != Some(position))
+        );
+    }
+
     #[test]
     fn test_calculate_boundaries() {
         let challenges = vec![
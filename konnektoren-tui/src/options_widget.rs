@@ -22,21 +22,20 @@ impl OptionsWidget {
 
 impl Widget for OptionsWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        match self.challenge_type {
-            ChallengeType::MultipleChoice(ref dataset) => {
-                let block = Block::bordered()
-                    .title(" Options ".bold())
-                    .border_set(border::ROUNDED);
+        let block = Block::bordered()
+            .title(" Options ".bold())
+            .border_set(border::ROUNDED);
 
+        let text = match self.challenge_type {
+            ChallengeType::MultipleChoice(ref dataset) => {
                 let options = dataset
                     .options
                     .iter()
                     .map(|option| Line::from(format!("<{}> {}", option.id, option.name)));
-
-                let text = Text::from(options.collect::<Vec<Line>>());
-                Paragraph::new(text).block(block).render(area, buf);
+                Text::from(options.collect::<Vec<Line>>())
             }
-            _ => panic!("Invalid challenge type"),
-        }
+            _ => Text::from("Options are unsupported in the TUI"),
+        };
+        Paragraph::new(text).block(block).render(area, buf);
     }
 }
@@ -7,7 +7,9 @@ use ratatui::{
     widgets::{Block, Paragraph, Widget},
 };
 
-use konnektoren_core::challenges::{Challenge, ChallengeResult, ChallengeType, Performance};
+use konnektoren_core::challenges::{
+    Challenge, ChallengeResult, ChallengeType, Performance, TokenStatus,
+};
 
 pub struct ResultsWidget<'a> {
     pub challenge: &'a Challenge,
@@ -52,7 +54,23 @@ impl Widget for ResultsWidget<'_> {
                     },
                 )
             }
-            _ => todo!("Implement other challenge types"),
+            (ChallengeType::Translation(dataset), ChallengeResult::Translation(answers)) => answers
+                .iter()
+                .filter_map(|answer| dataset.align_answer(answer))
+                .fold(Text::default(), |mut text, alignment| {
+                    let words = alignment.tokens.iter().flat_map(|token| {
+                        let word = match token.status {
+                            TokenStatus::Matched => token.text.clone().green(),
+                            TokenStatus::Misplaced => token.text.clone().yellow(),
+                            TokenStatus::Missing => format!("[{}]", token.text).red(),
+                            TokenStatus::Extra => token.text.clone().red().crossed_out(),
+                        };
+                        [" ".into(), word]
+                    });
+                    text.push_line(Line::from(words.collect::<Vec<_>>()));
+                    text
+                }),
+            _ => Text::from("Results are unsupported in the TUI"),
         };
 
         let text = text.into_iter().rev().collect::<Vec<Line>>();
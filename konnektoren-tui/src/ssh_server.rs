@@ -12,7 +12,7 @@ use tokio::sync::Mutex;
 use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
 use tracing::info;
 
-use crate::app::App;
+use crate::app::{App, SharedProfileStore};
 use crate::error::{Error, Result};
 
 type SshTerminal = Terminal<CrosstermBackend<TerminalHandle>>;
@@ -63,25 +63,33 @@ impl std::io::Write for TerminalHandle {
 #[derive(Clone)]
 pub struct SshServer {
     clients: Arc<Mutex<HashMap<usize, (SshTerminal, App)>>>,
+    /// Every SSH user plays the profile named after their user name
+    profiles: SharedProfileStore,
+    username: Option<String>,
     id: usize,
 }
 
 impl SshServer {
-    pub fn new() -> Self {
+    pub fn new(profiles: SharedProfileStore) -> Self {
         Self {
             clients: Arc::new(Mutex::new(HashMap::new())),
+            profiles,
+            username: None,
             id: 0,
         }
     }
 
-    fn load_or_generate_key() -> russh::keys::PrivateKey {
-        // Use /app/data if it exists (Docker), otherwise current directory
-        let key_dir = if std::path::Path::new("/app/data").exists() {
+    /// Uses /app/data if it exists (Docker), otherwise the current directory
+    fn data_dir() -> &'static str {
+        if std::path::Path::new("/app/data").exists() {
             "/app/data"
         } else {
             "."
-        };
-        let key_path = format!("{}/ssh_host_key", key_dir);
+        }
+    }
+
+    fn load_or_generate_key() -> russh::keys::PrivateKey {
+        let key_path = format!("{}/ssh_host_key", Self::data_dir());
 
         // Try to load existing key
         if Path::new(&key_path).exists() {
@@ -110,7 +118,8 @@ impl SshServer {
     }
 
     pub async fn run(addr: &str, port: u16) -> Result<()> {
-        let mut server = Self::new();
+        let profiles = App::open_profiles(format!("{}/profiles", Self::data_dir()))?;
+        let mut server = Self::new(profiles);
 
         // Start a background task to handle periodic updates if needed
         let clients = server.clients.clone();
@@ -172,7 +181,10 @@ impl Handler for SshServer {
         };
 
         let terminal = Terminal::with_options(backend, options)?;
-        let app = App::new();
+        let app = match &self.username {
+            Some(user) => App::with_profile(self.profiles.clone(), user)?,
+            None => App::new(),
+        };
 
         let mut clients = self.clients.lock().await;
         clients.insert(self.id, (terminal, app));
@@ -186,12 +198,7 @@ impl Handler for SshServer {
         _password: &str,
     ) -> std::result::Result<Auth, Self::Error> {
         info!("Password auth for user: {}", user);
-
-        // Set username in the app
-        let mut clients = self.clients.lock().await;
-        if let Some((_, app)) = clients.get_mut(&self.id) {
-            app.set_username(user.to_string());
-        }
+        self.username = Some(user.to_string());
 
         Ok(Auth::Accept)
     }
@@ -202,24 +209,14 @@ impl Handler for SshServer {
         _: &PublicKey,
     ) -> std::result::Result<Auth, Self::Error> {
         info!("Public key auth for user: {}", user);
-
-        // Set username in the app
-        let mut clients = self.clients.lock().await;
-        if let Some((_, app)) = clients.get_mut(&self.id) {
-            app.set_username(user.to_string());
-        }
+        self.username = Some(user.to_string());
 
         Ok(Auth::Accept)
     }
 
     async fn auth_none(&mut self, user: &str) -> std::result::Result<Auth, Self::Error> {
         info!("Auth none for user: {}", user);
-
-        // Set username in the app
-        let mut clients = self.clients.lock().await;
-        if let Some((_, app)) = clients.get_mut(&self.id) {
-            app.set_username(user.to_string());
-        }
+        self.username = Some(user.to_string());
 
         Ok(Auth::Accept)
     }
@@ -236,35 +233,54 @@ impl Handler for SshServer {
         {
             let mut clients = self.clients.lock().await;
             if let Some((_, app)) = clients.get_mut(&self.id) {
-                for byte in data {
-                    match byte {
-                        b'q' | 27 => {
-                            app.exit();
-                            should_close = true;
-                            break;
-                        }
-                        b'm' => {
-                            app.toggle_map();
-                            should_redraw = true;
+                if app.accepts_text() && !data.contains(&27) {
+                    for c in String::from_utf8_lossy(data).chars() {
+                        match c {
+                            '\r' | '\n' => {
+                                let _ = app.submit_text();
+                            }
+                            '\u{7f}' | '\u{8}' => app.pop_char(),
+                            '\t' => app.next_challenge(),
+                            c if !c.is_control() => app.push_char(c),
+                            _ => {}
                         }
-                        b'h' => {
-                            app.previous_question();
-                            should_redraw = true;
-                        }
-                        b'l' => {
-                            app.next_question();
-                            should_redraw = true;
-                        }
-                        b'\t' => {
-                            app.next_challenge();
-                            should_redraw = true;
-                        }
-                        b'0'..=b'9' => {
-                            let option_id = (byte - b'0') as usize;
-                            let _ = app.solve_option(option_id);
-                            should_redraw = true;
+                    }
+                    should_redraw = true;
+                } else {
+                    for byte in data {
+                        match byte {
+                            b'q' | 27 => {
+                                app.exit();
+                                should_close = true;
+                                break;
+                            }
+                            b'm' => {
+                                app.toggle_map();
+                                should_redraw = true;
+                            }
+                            b'h' => {
+                                app.previous_question();
+                                should_redraw = true;
+                            }
+                            b'l' => {
+                                app.next_question();
+                                should_redraw = true;
+                            }
+                            b'\t' => {
+                                app.next_challenge();
+                                should_redraw = true;
+                            }
+                            b'f' => {
+                                app.finish_challenge();
+                                should_redraw = true;
+                            }
+                            b'0'..=b'9' => {
+                                let option_id = (byte - b'0') as usize;
+                                let _ = app.solve_option(option_id);
+                                should_redraw = true;
+                            }
+                            _ => {}
                         }
-                        _ => {}
                     }
                 }
             }
@@ -350,7 +366,11 @@ impl Drop for SshServer {
         let clients = self.clients.clone();
         tokio::spawn(async move {
             let mut clients = clients.lock().await;
-            clients.remove(&id);
+            if let Some((_, app)) = clients.remove(&id)
+                && let Err(err) = app.save()
+            {
+                tracing::warn!("Failed to save session of client {}: {}", id, err);
+            }
         });
     }
 }